@@ -15,6 +15,8 @@ pub struct User {
 pub enum Permissions {
     /// A regular user
     User,
+    /// A user whose suggestions are applied immediately, and reviewed by moderators afterwards
+    TrustedContributor,
     /// A moderator who is able to approve suggestions
     Moderator,
     /// An administrator who is able to configure the site itself
@@ -25,6 +27,7 @@ impl Display for Permissions {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let s = match self {
             Permissions::User => "User",
+            Permissions::TrustedContributor => "Trusted contributor",
             Permissions::Moderator => "Moderator",
             Permissions::Administrator => "Administrator",
         };
@@ -0,0 +1,275 @@
+//! The concords which agree with a noun, derived from its noun class. Classes 1a and 2a take the
+//! same concords as classes 1 and 2.
+//!
+//! The `isixhosa` crate only provides the [`NounClass`] enum itself, without the prefixes or
+//! concords of each class, so they cannot be taken from it. Instead, the subject concords are those
+//! of [`SubjectConcord`], shared with the conjugator, and the relative concords are derived from
+//! them. The other concords are listed per class below, matched exhaustively on [`NounClass`] so
+//! that a class added to the crate cannot be missed.
+
+use crate::conjugation::SubjectConcord;
+use crate::i18n::{ToTranslationKey, TranslationKey};
+use isixhosa::noun::NounClass;
+use serde::Serialize;
+
+#[derive(Serialize, Copy, Clone, Debug, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcordKind {
+    /// e.g. si- in isitya siyaphuka
+    Subject,
+    /// e.g. -si- in ndiyasibona isitya
+    Object,
+    /// e.g. sa- in isitya sam
+    Possessive,
+    /// e.g. esi- in isitya esikhulu
+    Adjective,
+    /// e.g. esi- in isitya esiphukileyo
+    Relative,
+    /// "This", e.g. esi in esi sitya
+    DemonstrativeNear,
+    /// "That", e.g. eso in eso sitya
+    DemonstrativeMiddle,
+    /// "That over there", e.g. esiya in esiya sitya
+    DemonstrativeFar,
+}
+
+impl ConcordKind {
+    pub const ALL: [ConcordKind; 8] = [
+        ConcordKind::Subject,
+        ConcordKind::Object,
+        ConcordKind::Possessive,
+        ConcordKind::Adjective,
+        ConcordKind::Relative,
+        ConcordKind::DemonstrativeNear,
+        ConcordKind::DemonstrativeMiddle,
+        ConcordKind::DemonstrativeFar,
+    ];
+
+    /// Whether the concord is a standalone word rather than an affix
+    pub fn is_word(&self) -> bool {
+        matches!(
+            self,
+            ConcordKind::DemonstrativeNear
+                | ConcordKind::DemonstrativeMiddle
+                | ConcordKind::DemonstrativeFar
+        )
+    }
+}
+
+impl ToTranslationKey for ConcordKind {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            ConcordKind::Subject => "concords.subject",
+            ConcordKind::Object => "concords.object",
+            ConcordKind::Possessive => "concords.possessive",
+            ConcordKind::Adjective => "concords.adjective",
+            ConcordKind::Relative => "concords.relative",
+            ConcordKind::DemonstrativeNear => "concords.demonstrative-near",
+            ConcordKind::DemonstrativeMiddle => "concords.demonstrative-middle",
+            ConcordKind::DemonstrativeFar => "concords.demonstrative-far",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConcordTable {
+    pub noun_class: NounClass,
+    pub rows: Vec<ConcordRow>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConcordRow {
+    pub kind: ConcordKind,
+    /// The concord itself, without hyphens, e.g. "si"
+    pub concord: String,
+}
+
+impl ConcordRow {
+    /// The concord as it is usually written, with a hyphen marking where it attaches to the word
+    pub fn display_form(&self) -> String {
+        match self.kind {
+            ConcordKind::Object => format!("-{}-", self.concord),
+            kind if kind.is_word() => self.concord.clone(),
+            _ => format!("{}-", self.concord),
+        }
+    }
+}
+
+impl ConcordTable {
+    pub fn for_noun_class(noun_class: NounClass) -> ConcordTable {
+        ConcordTable {
+            noun_class,
+            rows: ConcordKind::ALL
+                .into_iter()
+                .map(|kind| ConcordRow {
+                    kind,
+                    concord: concord(noun_class, kind),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl SubjectConcord {
+    /// The subject which agrees with a noun of the given class
+    pub fn for_noun_class(noun_class: NounClass) -> SubjectConcord {
+        use NounClass::*;
+
+        match noun_class {
+            Class1Um | U => SubjectConcord::Class1,
+            Aba | Oo => SubjectConcord::Class2,
+            Class3Um => SubjectConcord::Class3,
+            Imi => SubjectConcord::Class4,
+            Ili => SubjectConcord::Class5,
+            Ama => SubjectConcord::Class6,
+            Isi => SubjectConcord::Class7,
+            Izi => SubjectConcord::Class8,
+            In => SubjectConcord::Class9,
+            Izin => SubjectConcord::Class10,
+            Ulu => SubjectConcord::Class11,
+            Ubu => SubjectConcord::Class14,
+            Uku => SubjectConcord::Class15,
+        }
+    }
+}
+
+/// The concord of the given kind which agrees with a noun of the given class
+pub fn concord(noun_class: NounClass, kind: ConcordKind) -> String {
+    use ConcordKind::*;
+    use NounClass::*;
+
+    let subject = SubjectConcord::for_noun_class(noun_class).concord();
+
+    let index = match kind {
+        Subject => return subject.to_owned(),
+        Relative => return relative_concord(subject),
+        Object => 0,
+        Possessive => 1,
+        Adjective => 2,
+        DemonstrativeNear => 3,
+        DemonstrativeMiddle => 4,
+        DemonstrativeFar => 5,
+    };
+
+    let concords = match noun_class {
+        Class1Um | U => ["m", "wa", "om", "lo", "lowo", "lowa"],
+        Aba | Oo => ["ba", "ba", "aba", "aba", "abo", "abaya"],
+        Class3Um => ["wu", "wa", "om", "lo", "lowo", "lowa"],
+        Imi => ["yi", "ya", "emi", "le", "leyo", "leya"],
+        Ili => ["li", "la", "eli", "eli", "elo", "eliya"],
+        Ama => ["wa", "a", "ama", "la", "lawo", "lawa"],
+        Isi => ["si", "sa", "esi", "esi", "eso", "esiya"],
+        Izi => ["zi", "za", "ezi", "ezi", "ezo", "eziya"],
+        In => ["yi", "ya", "en", "le", "leyo", "leya"],
+        Izin => ["zi", "za", "ezin", "ezi", "ezo", "eziya"],
+        Ulu => ["lu", "lwa", "olu", "olu", "olo", "oluya"],
+        Ubu => ["bu", "ba", "obu", "obu", "obo", "obuya"],
+        Uku => ["ku", "kwa", "oku", "oku", "oko", "okuya"],
+    };
+
+    concords[index].to_owned()
+}
+
+/// The relative concord is the subject concord preceded by the vowel agreeing with it: a after a,
+/// e after i and o after u. A subject concord which is only a vowel merges with it, e.g. esi- from
+/// si- but o- from u-.
+fn relative_concord(subject: &str) -> String {
+    let vowel = match subject.chars().last().unwrap() {
+        'i' => 'e',
+        'u' => 'o',
+        vowel => vowel,
+    };
+
+    if subject.len() == 1 {
+        vowel.to_string()
+    } else {
+        format!("{vowel}{subject}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ConcordKind::*;
+
+    fn concords(noun_class: NounClass) -> Vec<String> {
+        ConcordKind::ALL
+            .into_iter()
+            .map(|kind| concord(noun_class, kind))
+            .collect()
+    }
+
+    #[test]
+    fn concords_of_classes() {
+        // umntu, isitya and inja
+        assert_eq!(
+            concords(NounClass::Class1Um),
+            ["u", "m", "wa", "om", "o", "lo", "lowo", "lowa"]
+        );
+        assert_eq!(
+            concords(NounClass::Isi),
+            ["si", "si", "sa", "esi", "esi", "esi", "eso", "esiya"]
+        );
+        assert_eq!(
+            concords(NounClass::In),
+            ["i", "yi", "ya", "en", "e", "le", "leyo", "leya"]
+        );
+        assert_eq!(
+            concords(NounClass::Ama),
+            ["a", "wa", "a", "ama", "a", "la", "lawo", "lawa"]
+        );
+    }
+
+    #[test]
+    fn relative_concords() {
+        let relatives: Vec<String> = [
+            NounClass::Aba,
+            NounClass::Imi,
+            NounClass::Ulu,
+            NounClass::Izin,
+        ]
+        .into_iter()
+        .map(|noun_class| concord(noun_class, Relative))
+        .collect();
+
+        assert_eq!(relatives, ["aba", "e", "olu", "ezi"]);
+    }
+
+    #[test]
+    fn classes_1a_and_2a_share_concords() {
+        assert_eq!(concords(NounClass::U), concords(NounClass::Class1Um));
+        assert_eq!(concords(NounClass::Oo), concords(NounClass::Aba));
+        assert_eq!(
+            SubjectConcord::for_noun_class(NounClass::U),
+            SubjectConcord::Class1
+        );
+    }
+
+    #[test]
+    fn subject_concords_match_conjugation() {
+        for subject in SubjectConcord::ALL {
+            if let Some(noun_class) = subject.noun_class() {
+                assert_eq!(SubjectConcord::for_noun_class(noun_class), subject);
+                assert_eq!(concord(noun_class, Subject), subject.concord());
+            }
+        }
+    }
+
+    #[test]
+    fn display_forms() {
+        let table = ConcordTable::for_noun_class(NounClass::Isi);
+        let display = |kind| {
+            table
+                .rows
+                .iter()
+                .find(|row| row.kind == kind)
+                .unwrap()
+                .display_form()
+        };
+
+        assert_eq!(display(Subject), "si-");
+        assert_eq!(display(Object), "-si-");
+        assert_eq!(display(Possessive), "sa-");
+        assert_eq!(display(DemonstrativeFar), "esiya");
+    }
+}
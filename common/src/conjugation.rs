@@ -0,0 +1,564 @@
+//! Conjugation tables of verbs, generated from the infinitive. The forms are generated by rule, so
+//! moderators may override any irregular ones for a particular word.
+
+use crate::i18n::{ToTranslationKey, TranslationKey};
+use isixhosa::noun::NounClass;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// Stems which begin with a latent i. It surfaces as y after a prefix ending in i, e.g. andiyazi
+/// rather than *andazi, where other vowel-commencing stems coalesce with the prefix.
+const LATENT_I_STEMS: [&str; 5] = ["akha", "alusa", "aluka", "ambatha", "azi"];
+
+#[derive(
+    IntoPrimitive,
+    TryFromPrimitive,
+    Serialize,
+    Deserialize,
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialOrd,
+    Ord,
+    PartialEq,
+    Eq,
+)]
+#[repr(u8)]
+#[serde(rename_all = "snake_case")]
+pub enum Tense {
+    /// e.g. ndiyabona
+    Present = 1,
+    /// The remote past, e.g. ndabona
+    Past = 2,
+    /// e.g. ndiza kubona
+    Future = 3,
+    /// The recent past, e.g. ndibonile
+    Perfect = 4,
+    /// e.g. ndibone
+    Subjunctive = 5,
+}
+
+impl Tense {
+    pub const ALL: [Tense; 5] = [
+        Tense::Present,
+        Tense::Past,
+        Tense::Future,
+        Tense::Perfect,
+        Tense::Subjunctive,
+    ];
+
+    /// The name of the tense as used in forms, consistent with the serde serialization
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tense::Present => "present",
+            Tense::Past => "past",
+            Tense::Future => "future",
+            Tense::Perfect => "perfect",
+            Tense::Subjunctive => "subjunctive",
+        }
+    }
+}
+
+impl ToTranslationKey for Tense {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            Tense::Present => "conjugation.present",
+            Tense::Past => "conjugation.past",
+            Tense::Future => "conjugation.future",
+            Tense::Perfect => "conjugation.perfect",
+            Tense::Subjunctive => "conjugation.subjunctive",
+        })
+    }
+}
+
+#[derive(
+    IntoPrimitive,
+    TryFromPrimitive,
+    Serialize,
+    Deserialize,
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialOrd,
+    Ord,
+    PartialEq,
+    Eq,
+)]
+#[repr(u8)]
+#[serde(rename_all = "snake_case")]
+pub enum Polarity {
+    Positive = 1,
+    Negative = 2,
+}
+
+impl Polarity {
+    pub const ALL: [Polarity; 2] = [Polarity::Positive, Polarity::Negative];
+
+    /// The name of the polarity as used in forms, consistent with the serde serialization
+    pub fn name(&self) -> &'static str {
+        match self {
+            Polarity::Positive => "positive",
+            Polarity::Negative => "negative",
+        }
+    }
+}
+
+impl ToTranslationKey for Polarity {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            Polarity::Positive => "conjugation.positive",
+            Polarity::Negative => "conjugation.negative",
+        })
+    }
+}
+
+/// The subject which a verb agrees with, either a person or a noun class. Classes 1a and 2a share
+/// the concords of classes 1 and 2, so they are not listed separately.
+#[derive(
+    IntoPrimitive,
+    TryFromPrimitive,
+    Serialize,
+    Deserialize,
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialOrd,
+    Ord,
+    PartialEq,
+    Eq,
+)]
+#[repr(u8)]
+#[serde(rename_all = "snake_case")]
+pub enum SubjectConcord {
+    FirstSingular = 1,
+    SecondSingular = 2,
+    FirstPlural = 3,
+    SecondPlural = 4,
+    Class1 = 5,
+    Class2 = 6,
+    Class3 = 7,
+    Class4 = 8,
+    Class5 = 9,
+    Class6 = 10,
+    Class7 = 11,
+    Class8 = 12,
+    Class9 = 13,
+    Class10 = 14,
+    Class11 = 15,
+    Class14 = 16,
+    Class15 = 17,
+}
+
+impl SubjectConcord {
+    pub const ALL: [SubjectConcord; 17] = [
+        SubjectConcord::FirstSingular,
+        SubjectConcord::SecondSingular,
+        SubjectConcord::FirstPlural,
+        SubjectConcord::SecondPlural,
+        SubjectConcord::Class1,
+        SubjectConcord::Class2,
+        SubjectConcord::Class3,
+        SubjectConcord::Class4,
+        SubjectConcord::Class5,
+        SubjectConcord::Class6,
+        SubjectConcord::Class7,
+        SubjectConcord::Class8,
+        SubjectConcord::Class9,
+        SubjectConcord::Class10,
+        SubjectConcord::Class11,
+        SubjectConcord::Class14,
+        SubjectConcord::Class15,
+    ];
+
+    /// The name of the subject as used in forms, consistent with the serde serialization
+    pub fn name(&self) -> &'static str {
+        use SubjectConcord::*;
+
+        match self {
+            FirstSingular => "first_singular",
+            SecondSingular => "second_singular",
+            FirstPlural => "first_plural",
+            SecondPlural => "second_plural",
+            Class1 => "class1",
+            Class2 => "class2",
+            Class3 => "class3",
+            Class4 => "class4",
+            Class5 => "class5",
+            Class6 => "class6",
+            Class7 => "class7",
+            Class8 => "class8",
+            Class9 => "class9",
+            Class10 => "class10",
+            Class11 => "class11",
+            Class14 => "class14",
+            Class15 => "class15",
+        }
+    }
+
+    /// The noun class of the subject, or `None` if it is a person
+    pub fn noun_class(&self) -> Option<NounClass> {
+        use SubjectConcord::*;
+
+        Some(match self {
+            FirstSingular | SecondSingular | FirstPlural | SecondPlural => return None,
+            Class1 => NounClass::Class1Um,
+            Class2 => NounClass::Aba,
+            Class3 => NounClass::Class3Um,
+            Class4 => NounClass::Imi,
+            Class5 => NounClass::Ili,
+            Class6 => NounClass::Ama,
+            Class7 => NounClass::Isi,
+            Class8 => NounClass::Izi,
+            Class9 => NounClass::In,
+            Class10 => NounClass::Izin,
+            Class11 => NounClass::Ulu,
+            Class14 => NounClass::Ubu,
+            Class15 => NounClass::Uku,
+        })
+    }
+
+    /// The positive subject concord, e.g. ndi- in ndiyabona
+    pub fn concord(&self) -> &'static str {
+        use SubjectConcord::*;
+
+        match self {
+            FirstSingular => "ndi",
+            SecondSingular | Class1 | Class3 => "u",
+            FirstPlural | Class7 => "si",
+            SecondPlural => "ni",
+            Class2 => "ba",
+            Class4 | Class9 => "i",
+            Class5 => "li",
+            Class6 => "a",
+            Class8 | Class10 => "zi",
+            Class11 => "lu",
+            Class14 => "bu",
+            Class15 => "ku",
+        }
+    }
+
+    /// The negative subject concord, e.g. andi- in andiboni
+    pub fn negative_concord(&self) -> &'static str {
+        use SubjectConcord::*;
+
+        match self {
+            FirstSingular => "andi",
+            SecondSingular | Class15 => "aku",
+            Class1 => "aka",
+            Class3 => "awu",
+            FirstPlural | Class7 => "asi",
+            SecondPlural => "ani",
+            Class2 => "aba",
+            Class4 | Class9 => "ayi",
+            Class5 => "ali",
+            Class6 => "awa",
+            Class8 | Class10 => "azi",
+            Class11 => "alu",
+            Class14 => "abu",
+        }
+    }
+}
+
+/// A form of a verb which a moderator has set by hand, replacing the generated one
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConjugationOverride {
+    pub tense: Tense,
+    pub polarity: Polarity,
+    pub subject: SubjectConcord,
+    pub form: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConjugationTable {
+    pub stem: String,
+    pub paradigms: Vec<Paradigm>,
+}
+
+/// The forms of a verb in one tense, for every subject
+#[derive(Clone, Debug, Serialize)]
+pub struct Paradigm {
+    pub tense: Tense,
+    pub rows: Vec<ParadigmRow>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ParadigmRow {
+    pub subject: SubjectConcord,
+    pub positive: ConjugatedForm,
+    pub negative: ConjugatedForm,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConjugatedForm {
+    pub form: String,
+    /// Whether the form was set by a moderator rather than generated
+    pub is_override: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerbStem {
+    stem: String,
+    latent_i: bool,
+}
+
+impl VerbStem {
+    /// Extracts the stem from the infinitive, e.g. bona from ukubona, enza from ukwenza and oyika
+    /// from ukoyika. Returns `None` if it is not the infinitive of a single verb.
+    pub fn from_infinitive(infinitive: &str) -> Option<VerbStem> {
+        let infinitive = infinitive.trim().to_lowercase().replace('-', "");
+
+        let stem = infinitive
+            .strip_prefix("uku")
+            .or_else(|| {
+                infinitive
+                    .strip_prefix("ukw")
+                    .filter(|s| starts_with_vowel(s))
+            })
+            .or_else(|| infinitive.strip_prefix("uk").filter(|s| s.starts_with('o')))?;
+
+        if stem.is_empty() || !stem.chars().all(char::is_alphabetic) {
+            return None;
+        }
+
+        Some(VerbStem {
+            latent_i: LATENT_I_STEMS.contains(&stem),
+            stem: stem.to_owned(),
+        })
+    }
+
+    pub fn stem(&self) -> &str {
+        &self.stem
+    }
+
+    /// Generates the full table, using the overridden form where there is one
+    pub fn conjugate(&self, overrides: &[ConjugationOverride]) -> ConjugationTable {
+        let form = |tense, polarity, subject| {
+            let overridden = overrides
+                .iter()
+                .find(|o| o.tense == tense && o.polarity == polarity && o.subject == subject);
+
+            match overridden {
+                Some(o) => ConjugatedForm {
+                    form: o.form.clone(),
+                    is_override: true,
+                },
+                None => ConjugatedForm {
+                    form: self.generate(tense, polarity, subject),
+                    is_override: false,
+                },
+            }
+        };
+
+        let paradigms = Tense::ALL
+            .into_iter()
+            .map(|tense| Paradigm {
+                tense,
+                rows: SubjectConcord::ALL
+                    .into_iter()
+                    .map(|subject| ParadigmRow {
+                        subject,
+                        positive: form(tense, Polarity::Positive, subject),
+                        negative: form(tense, Polarity::Negative, subject),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        ConjugationTable {
+            stem: self.stem.clone(),
+            paradigms,
+        }
+    }
+
+    /// Generates a single form by rule, ignoring any overrides
+    pub fn generate(&self, tense: Tense, polarity: Polarity, subject: SubjectConcord) -> String {
+        let concord = subject.concord();
+        let negative = subject.negative_concord();
+        let stem = self.stem.as_str();
+
+        match (tense, polarity) {
+            (Tense::Present, Polarity::Positive) => self.join(&format!("{concord}ya"), stem),
+            (Tense::Present, Polarity::Negative) => self.join(negative, &self.with_ending("i")),
+            (Tense::Past, Polarity::Positive) => self.join(&coalesce(concord, "a", false), stem),
+            (Tense::Past, Polarity::Negative) => self.join(&coalesce(negative, "a", false), stem),
+            (Tense::Future, Polarity::Positive) => {
+                format!("{concord}za {}", self.join("ku", stem))
+            }
+            (Tense::Future, Polarity::Negative) => {
+                format!("{negative}yi {}", self.join("ku", stem))
+            }
+            (Tense::Perfect, Polarity::Positive) => self.join(concord, &self.with_ending("ile")),
+            (Tense::Perfect, Polarity::Negative) => self.join(negative, &self.with_ending("anga")),
+            (Tense::Subjunctive, Polarity::Positive) => self.join(concord, &self.with_ending("e")),
+            (Tense::Subjunctive, Polarity::Negative) => {
+                self.join(&format!("{concord}nga"), &self.with_ending("i"))
+            }
+        }
+    }
+
+    fn join(&self, prefix: &str, stem: &str) -> String {
+        coalesce(prefix, stem, self.latent_i)
+    }
+
+    /// Replaces the final -a of the stem with the ending. Stems which do not end in -a, such as
+    /// -azi and -thi, keep their final vowel, except before -anga which becomes -nga.
+    fn with_ending(&self, ending: &str) -> String {
+        match self.stem.strip_suffix('a') {
+            Some(root) => format!("{root}{ending}"),
+            None if ending == "anga" => format!("{}nga", self.stem),
+            None => self.stem.clone(),
+        }
+    }
+}
+
+fn starts_with_vowel(s: &str) -> bool {
+    s.starts_with(['a', 'e', 'i', 'o', 'u'])
+}
+
+/// Joins a prefix to a stem. Where a vowel-commencing stem follows a prefix ending in a vowel, the
+/// vowels coalesce: a, e and i are dropped (ndi + enza = ndenza), u becomes w (ku + enza = kwenza)
+/// except before o where it is dropped too (ku + oyika = koyika), and a lone i or u becomes y or w
+/// (i + enza = yenza). A latent i instead surfaces as y after i.
+fn coalesce(prefix: &str, stem: &str, latent_i: bool) -> String {
+    if !starts_with_vowel(stem) {
+        return format!("{prefix}{stem}");
+    }
+
+    match prefix.chars().last() {
+        Some('i') if latent_i => format!("{prefix}y{stem}"),
+        Some('i') if prefix == "i" => format!("y{stem}"),
+        Some('u') if prefix != "u" && stem.starts_with('o') => {
+            format!("{}{stem}", &prefix[..prefix.len() - 1])
+        }
+        Some('u') => format!("{}w{stem}", &prefix[..prefix.len() - 1]),
+        Some('a' | 'e' | 'i') => format!("{}{stem}", &prefix[..prefix.len() - 1]),
+        _ => format!("{prefix}{stem}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Polarity::*;
+    use SubjectConcord::*;
+    use Tense::*;
+
+    fn stem(infinitive: &str) -> VerbStem {
+        VerbStem::from_infinitive(infinitive).unwrap()
+    }
+
+    #[test]
+    fn stems_from_infinitives() {
+        assert_eq!(stem("ukuthetha").stem(), "thetha");
+        assert_eq!(stem("ukwenza").stem(), "enza");
+        assert_eq!(stem("ukwazi").stem(), "azi");
+        assert_eq!(stem("ukoyika").stem(), "oyika");
+        assert_eq!(stem(" Uku-bona ").stem(), "bona");
+
+        assert_eq!(VerbStem::from_infinitive("thetha"), None);
+        assert_eq!(VerbStem::from_infinitive("uku"), None);
+        assert_eq!(VerbStem::from_infinitive("ukuthetha kakuhle"), None);
+    }
+
+    #[test]
+    fn consonant_commencing_stem() {
+        let thetha = stem("ukuthetha");
+        let forms = [
+            (Present, Positive, FirstSingular, "ndiyathetha"),
+            (Present, Positive, Class2, "bayathetha"),
+            (Present, Negative, FirstSingular, "andithethi"),
+            (Present, Negative, SecondSingular, "akuthethi"),
+            (Present, Negative, Class1, "akathethi"),
+            (Past, Positive, FirstSingular, "ndathetha"),
+            (Past, Positive, Class1, "wathetha"),
+            (Past, Positive, Class4, "yathetha"),
+            (Past, Positive, Class6, "athetha"),
+            (Past, Negative, FirstPlural, "asathetha"),
+            (Future, Positive, FirstSingular, "ndiza kuthetha"),
+            (Future, Negative, FirstSingular, "andiyi kuthetha"),
+            (Perfect, Positive, SecondPlural, "nithethile"),
+            (Perfect, Negative, FirstSingular, "andithethanga"),
+            (Subjunctive, Positive, Class7, "sithethe"),
+            (Subjunctive, Negative, FirstSingular, "ndingathethi"),
+        ];
+
+        for (tense, polarity, subject, form) in forms {
+            assert_eq!(thetha.generate(tense, polarity, subject), form);
+        }
+    }
+
+    #[test]
+    fn vowel_commencing_stems() {
+        let oyika = stem("ukoyika");
+        let forms = [
+            (Present, Positive, FirstSingular, "ndiyoyika"),
+            (Present, Negative, FirstSingular, "andoyiki"),
+            (Present, Negative, SecondSingular, "akoyiki"),
+            (Present, Negative, Class3, "awoyiki"),
+            (Past, Positive, FirstSingular, "ndoyika"),
+            (Past, Positive, Class1, "woyika"),
+            (Future, Positive, Class2, "baza koyika"),
+            (Perfect, Positive, FirstSingular, "ndoyikile"),
+            (Perfect, Positive, Class1, "woyikile"),
+            (Perfect, Positive, Class4, "yoyikile"),
+            (Subjunctive, Negative, FirstSingular, "ndingoyiki"),
+        ];
+
+        for (tense, polarity, subject, form) in forms {
+            assert_eq!(oyika.generate(tense, polarity, subject), form);
+        }
+
+        let enza = stem("ukwenza");
+        assert_eq!(enza.generate(Present, Positive, FirstSingular), "ndiyenza");
+        assert_eq!(enza.generate(Perfect, Positive, SecondSingular), "wenzile");
+        assert_eq!(enza.generate(Future, Positive, FirstPlural), "siza kwenza");
+    }
+
+    #[test]
+    fn latent_i_stem() {
+        let azi = stem("ukwazi");
+        let forms = [
+            (Present, Positive, FirstSingular, "ndiyazi"),
+            (Present, Positive, Class1, "uyazi"),
+            (Present, Negative, FirstSingular, "andiyazi"),
+            (Present, Negative, FirstPlural, "asiyazi"),
+            (Present, Negative, SecondSingular, "akwazi"),
+            (Present, Negative, Class1, "akazi"),
+            (Future, Positive, FirstSingular, "ndiza kwazi"),
+        ];
+
+        for (tense, polarity, subject, form) in forms {
+            assert_eq!(azi.generate(tense, polarity, subject), form);
+        }
+    }
+
+    #[test]
+    fn overrides_replace_generated_forms() {
+        let overrides = [ConjugationOverride {
+            tense: Perfect,
+            polarity: Positive,
+            subject: FirstSingular,
+            form: "ndazi".to_owned(),
+        }];
+        let table = stem("ukwazi").conjugate(&overrides);
+
+        let perfect = table.paradigms.iter().find(|p| p.tense == Perfect).unwrap();
+        let row = perfect
+            .rows
+            .iter()
+            .find(|r| r.subject == FirstSingular)
+            .unwrap();
+        assert_eq!(row.positive.form, "ndazi");
+        assert!(row.positive.is_override);
+        assert!(!row.negative.is_override);
+
+        assert_eq!(table.paradigms.len(), Tense::ALL.len());
+        assert!(table
+            .paradigms
+            .iter()
+            .all(|p| p.rows.len() == SubjectConcord::ALL.len()));
+    }
+}
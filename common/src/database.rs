@@ -1,11 +1,14 @@
+use crate::conjugation::{ConjugationOverride, Polarity, SubjectConcord, Tense};
 use crate::database::db_impl::DbImpl;
 use crate::i18n::I18nInfo;
 use crate::language::{
-    ConjunctionFollowedBy, NounClassExt, PartOfSpeech, Transitivity, WordLinkType,
+    ConjunctionFollowedBy, EtymologyLanguage, LabelKind, NounClassExt, PartOfSpeech, ReferenceKind,
+    Transitivity, WordLinkType,
 };
 use crate::serialization::{DiscrimOutOfRange, WithDeleteSentinel};
 use crate::types::{
-    Dataset, ExistingExample, ExistingLinkedWord, ExistingWord, PublicUserInfo, WordHit,
+    Citation, Dataset, ExistingExample, ExistingLinkedWord, ExistingSense, ExistingWord, Label,
+    PublicUserInfo, Reference, WordHit,
 };
 use anyhow::{Context, Result};
 use askama_warp::warp;
@@ -14,16 +17,21 @@ use fallible_iterator::FallibleIterator;
 use fluent_templates::ArcLoader;
 use isixhosa::noun::NounClass;
 use num_enum::TryFromPrimitive;
+use parking_lot::lock_api::ArcReentrantMutexGuard;
+use parking_lot::{RawMutex, RawThreadId, ReentrantMutex};
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, Value, ValueRef};
-use rusqlite::{params, Row};
+use rusqlite::{params, Connection, Row};
 use rusqlite::{OptionalExtension, ToSql};
 use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
+use std::marker::PhantomData;
 use std::num::NonZeroU64;
+use std::ops::Deref;
 use std::str::FromStr;
-use tracing::{instrument, Span};
+use std::sync::Arc;
+use tracing::{error, instrument, Span};
 
 #[derive(Clone)]
 pub struct DbBase(pub Pool<SqliteConnectionManager>);
@@ -36,15 +44,16 @@ impl DbBase {
 
 pub mod db_impl {
     use super::*;
-    use r2d2::PooledConnection;
     use r2d2_sqlite::SqliteConnectionManager;
 
     #[derive(Clone)]
     pub struct DbImpl(pub Pool<SqliteConnectionManager>);
 
     impl PublicAccessDb for DbImpl {
-        fn get(&self) -> Result<PooledConnection<SqliteConnectionManager>, r2d2::Error> {
-            self.0.get()
+        fn get(&self) -> Result<DbConnection, r2d2::Error> {
+            self.0
+                .get()
+                .map(|conn| DbConnection(ConnectionKind::Pooled(conn)))
         }
     }
 
@@ -54,13 +63,96 @@ pub mod db_impl {
 }
 
 pub trait PublicAccessDb: Clone + Send + Sync + 'static {
-    fn get(&self) -> Result<PooledConnection<SqliteConnectionManager>, r2d2::Error>;
+    fn get(&self) -> Result<DbConnection, r2d2::Error>;
 }
 
 pub trait UserAccessDb: PublicAccessDb {}
 pub trait ModeratorAccessDb: UserAccessDb {}
 pub trait AdministratorAccessDb: ModeratorAccessDb {}
 
+/// A connection to the database, either taken from the pool or the connection of a
+/// [`DbTransaction`].
+pub struct DbConnection(ConnectionKind);
+
+enum ConnectionKind {
+    Pooled(PooledConnection<SqliteConnectionManager>),
+    Transaction(ArcReentrantMutexGuard<RawMutex, RawThreadId, TransactionConnection>),
+}
+
+impl Deref for DbConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        match &self.0 {
+            ConnectionKind::Pooled(conn) => conn,
+            ConnectionKind::Transaction(conn) => &conn.0,
+        }
+    }
+}
+
+/// The connection of a transaction, which rolls it back if it was never committed.
+struct TransactionConnection(PooledConnection<SqliteConnectionManager>);
+
+impl Drop for TransactionConnection {
+    fn drop(&mut self) {
+        if !self.0.is_autocommit() {
+            if let Err(e) = self.0.execute_batch("ROLLBACK;") {
+                error!("Error rolling back transaction: {e:?}");
+            }
+        }
+    }
+}
+
+/// A database with the same access as `D`, where everything happens on one connection within a
+/// single transaction. The changes are only kept if it is [committed](DbTransaction::commit) -
+/// otherwise they are rolled back once the last clone is dropped, e.g. when a task fails or panics.
+pub struct DbTransaction<D> {
+    conn: Arc<ReentrantMutex<TransactionConnection>>,
+    access: PhantomData<D>,
+}
+
+impl<D: PublicAccessDb> DbTransaction<D> {
+    pub fn begin(db: &D) -> DbTransaction<D> {
+        let conn = match db.get().unwrap().0 {
+            ConnectionKind::Pooled(conn) => conn,
+            ConnectionKind::Transaction(_) => panic!("Transactions cannot be nested"),
+        };
+
+        conn.execute_batch("BEGIN IMMEDIATE;").unwrap();
+
+        DbTransaction {
+            conn: Arc::new(ReentrantMutex::new(TransactionConnection(conn))),
+            access: PhantomData,
+        }
+    }
+
+    /// Commits the transaction. If this fails, it is rolled back once the last clone is dropped.
+    pub fn commit(&self) -> rusqlite::Result<()> {
+        self.conn.lock().0.execute_batch("COMMIT;")
+    }
+}
+
+impl<D> Clone for DbTransaction<D> {
+    fn clone(&self) -> Self {
+        DbTransaction {
+            conn: self.conn.clone(),
+            access: PhantomData,
+        }
+    }
+}
+
+impl<D: PublicAccessDb> PublicAccessDb for DbTransaction<D> {
+    fn get(&self) -> Result<DbConnection, r2d2::Error> {
+        Ok(DbConnection(ConnectionKind::Transaction(
+            self.conn.lock_arc(),
+        )))
+    }
+}
+
+impl<D: UserAccessDb> UserAccessDb for DbTransaction<D> {}
+impl<D: ModeratorAccessDb> ModeratorAccessDb for DbTransaction<D> {}
+impl<D: AdministratorAccessDb> AdministratorAccessDb for DbTransaction<D> {}
+
 pub fn with_public_db(
     db: DbBase,
 ) -> impl Filter<Extract = (impl PublicAccessDb,), Error = Infallible> + Clone {
@@ -244,7 +336,7 @@ impl ExistingLinkedWord {
 
         Span::current().record("results", vec.len());
 
-        vec.sort_by_key(|l| l.link_type);
+        vec.sort_by_key(|l| l.relative_link_type());
         vec
     }
 
@@ -308,15 +400,158 @@ impl ExistingLinkedWord {
     }
 }
 
+impl ExistingSense {
+    #[instrument(
+        level = "trace",
+        name = "Fetch all existing senses for word",
+        fields(results),
+        skip(db)
+    )]
+    pub fn fetch_all_for_word(db: &impl PublicAccessDb, word_id: u64) -> Vec<ExistingSense> {
+        const SELECT: &str = "
+            SELECT sense_id, word_id, sense_order, english, is_informal FROM senses
+                WHERE word_id = ?1
+                ORDER BY sense_order, sense_id;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+        let rows = query.query(params![word_id]).unwrap();
+
+        #[allow(clippy::redundant_closure)] // "implementation of FnOnce is not general enough"
+        let mut senses: Vec<Self> = rows
+            .map(|row| ExistingSense::try_from(row))
+            .collect()
+            .unwrap();
+
+        for sense in &mut senses {
+            sense.populate(db);
+        }
+
+        Span::current().record("results", senses.len());
+
+        senses
+    }
+
+    #[instrument(
+        level = "trace",
+        name = "Fetch existing sense",
+        fields(found),
+        skip(db)
+    )]
+    pub fn fetch(db: &impl PublicAccessDb, sense_id: u64) -> Option<ExistingSense> {
+        const SELECT: &str = "
+            SELECT sense_id, word_id, sense_order, english, is_informal FROM senses
+                WHERE sense_id = ?1;
+        ";
+
+        let conn = db.get().unwrap();
+        #[allow(clippy::redundant_closure)] // "implementation of FnOnce is not general enough"
+        let mut opt = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query_row(params![sense_id], |row| ExistingSense::try_from(row))
+            .optional()
+            .unwrap();
+
+        if let Some(sense) = opt.as_mut() {
+            sense.populate(db);
+        }
+
+        Span::current().record("found", opt.is_some());
+
+        opt
+    }
+
+    /// The sense which the example is assigned to, if any
+    pub fn fetch_id_for_example(db: &impl PublicAccessDb, example_id: u64) -> Option<u64> {
+        const SELECT: &str = "SELECT sense_id FROM example_senses WHERE example_id = ?1;";
+
+        let conn = db.get().unwrap();
+        conn.prepare(SELECT)
+            .unwrap()
+            .query_row(params![example_id], |row| row.get("sense_id"))
+            .optional()
+            .unwrap()
+    }
+
+    /// The sense of the given word which the linked word is assigned to, if any
+    pub fn fetch_id_for_linked_word(
+        db: &impl PublicAccessDb,
+        link_id: u64,
+        word_id: u64,
+    ) -> Option<u64> {
+        const SELECT: &str = "
+            SELECT linked_word_senses.sense_id FROM linked_word_senses
+                INNER JOIN senses ON linked_word_senses.sense_id = senses.sense_id
+                WHERE linked_word_senses.link_id = ?1 AND senses.word_id = ?2;
+        ";
+
+        let conn = db.get().unwrap();
+        conn.prepare(SELECT)
+            .unwrap()
+            .query_row(params![link_id, word_id], |row| row.get("sense_id"))
+            .optional()
+            .unwrap()
+    }
+
+    fn populate(&mut self, db: &impl PublicAccessDb) {
+        const SELECT_EXAMPLES: &str =
+            "SELECT example_id FROM example_senses WHERE sense_id = ?1 ORDER BY example_id;";
+        const SELECT_LINKED_WORDS: &str =
+            "SELECT link_id FROM linked_word_senses WHERE sense_id = ?1 ORDER BY link_id;";
+        const SELECT_LABELS: &str =
+            "SELECT label_id FROM sense_labels WHERE sense_id = ?1 ORDER BY label_id;";
+
+        let conn = db.get().unwrap();
+        let ids = |sql: &str, col: &str| -> Vec<u64> {
+            conn.prepare(sql)
+                .unwrap()
+                .query(params![self.sense_id])
+                .unwrap()
+                .map(|row| row.get(col))
+                .collect()
+                .unwrap()
+        };
+
+        self.examples = ids(SELECT_EXAMPLES, "example_id");
+        self.linked_words = ids(SELECT_LINKED_WORDS, "link_id");
+        self.labels = ids(SELECT_LABELS, "label_id");
+    }
+}
+
+impl TryFrom<&Row<'_>> for ExistingSense {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(ExistingSense {
+            sense_id: row.get("sense_id")?,
+            word_id: row.get("word_id")?,
+            sense_order: row.get("sense_order")?,
+            english: row.get("english")?,
+            is_informal: row.get("is_informal")?,
+            examples: vec![],
+            linked_words: vec![],
+            labels: vec![],
+        })
+    }
+}
+
 impl ExistingWord {
     #[instrument(name = "Fetch full existing word", fields(found), skip(db))]
     pub fn fetch_full(db: &impl PublicAccessDb, id: u64) -> Option<ExistingWord> {
         let mut word = ExistingWord::fetch_alone(db, id);
         if let Some(word) = word.as_mut() {
+            word.senses = ExistingSense::fetch_all_for_word(db, id);
             word.examples = ExistingExample::fetch_all_for_word(db, id);
             word.linked_words = ExistingLinkedWord::fetch_all_for_word(db, id);
             word.contributors = PublicUserInfo::fetch_public_contributors_for_word(db, id);
             word.datasets = Dataset::fetch_all_for_word(db, id);
+            word.labels = Label::fetch_all_for_word(db, id);
+            word.sense_labels = Label::fetch_all_for_senses_of_word(db, id);
+            word.citations = Citation::fetch_all_for_word(db, id);
+            word.references = Reference::fetch_all_for_word(db, id);
+            word.conjugation_overrides = ConjugationOverride::fetch_all_for_word(db, id);
         }
 
         Span::current().record("found", word.is_some());
@@ -324,6 +559,41 @@ impl ExistingWord {
         word
     }
 
+    /// Every expression on the site in alphabetical order, with just its linked words populated so
+    /// that its components can be listed.
+    #[instrument(name = "Fetch all expressions", fields(results), skip(db))]
+    pub fn fetch_all_expressions(db: &impl PublicAccessDb) -> Vec<ExistingWord> {
+        const SELECT: &str = "
+            SELECT
+                word_id, english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
+                is_inchoative, is_informal, transitivity, followed_by, noun_class, note,
+                etymology_language, etymology_form, etymology_note, literal_translation
+            FROM words
+            WHERE part_of_speech = ?1
+            ORDER BY xhosa COLLATE NOCASE;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        #[allow(clippy::redundant_closure)] // lifetime issue
+        let mut expressions: Vec<ExistingWord> = query
+            .query(params![PartOfSpeech::Expression])
+            .unwrap()
+            .map(|row| ExistingWord::try_from(row))
+            .collect()
+            .unwrap();
+
+        for expression in &mut expressions {
+            expression.linked_words =
+                ExistingLinkedWord::fetch_all_for_word(db, expression.word_id);
+        }
+
+        Span::current().record("results", expressions.len());
+
+        expressions
+    }
+
     #[instrument(
         level = "trace",
         name = "Fetch just existing word",
@@ -334,7 +604,8 @@ impl ExistingWord {
         const SELECT_ORIGINAL: &str = "
             SELECT
                 word_id, english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
-                is_inchoative, is_informal, transitivity, followed_by, noun_class, note
+                is_inchoative, is_informal, transitivity, followed_by, noun_class, note,
+                etymology_language, etymology_form, etymology_note, literal_translation
             FROM words
             WHERE word_id = ?1;
         ";
@@ -397,6 +668,8 @@ impl WordHit {
             is_informal: row.get("is_informal")?,
             transitivity: row.get_with_sentinel("transitivity")?,
             is_suggestion: id.is_suggested(),
+            senses: vec![],
+            rank_weight: 1.0,
             noun_class: row
                 .get_with_sentinel("noun_class")?
                 .map(|c: NounClass| c.to_prefixes()),
@@ -434,7 +707,7 @@ impl WordHit {
 
         // WTF rustc?
         #[allow(clippy::redundant_closure)] // implementation of FnOnce is not general enough
-        let v = conn
+        let mut v = conn
             .prepare(stmt)
             .unwrap()
             .query_row(params![id.inner()], |row| {
@@ -443,10 +716,126 @@ impl WordHit {
             .optional()
             .unwrap();
 
+        if let Some(hit) = v.as_mut() {
+            hit.senses = WordHit::fetch_sense_glosses(db, id);
+        }
+
         Span::current().record("found", v.is_some());
 
         v
     }
+
+    /// The glosses of the senses of the word, in order. For a suggested word, these are the senses
+    /// suggested along with it.
+    pub fn fetch_sense_glosses(db: &impl PublicAccessDb, id: WordOrSuggestionId) -> Vec<String> {
+        const SELECT_EXISTING: &str =
+            "SELECT english FROM senses WHERE word_id = ?1 ORDER BY sense_order, sense_id;";
+        const SELECT_SUGGESTED: &str = "
+            SELECT english FROM sense_suggestions
+                WHERE suggested_word_id = ?1 AND english IS NOT NULL
+                ORDER BY sense_order, suggestion_id;
+        ";
+
+        let stmt = match id {
+            WordOrSuggestionId::ExistingWord { .. } => SELECT_EXISTING,
+            WordOrSuggestionId::Suggested { .. } => SELECT_SUGGESTED,
+        };
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(stmt).unwrap();
+        query
+            .query(params![id.inner()])
+            .unwrap()
+            .map(|row| row.get("english"))
+            .collect()
+            .unwrap()
+    }
+
+    /// Every noun of the given class in alphabetical order
+    #[instrument(name = "Fetch nouns in class", fields(results), skip(db))]
+    pub fn fetch_all_in_noun_class(
+        db: &impl PublicAccessDb,
+        noun_class: NounClass,
+    ) -> Vec<WordHit> {
+        const SELECT: &str = "
+            SELECT
+                word_id, english, xhosa, part_of_speech, is_plural, is_inchoative, is_informal,
+                transitivity, noun_class
+            FROM words
+            WHERE part_of_speech = ?1 AND noun_class = ?2
+            ORDER BY xhosa COLLATE NOCASE;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+        let mut hits: Vec<WordHit> = query
+            .query(params![PartOfSpeech::Noun, noun_class.as_u8()])
+            .unwrap()
+            .map(|row| {
+                let id = WordOrSuggestionId::existing(row.get::<&str, i64>("word_id")? as u64);
+                WordHit::try_from_row_and_id(row, id)
+            })
+            .collect()
+            .unwrap();
+
+        for hit in &mut hits {
+            hit.senses = WordHit::fetch_sense_glosses(db, WordOrSuggestionId::existing(hit.id));
+        }
+
+        Span::current().record("results", hits.len());
+
+        hits
+    }
+
+    /// The expressions which the word is linked to as a component, in alphabetical order.
+    #[instrument(
+        level = "trace",
+        name = "Fetch expressions containing word",
+        fields(results),
+        skip(db)
+    )]
+    pub fn fetch_expressions_containing(db: &impl PublicAccessDb, word_id: u64) -> Vec<WordHit> {
+        // The expression is the second word of a ComponentOf link and the first of a HasComponent
+        const SELECT: &str = "
+            SELECT
+                words.word_id, english, xhosa, part_of_speech, is_plural, is_inchoative,
+                is_informal, transitivity, noun_class
+            FROM linked_words
+            INNER JOIN words ON words.word_id = (
+                CASE WHEN linked_words.link_type = ?2 THEN second_word_id ELSE first_word_id END
+            )
+            WHERE words.part_of_speech = ?4 AND (
+                (linked_words.link_type = ?2 AND first_word_id = ?1) OR
+                (linked_words.link_type = ?3 AND second_word_id = ?1)
+            )
+            ORDER BY xhosa COLLATE NOCASE;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+        let mut hits: Vec<WordHit> = query
+            .query(params![
+                word_id,
+                WordLinkType::ComponentOf,
+                WordLinkType::HasComponent,
+                PartOfSpeech::Expression,
+            ])
+            .unwrap()
+            .map(|row| {
+                let id = WordOrSuggestionId::existing(row.get::<&str, i64>("word_id")? as u64);
+                WordHit::try_from_row_and_id(row, id)
+            })
+            .collect()
+            .unwrap();
+
+        for hit in &mut hits {
+            hit.senses = WordHit::fetch_sense_glosses(db, WordOrSuggestionId::existing(hit.id));
+        }
+
+        Span::current().record("results", hits.len());
+
+        hits
+    }
 }
 
 impl PublicUserInfo {
@@ -600,6 +989,367 @@ impl Dataset {
     }
 }
 
+impl Label {
+    pub fn upsert(
+        db: &impl AdministratorAccessDb,
+        id: Option<u64>,
+        kind: LabelKind,
+        name: String,
+        description: String,
+        rank_weight: f64,
+    ) -> Result<u64> {
+        const UPSERT: &str = "
+            INSERT INTO labels (label_id, kind, name, description, rank_weight)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(label_id) DO UPDATE SET
+                kind = excluded.kind,
+                name = excluded.name,
+                description = excluded.description,
+                rank_weight = excluded.rank_weight
+            RETURNING label_id;
+        ";
+
+        let conn = db.get()?;
+        let mut query = conn.prepare(UPSERT)?;
+
+        query
+            .query_row(params![id, kind, name, description, rank_weight], |row| {
+                row.get("label_id")
+            })
+            .context("Failed to insert label")
+    }
+
+    pub fn fetch_by_id(db: &impl PublicAccessDb, id: u64) -> Option<Label> {
+        const SELECT: &str = "
+            SELECT label_id, kind, name, description, rank_weight FROM labels WHERE label_id = ?1;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        query
+            .query_row(params![id], |row| Label::try_from(row))
+            .optional()
+            .unwrap()
+    }
+
+    pub fn delete_by_id(db: &impl AdministratorAccessDb, id: u64) -> bool {
+        const DELETE: &str = "DELETE FROM labels WHERE label_id = ?1;";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(DELETE).unwrap();
+
+        query.execute(params![id]).unwrap() == 1
+    }
+
+    /// All labels configured for the site, grouped by kind
+    pub fn fetch_all(db: &impl PublicAccessDb) -> Vec<Label> {
+        const SELECT: &str = "
+            SELECT label_id, kind, name, description, rank_weight FROM labels
+                ORDER BY kind, name COLLATE NOCASE;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        #[allow(clippy::redundant_closure)] // lifetime issue
+        query
+            .query(params![])
+            .unwrap()
+            .map(|row| Label::try_from(row))
+            .collect()
+            .unwrap()
+    }
+
+    /// Labels of the word as a whole, not including those of its senses
+    pub fn fetch_all_for_word(db: &impl PublicAccessDb, word: u64) -> Vec<Label> {
+        const SELECT: &str = "
+            SELECT labels.label_id, labels.kind, labels.name, labels.description, labels.rank_weight
+            FROM word_labels
+            INNER JOIN labels ON labels.label_id = word_labels.label_id
+            WHERE word_id = ?1
+            ORDER BY labels.kind, labels.name COLLATE NOCASE;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        #[allow(clippy::redundant_closure)] // lifetime issue
+        query
+            .query(params![word])
+            .unwrap()
+            .map(|row| Label::try_from(row))
+            .collect()
+            .unwrap()
+    }
+
+    /// Every label used by at least one sense of the word
+    pub fn fetch_all_for_senses_of_word(db: &impl PublicAccessDb, word: u64) -> Vec<Label> {
+        const SELECT: &str = "
+            SELECT DISTINCT
+                labels.label_id, labels.kind, labels.name, labels.description, labels.rank_weight
+            FROM sense_labels
+            INNER JOIN labels ON labels.label_id = sense_labels.label_id
+            INNER JOIN senses ON senses.sense_id = sense_labels.sense_id
+            WHERE senses.word_id = ?1
+            ORDER BY labels.kind, labels.name COLLATE NOCASE;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        #[allow(clippy::redundant_closure)] // lifetime issue
+        query
+            .query(params![word])
+            .unwrap()
+            .map(|row| Label::try_from(row))
+            .collect()
+            .unwrap()
+    }
+}
+
+impl Reference {
+    pub fn upsert(
+        db: &impl ModeratorAccessDb,
+        id: Option<u64>,
+        reference: &Reference,
+    ) -> Result<u64> {
+        const UPSERT: &str = "
+            INSERT INTO bibliography (reference_id, kind, author, title, year, publisher, url)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT(reference_id) DO UPDATE SET
+                kind = excluded.kind,
+                author = excluded.author,
+                title = excluded.title,
+                year = excluded.year,
+                publisher = excluded.publisher,
+                url = excluded.url
+            RETURNING reference_id;
+        ";
+
+        let conn = db.get()?;
+        let mut query = conn.prepare(UPSERT)?;
+
+        query
+            .query_row(
+                params![
+                    id,
+                    reference.kind,
+                    reference.author,
+                    reference.title,
+                    reference.year,
+                    reference.publisher,
+                    reference.url
+                ],
+                |row| row.get("reference_id"),
+            )
+            .context("Failed to insert reference")
+    }
+
+    pub fn fetch_by_id(db: &impl PublicAccessDb, id: u64) -> Option<Reference> {
+        const SELECT: &str = "
+            SELECT reference_id, kind, author, title, year, publisher, url
+            FROM bibliography WHERE reference_id = ?1;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        query
+            .query_row(params![id], |row| Reference::try_from(row))
+            .optional()
+            .unwrap()
+    }
+
+    /// All references in the bibliography, so that moderators can cite them from any entry
+    pub fn fetch_all(db: &impl PublicAccessDb) -> Vec<Reference> {
+        const SELECT: &str = "
+            SELECT reference_id, kind, author, title, year, publisher, url FROM bibliography
+                ORDER BY author COLLATE NOCASE, year, title COLLATE NOCASE;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        #[allow(clippy::redundant_closure)] // lifetime issue
+        query
+            .query(params![])
+            .unwrap()
+            .map(|row| Reference::try_from(row))
+            .collect()
+            .unwrap()
+    }
+
+    /// Every reference cited by the word, its senses or its examples
+    pub fn fetch_all_for_word(db: &impl PublicAccessDb, word: u64) -> Vec<Reference> {
+        const SELECT: &str = "
+            SELECT DISTINCT
+                bibliography.reference_id, bibliography.kind, bibliography.author,
+                bibliography.title, bibliography.year, bibliography.publisher, bibliography.url
+            FROM citations
+            INNER JOIN bibliography ON bibliography.reference_id = citations.reference_id
+            WHERE citations.word_id = ?1
+            ORDER BY
+                bibliography.author COLLATE NOCASE, bibliography.year,
+                bibliography.title COLLATE NOCASE;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        #[allow(clippy::redundant_closure)] // lifetime issue
+        query
+            .query(params![word])
+            .unwrap()
+            .map(|row| Reference::try_from(row))
+            .collect()
+            .unwrap()
+    }
+}
+
+impl Citation {
+    pub fn fetch_by_id(db: &impl PublicAccessDb, id: u64) -> Option<Citation> {
+        const SELECT: &str = "
+            SELECT citation_id, reference_id, word_id, sense_id, example_id, pages
+            FROM citations WHERE citation_id = ?1;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        query
+            .query_row(params![id], |row| Citation::try_from(row))
+            .optional()
+            .unwrap()
+    }
+
+    /// Citations of the word, its senses and its examples
+    pub fn fetch_all_for_word(db: &impl PublicAccessDb, word: u64) -> Vec<Citation> {
+        const SELECT: &str = "
+            SELECT citation_id, reference_id, word_id, sense_id, example_id, pages
+            FROM citations WHERE word_id = ?1
+            ORDER BY citation_id;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        #[allow(clippy::redundant_closure)] // lifetime issue
+        query
+            .query(params![word])
+            .unwrap()
+            .map(|row| Citation::try_from(row))
+            .collect()
+            .unwrap()
+    }
+}
+
+impl ConjugationOverride {
+    pub fn fetch_all_for_word(db: &impl PublicAccessDb, word: u64) -> Vec<ConjugationOverride> {
+        const SELECT: &str = "
+            SELECT tense, polarity, subject, form FROM conjugation_overrides
+                WHERE word_id = ?1
+                ORDER BY tense, polarity, subject;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+
+        #[allow(clippy::redundant_closure)] // lifetime issue
+        query
+            .query(params![word])
+            .unwrap()
+            .map(|row| ConjugationOverride::try_from(row))
+            .collect()
+            .unwrap()
+    }
+
+    /// Replaces all of the overridden forms of the word with the given ones
+    #[instrument(name = "Replace conjugation overrides", skip(db, overrides), fields(count = overrides.len()))]
+    pub fn replace_all_for_word(
+        db: &impl ModeratorAccessDb,
+        word: u64,
+        overrides: &[ConjugationOverride],
+    ) {
+        const DELETE: &str = "DELETE FROM conjugation_overrides WHERE word_id = ?1;";
+        const INSERT: &str = "
+            INSERT INTO conjugation_overrides (word_id, tense, polarity, subject, form)
+                VALUES (?1, ?2, ?3, ?4, ?5);
+        ";
+
+        let conn = db.get().unwrap();
+        conn.prepare(DELETE)
+            .unwrap()
+            .execute(params![word])
+            .unwrap();
+
+        let mut insert = conn.prepare(INSERT).unwrap();
+        for o in overrides {
+            insert
+                .execute(params![word, o.tense, o.polarity, o.subject, o.form])
+                .unwrap();
+        }
+    }
+}
+
+impl TryFrom<&Row<'_>> for Reference {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(Reference {
+            id: row.get("reference_id")?,
+            kind: row.get("kind")?,
+            author: row.get("author")?,
+            title: row.get("title")?,
+            year: row.get("year")?,
+            publisher: row.get("publisher")?,
+            url: row.get("url")?,
+        })
+    }
+}
+
+impl TryFrom<&Row<'_>> for Citation {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(Citation {
+            citation_id: row.get("citation_id")?,
+            reference_id: row.get("reference_id")?,
+            word_id: row.get("word_id")?,
+            sense_id: row.get("sense_id")?,
+            example_id: row.get("example_id")?,
+            pages: row.get("pages")?,
+        })
+    }
+}
+
+impl TryFrom<&Row<'_>> for ConjugationOverride {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(ConjugationOverride {
+            tense: row.get("tense")?,
+            polarity: row.get("polarity")?,
+            subject: row.get("subject")?,
+            form: row.get("form")?,
+        })
+    }
+}
+
+impl TryFrom<&Row<'_>> for Label {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(Label {
+            id: row.get("label_id")?,
+            kind: row.get("kind")?,
+            name: row.get("name")?,
+            description: row.get("description")?,
+            rank_weight: row.get("rank_weight")?,
+        })
+    }
+}
+
 impl TryFrom<&Row<'_>> for PublicUserInfo {
     type Error = rusqlite::Error;
 
@@ -647,10 +1397,20 @@ impl TryFrom<&Row<'_>> for ExistingWord {
             noun_class: row.get_with_sentinel("noun_class")?,
             note: row.get("note")?,
             is_informal: row.get("is_informal")?,
+            etymology_language: row.get_with_sentinel("etymology_language")?,
+            etymology_form: row.get("etymology_form")?,
+            etymology_note: row.get("etymology_note")?,
+            literal_translation: row.get("literal_translation")?,
+            senses: vec![],
             examples: vec![],
             linked_words: vec![],
             contributors: vec![],
             datasets: vec![],
+            labels: vec![],
+            sense_labels: vec![],
+            citations: vec![],
+            references: vec![],
+            conjugation_overrides: vec![],
         })
     }
 }
@@ -696,6 +1456,20 @@ impl FromSql for Transitivity {
     }
 }
 
+impl ToSql for EtymologyLanguage {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Integer((*self as u8) as i64)))
+    }
+}
+
+impl FromSql for EtymologyLanguage {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        let err = || FromSqlError::Other(Box::new(DiscrimOutOfRange(v, "EtymologyLanguage")));
+        Self::try_from_primitive(v.try_into().map_err(|_| err())?).map_err(|_| err())
+    }
+}
+
 impl FromSql for WordLinkType {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         let v = value.as_i64()?;
@@ -710,6 +1484,76 @@ impl ToSql for WordLinkType {
     }
 }
 
+impl FromSql for LabelKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        let err = || FromSqlError::Other(Box::new(DiscrimOutOfRange(v, "LabelKind")));
+        Self::try_from_primitive(v.try_into().map_err(|_| err())?).map_err(|_| err())
+    }
+}
+
+impl ToSql for LabelKind {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>, rusqlite::Error> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for ReferenceKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        let err = || FromSqlError::Other(Box::new(DiscrimOutOfRange(v, "ReferenceKind")));
+        Self::try_from_primitive(v.try_into().map_err(|_| err())?).map_err(|_| err())
+    }
+}
+
+impl ToSql for ReferenceKind {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>, rusqlite::Error> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for Tense {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        let err = || FromSqlError::Other(Box::new(DiscrimOutOfRange(v, "Tense")));
+        Self::try_from_primitive(v.try_into().map_err(|_| err())?).map_err(|_| err())
+    }
+}
+
+impl ToSql for Tense {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>, rusqlite::Error> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for Polarity {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        let err = || FromSqlError::Other(Box::new(DiscrimOutOfRange(v, "Polarity")));
+        Self::try_from_primitive(v.try_into().map_err(|_| err())?).map_err(|_| err())
+    }
+}
+
+impl ToSql for Polarity {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>, rusqlite::Error> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for SubjectConcord {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        let err = || FromSqlError::Other(Box::new(DiscrimOutOfRange(v, "SubjectConcord")));
+        Self::try_from_primitive(v.try_into().map_err(|_| err())?).map_err(|_| err())
+    }
+}
+
+impl ToSql for SubjectConcord {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>, rusqlite::Error> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
 impl<T> FromSql for WithDeleteSentinel<T>
 where
     T: TryFromPrimitive,
@@ -1,3 +1,4 @@
+use crate::conjugation::SubjectConcord;
 use crate::i18n::{I18nInfo, TranslationKey};
 use crate::i18n_args;
 use crate::language::{NounClassExt, NounClassPrefixes};
@@ -235,6 +236,20 @@ impl<L: Loader + 'static> DisplayHtml<L> for NounClassSelect {
     }
 }
 
+impl<L: Loader + 'static> DisplayHtml<L> for SubjectConcord {
+    fn fmt(&self, f: &mut HtmlFormatter<L>) -> fmt::Result {
+        let key = match self {
+            SubjectConcord::FirstSingular => "conjugation.first-singular",
+            SubjectConcord::SecondSingular => "conjugation.second-singular",
+            SubjectConcord::FirstPlural => "conjugation.first-plural",
+            SubjectConcord::SecondPlural => "conjugation.second-plural",
+            _ => return NounClassSelect(self.noun_class().unwrap()).fmt(f),
+        };
+
+        f.write_text(&TranslationKey::new(key))
+    }
+}
+
 impl WordHit {
     pub fn hyperlinked(&self) -> HyperlinkWrapper<'_> {
         HyperlinkWrapper(self)
@@ -327,6 +342,18 @@ macro_rules! impl_display_html {
                     f.write_raw_str(")")?;
                 }
 
+                let mut glosses = self.sense_glosses().peekable();
+                if glosses.peek().is_some() {
+                    f.write_raw_str(" [")?;
+                    for (i, gloss) in glosses.enumerate() {
+                        if i > 0 {
+                            f.write_raw_str("; ")?;
+                        }
+                        f.write_raw_str(&format!("{}. {gloss}", i + 1))?;
+                    }
+                    f.write_raw_str("]")?;
+                }
+
                 Ok(())
             }
         })*
@@ -163,6 +163,7 @@ impl<L: Loader + 'static> I18nInfo<L> {
             "preposition",
             "ideophone",
             "bound_morpheme",
+            "expression",
             "linked-words.choose",
             "linked-words.search",
             "linked-words.plurality",
@@ -170,8 +171,28 @@ impl<L: Loader + 'static> I18nInfo<L> {
             "linked-words.antonym",
             "linked-words.related",
             "linked-words.confusable",
+            "linked-words.synonym",
+            "linked-words.derived-from",
+            "linked-words.derivation-of",
+            "linked-words.causative-of",
+            "linked-words.has-causative",
+            "linked-words.applicative-of",
+            "linked-words.has-applicative",
+            "linked-words.passive-of",
+            "linked-words.has-passive",
+            "linked-words.diminutive-of",
+            "linked-words.has-diminutive",
+            "linked-words.hyponym-of",
+            "linked-words.hypernym-of",
+            "linked-words.component-of",
+            "linked-words.has-component",
             "examples.source",
             "examples.target",
+            "senses.sense",
+            "senses.gloss",
+            "senses.none",
+            "labels",
+            "informal",
             "delete",
         ]
         .into_iter()
@@ -35,6 +35,8 @@ pub enum PartOfSpeech {
     Preposition = 8,
     Ideophone = 9,
     BoundMorpheme = 10,
+    /// An idiom or other multi-word expression, whose component words are linked to it
+    Expression = 11,
 }
 
 impl PartOfSpeech {
@@ -208,6 +210,95 @@ impl FromStr for Transitivity {
     }
 }
 
+/// The language which a word was loaned from or otherwise originates in
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive, Serialize, Deserialize,
+)]
+#[repr(u8)]
+#[serde(rename_all = "snake_case")]
+pub enum EtymologyLanguage {
+    English = 1,
+    Afrikaans = 2,
+    Dutch = 3,
+    Khoekhoe = 4,
+    San = 5,
+    Zulu = 6,
+    Sotho = 7,
+    Portuguese = 8,
+    Other = 9,
+}
+
+impl EtymologyLanguage {
+    pub const ALL: [EtymologyLanguage; 9] = [
+        EtymologyLanguage::English,
+        EtymologyLanguage::Afrikaans,
+        EtymologyLanguage::Dutch,
+        EtymologyLanguage::Khoekhoe,
+        EtymologyLanguage::San,
+        EtymologyLanguage::Zulu,
+        EtymologyLanguage::Sotho,
+        EtymologyLanguage::Portuguese,
+        EtymologyLanguage::Other,
+    ];
+
+    /// The name of the language as used in forms, consistent with the serde serialization
+    pub fn name(&self) -> &'static str {
+        match self {
+            EtymologyLanguage::English => "english",
+            EtymologyLanguage::Afrikaans => "afrikaans",
+            EtymologyLanguage::Dutch => "dutch",
+            EtymologyLanguage::Khoekhoe => "khoekhoe",
+            EtymologyLanguage::San => "san",
+            EtymologyLanguage::Zulu => "zulu",
+            EtymologyLanguage::Sotho => "sotho",
+            EtymologyLanguage::Portuguese => "portuguese",
+            EtymologyLanguage::Other => "other",
+        }
+    }
+}
+
+impl ToTranslationKey for EtymologyLanguage {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            EtymologyLanguage::English => "etymology-language.english",
+            EtymologyLanguage::Afrikaans => "etymology-language.afrikaans",
+            EtymologyLanguage::Dutch => "etymology-language.dutch",
+            EtymologyLanguage::Khoekhoe => "etymology-language.khoekhoe",
+            EtymologyLanguage::San => "etymology-language.san",
+            EtymologyLanguage::Zulu => "etymology-language.zulu",
+            EtymologyLanguage::Sotho => "etymology-language.sotho",
+            EtymologyLanguage::Portuguese => "etymology-language.portuguese",
+            EtymologyLanguage::Other => "etymology-language.other",
+        })
+    }
+}
+
+impl<L: Loader + 'static> DisplayHtml<L> for EtymologyLanguage {
+    fn fmt(&self, f: &mut HtmlFormatter<L>) -> fmt::Result {
+        f.write_text(&self.translation_key())
+    }
+}
+
+pub struct InvalidEtymologyLanguage(String);
+
+impl Display for InvalidEtymologyLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("invalid source language: `{}`", self.0))
+    }
+}
+
+impl FromStr for EtymologyLanguage {
+    type Err = InvalidEtymologyLanguage;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        EtymologyLanguage::ALL
+            .into_iter()
+            .find(|l| l.name() == name)
+            .ok_or_else(|| InvalidEtymologyLanguage(s.to_owned()))
+    }
+}
+
 /// Noun class prefixes with singular and plural
 #[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
 pub struct NounClassPrefixes {
@@ -269,6 +360,9 @@ impl NounClassExt for NounClass {
     }
 }
 
+/// How two linked words relate to each other. Directional link types describe the first word of the
+/// link in relation to the second, and come in pairs with their inverse, so that the link can be
+/// shown from the point of view of either word.
 #[derive(
     IntoPrimitive,
     TryFromPrimitive,
@@ -290,6 +384,134 @@ pub enum WordLinkType {
     Antonym = 3,
     Related = 4,
     Confusable = 5,
+    Synonym = 6,
+    /// e.g. bonisa is derived by verb extension from bona
+    DerivedFrom = 7,
+    DerivationOf = 8,
+    CausativeOf = 9,
+    HasCausative = 10,
+    ApplicativeOf = 11,
+    HasApplicative = 12,
+    PassiveOf = 13,
+    HasPassive = 14,
+    DiminutiveOf = 15,
+    HasDiminutive = 16,
+    /// e.g. a sparrow is a kind of bird
+    HyponymOf = 17,
+    HypernymOf = 18,
+    /// A word which is part of a compound or idiom
+    ComponentOf = 19,
+    HasComponent = 20,
+}
+
+impl WordLinkType {
+    pub const ALL: [WordLinkType; 20] = [
+        WordLinkType::PluralOrSingular,
+        WordLinkType::AlternateUse,
+        WordLinkType::Antonym,
+        WordLinkType::Synonym,
+        WordLinkType::Related,
+        WordLinkType::Confusable,
+        WordLinkType::DerivedFrom,
+        WordLinkType::DerivationOf,
+        WordLinkType::CausativeOf,
+        WordLinkType::HasCausative,
+        WordLinkType::ApplicativeOf,
+        WordLinkType::HasApplicative,
+        WordLinkType::PassiveOf,
+        WordLinkType::HasPassive,
+        WordLinkType::DiminutiveOf,
+        WordLinkType::HasDiminutive,
+        WordLinkType::HyponymOf,
+        WordLinkType::HypernymOf,
+        WordLinkType::ComponentOf,
+        WordLinkType::HasComponent,
+    ];
+
+    /// The link type as seen from the other word of the link. Symmetric link types are their own
+    /// inverse.
+    pub fn inverse(&self) -> WordLinkType {
+        use WordLinkType::*;
+
+        match self {
+            PluralOrSingular | AlternateUse | Antonym | Related | Confusable | Synonym => *self,
+            DerivedFrom => DerivationOf,
+            DerivationOf => DerivedFrom,
+            CausativeOf => HasCausative,
+            HasCausative => CausativeOf,
+            ApplicativeOf => HasApplicative,
+            HasApplicative => ApplicativeOf,
+            PassiveOf => HasPassive,
+            HasPassive => PassiveOf,
+            DiminutiveOf => HasDiminutive,
+            HasDiminutive => DiminutiveOf,
+            HyponymOf => HypernymOf,
+            HypernymOf => HyponymOf,
+            ComponentOf => HasComponent,
+            HasComponent => ComponentOf,
+        }
+    }
+
+    pub fn is_directional(&self) -> bool {
+        self.inverse() != *self
+    }
+
+    /// The name of the link type as used in forms, consistent with the serde serialization
+    pub fn name(&self) -> &'static str {
+        match self {
+            WordLinkType::PluralOrSingular => "plural_or_singular",
+            WordLinkType::AlternateUse => "alternate_use",
+            WordLinkType::Antonym => "antonym",
+            WordLinkType::Related => "related",
+            WordLinkType::Confusable => "confusable",
+            WordLinkType::Synonym => "synonym",
+            WordLinkType::DerivedFrom => "derived_from",
+            WordLinkType::DerivationOf => "derivation_of",
+            WordLinkType::CausativeOf => "causative_of",
+            WordLinkType::HasCausative => "has_causative",
+            WordLinkType::ApplicativeOf => "applicative_of",
+            WordLinkType::HasApplicative => "has_applicative",
+            WordLinkType::PassiveOf => "passive_of",
+            WordLinkType::HasPassive => "has_passive",
+            WordLinkType::DiminutiveOf => "diminutive_of",
+            WordLinkType::HasDiminutive => "has_diminutive",
+            WordLinkType::HyponymOf => "hyponym_of",
+            WordLinkType::HypernymOf => "hypernym_of",
+            WordLinkType::ComponentOf => "component_of",
+            WordLinkType::HasComponent => "has_component",
+        }
+    }
+
+    /// Whether this link type makes sense between words with the given parts of speech, with this
+    /// link type describing the first word in relation to the second. Words whose part of speech is
+    /// not known yet are given the benefit of the doubt.
+    pub fn is_valid_between(
+        &self,
+        first: Option<PartOfSpeech>,
+        second: Option<PartOfSpeech>,
+    ) -> bool {
+        use WordLinkType::*;
+
+        let both = |pos: PartOfSpeech| {
+            first.map_or(true, |p| p == pos) && second.map_or(true, |p| p == pos)
+        };
+
+        match self {
+            PluralOrSingular | DiminutiveOf | HasDiminutive => both(PartOfSpeech::Noun),
+            CausativeOf | HasCausative | ApplicativeOf | HasApplicative | PassiveOf
+            | HasPassive => both(PartOfSpeech::Verb),
+            // A bound morpheme can't be a compound or idiom in itself
+            ComponentOf => second != Some(PartOfSpeech::BoundMorpheme),
+            HasComponent => first != Some(PartOfSpeech::BoundMorpheme),
+            _ => true,
+        }
+    }
+
+    /// Whether two links between the same words, in the same order, contradict each other, e.g.
+    /// each word being derived from the other.
+    pub fn contradicts(&self, other: WordLinkType) -> bool {
+        self.is_directional() && other == self.inverse()
+    }
 }
 
 #[allow(dead_code)] // In case we want to use field this later
@@ -299,27 +521,187 @@ impl FromStr for WordLinkType {
     type Err = InvalidWordLinkType;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "plural_or_singular" => WordLinkType::PluralOrSingular,
-            "alternate_use" => WordLinkType::AlternateUse,
-            "antonym" => WordLinkType::Antonym,
-            "related" => WordLinkType::Related,
-            "confusable" => WordLinkType::Confusable,
-            _ => return Err(InvalidWordLinkType(s.to_owned())),
-        })
+        WordLinkType::ALL
+            .into_iter()
+            .find(|t| t.name() == s)
+            .ok_or_else(|| InvalidWordLinkType(s.to_owned()))
     }
 }
 
 impl<L: Loader + 'static> DisplayHtml<L> for WordLinkType {
     fn fmt(&self, f: &mut HtmlFormatter<L>) -> fmt::Result {
-        let s = match self {
-            WordLinkType::PluralOrSingular => TranslationKey::new("linked-words.plurality"),
-            WordLinkType::Antonym => TranslationKey::new("linked-words.antonym"),
-            WordLinkType::Related => TranslationKey::new("linked-words.related"),
-            WordLinkType::Confusable => TranslationKey::new("linked-words.confusable"),
-            WordLinkType::AlternateUse => TranslationKey::new("linked-words.alternate"),
-        };
+        f.write_text(&self.translation_key())
+    }
+}
 
-        f.write_text(&s)
+impl ToTranslationKey for WordLinkType {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            WordLinkType::PluralOrSingular => "linked-words.plurality",
+            WordLinkType::Antonym => "linked-words.antonym",
+            WordLinkType::Related => "linked-words.related",
+            WordLinkType::Confusable => "linked-words.confusable",
+            WordLinkType::AlternateUse => "linked-words.alternate",
+            WordLinkType::Synonym => "linked-words.synonym",
+            WordLinkType::DerivedFrom => "linked-words.derived-from",
+            WordLinkType::DerivationOf => "linked-words.derivation-of",
+            WordLinkType::CausativeOf => "linked-words.causative-of",
+            WordLinkType::HasCausative => "linked-words.has-causative",
+            WordLinkType::ApplicativeOf => "linked-words.applicative-of",
+            WordLinkType::HasApplicative => "linked-words.has-applicative",
+            WordLinkType::PassiveOf => "linked-words.passive-of",
+            WordLinkType::HasPassive => "linked-words.has-passive",
+            WordLinkType::DiminutiveOf => "linked-words.diminutive-of",
+            WordLinkType::HasDiminutive => "linked-words.has-diminutive",
+            WordLinkType::HyponymOf => "linked-words.hyponym-of",
+            WordLinkType::HypernymOf => "linked-words.hypernym-of",
+            WordLinkType::ComponentOf => "linked-words.component-of",
+            WordLinkType::HasComponent => "linked-words.has-component",
+        })
+    }
+}
+
+/// The kind of a usage label. The labels themselves are configured per site.
+#[derive(
+    IntoPrimitive,
+    TryFromPrimitive,
+    Serialize,
+    Deserialize,
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialOrd,
+    Ord,
+    PartialEq,
+    Eq,
+)]
+#[repr(u8)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelKind {
+    /// e.g. slang, formal
+    Register = 1,
+    /// e.g. Mpondo, Bhaca
+    Dialect = 2,
+    /// e.g. medical, legal, religious
+    Domain = 3,
+    /// e.g. archaic, offensive
+    Usage = 4,
+}
+
+impl LabelKind {
+    pub const ALL: [LabelKind; 4] = [
+        LabelKind::Register,
+        LabelKind::Dialect,
+        LabelKind::Domain,
+        LabelKind::Usage,
+    ];
+
+    /// The name of the kind as used in forms, consistent with the serde serialization
+    pub fn name(&self) -> &'static str {
+        match self {
+            LabelKind::Register => "register",
+            LabelKind::Dialect => "dialect",
+            LabelKind::Domain => "domain",
+            LabelKind::Usage => "usage",
+        }
+    }
+}
+
+impl ToTranslationKey for LabelKind {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            LabelKind::Register => "labels.register",
+            LabelKind::Dialect => "labels.dialect",
+            LabelKind::Domain => "labels.domain",
+            LabelKind::Usage => "labels.usage",
+        })
+    }
+}
+
+/// The kind of work a bibliographic reference points to
+#[derive(
+    IntoPrimitive,
+    TryFromPrimitive,
+    Serialize,
+    Deserialize,
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    PartialOrd,
+    Ord,
+    PartialEq,
+    Eq,
+)]
+#[repr(u8)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    Dictionary = 1,
+    Grammar = 2,
+    Book = 3,
+    Article = 4,
+    Corpus = 5,
+    Website = 6,
+}
+
+impl ReferenceKind {
+    pub const ALL: [ReferenceKind; 6] = [
+        ReferenceKind::Dictionary,
+        ReferenceKind::Grammar,
+        ReferenceKind::Book,
+        ReferenceKind::Article,
+        ReferenceKind::Corpus,
+        ReferenceKind::Website,
+    ];
+
+    /// The name of the kind as used in forms, consistent with the serde serialization
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReferenceKind::Dictionary => "dictionary",
+            ReferenceKind::Grammar => "grammar",
+            ReferenceKind::Book => "book",
+            ReferenceKind::Article => "article",
+            ReferenceKind::Corpus => "corpus",
+            ReferenceKind::Website => "website",
+        }
+    }
+}
+
+impl ToTranslationKey for ReferenceKind {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            ReferenceKind::Dictionary => "references.kind-dictionary",
+            ReferenceKind::Grammar => "references.kind-grammar",
+            ReferenceKind::Book => "references.kind-book",
+            ReferenceKind::Article => "references.kind-article",
+            ReferenceKind::Corpus => "references.kind-corpus",
+            ReferenceKind::Website => "references.kind-website",
+        })
+    }
+}
+
+/// A likely mistake in the grammatical information of a word. Warnings do not prevent a word from
+/// being submitted, since the rules do not cover every irregular word.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum GrammarWarning {
+    /// The noun does not begin with any prefix of its noun class
+    NounPrefixMismatch,
+    /// The noun is marked plural but its class is singular, or the other way around
+    PluralMismatch,
+    /// The infinitive does not begin with uku-, ukw- or uk-
+    InvalidInfinitive,
+    /// The infinitive is not formed from the verb
+    InfinitiveMismatch,
+}
+
+impl ToTranslationKey for GrammarWarning {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            GrammarWarning::NounPrefixMismatch => "grammar-warnings.noun-prefix",
+            GrammarWarning::PluralMismatch => "grammar-warnings.plural",
+            GrammarWarning::InvalidInfinitive => "grammar-warnings.invalid-infinitive",
+            GrammarWarning::InfinitiveMismatch => "grammar-warnings.infinitive-mismatch",
+        })
     }
 }
@@ -1,6 +1,9 @@
 pub mod auth;
+pub mod concord;
+pub mod conjugation;
 pub mod format;
 pub mod language;
+pub mod noun_forms;
 pub mod serialization;
 pub mod templates;
 pub mod types;
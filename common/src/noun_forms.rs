@@ -0,0 +1,281 @@
+//! Forms derived from a noun by rule: its singular or plural counterpart, its locative and its
+//! diminutive. The rules cover the regular patterns only, so the forms are shown as generated and
+//! may need correcting for irregular nouns.
+
+use isixhosa::noun::NounClass;
+use serde::Serialize;
+
+/// Consonants which palatalise before the -w- of a locative or diminutive suffix, longest first,
+/// e.g. intaba becomes intatyana and umlomo becomes emlonyeni
+const PALATALISATIONS: [(&str, &str); 8] = [
+    ("mph", "ntsh"),
+    ("mp", "ntsh"),
+    ("mb", "nj"),
+    ("bh", "j"),
+    ("ph", "tsh"),
+    ("p", "tsh"),
+    ("b", "ty"),
+    ("m", "ny"),
+];
+
+#[derive(Clone, Debug, Serialize)]
+pub struct NounForms {
+    /// The plural of a singular noun or the singular of a plural noun, if the class has one
+    pub counterpart: Option<GeneratedNoun>,
+    /// e.g. endlwini from indlu
+    pub locative: String,
+    /// e.g. intatyana from intaba
+    pub diminutive: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct GeneratedNoun {
+    pub xhosa: String,
+    pub noun_class: NounClass,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NounStem {
+    noun_class: NounClass,
+    /// The prefix as the noun was written, e.g. is- in isandla
+    prefix: String,
+    stem: String,
+}
+
+impl NounStem {
+    /// Splits the noun into its class prefix and stem. Returns `None` if it is not a single word
+    /// beginning with a prefix of the class.
+    pub fn from_noun(xhosa: &str, noun_class: NounClass) -> Option<NounStem> {
+        let xhosa = xhosa.trim().to_lowercase();
+
+        if xhosa.is_empty() || !xhosa.chars().all(|c| c.is_alphabetic() || c == '-') {
+            return None;
+        }
+
+        let (prefix, stem) = prefixes(noun_class)
+            .iter()
+            .find_map(|prefix| Some((*prefix, xhosa.strip_prefix(prefix)?)))?;
+        // Loanwords beginning with a vowel are written with a hyphen, e.g. i-orenji
+        let stem = stem.trim_start_matches('-');
+
+        if stem.is_empty() || !stem.ends_with(is_vowel) {
+            return None;
+        }
+
+        Some(NounStem {
+            noun_class,
+            prefix: prefix.to_owned(),
+            stem: stem.to_owned(),
+        })
+    }
+
+    pub fn forms(&self) -> NounForms {
+        NounForms {
+            counterpart: self.counterpart(),
+            locative: self.locative(),
+            diminutive: self.diminutive(),
+        }
+    }
+
+    /// The noun in the paired singular or plural class, e.g. izitya from isitya
+    pub fn counterpart(&self) -> Option<GeneratedNoun> {
+        use NounClass::*;
+
+        let stem = self.stem.as_str();
+        let monosyllabic = syllables(stem) == 1;
+
+        let (noun_class, prefix) = match self.noun_class {
+            Class1Um => (Aba, "aba"),
+            Aba => (Class1Um, if monosyllabic { "umu" } else { "um" }),
+            U => (Oo, "oo"),
+            Oo => (U, "u"),
+            Class3Um => (Imi, "imi"),
+            Imi => (Class3Um, if monosyllabic { "umu" } else { "um" }),
+            Ili => (Ama, "ama"),
+            Ama => (Ili, if monosyllabic { "ili" } else { "i" }),
+            Isi => (Izi, "izi"),
+            Izi => (Isi, "isi"),
+            In => (Izin, if monosyllabic { "izi" } else { "ii" }),
+            Izin => (In, "i"),
+            Ulu | Ubu | Uku => return None,
+        };
+
+        Some(GeneratedNoun {
+            xhosa: coalesce(prefix, stem),
+            noun_class,
+        })
+    }
+
+    /// e.g. esityeni from isitya. Nouns of classes 1a and 2a, mostly names and kinship terms, take
+    /// ku- instead, e.g. kutata.
+    pub fn locative(&self) -> String {
+        match self.noun_class {
+            NounClass::U => format!("ku{}", self.stem),
+            NounClass::Oo => format!("koo{}", self.stem),
+            // The initial vowel of the noun becomes e-, and the rest of the prefix stays the same.
+            // A final -i or -u takes -ini rather than -eni, e.g. emzini from umzi and endlwini from
+            // indlu.
+            _ => format!(
+                "e{}{}",
+                &self.prefix[1..],
+                with_suffix(&self.stem, "eni", "ini", false)
+            ),
+        }
+    }
+
+    /// e.g. intatyana from intaba. Stems which already end in -ana take -anyana instead.
+    pub fn diminutive(&self) -> String {
+        let suffixed = if self.stem.ends_with("ana") {
+            format!("{}anyana", self.stem.strip_suffix("ana").unwrap())
+        } else {
+            with_suffix(&self.stem, "ana", "ana", true)
+        };
+
+        format!("{}{suffixed}", self.prefix)
+    }
+}
+
+/// Whether the noun begins with one of the prefixes of the class. Unlike [`NounStem::from_noun`],
+/// this also accepts nouns of more than one word.
+pub fn has_class_prefix(xhosa: &str, noun_class: NounClass) -> bool {
+    let xhosa = xhosa.trim().to_lowercase();
+    prefixes(noun_class).iter().any(|p| xhosa.starts_with(p))
+}
+
+/// The prefixes which nouns of the class may begin with, longest first
+fn prefixes(noun_class: NounClass) -> &'static [&'static str] {
+    use NounClass::*;
+
+    match noun_class {
+        Class1Um | Class3Um => &["umu", "um"],
+        Aba => &["abe", "aba", "ab"],
+        U => &["u"],
+        Oo => &["oo"],
+        Imi => &["imi"],
+        Ili => &["ili", "i"],
+        Ama => &["ame", "ama", "am"],
+        Isi => &["isi", "is"],
+        Izi => &["izi", "iz"],
+        // The nasal is part of the stem, e.g. i-nja and ii-ndlela
+        In => &["i"],
+        Izin => &["izi", "ii"],
+        Ulu => &["ulu", "u"],
+        Ubu => &["ubu", "ub"],
+        Uku => &["uku", "ukw"],
+    }
+}
+
+/// Replaces the final vowel of the stem with the suffix, or with `close_suffix` after a final -i or
+/// -u.
+/// After a final -o or -u, the suffix is preceded by -w-, which palatalises a preceding labial
+/// consonant. When `always_palatalise` is set, labials palatalise before any final vowel, as in the
+/// diminutive.
+fn with_suffix(stem: &str, suffix: &str, close_suffix: &str, always_palatalise: bool) -> String {
+    let root = &stem[..stem.len() - 1];
+    let rounded = stem.ends_with(['o', 'u']);
+    let suffix = if stem.ends_with(['i', 'u']) {
+        close_suffix
+    } else {
+        suffix
+    };
+
+    if rounded || always_palatalise {
+        for (labial, palatal) in PALATALISATIONS {
+            if let Some(root) = root.strip_suffix(labial) {
+                return format!("{root}{palatal}{suffix}");
+            }
+        }
+    }
+
+    if rounded {
+        format!("{root}w{suffix}")
+    } else {
+        format!("{root}{suffix}")
+    }
+}
+
+/// Joins a prefix to a stem, dropping the final vowel of the prefix before a vowel-commencing stem,
+/// e.g. aba- and -akhi give abakhi
+fn coalesce(prefix: &str, stem: &str) -> String {
+    if stem.starts_with(is_vowel) {
+        format!("{}{stem}", prefix.trim_end_matches(is_vowel))
+    } else {
+        format!("{prefix}{stem}")
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn syllables(stem: &str) -> usize {
+    stem.chars().filter(|c| is_vowel(*c)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noun(xhosa: &str, noun_class: NounClass) -> NounStem {
+        NounStem::from_noun(xhosa, noun_class).unwrap()
+    }
+
+    fn counterpart(xhosa: &str, noun_class: NounClass) -> Option<String> {
+        noun(xhosa, noun_class).counterpart().map(|n| n.xhosa)
+    }
+
+    #[test]
+    fn splits_prefixes() {
+        assert_eq!(noun("isitya", NounClass::Isi).stem, "tya");
+        assert_eq!(noun("isandla", NounClass::Isi).stem, "andla");
+        assert_eq!(noun("inja", NounClass::In).stem, "nja");
+        assert_eq!(noun("i-orenji", NounClass::In).stem, "orenji");
+
+        assert_eq!(NounStem::from_noun("isitya", NounClass::Ubu), None);
+        assert_eq!(NounStem::from_noun("isitya esikhulu", NounClass::Isi), None);
+        assert!(has_class_prefix("isitya esikhulu", NounClass::Isi));
+    }
+
+    #[test]
+    fn counterparts() {
+        assert_eq!(counterpart("umntu", NounClass::Class1Um).unwrap(), "abantu");
+        assert_eq!(
+            counterpart("umfazi", NounClass::Class1Um).unwrap(),
+            "abafazi"
+        );
+        assert_eq!(
+            counterpart("umlomo", NounClass::Class3Um).unwrap(),
+            "imilomo"
+        );
+        assert_eq!(counterpart("iqanda", NounClass::Ili).unwrap(), "amaqanda");
+        assert_eq!(counterpart("isitya", NounClass::Isi).unwrap(), "izitya");
+        assert_eq!(counterpart("izitya", NounClass::Izi).unwrap(), "isitya");
+        assert_eq!(counterpart("inja", NounClass::In).unwrap(), "izinja");
+        assert_eq!(counterpart("intaba", NounClass::In).unwrap(), "iintaba");
+        assert_eq!(counterpart("utata", NounClass::U).unwrap(), "ootata");
+        assert_eq!(counterpart("ubuhle", NounClass::Ubu), None);
+    }
+
+    #[test]
+    fn locatives() {
+        assert_eq!(noun("isitya", NounClass::Isi).locative(), "esityeni");
+        assert_eq!(noun("intaba", NounClass::In).locative(), "entabeni");
+        assert_eq!(noun("isikolo", NounClass::Isi).locative(), "esikolweni");
+        assert_eq!(noun("indlu", NounClass::In).locative(), "endlwini");
+        assert_eq!(noun("umzi", NounClass::Class3Um).locative(), "emzini");
+        assert_eq!(noun("amanzi", NounClass::Ama).locative(), "emanzini");
+        assert_eq!(noun("umlomo", NounClass::Class3Um).locative(), "emlonyeni");
+        assert_eq!(noun("utata", NounClass::U).locative(), "kutata");
+    }
+
+    #[test]
+    fn diminutives() {
+        assert_eq!(noun("inja", NounClass::In).diminutive(), "injana");
+        assert_eq!(noun("intaba", NounClass::In).diminutive(), "intatyana");
+        assert_eq!(noun("umzi", NounClass::Class3Um).diminutive(), "umzana");
+        assert_eq!(
+            noun("umntwana", NounClass::Class1Um).diminutive(),
+            "umntwanyana"
+        );
+    }
+}
@@ -18,6 +18,11 @@ where
     pub i18n_info: I18nInfo<L>,
     pub word: ExistingWord,
     pub previous_success: Option<WordChangeMethod>,
+    /// Likely mistakes in the edit which was just suggested, if any
+    pub grammar_warnings: Vec<GrammarWarning>,
+    /// Whether this shows how a suggestion would look once accepted rather than an existing word,
+    /// in which case links and forms which act on the word are left out
+    pub preview: bool,
 }
 
 pub enum WordChangeMethod {
@@ -1,6 +1,10 @@
+use crate::concord::ConcordTable;
+use crate::conjugation::{ConjugationOverride, ConjugationTable, VerbStem};
 use crate::language::{
-    ConjunctionFollowedBy, NounClassPrefixes, PartOfSpeech, Transitivity, WordLinkType,
+    ConjunctionFollowedBy, EtymologyLanguage, LabelKind, NounClassPrefixes, PartOfSpeech,
+    ReferenceKind, Transitivity, WordLinkType,
 };
+use crate::noun_forms::{NounForms, NounStem};
 use isixhosa::noun::NounClass;
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
@@ -24,6 +28,25 @@ pub struct ExistingLinkedWord {
     pub other: WordHit,
 }
 
+/// One meaning of a word, with its own gloss and register. Examples and linked words may each be
+/// assigned to a sense, or belong to the word as a whole.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExistingSense {
+    pub sense_id: u64,
+    pub word_id: u64,
+    /// The position of the sense within the word, starting from 1
+    pub sense_order: u32,
+
+    pub english: String,
+    pub is_informal: bool,
+
+    pub examples: Vec<u64>,
+    pub linked_words: Vec<u64>,
+    // Snapshots recorded before labels existed have none
+    #[serde(default)]
+    pub labels: Vec<u64>,
+}
+
 #[derive(Debug)]
 pub struct ExistingWord {
     pub word_id: u64,
@@ -43,10 +66,42 @@ pub struct ExistingWord {
 
     pub is_informal: bool,
 
+    pub etymology_language: Option<EtymologyLanguage>,
+    /// The form of the word in the source language, e.g. "oranje" for i-orenji
+    pub etymology_form: String,
+    /// Free-text notes on the origin of the word, including any references
+    pub etymology_note: String,
+
+    /// The word-for-word translation of an expression, as opposed to its idiomatic meaning which
+    /// is given by `english`
+    pub literal_translation: String,
+
+    pub senses: Vec<ExistingSense>,
     pub examples: Vec<ExistingExample>,
     pub linked_words: Vec<ExistingLinkedWord>,
     pub contributors: Vec<PublicUserInfo>,
     pub datasets: Vec<Dataset>,
+    /// Labels of the word as a whole
+    pub labels: Vec<Label>,
+    /// Every label used by any sense of the word
+    pub sense_labels: Vec<Label>,
+    /// Citations of the word, its senses and its examples
+    pub citations: Vec<Citation>,
+    /// Every reference cited by the word, its senses or its examples
+    pub references: Vec<Reference>,
+    /// Irregular forms of the verb set by moderators, replacing the generated ones
+    pub conjugation_overrides: Vec<ConjugationOverride>,
+}
+
+impl ExistingLinkedWord {
+    /// The link type describing the word which the link was fetched for in relation to `other`.
+    pub fn relative_link_type(&self) -> WordLinkType {
+        if self.other.id == self.first_word_id && self.first_word_id != self.second_word_id {
+            self.link_type.inverse()
+        } else {
+            self.link_type
+        }
+    }
 }
 
 impl ExistingWord {
@@ -62,6 +117,124 @@ impl ExistingWord {
             || self.noun_class.is_some()
             || !self.note.is_empty()
             || self.is_informal
+            || !self.literal_translation.is_empty()
+    }
+
+    pub fn has_etymology(&self) -> bool {
+        self.etymology_language.is_some()
+            || !self.etymology_form.is_empty()
+            || !self.etymology_note.is_empty()
+    }
+
+    /// The conjugation table of the verb, or `None` if it is not a verb with a regular infinitive
+    pub fn conjugation(&self) -> Option<ConjugationTable> {
+        if self.part_of_speech != Some(PartOfSpeech::Verb) {
+            return None;
+        }
+
+        let stem = VerbStem::from_infinitive(&self.infinitive)?;
+        Some(stem.conjugate(&self.conjugation_overrides))
+    }
+
+    /// The concords which agree with the noun, or `None` if it is not a noun with a known class
+    pub fn concords(&self) -> Option<ConcordTable> {
+        if self.part_of_speech != Some(PartOfSpeech::Noun) {
+            return None;
+        }
+
+        self.noun_class.map(ConcordTable::for_noun_class)
+    }
+
+    /// The forms generated from the noun, or `None` if it is not a noun which the rules apply to
+    pub fn noun_forms(&self) -> Option<NounForms> {
+        if self.part_of_speech != Some(PartOfSpeech::Noun) {
+            return None;
+        }
+
+        NounStem::from_noun(&self.xhosa, self.noun_class?).map(|stem| stem.forms())
+    }
+
+    /// The singular or plural counterpart of the noun, if it is linked
+    pub fn linked_counterpart(&self) -> Option<&WordHit> {
+        self.linked_words
+            .iter()
+            .find(|l| l.link_type == WordLinkType::PluralOrSingular)
+            .map(|l| &l.other)
+    }
+
+    /// The words which make up the expression, as linked with `HasComponent` links
+    pub fn components(&self) -> Vec<&WordHit> {
+        self.linked_words
+            .iter()
+            .filter(|l| l.relative_link_type() == WordLinkType::HasComponent)
+            .map(|l| &l.other)
+            .collect()
+    }
+
+    pub fn sense_glosses(&self) -> impl Iterator<Item = &str> + '_ {
+        self.senses.iter().map(|s| s.english.as_str())
+    }
+
+    pub fn examples_for_sense(&self, sense: &ExistingSense) -> Vec<&ExistingExample> {
+        self.examples
+            .iter()
+            .filter(|e| sense.examples.contains(&e.example_id))
+            .collect()
+    }
+
+    /// Examples which do not belong to any particular sense of the word
+    pub fn examples_without_sense(&self) -> Vec<&ExistingExample> {
+        self.examples
+            .iter()
+            .filter(|e| {
+                !self
+                    .senses
+                    .iter()
+                    .any(|s| s.examples.contains(&e.example_id))
+            })
+            .collect()
+    }
+
+    pub fn linked_words_for_sense(&self, sense: &ExistingSense) -> Vec<&ExistingLinkedWord> {
+        self.linked_words
+            .iter()
+            .filter(|l| sense.linked_words.contains(&l.link_id))
+            .collect()
+    }
+
+    /// Linked words which do not belong to any particular sense of the word
+    pub fn linked_words_without_sense(&self) -> Vec<&ExistingLinkedWord> {
+        self.linked_words
+            .iter()
+            .filter(|l| {
+                !self
+                    .senses
+                    .iter()
+                    .any(|s| s.linked_words.contains(&l.link_id))
+            })
+            .collect()
+    }
+
+    pub fn labels_for_sense(&self, sense: &ExistingSense) -> Vec<&Label> {
+        self.sense_labels
+            .iter()
+            .filter(|l| sense.labels.contains(&l.id))
+            .collect()
+    }
+
+    pub fn citations_of(&self, reference: &Reference) -> Vec<&Citation> {
+        self.citations
+            .iter()
+            .filter(|c| c.reference_id == reference.id)
+            .collect()
+    }
+
+    pub fn sense_by_id(&self, sense_id: u64) -> Option<&ExistingSense> {
+        self.senses.iter().find(|s| s.sense_id == sense_id)
+    }
+
+    pub fn example_by_id(&self, example_id: u64) -> Option<&ExistingExample> {
+        self.examples.iter().find(|e| e.example_id == example_id)
     }
 }
 
@@ -76,7 +249,17 @@ pub struct WordHit {
     pub is_informal: bool,
     pub transitivity: Option<Transitivity>,
     pub noun_class: Option<NounClassPrefixes>,
+    /// The glosses of each sense of the word, in order
+    #[serde(default)]
+    pub senses: Vec<String>,
     pub is_suggestion: bool,
+    /// The product of the rank weights of the labels of the word. Only used to rank search results.
+    #[serde(skip, default = "default_rank_weight")]
+    pub rank_weight: f64,
+}
+
+fn default_rank_weight() -> f64 {
+    1.0
 }
 
 impl Hash for WordHit {
@@ -105,7 +288,9 @@ impl WordHit {
             is_informal: false,
             transitivity: None,
             noun_class: None,
+            senses: Vec::new(),
             is_suggestion: false,
+            rank_weight: 1.0,
         }
     }
 
@@ -117,6 +302,10 @@ impl WordHit {
             || self.noun_class.is_some()
             || self.is_informal
     }
+
+    pub fn sense_glosses(&self) -> impl Iterator<Item = &str> + '_ {
+        self.senses.iter().map(String::as_str)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -126,6 +315,53 @@ pub struct PublicUserInfo {
     pub display_name: bool,
 }
 
+/// A site-configured label marking the register, dialect, domain or usage of a word or sense
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Label {
+    pub id: u64,
+    pub kind: LabelKind,
+    pub name: String,
+    pub description: String,
+    /// Multiplies the search ranking of words with this label, so a weight below 1 makes them rank
+    /// lower than otherwise equally good matches
+    pub rank_weight: f64,
+}
+
+/// A printed or online work, such as a dictionary, grammar or corpus, which entries can cite.
+/// References are shared between all the entries which cite them.
+#[derive(Clone, Debug)]
+pub struct Reference {
+    pub id: u64,
+    pub kind: ReferenceKind,
+    pub author: String,
+    pub title: String,
+    pub year: Option<u32>,
+    pub publisher: Option<String>,
+    pub url: Option<String>,
+}
+
+impl Reference {
+    /// A short plaintext form of the reference, e.g. "Kropf (1915), A Kaffir-English Dictionary"
+    pub fn short_form(&self) -> String {
+        match self.year {
+            Some(year) => format!("{} ({year}), {}", self.author, self.title),
+            None => format!("{}, {}", self.author, self.title),
+        }
+    }
+}
+
+/// A citation of a reference by a word, or by one of its senses or examples
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Citation {
+    pub citation_id: u64,
+    pub reference_id: u64,
+    pub word_id: u64,
+    pub sense_id: Option<u64>,
+    pub example_id: Option<u64>,
+    /// The page or pages cited, e.g. "p. 112". May be empty.
+    pub pages: String,
+}
+
 /// An external dataset from which a word in the dictionary is sourced
 #[derive(Clone, Debug)]
 pub struct Dataset {
@@ -1,5 +1,9 @@
 use crate::auth::{with_administrator_auth, FullUser};
+use crate::database::outcomes::SuggestionType;
+use crate::database::review::ReviewPolicy;
 use crate::i18n::{I18nInfo, SiteContext};
+use crate::search::TantivyClient;
+use crate::serialization::{deserialize_checkbox, false_fn};
 use crate::{spawn_blocking_child, DebugBoxedExt};
 use anyhow::{bail, Context, Result};
 use askama::Template;
@@ -8,7 +12,10 @@ use image::{DynamicImage, ImageFormat, ImageReader};
 use isixhosa_click_macros::I18nTemplate;
 use isixhosa_common::auth::Auth;
 use isixhosa_common::database::{AdministratorAccessDb, DbBase};
-use isixhosa_common::types::Dataset;
+use isixhosa_common::language::LabelKind;
+use isixhosa_common::types::{Dataset, Label};
+use serde::Deserialize;
+use serde_with::{serde_as, NoneAsEmptyString};
 use std::io::{Cursor, Read};
 use std::sync::Arc;
 use warp::multipart::FormData;
@@ -17,9 +24,11 @@ use warp::{Filter, Rejection, Reply};
 
 pub fn admin(
     db: DbBase,
+    tantivy: Arc<TantivyClient>,
     site_ctx: Arc<SiteContext>,
 ) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
     let base = with_administrator_auth(db, site_ctx);
+    let with_tantivy = warp::any().map(move || tantivy.clone());
 
     let settings = warp::path::end()
         .and(base.clone())
@@ -54,13 +63,66 @@ pub fn admin(
         .and(warp::post())
         .and_then(reply_delete_dataset);
 
+    let add_label_route = warp::path("add_label").and(warp::path::end());
+
+    let add_label_form =
+        add_label_route
+            .and(warp::get())
+            .and(base.clone())
+            .map(|user: FullUser, i18n_info, _db| AddLabel {
+                auth: user.into(),
+                i18n_info,
+                label: Default::default(),
+            });
+
+    let add_label_submit = add_label_route
+        .and(base.clone())
+        .and(warp::post())
+        .and(warp::body::content_length_limit(64 * 1024))
+        .and(warp::body::form::<LabelForm>())
+        .and(with_tantivy.clone())
+        .and_then(reply_add_label);
+
+    let edit_label_form = warp::path!("label" / u64 / "edit")
+        .and(base.clone())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and_then(reply_edit_label_form);
+
+    let delete_label = warp::path!("label" / u64 / "delete")
+        .and(base.clone())
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(with_tantivy)
+        .and_then(reply_delete_label);
+
+    let add_review_policy = warp::path!("review_policy")
+        .and(base.clone())
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::content_length_limit(4 * 1024))
+        .and(warp::body::form::<ReviewPolicyForm>())
+        .and_then(reply_add_review_policy);
+
+    let delete_review_policy = warp::path!("review_policy" / u64 / "delete")
+        .and(base.clone())
+        .and(warp::path::end())
+        .and(warp::post())
+        .and_then(reply_delete_review_policy);
+
     warp::path!("admin" / "settings" / ..)
         .and(
             settings
                 .or(add_dataset_form)
                 .or(add_dataset_submit)
                 .or(edit_dataset_form)
-                .or(delete_dataset),
+                .or(delete_dataset)
+                .or(add_label_form)
+                .or(add_label_submit)
+                .or(edit_label_form)
+                .or(delete_label)
+                .or(add_review_policy)
+                .or(delete_review_policy),
         )
         .debug_boxed()
 }
@@ -69,6 +131,10 @@ enum Action {
     None,
     AddDataset,
     DeleteDataset,
+    AddLabel,
+    DeleteLabel,
+    AddReviewPolicy,
+    DeleteReviewPolicy,
 }
 
 async fn reply_settings(
@@ -77,12 +143,22 @@ async fn reply_settings(
     db: impl AdministratorAccessDb,
     previous_success: Result<Action, Action>,
 ) -> Result<impl Reply, Rejection> {
+    let (datasets, labels, review_policies) = spawn_blocking_child(move || {
+        (
+            Dataset::fetch_all(&db),
+            Label::fetch_all(&db),
+            ReviewPolicy::fetch_all(&db),
+        )
+    })
+    .await
+    .unwrap();
+
     Ok(SiteSettings {
         auth: user.into(),
         i18n_info,
-        datasets: spawn_blocking_child(move || Dataset::fetch_all(&db))
-            .await
-            .unwrap(),
+        datasets,
+        labels,
+        review_policies,
         previous_success,
     })
 }
@@ -177,6 +253,185 @@ async fn reply_edit_dataset_form(
     })
 }
 
+async fn reply_add_label(
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl AdministratorAccessDb,
+    form: LabelForm,
+    tantivy: Arc<TantivyClient>,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let res = spawn_blocking_child(move || {
+        if !form.rank_weight.is_finite() || form.rank_weight <= 0.0 {
+            bail!("Invalid rank weight {}", form.rank_weight);
+        }
+
+        Label::upsert(
+            &db_clone,
+            form.id,
+            form.kind,
+            form.name,
+            form.description,
+            form.rank_weight,
+        )
+    })
+    .await
+    .context("Failed to join task")
+    .and_then(|x| x);
+
+    let success = match res {
+        Ok(_) => {
+            // The rank weight of the label may have changed
+            tantivy.reindex_database(db.clone()).await;
+            Ok(Action::AddLabel)
+        }
+        Err(error) => {
+            tracing::error!(?error, "Failed to add label");
+            Err(Action::AddLabel)
+        }
+    };
+
+    reply_settings(user, i18n_info, db, success).await
+}
+
+async fn reply_delete_label(
+    label_id: u64,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl AdministratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let success = spawn_blocking_child(move || Label::delete_by_id(&db_clone, label_id))
+        .await
+        .unwrap();
+
+    let success = if success {
+        // Words which had the label should no longer be found when filtering by it
+        tantivy.reindex_database(db.clone()).await;
+        Ok(Action::DeleteLabel)
+    } else {
+        Err(Action::DeleteLabel)
+    };
+
+    reply_settings(user, i18n_info, db, success).await
+}
+
+async fn reply_edit_label_form(
+    label_id: u64,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl AdministratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let label = spawn_blocking_child(move || Label::fetch_by_id(&db, label_id))
+        .await
+        .unwrap()
+        .ok_or(warp::reject::not_found())?
+        .into();
+
+    Ok(AddLabel {
+        auth: user.into(),
+        i18n_info,
+        label,
+    })
+}
+
+async fn reply_add_review_policy(
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl AdministratorAccessDb,
+    form: ReviewPolicyForm,
+) -> Result<impl Reply, Rejection> {
+    let success = if form.required_approvals == 0 {
+        Err(Action::AddReviewPolicy)
+    } else {
+        let db = db.clone();
+        spawn_blocking_child(move || {
+            ReviewPolicy::insert(
+                &db,
+                form.suggestion_type,
+                form.dataset_id,
+                form.required_approvals,
+                form.allow_voting,
+            )
+        })
+        .await
+        .unwrap();
+
+        Ok(Action::AddReviewPolicy)
+    };
+
+    reply_settings(user, i18n_info, db, success).await
+}
+
+async fn reply_delete_review_policy(
+    policy_id: u64,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl AdministratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let success = spawn_blocking_child(move || ReviewPolicy::delete_by_id(&db_clone, policy_id))
+        .await
+        .unwrap();
+
+    let success = if success {
+        Ok(Action::DeleteReviewPolicy)
+    } else {
+        Err(Action::DeleteReviewPolicy)
+    };
+
+    reply_settings(user, i18n_info, db, success).await
+}
+
+#[serde_as]
+#[derive(Deserialize, Debug)]
+struct ReviewPolicyForm {
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    suggestion_type: Option<SuggestionType>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    dataset_id: Option<u64>,
+    required_approvals: u32,
+    #[serde(default = "false_fn")]
+    #[serde(deserialize_with = "deserialize_checkbox")]
+    allow_voting: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct LabelForm {
+    id: Option<u64>,
+    kind: LabelKind,
+    name: String,
+    description: String,
+    rank_weight: f64,
+}
+
+impl Default for LabelForm {
+    fn default() -> Self {
+        LabelForm {
+            id: None,
+            kind: LabelKind::Register,
+            name: String::new(),
+            description: String::new(),
+            rank_weight: 1.0,
+        }
+    }
+}
+
+impl From<Label> for LabelForm {
+    fn from(l: Label) -> Self {
+        LabelForm {
+            id: Some(l.id),
+            kind: l.kind,
+            name: l.name,
+            description: l.description,
+            rank_weight: l.rank_weight,
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 struct DatasetForm {
     pub id: Option<u64>,
@@ -253,6 +508,8 @@ struct SiteSettings {
     auth: Auth,
     i18n_info: I18nInfo,
     datasets: Vec<Dataset>,
+    labels: Vec<Label>,
+    review_policies: Vec<ReviewPolicy>,
     previous_success: Result<Action, Action>,
 }
 
@@ -263,3 +520,11 @@ struct AddDataset {
     i18n_info: I18nInfo,
     dataset: DatasetForm,
 }
+
+#[derive(I18nTemplate, Template)]
+#[template(path = "add_label.askama.html")]
+struct AddLabel {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    label: LabelForm,
+}
@@ -0,0 +1,172 @@
+use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::scopes;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::conjugation::{
+    ConjugationOverride, ConjugationTable, Polarity, SubjectConcord, Tense, VerbStem,
+};
+use isixhosa_common::database::{with_public_db, DbBase, ModeratorAccessDb, PublicAccessDb};
+use isixhosa_common::format::DisplayHtml;
+use isixhosa_common::language::PartOfSpeech;
+use isixhosa_common::types::ExistingWord;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{body, reply, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "conjugation.askama.html")]
+struct ConjugationPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    word: ExistingWord,
+    table: ConjugationTable,
+    previous_success: Option<bool>,
+}
+
+pub fn conjugation(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let conjugation_json = warp::get()
+        .and(warp::path![u64 / "conjugation.json"])
+        .and(warp::path::end())
+        .and(with_public_db(db.clone()))
+        .and_then(conjugation_json);
+
+    let conjugation_page = warp::get()
+        .and(warp::path![u64 / "conjugation"])
+        .and(warp::path::end())
+        .and(warp::any().map(|| None)) // previous_success is None
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(conjugation_page);
+
+    let submit_overrides = warp::post()
+        .and(warp::path![u64 / "conjugation"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::form())
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(submit_overrides);
+
+    warp::path("word")
+        .and(conjugation_json.or(conjugation_page).or(submit_overrides))
+        .debug_boxed()
+}
+
+#[instrument(name = "Serve conjugation table as JSON", skip(db))]
+async fn conjugation_json(word_id: u64, db: impl PublicAccessDb) -> Result<impl Reply, Rejection> {
+    let table = spawn_blocking_child(move || {
+        ExistingWord::fetch_full(&db, word_id).and_then(|word| word.conjugation())
+    })
+    .await
+    .unwrap();
+
+    match table {
+        Some(table) => Ok(reply::json(&table)),
+        None => Err(warp::reject::not_found()),
+    }
+}
+
+#[instrument(name = "Display conjugation overrides page", skip(user, db))]
+async fn conjugation_page(
+    word_id: u64,
+    previous_success: Option<bool>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let word = spawn_blocking_child(move || ExistingWord::fetch_full(&db, word_id))
+        .await
+        .unwrap();
+
+    let table = word.as_ref().and_then(|word| word.conjugation());
+
+    Ok(match (word, table) {
+        (Some(word), Some(table)) => ConjugationPage {
+            auth: user.into(),
+            i18n_info,
+            word,
+            table,
+            previous_success,
+        }
+        .into_response(),
+        _ => NotFound {
+            auth: user.into(),
+            i18n_info,
+        }
+        .into_response(),
+    })
+}
+
+/// Parses a form field named `{tense}.{polarity}.{subject}`, as in the conjugation page
+fn parse_field(name: &str) -> Option<(Tense, Polarity, SubjectConcord)> {
+    let mut parts = name.split('.');
+    let (tense, polarity, subject) = (parts.next()?, parts.next()?, parts.next()?);
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((
+        Tense::ALL.into_iter().find(|t| t.name() == tense)?,
+        Polarity::ALL.into_iter().find(|p| p.name() == polarity)?,
+        SubjectConcord::ALL
+            .into_iter()
+            .find(|s| s.name() == subject)?,
+    ))
+}
+
+#[instrument(name = "Submit conjugation overrides", skip(user, db))]
+async fn submit_overrides(
+    word_id: u64,
+    form: HashMap<String, String>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let success = spawn_blocking_child(move || {
+        if !scopes::may_moderate_word(&db_clone, reviewer, word_id) {
+            return false;
+        }
+
+        let stem = ExistingWord::fetch_alone(&db_clone, word_id)
+            .filter(|word| word.part_of_speech == Some(PartOfSpeech::Verb))
+            .and_then(|word| VerbStem::from_infinitive(&word.infinitive));
+        let Some(stem) = stem else {
+            return false;
+        };
+
+        // Only forms which differ from the generated ones are stored, so that clearing a field or
+        // setting it back to the generated form removes the override
+        let mut overrides: Vec<ConjugationOverride> = form
+            .into_iter()
+            .filter_map(|(name, form)| {
+                let (tense, polarity, subject) = parse_field(&name)?;
+                let form = form.trim().to_owned();
+                let generated = stem.generate(tense, polarity, subject);
+
+                (!form.is_empty() && form != generated).then_some(ConjugationOverride {
+                    tense,
+                    polarity,
+                    subject,
+                    form,
+                })
+            })
+            .collect();
+        overrides.sort_by_key(|o| (o.tense, o.polarity, o.subject));
+
+        ConjugationOverride::replace_all_for_word(&db_clone, word_id, &overrides);
+        true
+    })
+    .await
+    .unwrap();
+
+    conjugation_page(word_id, Some(success), user, i18n_info, db).await
+}
@@ -0,0 +1,62 @@
+use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::counterpart::create_counterpart;
+use crate::database::scopes;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::search::TantivyClient;
+use crate::{spawn_blocking_child, DebugBoxedExt};
+use isixhosa_common::database::{DbBase, ModeratorAccessDb};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::http::Uri;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Deserialize, Debug)]
+struct CounterpartForm {
+    english: String,
+}
+
+pub fn counterpart(
+    db: DbBase,
+    tantivy: Arc<TantivyClient>,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    warp::post()
+        .and(warp::path!["word" / u64 / "counterpart"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(4 * 1024))
+        .and(body::form())
+        .and(warp::any().map(move || tantivy.clone()))
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(submit_counterpart)
+        .debug_boxed()
+}
+
+#[instrument(name = "Create noun counterpart", skip(tantivy, user, db))]
+async fn submit_counterpart(
+    word_id: u64,
+    form: CounterpartForm,
+    tantivy: Arc<TantivyClient>,
+    user: FullUser,
+    _i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let reviewer = user.id;
+    let new_word_id = spawn_blocking_child(move || {
+        if !scopes::may_moderate_word(&db, reviewer, word_id) {
+            return None;
+        }
+
+        create_counterpart(&db, tantivy, word_id, &form.english, reviewer)
+    })
+    .await
+    .unwrap();
+
+    // If the counterpart could not be created, e.g. because it was linked in the meantime, the
+    // word page shows the current state
+    let uri: Uri = format!("/word/{}", new_word_id.unwrap_or(word_id))
+        .parse()
+        .unwrap();
+    Ok(warp::redirect::see_other(uri))
+}
@@ -5,9 +5,24 @@ use isixhosa_common::types::PublicUserInfo;
 use rusqlite::params;
 use tracing::instrument;
 
+pub mod changes;
+pub mod conflicts;
+pub mod counterpart;
 pub mod deletion;
+pub mod discussion;
+pub mod merge;
+pub mod outcomes;
+pub mod queue;
+pub mod references;
+pub mod review;
+pub mod revisions;
+pub mod scopes;
+pub mod senses;
+pub mod split;
 pub mod submit;
 pub mod suggestion;
+pub mod trash;
+pub mod trusted;
 pub mod user;
 
 #[instrument(name = "Add user attribution", skip(db))]
@@ -0,0 +1,805 @@
+//! Persistent records of accepted changes to words and the items associated with them, as shown
+//! on the recent changes page.
+
+use crate::database::suggestion::MaybeEdited;
+use crate::database::WordOrSuggestionId;
+use crate::export::{
+    DatasetAttributionRecord, LabelAttributionRecord, LinkedWordRecord, WordRecord,
+};
+use crate::i18n::I18nInfo;
+use chrono::{DateTime, Utc};
+use fallible_iterator::FallibleIterator;
+use fluent_templates::ArcLoader;
+use isixhosa_common::database::{ModeratorAccessDb, PublicAccessDb};
+use isixhosa_common::format::DisplayHtml;
+use isixhosa_common::i18n::{ToTranslationKey, TranslationKey};
+use isixhosa_common::types::{
+    Citation, Dataset, ExistingExample, ExistingSense, ExistingWord, Label, PublicUserInfo,
+    Reference, WordHit,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, Value, ValueRef};
+use rusqlite::{params, OptionalExtension, Row, ToSql};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, NoneAsEmptyString};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::num::NonZeroU64;
+use std::str::FromStr;
+use tracing::{instrument, Span};
+
+/// How many changes are shown on one page of the recent changes list
+pub const CHANGES_PER_PAGE: u64 = 100;
+
+/// What kind of item a change was made to
+#[derive(
+    IntoPrimitive, TryFromPrimitive, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[repr(u8)]
+pub enum ChangeTarget {
+    Word = 1,
+    Example = 2,
+    LinkedWord = 3,
+    DatasetAttribution = 4,
+    Sense = 5,
+    Label = 6,
+    Citation = 7,
+}
+
+impl ChangeTarget {
+    pub const ALL: [ChangeTarget; 7] = [
+        ChangeTarget::Word,
+        ChangeTarget::Example,
+        ChangeTarget::LinkedWord,
+        ChangeTarget::DatasetAttribution,
+        ChangeTarget::Sense,
+        ChangeTarget::Label,
+        ChangeTarget::Citation,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeTarget::Word => "word",
+            ChangeTarget::Example => "example",
+            ChangeTarget::LinkedWord => "linked_word",
+            ChangeTarget::DatasetAttribution => "dataset_attribution",
+            ChangeTarget::Sense => "sense",
+            ChangeTarget::Label => "label",
+            ChangeTarget::Citation => "citation",
+        }
+    }
+}
+
+/// Whether the item was added, edited, or deleted
+#[derive(IntoPrimitive, TryFromPrimitive, Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ChangeKind {
+    Added = 1,
+    Edited = 2,
+    Deleted = 3,
+}
+
+impl ChangeKind {
+    pub const ALL: [ChangeKind; 3] = [ChangeKind::Added, ChangeKind::Edited, ChangeKind::Deleted];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Edited => "edited",
+            ChangeKind::Deleted => "deleted",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidChangeFilter(String);
+
+impl Display for InvalidChangeFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid change filter {}", self.0)
+    }
+}
+
+impl FromStr for ChangeTarget {
+    type Err = InvalidChangeFilter;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ChangeTarget::ALL
+            .into_iter()
+            .find(|target| target.name() == s)
+            .ok_or_else(|| InvalidChangeFilter(s.to_owned()))
+    }
+}
+
+impl Display for ChangeTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ChangeKind {
+    type Err = InvalidChangeFilter;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ChangeKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| InvalidChangeFilter(s.to_owned()))
+    }
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl ToTranslationKey for ChangeTarget {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            ChangeTarget::Word => "recent-changes.target-word",
+            ChangeTarget::Example => "recent-changes.target-example",
+            ChangeTarget::LinkedWord => "recent-changes.target-linked-word",
+            ChangeTarget::DatasetAttribution => "recent-changes.target-dataset-attribution",
+            ChangeTarget::Sense => "recent-changes.target-sense",
+            ChangeTarget::Label => "recent-changes.target-label",
+            ChangeTarget::Citation => "recent-changes.target-citation",
+        })
+    }
+}
+
+impl ToTranslationKey for ChangeKind {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            ChangeKind::Added => "recent-changes.kind-added",
+            ChangeKind::Edited => "recent-changes.kind-edited",
+            ChangeKind::Deleted => "recent-changes.kind-deleted",
+        })
+    }
+}
+
+impl ToSql for ChangeTarget {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for ChangeTarget {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        u8::try_from(v)
+            .ok()
+            .and_then(|v| Self::try_from_primitive(v).ok())
+            .ok_or(FromSqlError::OutOfRange(v))
+    }
+}
+
+impl ToSql for ChangeKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for ChangeKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        u8::try_from(v)
+            .ok()
+            .and_then(|v| Self::try_from_primitive(v).ok())
+            .ok_or(FromSqlError::OutOfRange(v))
+    }
+}
+
+/// The state of an item before or after a change, stored as JSON.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChangeSnapshot {
+    Word(WordRecord),
+    Example(ExistingExample),
+    LinkedWord(LinkedWordRecord),
+    DatasetAttribution(DatasetAttributionRecord),
+    Sense(ExistingSense),
+    Label(LabelAttributionRecord),
+    Citation(Citation),
+}
+
+impl ChangeSnapshot {
+    pub fn fetch_word(db: &impl PublicAccessDb, word_id: u64) -> Option<ChangeSnapshot> {
+        ExistingWord::fetch_alone(db, word_id).map(|w| ChangeSnapshot::Word(w.into()))
+    }
+
+    pub fn fetch_example(db: &impl PublicAccessDb, example_id: u64) -> Option<ChangeSnapshot> {
+        ExistingExample::fetch(db, example_id).map(ChangeSnapshot::Example)
+    }
+
+    #[instrument(
+        level = "trace",
+        name = "Fetch linked word snapshot",
+        fields(found),
+        skip(db)
+    )]
+    pub fn fetch_linked_word(db: &impl PublicAccessDb, link_id: u64) -> Option<ChangeSnapshot> {
+        const SELECT: &str = "
+            SELECT link_id, link_type, first_word_id, second_word_id FROM linked_words
+                WHERE link_id = ?1;
+        ";
+
+        let conn = db.get().unwrap();
+        #[allow(clippy::redundant_closure)] // "implementation of FnOnce is not general enough"
+        let opt = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query_row(params![link_id], |row| LinkedWordRecord::try_from(row))
+            .optional()
+            .unwrap();
+
+        Span::current().record("found", opt.is_some());
+
+        opt.map(ChangeSnapshot::LinkedWord)
+    }
+
+    pub fn fetch_sense(db: &impl PublicAccessDb, sense_id: u64) -> Option<ChangeSnapshot> {
+        ExistingSense::fetch(db, sense_id).map(ChangeSnapshot::Sense)
+    }
+
+    pub fn fetch_citation(db: &impl PublicAccessDb, citation_id: u64) -> Option<ChangeSnapshot> {
+        Citation::fetch_by_id(db, citation_id).map(ChangeSnapshot::Citation)
+    }
+
+    pub fn target(&self) -> ChangeTarget {
+        match self {
+            ChangeSnapshot::Word(_) => ChangeTarget::Word,
+            ChangeSnapshot::Example(_) => ChangeTarget::Example,
+            ChangeSnapshot::LinkedWord(_) => ChangeTarget::LinkedWord,
+            ChangeSnapshot::DatasetAttribution(_) => ChangeTarget::DatasetAttribution,
+            ChangeSnapshot::Sense(_) => ChangeTarget::Sense,
+            ChangeSnapshot::Label(_) => ChangeTarget::Label,
+            ChangeSnapshot::Citation(_) => ChangeTarget::Citation,
+        }
+    }
+
+    /// The words which this item belongs to
+    pub fn word_ids(&self) -> (u64, Option<u64>) {
+        match self {
+            ChangeSnapshot::Word(w) => (w.word_id, None),
+            ChangeSnapshot::Example(e) => (e.word_id, None),
+            ChangeSnapshot::LinkedWord(l) => (l.first, Some(l.second)),
+            ChangeSnapshot::DatasetAttribution(d) => (d.word_id, None),
+            ChangeSnapshot::Sense(s) => (s.word_id, None),
+            ChangeSnapshot::Label(l) => (l.word_id, None),
+            ChangeSnapshot::Citation(c) => (c.word_id, None),
+        }
+    }
+
+    /// Renders each field of the snapshot as plaintext, alongside the translation key of its name.
+    /// The words which linked words refer to are looked up in `linked_words`, which should be
+    /// fetched for all of the snapshots being rendered at once.
+    pub fn fields(
+        &self,
+        db: &impl PublicAccessDb,
+        i18n_info: &I18nInfo,
+        datasets: &HashMap<u64, Dataset>,
+        linked_words: &LinkedWordHits,
+        word_id: u64,
+    ) -> Vec<(&'static str, String)> {
+        fn text(val: impl DisplayHtml<ArcLoader>, i18n_info: &I18nInfo) -> String {
+            val.to_plaintext(i18n_info).to_string()
+        }
+
+        fn label_name(db: &impl PublicAccessDb, id: u64) -> String {
+            Label::fetch_by_id(db, id)
+                .map(|l| l.name)
+                .unwrap_or_else(|| format!("#{id}"))
+        }
+
+        let yes_no = |b: bool| text(TranslationKey::new(if b { "yes" } else { "no" }), i18n_info);
+
+        match self {
+            ChangeSnapshot::Word(w) => vec![
+                ("recent-changes.english", w.english.clone()),
+                ("recent-changes.xhosa", w.xhosa.clone()),
+                ("part-of-speech", text(&w.part_of_speech, i18n_info)),
+                ("with-tone-markings", w.xhosa_tone_markings.clone()),
+                ("infinitive", w.infinitive.clone()),
+                (
+                    "plurality",
+                    text(
+                        TranslationKey::new(if w.is_plural {
+                            "plurality.plural"
+                        } else {
+                            "plurality.singular"
+                        }),
+                        i18n_info,
+                    ),
+                ),
+                ("inchoative", yes_no(w.is_inchoative)),
+                (
+                    "transitivity",
+                    text(
+                        w.transitivity.map(|t| t.explicit_moderation_page()),
+                        i18n_info,
+                    ),
+                ),
+                ("followed-by", text(&w.followed_by, i18n_info)),
+                ("noun-class", text(&w.noun_class, i18n_info)),
+                ("note", w.note.clone()),
+                ("informal", yes_no(w.is_informal)),
+                ("etymology.language", text(&w.etymology_language, i18n_info)),
+                ("etymology.form", w.etymology_form.clone()),
+                ("etymology.note", w.etymology_note.clone()),
+                ("literal-translation", w.literal_translation.clone()),
+            ],
+            ChangeSnapshot::Example(e) => vec![
+                ("examples.source", e.english.clone()),
+                ("examples.target", e.xhosa.clone()),
+            ],
+            ChangeSnapshot::LinkedWord(l) => {
+                let (other, link_type) = if l.first == word_id {
+                    (l.second, l.link_type)
+                } else {
+                    (l.first, l.link_type.inverse())
+                };
+                let other = linked_words
+                    .words
+                    .get(&other)
+                    .map(|hit| text(hit, i18n_info))
+                    .unwrap_or_else(|| format!("#{other}"));
+
+                vec![
+                    ("linked-words.link-type", text(&link_type, i18n_info)),
+                    ("linked-words.other-word", other),
+                ]
+            }
+            ChangeSnapshot::DatasetAttribution(d) => {
+                let name = datasets
+                    .get(&d.dataset_id)
+                    .map(|d| d.name.clone())
+                    .unwrap_or_else(|| format!("#{}", d.dataset_id));
+                vec![("dataset", name)]
+            }
+            ChangeSnapshot::Sense(s) => {
+                let examples = s
+                    .examples
+                    .iter()
+                    .map(|id| {
+                        ExistingExample::fetch(db, *id)
+                            .map(|e| e.english)
+                            .unwrap_or_else(|| format!("#{id}"))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                let linked_words = s
+                    .linked_words
+                    .iter()
+                    .map(|id| match linked_words.links.get(id) {
+                        Some(&(first, second)) => {
+                            let other = if first == s.word_id { second } else { first };
+                            linked_words
+                                .words
+                                .get(&other)
+                                .map(|hit| text(hit, i18n_info))
+                                .unwrap_or_else(|| format!("#{other}"))
+                        }
+                        None => format!("#{id}"),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                let labels = s
+                    .labels
+                    .iter()
+                    .map(|id| label_name(db, *id))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+
+                vec![
+                    ("senses.number", s.sense_order.to_string()),
+                    ("senses.gloss", s.english.clone()),
+                    ("informal", yes_no(s.is_informal)),
+                    ("examples", examples),
+                    ("linked-words", linked_words),
+                    ("labels", labels),
+                ]
+            }
+            ChangeSnapshot::Label(l) => vec![("labels", label_name(db, l.label_id))],
+            ChangeSnapshot::Citation(c) => {
+                let reference = Reference::fetch_by_id(db, c.reference_id)
+                    .map(|r| r.short_form())
+                    .unwrap_or_else(|| format!("#{}", c.reference_id));
+                let sense = c
+                    .sense_id
+                    .map(|id| {
+                        ExistingSense::fetch(db, id)
+                            .map(|s| s.english)
+                            .unwrap_or_else(|| format!("#{id}"))
+                    })
+                    .unwrap_or_default();
+                let example = c
+                    .example_id
+                    .map(|id| {
+                        ExistingExample::fetch(db, id)
+                            .map(|e| e.english)
+                            .unwrap_or_else(|| format!("#{id}"))
+                    })
+                    .unwrap_or_default();
+
+                vec![
+                    ("references.reference", reference),
+                    ("references.sense", sense),
+                    ("references.example", example),
+                    ("references.pages", c.pages.clone()),
+                ]
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub value: MaybeEdited<String>,
+}
+
+impl FieldDiff {
+    /// Compares the rendered fields of an item before and after a change. `None` means that the
+    /// item did not exist at that point, in which case every non-empty field is shown.
+    pub fn between(
+        old: Option<Vec<(&'static str, String)>>,
+        new: Option<Vec<(&'static str, String)>>,
+    ) -> Vec<FieldDiff> {
+        match (old, new) {
+            (None, Some(new)) => new
+                .into_iter()
+                .filter(|(_, new)| !new.is_empty())
+                .map(|(field, new)| FieldDiff {
+                    field,
+                    value: MaybeEdited::New(new),
+                })
+                .collect(),
+            (Some(old), None) => old
+                .into_iter()
+                .filter(|(_, old)| !old.is_empty())
+                .map(|(field, old)| FieldDiff {
+                    field,
+                    value: MaybeEdited::Edited {
+                        old,
+                        new: String::new(),
+                    },
+                })
+                .collect(),
+            (Some(old), Some(new)) => old
+                .into_iter()
+                .zip(new)
+                .filter(|((_, old), (_, new))| old != new)
+                .map(|((field, old), (_, new))| FieldDiff {
+                    field,
+                    value: MaybeEdited::Edited { old, new },
+                })
+                .collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+/// The words which linked words in a set of snapshots refer to, fetched all at once rather than
+/// one at a time as each snapshot is rendered
+#[derive(Default)]
+pub struct LinkedWordHits {
+    /// The first and second word of each link which a sense refers to, by link id
+    links: HashMap<u64, (u64, u64)>,
+    words: HashMap<u64, WordHit>,
+}
+
+impl LinkedWordHits {
+    #[instrument(level = "trace", name = "Fetch linked words of snapshots", skip_all)]
+    pub fn fetch<'a>(
+        db: &impl PublicAccessDb,
+        snapshots: impl IntoIterator<Item = &'a ChangeSnapshot>,
+    ) -> LinkedWordHits {
+        const SELECT_LINKS: &str = "
+            SELECT link_id, first_word_id, second_word_id FROM linked_words
+                WHERE link_id IN (SELECT value FROM json_each(?1));
+        ";
+        const SELECT_WORDS: &str = "
+            SELECT
+                word_id, english, xhosa, part_of_speech, is_plural, is_inchoative, is_informal,
+                transitivity, noun_class
+            FROM words
+            WHERE word_id IN (SELECT value FROM json_each(?1));
+        ";
+        const SELECT_SENSES: &str = "
+            SELECT word_id, english FROM senses
+                WHERE word_id IN (SELECT value FROM json_each(?1))
+                ORDER BY word_id, sense_order, sense_id;
+        ";
+
+        let mut word_ids = Vec::new();
+        let mut link_ids = Vec::new();
+
+        for snapshot in snapshots {
+            match snapshot {
+                ChangeSnapshot::LinkedWord(l) => word_ids.extend([l.first, l.second]),
+                ChangeSnapshot::Sense(s) => link_ids.extend(&s.linked_words),
+                _ => {}
+            }
+        }
+
+        if word_ids.is_empty() && link_ids.is_empty() {
+            return LinkedWordHits::default();
+        }
+
+        let conn = db.get().unwrap();
+
+        let links: HashMap<u64, (u64, u64)> = conn
+            .prepare(SELECT_LINKS)
+            .unwrap()
+            .query(params![serde_json::to_string(&link_ids).unwrap()])
+            .unwrap()
+            .map(|row| {
+                Ok((
+                    row.get("link_id")?,
+                    (row.get("first_word_id")?, row.get("second_word_id")?),
+                ))
+            })
+            .collect()
+            .unwrap();
+
+        word_ids.extend(links.values().flat_map(|&(first, second)| [first, second]));
+        let word_ids = serde_json::to_string(&word_ids).unwrap();
+
+        let mut words: HashMap<u64, WordHit> = conn
+            .prepare(SELECT_WORDS)
+            .unwrap()
+            .query(params![word_ids])
+            .unwrap()
+            .map(|row| {
+                let id: u64 = row.get("word_id")?;
+                let hit = WordHit::try_from_row_and_id(row, WordOrSuggestionId::existing(id))?;
+                Ok((id, hit))
+            })
+            .collect()
+            .unwrap();
+
+        conn.prepare(SELECT_SENSES)
+            .unwrap()
+            .query(params![word_ids])
+            .unwrap()
+            .for_each(|row| {
+                let word_id: u64 = row.get("word_id")?;
+                if let Some(hit) = words.get_mut(&word_id) {
+                    hit.senses.push(row.get("english")?);
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        LinkedWordHits { links, words }
+    }
+}
+
+#[derive(Debug)]
+pub struct WordChange {
+    pub change_id: u64,
+    pub word_id: u64,
+    /// `None` if the word has since been deleted
+    pub word: Option<WordHit>,
+    pub target: ChangeTarget,
+    pub kind: ChangeKind,
+    /// `None` if the user has since been deleted
+    pub suggesting_user: Option<PublicUserInfo>,
+    /// `None` if the user has since been deleted
+    pub reviewing_user: Option<PublicUserInfo>,
+    pub changed_at: DateTime<Utc>,
+    pub diff: Vec<FieldDiff>,
+}
+
+/// Filters for the recent changes list. Every filter is optional.
+#[serde_as]
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ChangeFilter {
+    /// Either the suggesting or reviewing user
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<u64>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word: Option<u64>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<ChangeTarget>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ChangeKind>,
+    /// Only show changes older than this change id
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<u64>,
+}
+
+impl ChangeFilter {
+    pub fn is_empty(&self) -> bool {
+        self.user.is_none() && self.word.is_none() && self.target.is_none() && self.kind.is_none()
+    }
+}
+
+impl WordChange {
+    /// Records an accepted change to a word or one of its associated items. The kind of change is
+    /// inferred from which snapshots are present. Nothing is recorded if both snapshots are equal.
+    #[instrument(name = "Record word change", fields(recorded), skip(db, old, new))]
+    pub fn record(
+        db: &impl ModeratorAccessDb,
+        suggesting_user: NonZeroU64,
+        reviewing_user: NonZeroU64,
+        old: Option<ChangeSnapshot>,
+        new: Option<ChangeSnapshot>,
+    ) {
+        const INSERT: &str = "
+            INSERT INTO word_changes (
+                word_id, second_word_id, change_target, change_kind, suggesting_user,
+                reviewing_user, changed_at, old_value, new_value
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9);
+        ";
+
+        let (kind, snapshot) = match (&old, &new) {
+            (None, Some(new)) => (ChangeKind::Added, new),
+            (Some(old), None) => (ChangeKind::Deleted, old),
+            (Some(_), Some(new)) => (ChangeKind::Edited, new),
+            (None, None) => {
+                Span::current().record("recorded", false);
+                return;
+            }
+        };
+
+        let to_json =
+            |s: &Option<ChangeSnapshot>| s.as_ref().map(|s| serde_json::to_string(s).unwrap());
+        let (old_value, new_value) = (to_json(&old), to_json(&new));
+
+        if old_value == new_value {
+            Span::current().record("recorded", false);
+            return;
+        }
+
+        let (word_id, second_word_id) = snapshot.word_ids();
+
+        db.get()
+            .unwrap()
+            .prepare(INSERT)
+            .unwrap()
+            .execute(params![
+                word_id,
+                second_word_id,
+                snapshot.target(),
+                kind,
+                suggesting_user.get(),
+                reviewing_user.get(),
+                Utc::now(),
+                old_value,
+                new_value,
+            ])
+            .unwrap();
+
+        Span::current().record("recorded", true);
+    }
+
+    #[instrument(name = "Fetch recent changes", fields(results), skip(db, i18n_info))]
+    pub fn fetch_filtered(
+        db: &impl PublicAccessDb,
+        i18n_info: &I18nInfo,
+        filter: &ChangeFilter,
+    ) -> Vec<WordChange> {
+        const SELECT: &str = "
+            SELECT word_changes.change_id, word_changes.word_id, word_changes.change_target,
+                   word_changes.change_kind, word_changes.changed_at, word_changes.old_value,
+                   word_changes.new_value, word_changes.suggesting_user, suggestor.username,
+                   suggestor.display_name, word_changes.reviewing_user,
+                   reviewer.username AS reviewer_username,
+                   reviewer.display_name AS reviewer_display_name,
+                   words.word_id AS existing_word_id, words.english, words.xhosa,
+                   words.part_of_speech, words.is_plural, words.is_inchoative, words.is_informal,
+                   words.transitivity, words.noun_class
+            FROM word_changes
+            LEFT OUTER JOIN users AS suggestor ON word_changes.suggesting_user = suggestor.user_id
+            LEFT OUTER JOIN users AS reviewer ON word_changes.reviewing_user = reviewer.user_id
+            LEFT OUTER JOIN words ON word_changes.word_id = words.word_id
+            WHERE
+                (?1 IS NULL OR word_changes.suggesting_user = ?1 OR word_changes.reviewing_user = ?1) AND
+                (?2 IS NULL OR word_changes.word_id = ?2 OR word_changes.second_word_id = ?2) AND
+                (?3 IS NULL OR word_changes.change_target = ?3) AND
+                (?4 IS NULL OR word_changes.change_kind = ?4) AND
+                (?5 IS NULL OR word_changes.change_id < ?5)
+            ORDER BY word_changes.change_id DESC
+            LIMIT ?6;
+        ";
+
+        let datasets: HashMap<u64, Dataset> = Dataset::fetch_all(db)
+            .into_iter()
+            .map(|d| (d.id, d))
+            .collect();
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+        let rows = query
+            .query(params![
+                filter.user,
+                filter.word,
+                filter.target,
+                filter.kind,
+                filter.before,
+                CHANGES_PER_PAGE,
+            ])
+            .unwrap();
+
+        let rows: Vec<(WordChange, Option<ChangeSnapshot>, Option<ChangeSnapshot>)> = rows
+            .map(|row| Ok(WordChange::from_row(row)))
+            .collect()
+            .unwrap();
+
+        let snapshots = rows.iter().flat_map(|(_, old, new)| old.iter().chain(new));
+        let linked_words = LinkedWordHits::fetch(db, snapshots);
+
+        let changes: Vec<WordChange> = rows
+            .into_iter()
+            .map(|(mut change, old, new)| {
+                let word_id = change.word_id;
+                let fields = |s: Option<ChangeSnapshot>| {
+                    s.map(|s| s.fields(db, i18n_info, &datasets, &linked_words, word_id))
+                };
+                change.diff = FieldDiff::between(fields(old), fields(new));
+                change
+            })
+            .collect();
+
+        Span::current().record("results", changes.len());
+
+        changes
+    }
+
+    /// Reads a change from the row along with its old and new snapshots. Its diff is left empty
+    /// until the snapshots are rendered.
+    fn from_row(row: &Row<'_>) -> (WordChange, Option<ChangeSnapshot>, Option<ChangeSnapshot>) {
+        let snapshot = |col: &str| {
+            row.get::<&str, Option<String>>(col)
+                .unwrap()
+                .map(|json| serde_json::from_str::<ChangeSnapshot>(&json).unwrap())
+        };
+
+        let word_id: u64 = row.get("word_id").unwrap();
+        let kind: ChangeKind = row.get("change_kind").unwrap();
+
+        let user = |id: &str, username: &str, display_name: &str| {
+            row.get::<&str, Option<u64>>(id)
+                .unwrap()
+                .and_then(NonZeroU64::new)
+                .map(|id| PublicUserInfo {
+                    id,
+                    username: row.get(username).unwrap(),
+                    display_name: row.get(display_name).unwrap(),
+                })
+        };
+
+        // The word is joined in the same query rather than being fetched separately for each row
+        let word = row
+            .get::<&str, Option<u64>>("existing_word_id")
+            .unwrap()
+            .map(|id| WordHit::try_from_row_and_id(row, WordOrSuggestionId::existing(id)).unwrap());
+
+        let change = WordChange {
+            change_id: row.get("change_id").unwrap(),
+            word_id,
+            word,
+            target: row.get("change_target").unwrap(),
+            kind,
+            suggesting_user: user("suggesting_user", "username", "display_name"),
+            reviewing_user: user(
+                "reviewing_user",
+                "reviewer_username",
+                "reviewer_display_name",
+            ),
+            changed_at: row.get("changed_at").unwrap(),
+            diff: Vec::new(),
+        };
+
+        (change, snapshot("old_value"), snapshot("new_value"))
+    }
+}
@@ -0,0 +1,248 @@
+//! Conflicts between a suggested edit to a word and other edits to the same fields. Suggestions only
+//! store the fields which they change, so a suggestion made before another edit to the same field
+//! was accepted would silently undo that edit if it were accepted as it is. Each suggested edit
+//! keeps the word it was made against as its base, and must be rebased onto the word as it is now
+//! before it can be accepted if the fields it changes have changed since.
+
+use crate::database::changes::{ChangeSnapshot, LinkedWordHits};
+use crate::database::suggestion::SuggestedWord;
+use crate::export::WordRecord;
+use crate::i18n::I18nInfo;
+use isixhosa_common::database::{ModeratorAccessDb, PublicAccessDb, UserAccessDb};
+use isixhosa_common::types::{ExistingWord, PublicUserInfo};
+use rusqlite::{params, OptionalExtension};
+use std::collections::HashMap;
+use tracing::{instrument, Span};
+
+/// Fields which a suggestion changes and which something else changes too
+#[derive(Debug)]
+pub struct Conflict {
+    /// The other pending suggestion for the word, or `None` if the conflict is with changes made
+    /// to the word after the suggestion was made
+    pub other: Option<ConflictingSuggestion>,
+    pub fields: Vec<ConflictField>,
+}
+
+#[derive(Debug)]
+pub struct ConflictingSuggestion {
+    pub suggestion_id: u64,
+    pub suggesting_user: PublicUserInfo,
+}
+
+/// The three versions of a field, rendered as plaintext
+#[derive(Debug)]
+pub struct ConflictField {
+    /// The translation key of the name of the field
+    pub field: &'static str,
+    /// The value which both changes were made against
+    pub original: String,
+    /// The value of this suggestion
+    pub this: String,
+    /// The value of the other suggestion, or of the word as it is now
+    pub other: String,
+}
+
+/// Records the word as it is now as the base of the suggestion, unless the suggestion has one
+/// already. Edits to a suggestion keep the base it was first made against.
+#[instrument(name = "Record word suggestion base", skip(db))]
+pub fn record_base(db: &impl UserAccessDb, suggestion_id: u64, word_id: u64) {
+    const INSERT: &str = "
+        INSERT INTO word_suggestion_bases (suggestion_id, base) VALUES (?1, ?2)
+            ON CONFLICT DO NOTHING;
+    ";
+
+    let Some(word) = ExistingWord::fetch_alone(db, word_id) else {
+        return;
+    };
+    let base = serde_json::to_string(&WordRecord::from(word)).unwrap();
+
+    let conn = db.get().unwrap();
+    conn.prepare(INSERT)
+        .unwrap()
+        .execute(params![suggestion_id, base])
+        .unwrap();
+}
+
+/// Makes the word as it is now the base of the suggestion, so that it may be accepted over the
+/// changes made to the word since it was suggested. Returns `false` if the suggestion does not
+/// exist or is not an edit of an existing word.
+#[instrument(name = "Rebase word suggestion", fields(rebased), skip(db))]
+pub fn rebase(db: &impl ModeratorAccessDb, suggestion_id: u64) -> bool {
+    const UPSERT: &str = "
+        INSERT INTO word_suggestion_bases (suggestion_id, base) VALUES (?1, ?2)
+            ON CONFLICT(suggestion_id) DO UPDATE SET base = excluded.base;
+    ";
+
+    let word = SuggestedWord::fetch_alone(db, suggestion_id)
+        .and_then(|s| s.word_id)
+        .and_then(|word_id| ExistingWord::fetch_alone(db, word_id));
+
+    let Some(word) = word else {
+        Span::current().record("rebased", false);
+        return false;
+    };
+    let base = serde_json::to_string(&WordRecord::from(word)).unwrap();
+
+    let conn = db.get().unwrap();
+    conn.prepare(UPSERT)
+        .unwrap()
+        .execute(params![suggestion_id, base])
+        .unwrap();
+
+    Span::current().record("rebased", true);
+    true
+}
+
+/// Whether the fields which the suggestion changes have changed since it was made, in which case
+/// it must be rebased before it is accepted
+pub fn needs_rebase(db: &impl ModeratorAccessDb, i18n_info: &I18nInfo, suggestion_id: u64) -> bool {
+    SuggestedWord::fetch_alone(db, suggestion_id)
+        .and_then(|s| changed_since(db, i18n_info, &s))
+        .is_some()
+}
+
+/// Finds the conflicts of each of the suggestions with the changes made to its word since it was
+/// made, and with the other pending suggestions for the same word
+#[instrument(name = "Find word suggestion conflicts", fields(conflicting), skip_all)]
+pub fn fetch_all_for(
+    db: &impl ModeratorAccessDb,
+    i18n_info: &I18nInfo,
+    suggestions: &[SuggestedWord],
+) -> HashMap<u64, Vec<Conflict>> {
+    let word_ids: Vec<u64> = suggestions.iter().filter_map(|s| s.word_id).collect();
+    let pending = SuggestedWord::fetch_alone_for_existing_words(db, &word_ids);
+    let mut all = HashMap::new();
+
+    for s in suggestions {
+        let Some(word_id) = s.word_id else {
+            continue;
+        };
+
+        let mut conflicts: Vec<Conflict> = changed_since(db, i18n_info, s).into_iter().collect();
+
+        let (Some(current), Some(this)) = (
+            fields_of(db, i18n_info, word_id, None),
+            fields_of(db, i18n_info, word_id, Some(s)),
+        ) else {
+            continue;
+        };
+
+        let others = pending
+            .iter()
+            .filter(|o| o.word_id == Some(word_id) && o.suggestion_id != s.suggestion_id);
+
+        for other in others {
+            let Some(other_fields) = fields_of(db, i18n_info, word_id, Some(other)) else {
+                continue;
+            };
+
+            // Only fields which both suggestions change
+            let mut fields = three_way(current.clone(), this.clone(), other_fields);
+            fields.retain(|f| f.this != f.original);
+
+            if !fields.is_empty() {
+                conflicts.push(Conflict {
+                    other: Some(ConflictingSuggestion {
+                        suggestion_id: other.suggestion_id,
+                        suggesting_user: other.suggesting_user.clone(),
+                    }),
+                    fields,
+                });
+            }
+        }
+
+        if !conflicts.is_empty() {
+            all.insert(s.suggestion_id, conflicts);
+        }
+    }
+
+    Span::current().record("conflicting", all.len());
+
+    all
+}
+
+/// The fields which the suggestion changes that were changed by something else after it was made.
+/// Returns `None` if there are none, or if the suggestion has no base as it adds a new word or was
+/// made before bases were recorded.
+fn changed_since(
+    db: &impl ModeratorAccessDb,
+    i18n_info: &I18nInfo,
+    suggestion: &SuggestedWord,
+) -> Option<Conflict> {
+    const SELECT: &str = "SELECT base FROM word_suggestion_bases WHERE suggestion_id = ?1;";
+
+    let word_id = suggestion.word_id?;
+
+    let conn = db.get().unwrap();
+    let base: String = conn
+        .prepare(SELECT)
+        .unwrap()
+        .query_row(params![suggestion.suggestion_id], |row| row.get("base"))
+        .optional()
+        .unwrap()?;
+
+    let base: WordRecord = serde_json::from_str(&base).unwrap();
+    let base = ChangeSnapshot::Word(base).fields(
+        db,
+        i18n_info,
+        &HashMap::new(),
+        &LinkedWordHits::default(),
+        word_id,
+    );
+
+    let fields = three_way(
+        base,
+        fields_of(db, i18n_info, word_id, Some(suggestion))?,
+        fields_of(db, i18n_info, word_id, None)?,
+    );
+
+    (!fields.is_empty()).then_some(Conflict {
+        other: None,
+        fields,
+    })
+}
+
+/// The rendered fields of the word as it is now, or as it would be if the suggestion were accepted
+fn fields_of(
+    db: &impl PublicAccessDb,
+    i18n_info: &I18nInfo,
+    word_id: u64,
+    suggestion: Option<&SuggestedWord>,
+) -> Option<Vec<(&'static str, String)>> {
+    let mut word = ExistingWord::fetch_alone(db, word_id)?;
+    if let Some(suggestion) = suggestion {
+        suggestion.apply_fields(&mut word);
+    }
+
+    let snapshot = ChangeSnapshot::Word(WordRecord::from(word));
+    Some(snapshot.fields(
+        db,
+        i18n_info,
+        &HashMap::new(),
+        &LinkedWordHits::default(),
+        word_id,
+    ))
+}
+
+/// The fields where the other version differs from both the original and this version, i.e. where
+/// accepting this version would undo the other
+fn three_way(
+    original: Vec<(&'static str, String)>,
+    this: Vec<(&'static str, String)>,
+    other: Vec<(&'static str, String)>,
+) -> Vec<ConflictField> {
+    original
+        .into_iter()
+        .zip(this)
+        .zip(other)
+        .map(
+            |(((field, original), (_, this)), (_, other))| ConflictField {
+                field,
+                original,
+                this,
+                other,
+            },
+        )
+        .filter(|f| f.other != f.original && f.other != f.this)
+        .collect()
+}
@@ -0,0 +1,80 @@
+//! Creating the missing singular or plural counterpart of a noun from its generated form.
+
+use crate::database::changes::{ChangeSnapshot, WordChange};
+use crate::database::revisions::WordRevision;
+use crate::database::split::{insert_word, link_words};
+use crate::export::WordRecord;
+use crate::search::{TantivyClient, WordDocument};
+use futures::executor::block_on;
+use isixhosa_common::database::ModeratorAccessDb;
+use isixhosa_common::language::WordLinkType;
+use isixhosa_common::types::ExistingWord;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use tracing::{instrument, Span};
+
+/// Creates the generated singular or plural counterpart of the noun as a new word with the given
+/// English meaning, and links the two words as plural and singular. The new word takes the
+/// generated form and class, and none of the other information of the original.
+///
+/// Returns the ID of the new word, or `None` if the word does not exist, has no generated
+/// counterpart, or is already linked to one.
+#[instrument(
+    name = "Create noun counterpart",
+    fields(new_word_id),
+    skip(db, tantivy)
+)]
+pub fn create_counterpart(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    word_id: u64,
+    english: &str,
+    reviewer: NonZeroU64,
+) -> Option<u64> {
+    let english = english.trim();
+    if english.is_empty() {
+        return None;
+    }
+
+    let word = ExistingWord::fetch_full(db, word_id)?;
+    if word.linked_counterpart().is_some() {
+        return None;
+    }
+
+    let counterpart = word.noun_forms()?.counterpart?;
+
+    WordRevision::record_baseline(db, word_id);
+
+    let mut record = WordRecord::from(ExistingWord::fetch_alone(db, word_id)?);
+    record.english = english.to_owned();
+    record.xhosa = counterpart.xhosa;
+    record.noun_class = Some(counterpart.noun_class);
+    record.is_plural = !record.is_plural;
+    record.xhosa_tone_markings = String::new();
+    record.note = String::new();
+    record.etymology_language = None;
+    record.etymology_form = String::new();
+    record.etymology_note = String::new();
+
+    let new_word_id = insert_word(db, &record);
+    record.word_id = new_word_id;
+    let new = ChangeSnapshot::fetch_word(db, new_word_id);
+    WordChange::record(db, reviewer, reviewer, None, new);
+
+    link_words(
+        db,
+        WordLinkType::PluralOrSingular,
+        word_id,
+        new_word_id,
+        reviewer,
+    );
+
+    WordRevision::record(db, word_id, reviewer, reviewer);
+    WordRevision::record(db, new_word_id, reviewer, reviewer);
+
+    let new = WordDocument::from(&record).with_senses_and_labels_from_db(db);
+    block_on(tantivy.add_new_word(new));
+
+    Span::current().record("new_word_id", new_word_id);
+    Some(new_word_id)
+}
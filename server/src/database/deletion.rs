@@ -1,3 +1,6 @@
+use crate::database::changes::{ChangeSnapshot, WordChange};
+use crate::database::revisions::{with_revisions, WordRevision};
+use crate::database::trash;
 use crate::database::WordId;
 use crate::database::WordOrSuggestionId;
 use crate::i18n::I18nInfo;
@@ -7,6 +10,7 @@ use isixhosa_common::types::{ExistingExample, ExistingLinkedWord, PublicUserInfo
 use rusqlite::{params, Row};
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::num::NonZeroU64;
 use tracing::{instrument, Span};
 
 #[derive(Debug)]
@@ -19,11 +23,11 @@ pub struct WordDeletionSuggestion {
 
 impl WordDeletionSuggestion {
     #[instrument(
-        name = "Fetch all word deletion suggestions",
+        name = "Fetch word deletion suggestions with ids",
         fields(results),
         skip_all
     )]
-    pub fn fetch_all(db: &impl ModeratorAccessDb) -> Vec<Self> {
+    pub fn fetch_with_ids(db: &impl ModeratorAccessDb, suggestion_ids: &[u64]) -> Vec<Self> {
         const SELECT: &str =
             "SELECT words.word_id, words.english, words.xhosa, words.part_of_speech, words.is_plural,
                     words.is_inchoative, words.is_informal, words.transitivity, words.followed_by,
@@ -33,15 +37,17 @@ impl WordDeletionSuggestion {
             INNER JOIN word_deletion_suggestions
                 ON words.word_id = word_deletion_suggestions.word_id
             INNER JOIN users ON word_deletion_suggestions.suggesting_user = users.user_id
+            WHERE word_deletion_suggestions.suggestion_id IN (SELECT value FROM json_each(?1))
             ORDER BY words.word_id;";
 
+        let ids = serde_json::to_string(suggestion_ids).unwrap();
         let conn = db.get().unwrap();
 
         // thanks rustc for forcing this `let x = ...; x` very cool
         let x: Vec<Self> = conn
             .prepare(SELECT)
             .unwrap()
-            .query(params![])
+            .query(params![ids])
             .unwrap()
             .map(|row| {
                 Ok(WordDeletionSuggestion {
@@ -68,19 +74,40 @@ impl WordDeletionSuggestion {
         fields(word_id),
         skip(db)
     )]
-    pub fn fetch_word_id_for_suggestion(db: &impl ModeratorAccessDb, suggestion: u64) -> u64 {
-        const SELECT: &str =
-            "SELECT word_id FROM word_deletion_suggestions WHERE suggestion_id = ?1;";
+    fn fetch_word_id_for_suggestion(
+        db: &impl ModeratorAccessDb,
+        suggestion: u64,
+    ) -> (u64, NonZeroU64) {
+        const SELECT: &str = "
+            SELECT word_id, suggesting_user FROM word_deletion_suggestions
+                WHERE suggestion_id = ?1;
+        ";
 
         let conn = db.get().unwrap();
-        let word_id = conn
+        let (word_id, suggesting_user) = conn
             .prepare(SELECT)
             .unwrap()
-            .query_row(params![suggestion], |row| row.get("word_id"))
+            .query_row(params![suggestion], |row| {
+                Ok((row.get("word_id")?, row.get("suggesting_user")?))
+            })
             .unwrap();
 
         Span::current().record("word_id", word_id);
 
+        (word_id, NonZeroU64::new(suggesting_user).unwrap())
+    }
+
+    /// Moves the word to the trash, returning its id.
+    #[instrument(name = "Accept word deletion suggestion", skip(db))]
+    pub fn accept(db: &impl ModeratorAccessDb, suggestion: u64, reviewer: NonZeroU64) -> u64 {
+        let (word_id, suggesting_user) = Self::fetch_word_id_for_suggestion(db, suggestion);
+        let old = ChangeSnapshot::fetch_word(db, word_id);
+
+        // Keep the state of the word before it was deleted in its history too
+        WordRevision::record_baseline(db, word_id);
+        trash::trash_word(db, word_id, suggesting_user, reviewer);
+        WordChange::record(db, suggesting_user, reviewer, old, None);
+
         word_id
     }
 
@@ -119,11 +146,14 @@ impl TryFrom<&Row<'_>> for ExampleDeletionSuggestion {
 
 impl ExampleDeletionSuggestion {
     #[instrument(
-        name = "Fetch all example deletions suggestions",
+        name = "Fetch example deletion suggestions with ids",
         fields(results),
         skip(db)
     )]
-    pub fn fetch_all(db: &impl ModeratorAccessDb) -> impl Iterator<Item = (WordId, Vec<Self>)> {
+    pub fn fetch_with_ids(
+        db: &impl ModeratorAccessDb,
+        suggestion_ids: &[u64],
+    ) -> impl Iterator<Item = (WordId, Vec<Self>)> {
         const SELECT: &str =
             "SELECT examples.example_id, examples.word_id, examples.xhosa, examples.english,
                     example_deletion_suggestions.suggestion_id, example_deletion_suggestions.reason,
@@ -131,11 +161,14 @@ impl ExampleDeletionSuggestion {
             FROM examples
             INNER JOIN users ON example_deletion_suggestions.suggesting_user = users.user_id
             INNER JOIN example_deletion_suggestions
-                ON examples.example_id = example_deletion_suggestions.example_id;";
+                ON examples.example_id = example_deletion_suggestions.example_id
+            WHERE example_deletion_suggestions.suggestion_id IN (SELECT value FROM json_each(?1))
+            ORDER BY example_deletion_suggestions.suggestion_id;";
 
+        let ids = serde_json::to_string(suggestion_ids).unwrap();
         let conn = db.get().unwrap();
         let mut query = conn.prepare(SELECT).unwrap();
-        let deletions = query.query(params![]).unwrap();
+        let deletions = query.query(params![ids]).unwrap();
 
         let mut map: HashMap<WordId, Vec<Self>> = HashMap::new();
 
@@ -164,33 +197,41 @@ impl ExampleDeletionSuggestion {
         fields(example_id),
         skip(db)
     )]
-    fn fetch_example_id_for_suggestion(db: &impl ModeratorAccessDb, suggestion: u64) -> u64 {
-        const SELECT: &str =
-            "SELECT example_id FROM example_deletion_suggestions WHERE suggestion_id = ?1;";
+    fn fetch_example_id_for_suggestion(
+        db: &impl ModeratorAccessDb,
+        suggestion: u64,
+    ) -> (u64, NonZeroU64) {
+        const SELECT: &str = "
+            SELECT example_id, suggesting_user FROM example_deletion_suggestions
+                WHERE suggestion_id = ?1;
+        ";
 
         let conn = db.get().unwrap();
-        let example_id = conn
+        let (example_id, suggesting_user) = conn
             .prepare(SELECT)
             .unwrap()
-            .query_row(params![suggestion], |row| row.get("example_id"))
+            .query_row(params![suggestion], |row| {
+                Ok((row.get("example_id")?, row.get("suggesting_user")?))
+            })
             .unwrap();
 
         Span::current().record("example_id", example_id);
 
-        example_id
+        (example_id, NonZeroU64::new(suggesting_user).unwrap())
     }
 
     #[instrument(name = "Accept example deletion suggestion", skip(db))]
-    pub fn accept(db: &impl ModeratorAccessDb, suggestion: u64) {
-        const DELETE_EXAMPLE: &str = "DELETE FROM examples WHERE example_id = ?1;";
+    pub fn accept(db: &impl ModeratorAccessDb, suggestion: u64, reviewer: NonZeroU64) {
+        let (to_delete, suggesting_user) = Self::fetch_example_id_for_suggestion(db, suggestion);
+        let old = ChangeSnapshot::fetch_example(db, to_delete);
+        let word_ids: Vec<u64> = old.iter().map(|old| old.word_ids().0).collect();
 
-        let to_delete = Self::fetch_example_id_for_suggestion(db, suggestion);
-        let conn = db.get().unwrap();
-        conn.prepare(DELETE_EXAMPLE)
-            .unwrap()
-            .execute(params![to_delete])
-            .unwrap();
-        Self::delete_suggestion(db, suggestion);
+        with_revisions(db, &word_ids, suggesting_user, reviewer, || {
+            trash::trash_example(db, to_delete, suggesting_user, reviewer);
+            Self::delete_suggestion(db, suggestion);
+
+            WordChange::record(db, suggesting_user, reviewer, old, None);
+        });
     }
 
     #[instrument(name = "Delete example deletion suggestion", skip(db))]
@@ -238,13 +279,14 @@ impl LinkedWordDeletionSuggestion {
     }
 
     #[instrument(
-        name = "Fetch all linked word deletion suggestions",
+        name = "Fetch linked word deletion suggestions with ids",
         fields(results),
         skip(db)
     )]
-    pub fn fetch_all(
+    pub fn fetch_with_ids(
         db: &impl ModeratorAccessDb,
         i18n_info: I18nInfo,
+        suggestion_ids: &[u64],
     ) -> impl Iterator<Item = (WordId, Vec<Self>)> {
         const SELECT: &str =
             "SELECT linked_words.link_id, linked_words.link_type, linked_words.first_word_id,
@@ -254,11 +296,16 @@ impl LinkedWordDeletionSuggestion {
             FROM linked_words
             INNER JOIN users ON linked_word_deletion_suggestions.suggesting_user = users.user_id
             INNER JOIN linked_word_deletion_suggestions
-                ON linked_words.link_id = linked_word_deletion_suggestions.linked_word_id;";
+                ON linked_words.link_id = linked_word_deletion_suggestions.linked_word_id
+            WHERE linked_word_deletion_suggestions.suggestion_id IN (
+                SELECT value FROM json_each(?1)
+            )
+            ORDER BY linked_word_deletion_suggestions.suggestion_id;";
 
+        let ids = serde_json::to_string(suggestion_ids).unwrap();
         let conn = db.get().unwrap();
         let mut query = conn.prepare(SELECT).unwrap();
-        let deletions = query.query(params![]).unwrap();
+        let deletions = query.query(params![ids]).unwrap();
 
         let mut map: HashMap<WordId, Vec<Self>> = HashMap::new();
 
@@ -294,33 +341,47 @@ impl LinkedWordDeletionSuggestion {
         fields(link_id),
         skip(db)
     )]
-    fn fetch_link_id_for_suggestion(db: &impl ModeratorAccessDb, suggestion: u64) -> u64 {
-        const SELECT: &str =
-            "SELECT linked_word_id FROM linked_word_deletion_suggestions WHERE suggestion_id = ?1;";
+    fn fetch_link_id_for_suggestion(
+        db: &impl ModeratorAccessDb,
+        suggestion: u64,
+    ) -> (u64, NonZeroU64) {
+        const SELECT: &str = "
+            SELECT linked_word_id, suggesting_user FROM linked_word_deletion_suggestions
+                WHERE suggestion_id = ?1;
+        ";
 
         let conn = db.get().unwrap();
-        let link_id = conn
+        let (link_id, suggesting_user) = conn
             .prepare(SELECT)
             .unwrap()
-            .query_row(params![suggestion], |row| row.get("linked_word_id"))
+            .query_row(params![suggestion], |row| {
+                Ok((row.get("linked_word_id")?, row.get("suggesting_user")?))
+            })
             .unwrap();
 
         Span::current().record("link_id", link_id);
 
-        link_id
+        (link_id, NonZeroU64::new(suggesting_user).unwrap())
     }
 
     #[instrument(name = "Accept linked word deletion suggestion", skip(db))]
-    pub fn accept(db: &impl ModeratorAccessDb, suggestion: u64) {
-        const DELETE: &str = "DELETE FROM linked_words WHERE link_id = ?1;";
+    pub fn accept(db: &impl ModeratorAccessDb, suggestion: u64, reviewer: NonZeroU64) {
+        let (to_delete, suggesting_user) = Self::fetch_link_id_for_suggestion(db, suggestion);
+        let old = ChangeSnapshot::fetch_linked_word(db, to_delete);
+        let word_ids: Vec<u64> = old
+            .iter()
+            .flat_map(|old| {
+                let (first, second) = old.word_ids();
+                std::iter::once(first).chain(second)
+            })
+            .collect();
 
-        let to_delete = Self::fetch_link_id_for_suggestion(db, suggestion);
-        let conn = db.get().unwrap();
-        conn.prepare(DELETE)
-            .unwrap()
-            .execute(params![to_delete])
-            .unwrap();
-        Self::delete_suggestion(db, suggestion);
+        with_revisions(db, &word_ids, suggesting_user, reviewer, || {
+            trash::trash_linked_word(db, to_delete, suggesting_user, reviewer);
+            Self::delete_suggestion(db, suggestion);
+
+            WordChange::record(db, suggesting_user, reviewer, old, None);
+        });
     }
 
     #[instrument(name = "Delete linked word deletion suggestion", skip(db))]
@@ -0,0 +1,319 @@
+//! Discussion threads on pending suggestions between the suggesting user and moderators, and the
+//! "needs info" state, which parks a suggestion in the moderation queue until the suggesting user
+//! replies.
+
+use crate::database::outcomes::{SuggestionSummary, SuggestionType};
+use chrono::{DateTime, Utc};
+use fallible_iterator::FallibleIterator;
+use isixhosa_common::database::{ModeratorAccessDb, UserAccessDb};
+use isixhosa_common::types::PublicUserInfo;
+use rusqlite::{params, OptionalExtension, Row};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::num::NonZeroU64;
+use tracing::{instrument, Span};
+
+#[derive(Clone, Debug)]
+pub struct SuggestionComment {
+    pub comment_id: u64,
+    pub parent_comment_id: Option<u64>,
+    pub author: PublicUserInfo,
+    pub body: String,
+    pub posted_at: DateTime<Utc>,
+}
+
+/// A comment in a discussion, in the order it is displayed in. Replies follow the comment they
+/// reply to, one level deeper.
+#[derive(Clone, Debug)]
+pub struct ThreadedComment {
+    pub depth: usize,
+    pub comment: SuggestionComment,
+}
+
+/// The state of the discussion on a suggestion, as shown on the moderation page
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DiscussionStatus {
+    pub comments: u64,
+    pub needs_info: bool,
+}
+
+impl SuggestionComment {
+    #[instrument(name = "Fetch suggestion discussion", fields(results), skip(db))]
+    pub fn fetch_thread(
+        db: &impl UserAccessDb,
+        suggestion_type: SuggestionType,
+        suggestion_id: u64,
+    ) -> Vec<ThreadedComment> {
+        const SELECT: &str = "
+            SELECT suggestion_comments.comment_id, suggestion_comments.parent_comment_id,
+                   suggestion_comments.author AS suggesting_user, users.username,
+                   users.display_name, suggestion_comments.body, suggestion_comments.posted_at
+            FROM suggestion_comments
+            INNER JOIN users ON suggestion_comments.author = users.user_id
+            WHERE suggestion_comments.suggestion_type = ?1
+                AND suggestion_comments.suggestion_id = ?2
+            ORDER BY suggestion_comments.comment_id;
+        ";
+
+        let conn = db.get().unwrap();
+        let comments: Vec<SuggestionComment> = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query(params![suggestion_type, suggestion_id])
+            .unwrap()
+            .map(|row| Ok(SuggestionComment::from(row)))
+            .collect()
+            .unwrap();
+
+        Span::current().record("results", comments.len());
+
+        let mut replies: HashMap<Option<u64>, Vec<SuggestionComment>> = HashMap::new();
+        for comment in comments {
+            replies
+                .entry(comment.parent_comment_id)
+                .or_default()
+                .push(comment);
+        }
+
+        let mut thread = Vec::new();
+        push_replies(&mut thread, &mut replies, None, 0);
+        thread
+    }
+
+    /// Posts a comment on the suggestion, returning whether it was posted. A reply from the
+    /// suggesting user takes the suggestion out of the "needs info" state.
+    #[instrument(
+        name = "Post suggestion comment",
+        fields(
+            suggestion_type = ?suggestion.suggestion_type,
+            suggestion_id = suggestion.suggestion_id,
+        ),
+        skip(db, suggestion, body)
+    )]
+    pub fn post(
+        db: &impl UserAccessDb,
+        suggestion: &SuggestionSummary,
+        author: NonZeroU64,
+        parent_comment_id: Option<u64>,
+        body: &str,
+    ) -> bool {
+        const SELECT_PARENT: &str = "
+            SELECT 1 FROM suggestion_comments
+                WHERE comment_id = ?1 AND suggestion_type = ?2 AND suggestion_id = ?3;
+        ";
+
+        const INSERT: &str = "
+            INSERT INTO suggestion_comments (
+                suggestion_type, suggestion_id, parent_comment_id, author, body, posted_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6);
+        ";
+
+        let body = body.trim();
+        if body.is_empty() {
+            return false;
+        }
+
+        let conn = db.get().unwrap();
+
+        if let Some(parent) = parent_comment_id {
+            let parent_exists = conn
+                .prepare(SELECT_PARENT)
+                .unwrap()
+                .query_row(
+                    params![parent, suggestion.suggestion_type, suggestion.suggestion_id],
+                    |_| Ok(()),
+                )
+                .optional()
+                .unwrap()
+                .is_some();
+
+            if !parent_exists {
+                return false;
+            }
+        }
+
+        conn.prepare(INSERT)
+            .unwrap()
+            .execute(params![
+                suggestion.suggestion_type,
+                suggestion.suggestion_id,
+                parent_comment_id,
+                author.get(),
+                body,
+                Utc::now(),
+            ])
+            .unwrap();
+
+        if author == suggestion.suggesting_user {
+            clear_needs_info_inner(db, suggestion.suggestion_type, suggestion.suggestion_id);
+        }
+
+        true
+    }
+}
+
+fn push_replies(
+    thread: &mut Vec<ThreadedComment>,
+    replies: &mut HashMap<Option<u64>, Vec<SuggestionComment>>,
+    parent: Option<u64>,
+    depth: usize,
+) {
+    for comment in replies.remove(&parent).unwrap_or_default() {
+        let id = comment.comment_id;
+        thread.push(ThreadedComment { depth, comment });
+        push_replies(thread, replies, Some(id), depth + 1);
+    }
+}
+
+impl From<&Row<'_>> for SuggestionComment {
+    fn from(row: &Row<'_>) -> Self {
+        SuggestionComment {
+            comment_id: row.get("comment_id").unwrap(),
+            parent_comment_id: row.get("parent_comment_id").unwrap(),
+            author: PublicUserInfo::try_from(row).unwrap(),
+            body: row.get("body").unwrap(),
+            posted_at: row.get("posted_at").unwrap(),
+        }
+    }
+}
+
+#[instrument(name = "Check if suggestion needs info", fields(needs_info), skip(db))]
+pub fn needs_info(
+    db: &impl UserAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+) -> bool {
+    const SELECT: &str = "
+        SELECT 1 FROM suggestions_needing_info WHERE suggestion_type = ?1 AND suggestion_id = ?2;
+    ";
+
+    let conn = db.get().unwrap();
+    let needs_info = conn
+        .prepare(SELECT)
+        .unwrap()
+        .query_row(params![suggestion_type, suggestion_id], |_| Ok(()))
+        .optional()
+        .unwrap()
+        .is_some();
+
+    Span::current().record("needs_info", needs_info);
+    needs_info
+}
+
+/// Parks the suggestion in the moderation queue until the suggesting user replies
+#[instrument(name = "Mark suggestion as needing info", skip(db))]
+pub fn set_needs_info(
+    db: &impl ModeratorAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    marked_by: NonZeroU64,
+) {
+    const INSERT: &str = "
+        INSERT INTO suggestions_needing_info (suggestion_type, suggestion_id, marked_by, marked_at)
+            VALUES (?1, ?2, ?3, ?4);
+    ";
+
+    let conn = db.get().unwrap();
+    conn.prepare(INSERT)
+        .unwrap()
+        .execute(params![
+            suggestion_type,
+            suggestion_id,
+            marked_by.get(),
+            Utc::now()
+        ])
+        .unwrap();
+}
+
+#[instrument(name = "Clear suggestion needing info", skip(db))]
+pub fn clear_needs_info(
+    db: &impl ModeratorAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+) {
+    clear_needs_info_inner(db, suggestion_type, suggestion_id);
+}
+
+fn clear_needs_info_inner(
+    db: &impl UserAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+) {
+    const DELETE: &str = "
+        DELETE FROM suggestions_needing_info WHERE suggestion_type = ?1 AND suggestion_id = ?2;
+    ";
+
+    let conn = db.get().unwrap();
+    conn.prepare(DELETE)
+        .unwrap()
+        .execute(params![suggestion_type, suggestion_id])
+        .unwrap();
+}
+
+/// Deletes the discussion once the suggestion has been reviewed
+#[instrument(name = "Delete suggestion discussion", skip(db))]
+pub fn delete_discussion(
+    db: &impl ModeratorAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+) {
+    const DELETE_COMMENTS: &str = "
+        DELETE FROM suggestion_comments WHERE suggestion_type = ?1 AND suggestion_id = ?2;
+    ";
+
+    let conn = db.get().unwrap();
+    conn.prepare(DELETE_COMMENTS)
+        .unwrap()
+        .execute(params![suggestion_type, suggestion_id])
+        .unwrap();
+
+    clear_needs_info_inner(db, suggestion_type, suggestion_id);
+}
+
+#[instrument(name = "Fetch all discussion statuses", fields(results), skip_all)]
+pub fn fetch_all_statuses(
+    db: &impl ModeratorAccessDb,
+) -> HashMap<(SuggestionType, u64), DiscussionStatus> {
+    const SELECT_COMMENTS: &str = "
+        SELECT suggestion_type, suggestion_id, COUNT(*) FROM suggestion_comments
+            GROUP BY suggestion_type, suggestion_id;
+    ";
+
+    const SELECT_NEEDS_INFO: &str =
+        "SELECT suggestion_type, suggestion_id FROM suggestions_needing_info;";
+
+    let conn = db.get().unwrap();
+    let mut statuses: HashMap<(SuggestionType, u64), DiscussionStatus> = HashMap::new();
+
+    let comment_counts: Vec<(SuggestionType, u64, u64)> = conn
+        .prepare(SELECT_COMMENTS)
+        .unwrap()
+        .query(params![])
+        .unwrap()
+        .map(|row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .collect()
+        .unwrap();
+
+    for (suggestion_type, suggestion_id, comments) in comment_counts {
+        statuses
+            .entry((suggestion_type, suggestion_id))
+            .or_default()
+            .comments = comments;
+    }
+
+    let needing_info: Vec<(SuggestionType, u64)> = conn
+        .prepare(SELECT_NEEDS_INFO)
+        .unwrap()
+        .query(params![])
+        .unwrap()
+        .map(|row| Ok((row.get(0)?, row.get(1)?)))
+        .collect()
+        .unwrap();
+
+    for key in needing_info {
+        statuses.entry(key).or_default().needs_info = true;
+    }
+
+    Span::current().record("results", statuses.len());
+    statuses
+}
@@ -0,0 +1,332 @@
+//! Merging a duplicate word into another word, leaving a redirect behind.
+
+use crate::database::changes::{ChangeSnapshot, WordChange};
+use crate::database::revisions::WordRevision;
+use crate::database::trash;
+use crate::export::WordRecord;
+use crate::export::{DatasetAttributionRecord, LabelAttributionRecord};
+use crate::search::{TantivyClient, WordDocument};
+use futures::executor::block_on;
+use isixhosa_common::database::{ModeratorAccessDb, PublicAccessDb, WordOrSuggestionId};
+use isixhosa_common::language::WordLinkType;
+use isixhosa_common::types::{
+    Citation, Dataset, ExistingExample, ExistingLinkedWord, ExistingSense, ExistingWord, Label,
+};
+use rusqlite::{params, OptionalExtension};
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use tracing::{instrument, Span};
+
+/// Fetches the word which the given word was merged into, if it was merged.
+#[instrument(level = "trace", name = "Fetch word redirect", fields(found), skip(db))]
+pub fn fetch_redirect(db: &impl PublicAccessDb, word_id: u64) -> Option<u64> {
+    const SELECT: &str = "SELECT to_word_id FROM word_redirects WHERE from_word_id = ?1;";
+
+    let opt = db
+        .get()
+        .unwrap()
+        .prepare(SELECT)
+        .unwrap()
+        .query_row(params![word_id], |row| row.get("to_word_id"))
+        .optional()
+        .unwrap();
+
+    Span::current().record("found", opt.is_some());
+
+    opt
+}
+
+/// Merges the word `from` into the word `into`. Senses, examples, citations, linked words, user and
+/// dataset attributions, and pending suggestions are moved over, dropping linked words which would link
+/// `into` to itself or duplicate one of its existing links. `from` is then moved to the trash and
+/// a redirect to `into` is left in its place.
+///
+/// Returns `false` if either word does not exist or they are the same word.
+#[instrument(name = "Merge words", fields(merged), skip(db, tantivy))]
+pub fn merge_words(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    from: u64,
+    into: u64,
+    reviewer: NonZeroU64,
+) -> bool {
+    let both_exist = ExistingWord::fetch_alone(db, from).is_some()
+        && ExistingWord::fetch_alone(db, into).is_some();
+
+    if from == into || !both_exist {
+        Span::current().record("merged", false);
+        return false;
+    }
+
+    let from_links = ExistingLinkedWord::fetch_all_for_word(db, from);
+
+    // Words on the other side of moved links are changed too
+    let mut changed_words: Vec<u64> = from_links
+        .iter()
+        .map(|l| l.other.id)
+        .chain([into])
+        .collect();
+    changed_words.sort_unstable();
+    changed_words.dedup();
+
+    WordRevision::record_baseline(db, from);
+    for word_id in &changed_words {
+        WordRevision::record_baseline(db, *word_id);
+    }
+
+    move_senses(db, from, into, reviewer);
+    move_examples(db, from, into, reviewer);
+    move_citations(db, from, into, reviewer);
+    move_linked_words(db, from_links, into, reviewer);
+    move_attributions(db, from, into, reviewer);
+    move_suggestions(db, from, into);
+    add_redirect(db, from, into);
+
+    let old = ChangeSnapshot::fetch_word(db, from);
+    trash::trash_word(db, from, reviewer, reviewer);
+    WordChange::record(db, reviewer, reviewer, old, None);
+
+    for word_id in changed_words {
+        WordRevision::record(db, word_id, reviewer, reviewer);
+    }
+
+    let into = ExistingWord::fetch_alone(db, into).unwrap();
+    let into = WordDocument::from(&WordRecord::from(into)).with_senses_and_labels_from_db(db);
+    block_on(async move {
+        tantivy
+            .delete_word(WordOrSuggestionId::existing(from))
+            .await;
+        tantivy.edit_word(into).await;
+    });
+
+    Span::current().record("merged", true);
+    true
+}
+
+/// Moves the senses of `from` after the senses of `into`, keeping their order.
+fn move_senses(db: &impl ModeratorAccessDb, from: u64, into: u64, reviewer: NonZeroU64) {
+    const UPDATE: &str = "UPDATE senses SET word_id = ?2, sense_order = ?3 WHERE sense_id = ?1;";
+
+    let last = ExistingSense::fetch_all_for_word(db, into)
+        .iter()
+        .map(|s| s.sense_order)
+        .max()
+        .unwrap_or(0);
+
+    for (i, sense) in ExistingSense::fetch_all_for_word(db, from)
+        .into_iter()
+        .enumerate()
+    {
+        let sense_id = sense.sense_id;
+
+        db.get()
+            .unwrap()
+            .prepare(UPDATE)
+            .unwrap()
+            .execute(params![sense_id, into, last + 1 + i as u32])
+            .unwrap();
+
+        let new = ChangeSnapshot::fetch_sense(db, sense_id);
+        WordChange::record(
+            db,
+            reviewer,
+            reviewer,
+            Some(ChangeSnapshot::Sense(sense)),
+            new,
+        );
+    }
+}
+
+fn move_examples(db: &impl ModeratorAccessDb, from: u64, into: u64, reviewer: NonZeroU64) {
+    const UPDATE: &str = "UPDATE examples SET word_id = ?2 WHERE example_id = ?1;";
+
+    for example in ExistingExample::fetch_all_for_word(db, from) {
+        let example_id = example.example_id;
+
+        db.get()
+            .unwrap()
+            .prepare(UPDATE)
+            .unwrap()
+            .execute(params![example_id, into])
+            .unwrap();
+
+        let new = ChangeSnapshot::fetch_example(db, example_id);
+        WordChange::record(
+            db,
+            reviewer,
+            reviewer,
+            Some(ChangeSnapshot::Example(example)),
+            new,
+        );
+    }
+}
+
+/// Moves every citation of `from` over, including those of its senses and examples, which have
+/// already been moved.
+fn move_citations(db: &impl ModeratorAccessDb, from: u64, into: u64, reviewer: NonZeroU64) {
+    const UPDATE: &str = "UPDATE citations SET word_id = ?2 WHERE citation_id = ?1;";
+
+    for citation in Citation::fetch_all_for_word(db, from) {
+        let citation_id = citation.citation_id;
+
+        db.get()
+            .unwrap()
+            .prepare(UPDATE)
+            .unwrap()
+            .execute(params![citation_id, into])
+            .unwrap();
+
+        let new = ChangeSnapshot::fetch_citation(db, citation_id);
+        WordChange::record(
+            db,
+            reviewer,
+            reviewer,
+            Some(ChangeSnapshot::Citation(citation)),
+            new,
+        );
+    }
+}
+
+fn move_linked_words(
+    db: &impl ModeratorAccessDb,
+    from_links: Vec<ExistingLinkedWord>,
+    into: u64,
+    reviewer: NonZeroU64,
+) {
+    // Keeps the direction of the link the same
+    const UPDATE: &str = "
+        UPDATE linked_words SET
+            first_word_id = CASE first_word_id WHEN ?2 THEN ?3 ELSE first_word_id END,
+            second_word_id = CASE second_word_id WHEN ?2 THEN ?3 ELSE second_word_id END
+        WHERE link_id = ?1;
+    ";
+
+    let mut existing: Vec<(WordLinkType, u64)> = ExistingLinkedWord::fetch_all_for_word(db, into)
+        .into_iter()
+        .map(|l| (l.relative_link_type(), l.other.id))
+        .collect();
+
+    for link in from_links {
+        let from = if link.first_word_id == link.other.id {
+            link.second_word_id
+        } else {
+            link.first_word_id
+        };
+        let key = (link.relative_link_type(), link.other.id);
+        let old = ChangeSnapshot::fetch_linked_word(db, link.link_id);
+
+        if link.other.id == into || existing.contains(&key) {
+            trash::trash_linked_word(db, link.link_id, reviewer, reviewer);
+            WordChange::record(db, reviewer, reviewer, old, None);
+            continue;
+        }
+
+        db.get()
+            .unwrap()
+            .prepare(UPDATE)
+            .unwrap()
+            .execute(params![link.link_id, from, into])
+            .unwrap();
+
+        let new = ChangeSnapshot::fetch_linked_word(db, link.link_id);
+        WordChange::record(db, reviewer, reviewer, old, new);
+        existing.push(key);
+    }
+}
+
+fn move_attributions(db: &impl ModeratorAccessDb, from: u64, into: u64, reviewer: NonZeroU64) {
+    const COPY_USERS: &str = "
+        INSERT INTO user_attributions (word_id, user_id)
+            SELECT ?2, user_id FROM user_attributions WHERE word_id = ?1;
+    ";
+    const INSERT_DATASET: &str =
+        "INSERT INTO dataset_attributions (word_id, dataset_id) VALUES (?1, ?2);";
+    const INSERT_LABEL: &str = "INSERT INTO word_labels (word_id, label_id) VALUES (?1, ?2);";
+
+    let conn = db.get().unwrap();
+    conn.prepare(COPY_USERS)
+        .unwrap()
+        .execute(params![from, into])
+        .unwrap();
+
+    for dataset in Dataset::fetch_all_for_word(db, from) {
+        let modified_rows = conn
+            .prepare(INSERT_DATASET)
+            .unwrap()
+            .execute(params![into, dataset.id])
+            .unwrap();
+
+        if modified_rows == 1 {
+            let record = ChangeSnapshot::DatasetAttribution(DatasetAttributionRecord {
+                word_id: into,
+                dataset_id: dataset.id,
+            });
+            WordChange::record(db, reviewer, reviewer, None, Some(record));
+        }
+    }
+
+    for label in Label::fetch_all_for_word(db, from) {
+        let modified_rows = conn
+            .prepare(INSERT_LABEL)
+            .unwrap()
+            .execute(params![into, label.id])
+            .unwrap();
+
+        if modified_rows == 1 {
+            let record = ChangeSnapshot::Label(LabelAttributionRecord {
+                word_id: into,
+                label_id: label.id,
+            });
+            WordChange::record(db, reviewer, reviewer, None, Some(record));
+        }
+    }
+}
+
+/// Points pending suggestions for `from` at `into` instead. Suggestions to delete `from` are
+/// dropped along with it, as are suggestions which would link `into` to itself.
+fn move_suggestions(db: &impl ModeratorAccessDb, from: u64, into: u64) {
+    const STATEMENTS: [&str; 9] = [
+        "UPDATE word_suggestions SET existing_word_id = ?2 WHERE existing_word_id = ?1;",
+        "UPDATE sense_suggestions SET existing_word_id = ?2 WHERE existing_word_id = ?1;",
+        "UPDATE example_suggestions SET existing_word_id = ?2 WHERE existing_word_id = ?1;",
+        "UPDATE linked_word_sense_suggestions SET existing_word_id = ?2
+            WHERE existing_word_id = ?1;",
+        "DELETE FROM linked_word_suggestions
+            WHERE (first_existing_word_id = ?1 AND second_existing_word_id = ?2)
+               OR (first_existing_word_id = ?2 AND second_existing_word_id = ?1);",
+        "UPDATE linked_word_suggestions SET first_existing_word_id = ?2
+            WHERE first_existing_word_id = ?1;",
+        "UPDATE linked_word_suggestions SET second_existing_word_id = ?2
+            WHERE second_existing_word_id = ?1;",
+        "UPDATE dataset_attribution_suggestions SET existing_word_id = ?2
+            WHERE existing_word_id = ?1;",
+        "UPDATE label_suggestions SET existing_word_id = ?2 WHERE existing_word_id = ?1;",
+    ];
+
+    let conn = db.get().unwrap();
+    for statement in STATEMENTS {
+        conn.prepare(statement)
+            .unwrap()
+            .execute(params![from, into])
+            .unwrap();
+    }
+}
+
+fn add_redirect(db: &impl ModeratorAccessDb, from: u64, into: u64) {
+    const STATEMENTS: [&str; 3] = [
+        // Words previously merged into `from` now redirect straight to `into`
+        "UPDATE word_redirects SET to_word_id = ?2 WHERE to_word_id = ?1;",
+        // `into` may itself have been merged away and restored before, so it must not redirect
+        "DELETE FROM word_redirects WHERE from_word_id = ?2;",
+        "INSERT INTO word_redirects (from_word_id, to_word_id) VALUES (?1, ?2)
+            ON CONFLICT(from_word_id) DO UPDATE SET to_word_id = excluded.to_word_id;",
+    ];
+
+    let conn = db.get().unwrap();
+    for statement in STATEMENTS {
+        conn.prepare(statement)
+            .unwrap()
+            .execute(params![from, into])
+            .unwrap();
+    }
+}
@@ -0,0 +1,590 @@
+//! Records of what happened to suggestions once they were reviewed, so that contributors can see
+//! whether their suggestions were accepted, and why they were rejected, on the my suggestions page.
+
+use crate::database::{discussion, review};
+use chrono::{DateTime, Utc};
+use fallible_iterator::FallibleIterator;
+use isixhosa_common::database::{ModeratorAccessDb, UserAccessDb};
+use isixhosa_common::i18n::{ToTranslationKey, TranslationKey};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, Value, ValueRef};
+use rusqlite::{params, OptionalExtension, Row, ToSql};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::num::NonZeroU64;
+use std::str::FromStr;
+use tracing::{instrument, Span};
+
+/// Which table a suggestion is stored in
+#[derive(
+    IntoPrimitive, TryFromPrimitive, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[repr(u8)]
+pub enum SuggestionType {
+    Word = 1,
+    WordDeletion = 2,
+    Sense = 3,
+    Example = 4,
+    ExampleDeletion = 5,
+    LinkedWord = 6,
+    LinkedWordDeletion = 7,
+    DatasetAttribution = 8,
+    Label = 9,
+}
+
+impl SuggestionType {
+    pub const ALL: [SuggestionType; 9] = [
+        SuggestionType::Word,
+        SuggestionType::WordDeletion,
+        SuggestionType::Sense,
+        SuggestionType::Example,
+        SuggestionType::ExampleDeletion,
+        SuggestionType::LinkedWord,
+        SuggestionType::LinkedWordDeletion,
+        SuggestionType::DatasetAttribution,
+        SuggestionType::Label,
+    ];
+
+    /// The name used for the suggestion type in forms and URLs
+    pub fn name(&self) -> &'static str {
+        match self {
+            SuggestionType::Word => "word",
+            SuggestionType::WordDeletion => "word_deletion",
+            SuggestionType::Sense => "sense",
+            SuggestionType::Example => "example",
+            SuggestionType::ExampleDeletion => "example_deletion",
+            SuggestionType::LinkedWord => "linked_word",
+            SuggestionType::LinkedWordDeletion => "linked_word_deletion",
+            SuggestionType::DatasetAttribution => "dataset_attribution",
+            SuggestionType::Label => "label",
+        }
+    }
+
+    /// Selects the columns of a [`SuggestionSummary`] from the suggestion table, aliased as `s`
+    fn select_summary(&self) -> &'static str {
+        match self {
+            SuggestionType::Word => {
+                "
+                SELECT s.suggestion_id, s.existing_word_id AS word_id, s.suggesting_user,
+                       COALESCE(s.english, words.english, '') || ' - ' ||
+                           COALESCE(s.xhosa, words.xhosa, '') AS summary,
+                       s.changes_summary
+                FROM word_suggestions AS s
+                LEFT JOIN words ON s.existing_word_id = words.word_id
+                "
+            }
+            SuggestionType::WordDeletion => {
+                "
+                SELECT s.suggestion_id, s.word_id, s.suggesting_user,
+                       words.english || ' - ' || words.xhosa AS summary,
+                       s.reason AS changes_summary
+                FROM word_deletion_suggestions AS s
+                INNER JOIN words ON s.word_id = words.word_id
+                "
+            }
+            SuggestionType::Sense => {
+                "
+                SELECT s.suggestion_id, s.existing_word_id AS word_id, s.suggesting_user,
+                       COALESCE(s.english, senses.english, '') AS summary, s.changes_summary
+                FROM sense_suggestions AS s
+                LEFT JOIN senses ON s.existing_sense_id = senses.sense_id
+                "
+            }
+            SuggestionType::Example => {
+                "
+                SELECT s.suggestion_id, s.existing_word_id AS word_id, s.suggesting_user,
+                       COALESCE(s.english, examples.english, '') || ' - ' ||
+                           COALESCE(s.xhosa, examples.xhosa, '') AS summary,
+                       s.changes_summary
+                FROM example_suggestions AS s
+                LEFT JOIN examples ON s.existing_example_id = examples.example_id
+                "
+            }
+            SuggestionType::ExampleDeletion => {
+                "
+                SELECT s.suggestion_id, examples.word_id, s.suggesting_user,
+                       examples.english || ' - ' || examples.xhosa AS summary,
+                       s.reason AS changes_summary
+                FROM example_deletion_suggestions AS s
+                INNER JOIN examples ON s.example_id = examples.example_id
+                "
+            }
+            SuggestionType::LinkedWord => {
+                "
+                SELECT s.suggestion_id,
+                       COALESCE(s.first_existing_word_id, linked_words.first_word_id) AS word_id,
+                       s.suggesting_user,
+                       COALESCE(w1.xhosa, s1.xhosa, '') || ' - ' ||
+                           COALESCE(w2.xhosa, s2.xhosa, '') AS summary,
+                       s.changes_summary
+                FROM linked_word_suggestions AS s
+                LEFT JOIN linked_words ON s.existing_linked_word_id = linked_words.link_id
+                LEFT JOIN words AS w1
+                    ON w1.word_id = COALESCE(s.first_existing_word_id, linked_words.first_word_id)
+                LEFT JOIN words AS w2
+                    ON w2.word_id = COALESCE(s.second_existing_word_id, linked_words.second_word_id)
+                LEFT JOIN word_suggestions AS s1 ON s1.suggestion_id = s.suggested_word_id
+                LEFT JOIN word_suggestions AS s2 ON s2.suggestion_id = s.second_suggested_word_id
+                "
+            }
+            SuggestionType::LinkedWordDeletion => {
+                "
+                SELECT s.suggestion_id, linked_words.first_word_id AS word_id, s.suggesting_user,
+                       w1.xhosa || ' - ' || w2.xhosa AS summary, s.reason AS changes_summary
+                FROM linked_word_deletion_suggestions AS s
+                INNER JOIN linked_words ON s.linked_word_id = linked_words.link_id
+                INNER JOIN words AS w1 ON linked_words.first_word_id = w1.word_id
+                INNER JOIN words AS w2 ON linked_words.second_word_id = w2.word_id
+                "
+            }
+            SuggestionType::DatasetAttribution => {
+                "
+                SELECT s.suggestion_id, s.existing_word_id AS word_id, s.suggesting_user,
+                       datasets.name AS summary, s.changes_summary
+                FROM dataset_attribution_suggestions AS s
+                INNER JOIN datasets ON s.dataset_id = datasets.dataset_id
+                "
+            }
+            SuggestionType::Label => {
+                "
+                SELECT s.suggestion_id, s.existing_word_id AS word_id, s.suggesting_user,
+                       labels.name AS summary, s.changes_summary
+                FROM label_suggestions AS s
+                INNER JOIN labels ON s.label_id = labels.label_id
+                "
+            }
+        }
+    }
+}
+
+/// What the reviewing moderator did with a suggestion
+#[derive(IntoPrimitive, TryFromPrimitive, Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Outcome {
+    Accepted = 1,
+    /// The moderator edited the suggestion before accepting it
+    AcceptedWithEdits = 2,
+    Rejected = 3,
+}
+
+/// Why a moderator rejected a suggestion. Any more detail is given in the reviewer's comment.
+#[derive(IntoPrimitive, TryFromPrimitive, Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RejectionReason {
+    Duplicate = 1,
+    Incorrect = 2,
+    Unclear = 3,
+    StyleGuide = 4,
+    OutOfScope = 5,
+    Other = 6,
+}
+
+impl RejectionReason {
+    pub const ALL: [RejectionReason; 6] = [
+        RejectionReason::Duplicate,
+        RejectionReason::Incorrect,
+        RejectionReason::Unclear,
+        RejectionReason::StyleGuide,
+        RejectionReason::OutOfScope,
+        RejectionReason::Other,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RejectionReason::Duplicate => "duplicate",
+            RejectionReason::Incorrect => "incorrect",
+            RejectionReason::Unclear => "unclear",
+            RejectionReason::StyleGuide => "style_guide",
+            RejectionReason::OutOfScope => "out_of_scope",
+            RejectionReason::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidRejectionReason(String);
+
+impl Display for InvalidRejectionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid rejection reason {}", self.0)
+    }
+}
+
+impl FromStr for RejectionReason {
+    type Err = InvalidRejectionReason;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RejectionReason::ALL
+            .into_iter()
+            .find(|reason| reason.name() == s)
+            .ok_or_else(|| InvalidRejectionReason(s.to_owned()))
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidSuggestionType(String);
+
+impl Display for InvalidSuggestionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid suggestion type {}", self.0)
+    }
+}
+
+impl FromStr for SuggestionType {
+    type Err = InvalidSuggestionType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SuggestionType::ALL
+            .into_iter()
+            .find(|ty| ty.name() == s)
+            .ok_or_else(|| InvalidSuggestionType(s.to_owned()))
+    }
+}
+
+impl ToTranslationKey for SuggestionType {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            SuggestionType::Word => "my-suggestions.type-word",
+            SuggestionType::WordDeletion => "my-suggestions.type-word-deletion",
+            SuggestionType::Sense => "my-suggestions.type-sense",
+            SuggestionType::Example => "my-suggestions.type-example",
+            SuggestionType::ExampleDeletion => "my-suggestions.type-example-deletion",
+            SuggestionType::LinkedWord => "my-suggestions.type-linked-word",
+            SuggestionType::LinkedWordDeletion => "my-suggestions.type-linked-word-deletion",
+            SuggestionType::DatasetAttribution => "my-suggestions.type-dataset-attribution",
+            SuggestionType::Label => "my-suggestions.type-label",
+        })
+    }
+}
+
+impl ToTranslationKey for Outcome {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            Outcome::Accepted => "my-suggestions.accepted",
+            Outcome::AcceptedWithEdits => "my-suggestions.accepted-with-edits",
+            Outcome::Rejected => "my-suggestions.rejected",
+        })
+    }
+}
+
+impl ToTranslationKey for RejectionReason {
+    fn translation_key(&self) -> TranslationKey<'_> {
+        TranslationKey::new(match self {
+            RejectionReason::Duplicate => "rejection-reason.duplicate",
+            RejectionReason::Incorrect => "rejection-reason.incorrect",
+            RejectionReason::Unclear => "rejection-reason.unclear",
+            RejectionReason::StyleGuide => "rejection-reason.style-guide",
+            RejectionReason::OutOfScope => "rejection-reason.out-of-scope",
+            RejectionReason::Other => "rejection-reason.other",
+        })
+    }
+}
+
+impl ToSql for SuggestionType {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for SuggestionType {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        u8::try_from(v)
+            .ok()
+            .and_then(|v| Self::try_from_primitive(v).ok())
+            .ok_or(FromSqlError::OutOfRange(v))
+    }
+}
+
+impl ToSql for Outcome {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for Outcome {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        u8::try_from(v)
+            .ok()
+            .and_then(|v| Self::try_from_primitive(v).ok())
+            .ok_or(FromSqlError::OutOfRange(v))
+    }
+}
+
+impl ToSql for RejectionReason {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Integer(*self as u8 as i64)))
+    }
+}
+
+impl FromSql for RejectionReason {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let v = value.as_i64()?;
+        u8::try_from(v)
+            .ok()
+            .and_then(|v| Self::try_from_primitive(v).ok())
+            .ok_or(FromSqlError::OutOfRange(v))
+    }
+}
+
+/// A short description of a pending suggestion, which is kept in its outcome once the suggestion
+/// itself is deleted
+#[derive(Clone, Debug)]
+pub struct SuggestionSummary {
+    pub suggestion_type: SuggestionType,
+    pub suggestion_id: u64,
+    /// The existing word which the suggestion is for, if any
+    pub word_id: Option<u64>,
+    pub suggesting_user: NonZeroU64,
+    pub summary: String,
+    pub changes_summary: String,
+}
+
+impl SuggestionSummary {
+    #[instrument(name = "Fetch suggestion summary", fields(found), skip(db))]
+    pub fn fetch(
+        db: &impl UserAccessDb,
+        suggestion_type: SuggestionType,
+        suggestion_id: u64,
+    ) -> Option<SuggestionSummary> {
+        let select = format!(
+            "{} WHERE s.suggestion_id = ?1;",
+            suggestion_type.select_summary()
+        );
+
+        let conn = db.get().unwrap();
+        let summary = conn
+            .prepare(&select)
+            .unwrap()
+            .query_row(params![suggestion_id], |row| {
+                Ok(Self::from_row(suggestion_type, row))
+            })
+            .optional()
+            .unwrap();
+
+        Span::current().record("found", summary.is_some());
+
+        summary
+    }
+
+    #[instrument(name = "Fetch pending suggestions for user", fields(results), skip(db))]
+    pub fn fetch_all_pending_for_user(
+        db: &impl UserAccessDb,
+        user_id: NonZeroU64,
+    ) -> Vec<SuggestionSummary> {
+        let summaries = Self::fetch_all_where(
+            db,
+            "WHERE s.suggesting_user = ?1 ORDER BY s.suggestion_id DESC",
+            params![user_id.get()],
+        );
+        Span::current().record("results", summaries.len());
+        summaries
+    }
+
+    #[instrument(name = "Fetch all pending suggestions", fields(results), skip(db))]
+    pub fn fetch_all_pending(db: &impl UserAccessDb) -> Vec<SuggestionSummary> {
+        let summaries = Self::fetch_all_where(db, "ORDER BY s.suggestion_id", params![]);
+        Span::current().record("results", summaries.len());
+        summaries
+    }
+
+    fn fetch_all_where(
+        db: &impl UserAccessDb,
+        condition: &str,
+        params: &[&dyn ToSql],
+    ) -> Vec<SuggestionSummary> {
+        let conn = db.get().unwrap();
+
+        SuggestionType::ALL
+            .into_iter()
+            .flat_map(|suggestion_type| {
+                let select = format!("{} {condition};", suggestion_type.select_summary());
+
+                conn.prepare(&select)
+                    .unwrap()
+                    .query(params)
+                    .unwrap()
+                    .map(|row| Ok(Self::from_row(suggestion_type, row)))
+                    .collect::<Vec<_>>()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn from_row(suggestion_type: SuggestionType, row: &Row<'_>) -> Self {
+        SuggestionSummary {
+            suggestion_type,
+            suggestion_id: row.get("suggestion_id").unwrap(),
+            word_id: row.get("word_id").unwrap(),
+            suggesting_user: NonZeroU64::new(row.get("suggesting_user").unwrap()).unwrap(),
+            summary: row.get("summary").unwrap(),
+            changes_summary: row.get("changes_summary").unwrap(),
+        }
+    }
+
+    /// The page on which the contributor can edit the suggestion while it is pending, if any
+    pub fn edit_link(&self) -> Option<String> {
+        match self.suggestion_type {
+            SuggestionType::Example => {
+                Some(format!("/suggestions/example/{}/edit", self.suggestion_id))
+            }
+            SuggestionType::LinkedWord => Some(format!(
+                "/suggestions/linked_word/{}/edit",
+                self.suggestion_id
+            )),
+            _ => None,
+        }
+    }
+
+    /// The discussion between the contributor and moderators about the suggestion
+    pub fn discussion_link(&self) -> String {
+        crate::discussion::discussion_url(self.suggestion_type, self.suggestion_id)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SuggestionOutcome {
+    pub outcome_id: u64,
+    pub suggestion_type: SuggestionType,
+    pub word_id: Option<u64>,
+    pub summary: String,
+    pub changes_summary: String,
+    pub outcome: Outcome,
+    pub rejection_reason: Option<RejectionReason>,
+    pub reviewer_comment: String,
+    pub reviewed_at: DateTime<Utc>,
+}
+
+impl SuggestionOutcome {
+    /// Records the outcome of reviewing the suggestion. Accepting a suggestion which a moderator
+    /// edited beforehand is recorded as accepting it with edits. The discussion, approvals and
+    /// votes on the suggestion are deleted along with it.
+    #[instrument(
+        name = "Record suggestion outcome",
+        fields(
+            suggestion_type = ?summary.suggestion_type,
+            suggestion_id = summary.suggestion_id,
+            outcome,
+        ),
+        skip(db, summary)
+    )]
+    pub fn record(
+        db: &impl ModeratorAccessDb,
+        summary: &SuggestionSummary,
+        reviewer: NonZeroU64,
+        accepted: bool,
+        rejection_reason: Option<RejectionReason>,
+        reviewer_comment: &str,
+    ) {
+        const INSERT: &str = "
+            INSERT INTO suggestion_outcomes (
+                suggestion_type, suggestion_id, word_id, suggesting_user, reviewing_user, summary,
+                changes_summary, outcome, rejection_reason, reviewer_comment, reviewed_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11);
+        ";
+
+        const DELETE_EDITED: &str = "
+            DELETE FROM reviewer_edited_suggestions
+                WHERE suggestion_type = ?1 AND suggestion_id = ?2;
+        ";
+
+        let conn = db.get().unwrap();
+        let was_edited = conn
+            .prepare(DELETE_EDITED)
+            .unwrap()
+            .execute(params![summary.suggestion_type, summary.suggestion_id])
+            .unwrap()
+            == 1;
+
+        let (outcome, rejection_reason) = match (accepted, was_edited) {
+            (true, false) => (Outcome::Accepted, None),
+            (true, true) => (Outcome::AcceptedWithEdits, None),
+            (false, _) => (
+                Outcome::Rejected,
+                Some(rejection_reason.unwrap_or(RejectionReason::Other)),
+            ),
+        };
+
+        Span::current().record("outcome", format!("{outcome:?}"));
+
+        conn.prepare(INSERT)
+            .unwrap()
+            .execute(params![
+                summary.suggestion_type,
+                summary.suggestion_id,
+                summary.word_id,
+                summary.suggesting_user.get(),
+                reviewer.get(),
+                summary.summary,
+                summary.changes_summary,
+                outcome,
+                rejection_reason,
+                reviewer_comment.trim(),
+                Utc::now(),
+            ])
+            .unwrap();
+
+        discussion::delete_discussion(db, summary.suggestion_type, summary.suggestion_id);
+        review::delete_reviews(db, summary.suggestion_type, summary.suggestion_id);
+    }
+
+    /// Marks the suggestion as edited by a moderator other than the one who suggested it
+    #[instrument(name = "Record reviewer edit of suggestion", skip(db))]
+    pub fn record_reviewer_edit(
+        db: &impl ModeratorAccessDb,
+        suggestion_type: SuggestionType,
+        suggestion_id: u64,
+    ) {
+        const INSERT: &str = "
+            INSERT INTO reviewer_edited_suggestions (suggestion_type, suggestion_id)
+                VALUES (?1, ?2);
+        ";
+
+        let conn = db.get().unwrap();
+        conn.prepare(INSERT)
+            .unwrap()
+            .execute(params![suggestion_type, suggestion_id])
+            .unwrap();
+    }
+
+    #[instrument(name = "Fetch suggestion outcomes for user", fields(results), skip(db))]
+    pub fn fetch_all_for_user(
+        db: &impl UserAccessDb,
+        user_id: NonZeroU64,
+    ) -> Vec<SuggestionOutcome> {
+        const SELECT: &str = "
+            SELECT outcome_id, suggestion_type, word_id, summary, changes_summary, outcome,
+                   rejection_reason, reviewer_comment, reviewed_at
+            FROM suggestion_outcomes
+            WHERE suggesting_user = ?1
+            ORDER BY reviewed_at DESC;
+        ";
+
+        let conn = db.get().unwrap();
+        let outcomes: Vec<SuggestionOutcome> = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query(params![user_id.get()])
+            .unwrap()
+            .map(|row| {
+                Ok(SuggestionOutcome {
+                    outcome_id: row.get("outcome_id")?,
+                    suggestion_type: row.get("suggestion_type")?,
+                    word_id: row.get("word_id")?,
+                    summary: row.get("summary")?,
+                    changes_summary: row.get("changes_summary")?,
+                    outcome: row.get("outcome")?,
+                    rejection_reason: row.get("rejection_reason")?,
+                    reviewer_comment: row.get("reviewer_comment")?,
+                    reviewed_at: row.get("reviewed_at")?,
+                })
+            })
+            .collect()
+            .unwrap();
+
+        Span::current().record("results", outcomes.len());
+
+        outcomes
+    }
+}
@@ -0,0 +1,349 @@
+//! The moderation queue, which is filtered, sorted and paged in the database so that only the
+//! suggestions on the page being shown need to be fetched. Whether new words have possible
+//! duplicates is recorded when they are submitted, as searching for them is too slow to do for
+//! the whole queue each time it is shown. Words which have not been checked since they were last
+//! edited are checked when they are next shown, and until then count as having no duplicates.
+
+use crate::database::outcomes::SuggestionType;
+use crate::database::scopes::{self, ModeratorScopes};
+use crate::search::JsWordHit;
+use fallible_iterator::FallibleIterator;
+use isixhosa_common::database::{ModeratorAccessDb, UserAccessDb};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter};
+use std::collections::{HashMap, HashSet};
+use tracing::{instrument, Span};
+
+/// Every pending suggestion, along with the section of the queue it is shown in and the item of
+/// that section it is part of. Words and word deletions are items of their own, while the other
+/// suggestions are grouped under the existing word which they are for. Suggestions associated to
+/// suggested new words are shown with the word suggestion, so are not included.
+const SUGGESTIONS: &str = "
+    suggestions (
+        section, item, suggestion_type, suggestion_id, suggesting_user, existing_word_id,
+        suggested_word_id
+    ) AS (
+        SELECT 0, suggestion_id, 1, suggestion_id, suggesting_user, existing_word_id,
+               IIF(existing_word_id IS NULL, suggestion_id, NULL)
+            FROM word_suggestions
+        UNION ALL
+        SELECT 1, suggestion_id, 2, suggestion_id, suggesting_user, word_id, NULL
+            FROM word_deletion_suggestions
+        UNION ALL
+        SELECT 2, existing_word_id, 3, suggestion_id, suggesting_user, existing_word_id, NULL
+            FROM sense_suggestions WHERE existing_word_id IS NOT NULL
+        UNION ALL
+        SELECT 2, existing_word_id, 4, suggestion_id, suggesting_user, existing_word_id, NULL
+            FROM example_suggestions WHERE existing_word_id IS NOT NULL
+        UNION ALL
+        SELECT 2, examples.word_id, 5, suggestion_id, suggesting_user, examples.word_id, NULL
+            FROM example_deletion_suggestions
+            INNER JOIN examples ON example_deletion_suggestions.example_id = examples.example_id
+        UNION ALL
+        SELECT 2, word_id, 6, suggestion_id, suggesting_user, word_id, NULL FROM (
+            SELECT suggestion_id, suggesting_user,
+                   COALESCE(
+                       first_existing_word_id, second_existing_word_id, linked_words.first_word_id
+                   ) AS word_id
+                FROM linked_word_suggestions
+                LEFT JOIN linked_words
+                    ON linked_word_suggestions.existing_linked_word_id = linked_words.link_id
+                WHERE suggested_word_id IS NULL AND second_suggested_word_id IS NULL
+        )
+        UNION ALL
+        SELECT 2, linked_words.first_word_id, 7, suggestion_id, suggesting_user,
+               linked_words.first_word_id, NULL
+            FROM linked_word_deletion_suggestions
+            INNER JOIN linked_words
+                ON linked_word_deletion_suggestions.linked_word_id = linked_words.link_id
+        UNION ALL
+        SELECT 2, existing_word_id, 8, suggestion_id, suggesting_user, existing_word_id, NULL
+            FROM dataset_attribution_suggestions WHERE existing_word_id IS NOT NULL
+        UNION ALL
+        SELECT 2, existing_word_id, 9, suggestion_id, suggesting_user, existing_word_id, NULL
+            FROM label_suggestions WHERE existing_word_id IS NOT NULL
+    )
+";
+
+/// The section of the moderation queue which an item is in. The sections are shown one after the
+/// other.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum QueueSection {
+    /// The item is a word suggestion
+    Words,
+    /// The item is a word deletion suggestion
+    WordDeletions,
+    /// The item is an existing word with the suggested edits associated to it
+    WordAssociatedEdits,
+}
+
+impl QueueSection {
+    fn from_index(index: u8) -> Self {
+        match index {
+            0 => QueueSection::Words,
+            1 => QueueSection::WordDeletions,
+            _ => QueueSection::WordAssociatedEdits,
+        }
+    }
+}
+
+/// Which suggestions are included in the queue, and how they are sorted
+#[derive(Clone, Debug, Default)]
+pub struct QueueQuery<'a> {
+    pub change_type: Option<SuggestionType>,
+    /// The username of the suggesting user, ignoring case
+    pub user: &'a str,
+    /// The dataset which the word must be in
+    pub dataset: Option<u64>,
+    /// Suggestions from before submission times were recorded count as older than any age
+    pub older_than_days: Option<u32>,
+    pub newer_than_days: Option<u32>,
+    /// Only new words can have duplicates, so when this is `true` only new words are included
+    pub duplicates: Option<bool>,
+    /// Text which the English or isiXhosa of the word must contain, ignoring case
+    pub search: &'a str,
+    pub newest_first: bool,
+    /// The scopes of the moderator, who only sees the words in them
+    pub scopes: Option<&'a ModeratorScopes>,
+}
+
+impl QueueQuery<'_> {
+    /// The common table expressions of the suggestions which pass the filters, along with their
+    /// parameters. An item of the queue is included if any of its suggestions are.
+    fn filtered(&self) -> (String, Vec<Value>) {
+        // Only new words are not for an existing word
+        let mut conditions = vec!["(words.word_id IS NOT NULL
+                OR (suggestions.section = 0 AND suggestions.existing_word_id IS NULL))"
+            .to_owned()];
+        let mut values = Vec::new();
+
+        if let Some(change_type) = self.change_type {
+            conditions.push("suggestions.suggestion_type = ?".to_owned());
+            values.push(Value::Integer(change_type as u8 as i64));
+        }
+        if !self.user.is_empty() {
+            conditions.push("users.username = ? COLLATE NOCASE".to_owned());
+            values.push(Value::Text(self.user.to_owned()));
+        }
+        if let Some(days) = self.older_than_days {
+            conditions.push(
+                "(suggestion_submissions.submitted_at IS NULL
+                    OR suggestion_submissions.submitted_at <= datetime('now', ?))"
+                    .to_owned(),
+            );
+            values.push(Value::Text(format!("-{days} days")));
+        }
+        if let Some(days) = self.newer_than_days {
+            conditions.push("suggestion_submissions.submitted_at > datetime('now', ?)".to_owned());
+            values.push(Value::Text(format!("-{days} days")));
+        }
+        if let Some(duplicates) = self.duplicates {
+            conditions
+                .push("COALESCE(word_suggestion_duplicates.has_duplicates, FALSE) = ?".to_owned());
+            values.push(Value::Integer(duplicates.into()));
+        }
+        if !self.search.is_empty() {
+            conditions.push(
+                "(instr(lower(COALESCE(word_suggestions.english, words.english)), lower(?)) > 0
+                    OR instr(lower(COALESCE(word_suggestions.xhosa, words.xhosa)), lower(?)) > 0)"
+                    .to_owned(),
+            );
+            values.push(Value::Text(self.search.to_owned()));
+            values.push(Value::Text(self.search.to_owned()));
+        }
+
+        let (existing, suggested) = (
+            "suggestions.existing_word_id",
+            "suggestions.suggested_word_id",
+        );
+        if let Some(dataset) = self.dataset {
+            conditions.push(scopes::word_in_any_condition(
+                existing,
+                suggested,
+                &HashSet::from([dataset]),
+                &HashSet::new(),
+            ));
+        }
+        if let Some(scopes) = self.scopes {
+            conditions.push(scopes.condition(existing, suggested));
+        }
+
+        let conditions = conditions.join(" AND ");
+        let ctes = format!(
+            "WITH {SUGGESTIONS}, filtered AS (
+                SELECT suggestions.section, suggestions.item, suggestions.suggestion_type,
+                       suggestions.suggestion_id, suggestion_submissions.submitted_at,
+                       suggestions_needing_info.suggestion_id IS NOT NULL AS needs_info
+                FROM suggestions
+                INNER JOIN users ON suggestions.suggesting_user = users.user_id
+                LEFT JOIN words ON suggestions.existing_word_id = words.word_id
+                LEFT JOIN word_suggestions
+                    ON suggestions.section = 0
+                        AND suggestions.suggestion_id = word_suggestions.suggestion_id
+                LEFT JOIN word_suggestion_duplicates
+                    ON suggestions.suggested_word_id = word_suggestion_duplicates.suggestion_id
+                LEFT JOIN suggestion_submissions
+                    ON suggestions.suggestion_type = suggestion_submissions.suggestion_type
+                        AND suggestions.suggestion_id = suggestion_submissions.suggestion_id
+                LEFT JOIN suggestions_needing_info
+                    ON suggestions.suggestion_type = suggestions_needing_info.suggestion_type
+                        AND suggestions.suggestion_id = suggestions_needing_info.suggestion_id
+                WHERE {conditions}
+            ), items AS (
+                SELECT section, item,
+                       -- Items with a suggestion from before submission times were recorded
+                       -- count as the oldest
+                       IIF(COUNT(submitted_at) < COUNT(*), NULL, MIN(submitted_at)) AS submitted_at,
+                       -- Suggestions waiting on more information are shown last in their section,
+                       -- but edits to existing words are only moved within their word
+                       IIF(section = 2, FALSE, MAX(needs_info)) AS parked
+                FROM filtered
+                GROUP BY section, item
+            )"
+        );
+
+        (ctes, values)
+    }
+}
+
+/// The number of items in the queue
+#[instrument(name = "Count moderation queue", fields(results), skip(db))]
+pub fn count(db: &impl ModeratorAccessDb, query: &QueueQuery) -> usize {
+    let (ctes, values) = query.filtered();
+    let select = format!("{ctes} SELECT COUNT(*) FROM items;");
+
+    let conn = db.get().unwrap();
+    let count: usize = conn
+        .prepare(&select)
+        .unwrap()
+        .query_row(params_from_iter(values), |row| row.get(0))
+        .unwrap();
+
+    Span::current().record("results", count);
+
+    count
+}
+
+/// The items of the queue in the given range, in the order that they are shown. These are the
+/// suggestion ids of words and word deletions, and the word ids of words with associated edits.
+#[instrument(name = "Fetch moderation queue page", fields(results), skip(db))]
+pub fn fetch_page(
+    db: &impl ModeratorAccessDb,
+    query: &QueueQuery,
+    offset: usize,
+    limit: usize,
+) -> Vec<(QueueSection, u64)> {
+    let (ctes, mut values) = query.filtered();
+    let order = if query.newest_first { "DESC" } else { "ASC" };
+    let select = format!(
+        "{ctes} SELECT section, item FROM items
+            ORDER BY section, parked, submitted_at {order}, item {order}
+            LIMIT ? OFFSET ?;"
+    );
+    values.push(Value::Integer(limit as i64));
+    values.push(Value::Integer(offset as i64));
+
+    let conn = db.get().unwrap();
+    let items: Vec<(QueueSection, u64)> = conn
+        .prepare(&select)
+        .unwrap()
+        .query(params_from_iter(values))
+        .unwrap()
+        .map(|row| Ok((QueueSection::from_index(row.get(0)?), row.get(1)?)))
+        .collect()
+        .unwrap();
+
+    Span::current().record("results", items.len());
+
+    items
+}
+
+/// The suggested edits associated to the given existing words which pass the filters
+#[instrument(
+    name = "Fetch filtered word associated edits",
+    fields(results),
+    skip(db)
+)]
+pub fn fetch_associated_edits(
+    db: &impl ModeratorAccessDb,
+    query: &QueueQuery,
+    word_ids: &[u64],
+) -> HashSet<(SuggestionType, u64)> {
+    let (ctes, mut values) = query.filtered();
+    let select = format!(
+        "{ctes} SELECT suggestion_type, suggestion_id FROM filtered
+            WHERE section = 2 AND item IN (SELECT value FROM json_each(?));"
+    );
+    values.push(Value::Text(serde_json::to_string(word_ids).unwrap()));
+
+    let conn = db.get().unwrap();
+    let edits: HashSet<(SuggestionType, u64)> = conn
+        .prepare(&select)
+        .unwrap()
+        .query(params_from_iter(values))
+        .unwrap()
+        .map(|row| Ok((row.get(0)?, row.get(1)?)))
+        .collect()
+        .unwrap();
+
+    Span::current().record("results", edits.len());
+
+    edits
+}
+
+/// Records whether the suggested new word has possible duplicates. The suggestions among its
+/// duplicates now have a possible duplicate too, namely this word.
+#[instrument(name = "Record word suggestion duplicates", skip(db, duplicates))]
+pub fn record_duplicates(
+    db: &impl UserAccessDb,
+    suggestion_id: u64,
+    duplicates: &HashSet<JsWordHit>,
+) {
+    const INSERT: &str =
+        "INSERT INTO word_suggestion_duplicates (suggestion_id, has_duplicates) VALUES (?1, ?2);";
+    const UPDATE: &str =
+        "UPDATE word_suggestion_duplicates SET has_duplicates = TRUE WHERE suggestion_id = ?1;";
+
+    let conn = db.get().unwrap();
+    conn.prepare(INSERT)
+        .unwrap()
+        .execute(params![suggestion_id, !duplicates.is_empty()])
+        .unwrap();
+
+    let mut update = conn.prepare(UPDATE).unwrap();
+    for duplicate in duplicates.iter().filter(|hit| hit.is_suggestion) {
+        update.execute(params![duplicate.id]).unwrap();
+    }
+}
+
+/// Whether each of the suggested new words has possible duplicates, by suggestion id. Words which
+/// have not been checked since they were last edited are left out.
+#[instrument(
+    name = "Fetch whether word suggestions have duplicates",
+    fields(results),
+    skip_all
+)]
+pub fn fetch_duplicate_flags(
+    db: &impl ModeratorAccessDb,
+    suggestion_ids: &[u64],
+) -> HashMap<u64, bool> {
+    const SELECT: &str = "
+        SELECT suggestion_id, has_duplicates FROM word_suggestion_duplicates
+            WHERE suggestion_id IN (SELECT value FROM json_each(?1));
+    ";
+
+    let ids = serde_json::to_string(suggestion_ids).unwrap();
+    let conn = db.get().unwrap();
+    let flags: HashMap<u64, bool> = conn
+        .prepare(SELECT)
+        .unwrap()
+        .query(params![ids])
+        .unwrap()
+        .map(|row| Ok((row.get("suggestion_id")?, row.get("has_duplicates")?)))
+        .collect()
+        .unwrap();
+
+    Span::current().record("results", flags.len());
+
+    flags
+}
@@ -0,0 +1,111 @@
+//! Citing references from the bibliography on words, senses and examples.
+
+use crate::database::changes::{ChangeSnapshot, WordChange};
+use crate::database::revisions::with_revisions;
+use isixhosa_common::database::ModeratorAccessDb;
+use isixhosa_common::types::{ExistingExample, ExistingSense, Reference};
+use rusqlite::params;
+use std::num::NonZeroU64;
+use tracing::{instrument, Span};
+
+/// What a new citation cites. At most one of the sense and example may be given, and they must
+/// belong to the word.
+#[derive(Debug)]
+pub struct NewCitation {
+    pub reference_id: u64,
+    pub word_id: u64,
+    pub sense_id: Option<u64>,
+    pub example_id: Option<u64>,
+    pub pages: String,
+}
+
+/// Adds a citation to the word, returning its ID, or `None` if the citation is invalid.
+#[instrument(name = "Add citation", fields(citation_id), skip(db))]
+pub fn add_citation(
+    db: &impl ModeratorAccessDb,
+    citation: NewCitation,
+    reviewer: NonZeroU64,
+) -> Option<u64> {
+    const INSERT: &str = "
+        INSERT INTO citations (reference_id, word_id, sense_id, example_id, pages)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            RETURNING citation_id;
+    ";
+
+    let word_id = citation.word_id;
+    let sense_valid = citation.sense_id.map_or(true, |id| {
+        ExistingSense::fetch(db, id).is_some_and(|s| s.word_id == word_id)
+    });
+    let example_valid = citation.example_id.map_or(true, |id| {
+        ExistingExample::fetch(db, id).is_some_and(|e| e.word_id == word_id)
+    });
+    let single_target = citation.sense_id.is_none() || citation.example_id.is_none();
+
+    if !sense_valid
+        || !example_valid
+        || !single_target
+        || Reference::fetch_by_id(db, citation.reference_id).is_none()
+    {
+        return None;
+    }
+
+    let citation_id = with_revisions(db, &[word_id], reviewer, reviewer, || {
+        let citation_id: u64 = db
+            .get()
+            .unwrap()
+            .prepare(INSERT)
+            .unwrap()
+            .query_row(
+                params![
+                    citation.reference_id,
+                    word_id,
+                    citation.sense_id,
+                    citation.example_id,
+                    citation.pages.trim(),
+                ],
+                |row| row.get("citation_id"),
+            )
+            .unwrap();
+
+        let new = ChangeSnapshot::fetch_citation(db, citation_id);
+        WordChange::record(db, reviewer, reviewer, None, new);
+
+        citation_id
+    });
+
+    Span::current().record("citation_id", citation_id);
+    Some(citation_id)
+}
+
+/// Removes a citation from the word. Returns whether the citation existed.
+#[instrument(name = "Remove citation", fields(found), skip(db))]
+pub fn remove_citation(
+    db: &impl ModeratorAccessDb,
+    word_id: u64,
+    citation_id: u64,
+    reviewer: NonZeroU64,
+) -> bool {
+    const DELETE: &str = "DELETE FROM citations WHERE citation_id = ?1;";
+
+    let old = match ChangeSnapshot::fetch_citation(db, citation_id) {
+        Some(ChangeSnapshot::Citation(c)) if c.word_id == word_id => ChangeSnapshot::Citation(c),
+        _ => {
+            Span::current().record("found", false);
+            return false;
+        }
+    };
+
+    with_revisions(db, &[word_id], reviewer, reviewer, || {
+        db.get()
+            .unwrap()
+            .prepare(DELETE)
+            .unwrap()
+            .execute(params![citation_id])
+            .unwrap();
+
+        WordChange::record(db, reviewer, reviewer, Some(old), None);
+    });
+
+    Span::current().record("found", true);
+    true
+}
@@ -0,0 +1,404 @@
+//! Review policies, which can require several moderators to approve a suggestion before it is
+//! applied, or let logged-in users vote on suggestions so that moderators can see what the
+//! community thinks of them. Policies apply to the whole site, or only to one type of suggestion
+//! or to the words in one dataset.
+
+use crate::database::outcomes::{SuggestionSummary, SuggestionType};
+use chrono::Utc;
+use fallible_iterator::FallibleIterator;
+use isixhosa_common::database::{AdministratorAccessDb, ModeratorAccessDb, UserAccessDb};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, Value, ValueRef};
+use rusqlite::{params, Row, ToSql};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use tracing::{instrument, Span};
+
+#[derive(Clone, Debug)]
+pub struct ReviewPolicy {
+    pub policy_id: u64,
+    /// The type of suggestion the policy applies to, or all types if `None`
+    pub suggestion_type: Option<SuggestionType>,
+    /// The dataset whose words the policy applies to, or all words if `None`
+    pub dataset_id: Option<u64>,
+    pub dataset_name: Option<String>,
+    pub required_approvals: u32,
+    pub allow_voting: bool,
+}
+
+/// The strictest combination of all the policies which apply to a suggestion
+#[derive(Copy, Clone, Debug)]
+pub struct AppliedPolicy {
+    pub required_approvals: u32,
+    pub allow_voting: bool,
+}
+
+impl Default for AppliedPolicy {
+    fn default() -> Self {
+        AppliedPolicy {
+            required_approvals: 1,
+            allow_voting: false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ReviewTally {
+    pub policy: AppliedPolicy,
+    pub approvals: u32,
+    pub upvotes: u32,
+    pub downvotes: u32,
+}
+
+#[derive(Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Vote {
+    Up,
+    Down,
+}
+
+impl ReviewPolicy {
+    #[instrument(name = "Fetch all review policies", fields(results), skip_all)]
+    pub fn fetch_all(db: &impl UserAccessDb) -> Vec<ReviewPolicy> {
+        const SELECT: &str = "
+            SELECT review_policies.policy_id, review_policies.suggestion_type,
+                   review_policies.dataset_id, datasets.name, review_policies.required_approvals,
+                   review_policies.allow_voting
+            FROM review_policies
+            LEFT JOIN datasets ON review_policies.dataset_id = datasets.dataset_id
+            ORDER BY review_policies.policy_id;
+        ";
+
+        let conn = db.get().unwrap();
+        let policies: Vec<ReviewPolicy> = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query(params![])
+            .unwrap()
+            .map(|row| Ok(ReviewPolicy::from(row)))
+            .collect()
+            .unwrap();
+
+        Span::current().record("results", policies.len());
+        policies
+    }
+
+    #[instrument(name = "Add review policy", skip(db))]
+    pub fn insert(
+        db: &impl AdministratorAccessDb,
+        suggestion_type: Option<SuggestionType>,
+        dataset_id: Option<u64>,
+        required_approvals: u32,
+        allow_voting: bool,
+    ) {
+        const INSERT: &str = "
+            INSERT INTO review_policies (
+                suggestion_type, dataset_id, required_approvals, allow_voting
+            ) VALUES (?1, ?2, ?3, ?4);
+        ";
+
+        let conn = db.get().unwrap();
+        conn.prepare(INSERT)
+            .unwrap()
+            .execute(params![
+                suggestion_type,
+                dataset_id,
+                required_approvals,
+                allow_voting
+            ])
+            .unwrap();
+    }
+
+    #[instrument(name = "Delete review policy", fields(found), skip(db))]
+    pub fn delete_by_id(db: &impl AdministratorAccessDb, policy_id: u64) -> bool {
+        const DELETE: &str = "DELETE FROM review_policies WHERE policy_id = ?1;";
+
+        let conn = db.get().unwrap();
+        let found = conn
+            .prepare(DELETE)
+            .unwrap()
+            .execute(params![policy_id])
+            .unwrap()
+            == 1;
+
+        Span::current().record("found", found);
+        found
+    }
+
+    fn applies_to(&self, suggestion_type: SuggestionType, word_datasets: &[u64]) -> bool {
+        self.suggestion_type
+            .map_or(true, |ty| ty == suggestion_type)
+            && self
+                .dataset_id
+                .map_or(true, |id| word_datasets.contains(&id))
+    }
+}
+
+impl From<&Row<'_>> for ReviewPolicy {
+    fn from(row: &Row<'_>) -> Self {
+        ReviewPolicy {
+            policy_id: row.get("policy_id").unwrap(),
+            suggestion_type: row.get("suggestion_type").unwrap(),
+            dataset_id: row.get("dataset_id").unwrap(),
+            dataset_name: row.get("name").unwrap(),
+            required_approvals: row.get("required_approvals").unwrap(),
+            allow_voting: row.get("allow_voting").unwrap(),
+        }
+    }
+}
+
+impl AppliedPolicy {
+    /// Combines the policies which apply to the suggestion, taking the most approvals any of them
+    /// require. Voting is allowed if any of them allow it.
+    pub fn resolve(
+        policies: &[ReviewPolicy],
+        suggestion_type: SuggestionType,
+        word_datasets: &[u64],
+    ) -> AppliedPolicy {
+        policies
+            .iter()
+            .filter(|policy| policy.applies_to(suggestion_type, word_datasets))
+            .fold(AppliedPolicy::default(), |applied, policy| AppliedPolicy {
+                required_approvals: applied.required_approvals.max(policy.required_approvals),
+                allow_voting: applied.allow_voting || policy.allow_voting,
+            })
+    }
+
+    #[instrument(
+        name = "Fetch review policy for suggestion",
+        fields(
+            suggestion_type = ?suggestion.suggestion_type,
+            suggestion_id = suggestion.suggestion_id,
+        ),
+        skip_all
+    )]
+    pub fn for_suggestion(db: &impl UserAccessDb, suggestion: &SuggestionSummary) -> AppliedPolicy {
+        let policies = ReviewPolicy::fetch_all(db);
+        let datasets = word_datasets(db, &policies, suggestion.word_id);
+        AppliedPolicy::resolve(&policies, suggestion.suggestion_type, &datasets)
+    }
+}
+
+/// The datasets the word is attributed to, which are only fetched if any policy needs them
+fn word_datasets(
+    db: &impl UserAccessDb,
+    policies: &[ReviewPolicy],
+    word_id: Option<u64>,
+) -> Vec<u64> {
+    const SELECT: &str = "SELECT dataset_id FROM dataset_attributions WHERE word_id = ?1;";
+
+    let word_id = match word_id {
+        Some(id) if policies.iter().any(|policy| policy.dataset_id.is_some()) => id,
+        _ => return Vec::new(),
+    };
+
+    let conn = db.get().unwrap();
+    let datasets: Vec<u64> = conn
+        .prepare(SELECT)
+        .unwrap()
+        .query(params![word_id])
+        .unwrap()
+        .map(|row| row.get(0))
+        .collect()
+        .unwrap();
+    datasets
+}
+
+/// Records the moderator's approval of the suggestion, returning how many moderators have now
+/// approved it
+#[instrument(name = "Approve suggestion", fields(approvals), skip(db))]
+pub fn approve(
+    db: &impl ModeratorAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    moderator: NonZeroU64,
+) -> u32 {
+    const INSERT: &str = "
+        INSERT INTO suggestion_approvals (suggestion_type, suggestion_id, moderator, approved_at)
+            VALUES (?1, ?2, ?3, ?4);
+    ";
+
+    const COUNT: &str = "
+        SELECT COUNT(*) FROM suggestion_approvals WHERE suggestion_type = ?1 AND suggestion_id = ?2;
+    ";
+
+    let conn = db.get().unwrap();
+    conn.prepare(INSERT)
+        .unwrap()
+        .execute(params![
+            suggestion_type,
+            suggestion_id,
+            moderator.get(),
+            Utc::now()
+        ])
+        .unwrap();
+
+    let approvals = conn
+        .prepare(COUNT)
+        .unwrap()
+        .query_row(params![suggestion_type, suggestion_id], |row| row.get(0))
+        .unwrap();
+
+    Span::current().record("approvals", approvals);
+    approvals
+}
+
+/// Replaces any earlier vote by the user on the suggestion
+#[instrument(name = "Vote on suggestion", skip(db))]
+pub fn vote(
+    db: &impl UserAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    voter: NonZeroU64,
+    vote: Vote,
+) {
+    const INSERT: &str = "
+        INSERT INTO suggestion_votes (suggestion_type, suggestion_id, voter, vote)
+            VALUES (?1, ?2, ?3, ?4);
+    ";
+
+    let conn = db.get().unwrap();
+    conn.prepare(INSERT)
+        .unwrap()
+        .execute(params![suggestion_type, suggestion_id, voter.get(), vote])
+        .unwrap();
+}
+
+#[instrument(name = "Fetch votes by user", fields(results), skip(db))]
+pub fn fetch_votes_by_user(
+    db: &impl UserAccessDb,
+    voter: NonZeroU64,
+) -> HashMap<(SuggestionType, u64), Vote> {
+    const SELECT: &str =
+        "SELECT suggestion_type, suggestion_id, vote FROM suggestion_votes WHERE voter = ?1;";
+
+    let conn = db.get().unwrap();
+    let votes: HashMap<(SuggestionType, u64), Vote> = conn
+        .prepare(SELECT)
+        .unwrap()
+        .query(params![voter.get()])
+        .unwrap()
+        .map(|row| Ok(((row.get(0)?, row.get(1)?), row.get(2)?)))
+        .collect()
+        .unwrap();
+
+    Span::current().record("results", votes.len());
+    votes
+}
+
+/// Fetches the approvals, votes and applied policy of every pending suggestion
+#[instrument(name = "Fetch all review tallies", fields(results), skip_all)]
+pub fn fetch_all_tallies(db: &impl UserAccessDb) -> HashMap<(SuggestionType, u64), ReviewTally> {
+    const SELECT_APPROVALS: &str = "
+        SELECT suggestion_type, suggestion_id, COUNT(*) FROM suggestion_approvals
+            GROUP BY suggestion_type, suggestion_id;
+    ";
+
+    const SELECT_VOTES: &str = "
+        SELECT suggestion_type, suggestion_id,
+               SUM(vote > 0) AS upvotes, SUM(vote < 0) AS downvotes
+        FROM suggestion_votes
+        GROUP BY suggestion_type, suggestion_id;
+    ";
+
+    let policies = ReviewPolicy::fetch_all(db);
+
+    let mut tallies: HashMap<(SuggestionType, u64), ReviewTally> =
+        SuggestionSummary::fetch_all_pending(db)
+            .into_iter()
+            .map(|suggestion| {
+                let datasets = word_datasets(db, &policies, suggestion.word_id);
+                let policy =
+                    AppliedPolicy::resolve(&policies, suggestion.suggestion_type, &datasets);
+                let tally = ReviewTally {
+                    policy,
+                    ..Default::default()
+                };
+                (
+                    (suggestion.suggestion_type, suggestion.suggestion_id),
+                    tally,
+                )
+            })
+            .collect();
+
+    let conn = db.get().unwrap();
+
+    let approvals: Vec<((SuggestionType, u64), u32)> = conn
+        .prepare(SELECT_APPROVALS)
+        .unwrap()
+        .query(params![])
+        .unwrap()
+        .map(|row| Ok(((row.get(0)?, row.get(1)?), row.get(2)?)))
+        .collect()
+        .unwrap();
+
+    for (key, count) in approvals {
+        if let Some(tally) = tallies.get_mut(&key) {
+            tally.approvals = count;
+        }
+    }
+
+    let votes: Vec<((SuggestionType, u64), u32, u32)> = conn
+        .prepare(SELECT_VOTES)
+        .unwrap()
+        .query(params![])
+        .unwrap()
+        .map(|row| Ok(((row.get(0)?, row.get(1)?), row.get(2)?, row.get(3)?)))
+        .collect()
+        .unwrap();
+
+    for (key, upvotes, downvotes) in votes {
+        if let Some(tally) = tallies.get_mut(&key) {
+            tally.upvotes = upvotes;
+            tally.downvotes = downvotes;
+        }
+    }
+
+    Span::current().record("results", tallies.len());
+    tallies
+}
+
+/// Deletes the approvals and votes once the suggestion has been reviewed
+#[instrument(name = "Delete suggestion approvals and votes", skip(db))]
+pub fn delete_reviews(
+    db: &impl ModeratorAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+) {
+    const DELETE_APPROVALS: &str = "
+        DELETE FROM suggestion_approvals WHERE suggestion_type = ?1 AND suggestion_id = ?2;
+    ";
+
+    const DELETE_VOTES: &str = "
+        DELETE FROM suggestion_votes WHERE suggestion_type = ?1 AND suggestion_id = ?2;
+    ";
+
+    let conn = db.get().unwrap();
+
+    for delete in [DELETE_APPROVALS, DELETE_VOTES] {
+        conn.prepare(delete)
+            .unwrap()
+            .execute(params![suggestion_type, suggestion_id])
+            .unwrap();
+    }
+}
+
+impl ToSql for Vote {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let v = match self {
+            Vote::Up => 1,
+            Vote::Down => -1,
+        };
+        Ok(ToSqlOutput::Owned(Value::Integer(v)))
+    }
+}
+
+impl FromSql for Vote {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_i64()? {
+            1 => Ok(Vote::Up),
+            -1 => Ok(Vote::Down),
+            v => Err(FromSqlError::OutOfRange(v)),
+        }
+    }
+}
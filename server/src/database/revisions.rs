@@ -0,0 +1,752 @@
+//! Full snapshots of a word and everything associated with it, recorded whenever a change to the
+//! word is accepted so that earlier versions can be compared against and restored.
+
+use crate::database::changes::{
+    ChangeKind, ChangeSnapshot, ChangeTarget, FieldDiff, LinkedWordHits, WordChange,
+};
+use crate::database::senses;
+use crate::export::{
+    DatasetAttributionRecord, LabelAttributionRecord, LinkedWordRecord, WordRecord,
+};
+use crate::i18n::I18nInfo;
+use crate::search::{TantivyClient, WordDocument};
+use chrono::{DateTime, Utc};
+use fallible_iterator::FallibleIterator;
+use futures::executor::block_on;
+use isixhosa_common::database::{ModeratorAccessDb, PublicAccessDb};
+use isixhosa_common::types::{
+    Citation, Dataset, ExistingExample, ExistingLinkedWord, ExistingSense, ExistingWord, Label,
+    PublicUserInfo,
+};
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use tracing::{instrument, Span};
+
+/// The state of a word and all of its senses, examples, linked words, dataset attributions, labels
+/// and citations at one point in time, stored as JSON.
+#[derive(Serialize, Deserialize)]
+pub struct RevisionSnapshot {
+    pub word: WordRecord,
+    pub examples: Vec<ExistingExample>,
+    pub linked_words: Vec<LinkedWordRecord>,
+    pub datasets: Vec<DatasetAttributionRecord>,
+    // Revisions recorded before words had senses have none
+    #[serde(default)]
+    pub senses: Vec<ExistingSense>,
+    // Likewise for labels
+    #[serde(default)]
+    pub labels: Vec<LabelAttributionRecord>,
+    // Likewise for citations
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+}
+
+impl RevisionSnapshot {
+    pub fn fetch(db: &impl PublicAccessDb, word_id: u64) -> Option<RevisionSnapshot> {
+        let word = ExistingWord::fetch_alone(db, word_id)?;
+
+        let mut examples = ExistingExample::fetch_all_for_word(db, word_id);
+        examples.sort_by_key(|e| e.example_id);
+
+        let mut linked_words: Vec<LinkedWordRecord> =
+            ExistingLinkedWord::fetch_all_for_word(db, word_id)
+                .into_iter()
+                .map(|l| LinkedWordRecord {
+                    link_id: l.link_id,
+                    link_type: l.link_type,
+                    first: l.first_word_id,
+                    second: l.second_word_id,
+                })
+                .collect();
+        linked_words.sort_by_key(|l| l.link_id);
+
+        let mut datasets: Vec<DatasetAttributionRecord> = Dataset::fetch_all_for_word(db, word_id)
+            .into_iter()
+            .map(|d| DatasetAttributionRecord {
+                word_id,
+                dataset_id: d.id,
+            })
+            .collect();
+        datasets.sort();
+
+        let mut senses = ExistingSense::fetch_all_for_word(db, word_id);
+        senses.sort_by_key(|s| s.sense_id);
+
+        let mut labels: Vec<LabelAttributionRecord> = Label::fetch_all_for_word(db, word_id)
+            .into_iter()
+            .map(|l| LabelAttributionRecord {
+                word_id,
+                label_id: l.id,
+            })
+            .collect();
+        labels.sort();
+
+        let citations = Citation::fetch_all_for_word(db, word_id);
+
+        Some(RevisionSnapshot {
+            word: word.into(),
+            examples,
+            linked_words,
+            datasets,
+            senses,
+            labels,
+            citations,
+        })
+    }
+
+    /// Splits the snapshot into its individual items, keyed by what they are and their id. The
+    /// word itself always comes first, senses come after the examples and linked words which may be
+    /// assigned to them, and citations come after the senses and examples which they may cite.
+    pub fn into_items(self) -> BTreeMap<(ChangeTarget, u64), ChangeSnapshot> {
+        let word = (
+            (ChangeTarget::Word, self.word.word_id),
+            ChangeSnapshot::Word(self.word),
+        );
+        let examples = self.examples.into_iter().map(|e| {
+            (
+                (ChangeTarget::Example, e.example_id),
+                ChangeSnapshot::Example(e),
+            )
+        });
+        let linked_words = self.linked_words.into_iter().map(|l| {
+            (
+                (ChangeTarget::LinkedWord, l.link_id),
+                ChangeSnapshot::LinkedWord(l),
+            )
+        });
+        let datasets = self.datasets.into_iter().map(|d| {
+            (
+                (ChangeTarget::DatasetAttribution, d.dataset_id),
+                ChangeSnapshot::DatasetAttribution(d),
+            )
+        });
+
+        let senses = self
+            .senses
+            .into_iter()
+            .map(|s| ((ChangeTarget::Sense, s.sense_id), ChangeSnapshot::Sense(s)));
+        let labels = self
+            .labels
+            .into_iter()
+            .map(|l| ((ChangeTarget::Label, l.label_id), ChangeSnapshot::Label(l)));
+        let citations = self.citations.into_iter().map(|c| {
+            (
+                (ChangeTarget::Citation, c.citation_id),
+                ChangeSnapshot::Citation(c),
+            )
+        });
+
+        std::iter::once(word)
+            .chain(examples)
+            .chain(linked_words)
+            .chain(datasets)
+            .chain(senses)
+            .chain(labels)
+            .chain(citations)
+            .collect()
+    }
+}
+
+/// The difference in one item between two revisions
+#[derive(Debug)]
+pub struct ItemDiff {
+    pub target: ChangeTarget,
+    pub kind: ChangeKind,
+    pub fields: Vec<FieldDiff>,
+}
+
+#[derive(Debug)]
+pub struct WordRevision {
+    pub revision_id: u64,
+    pub word_id: u64,
+    /// `None` for the revision recorded before the first tracked change to a word
+    pub suggesting_user: Option<PublicUserInfo>,
+    pub reviewing_user: Option<PublicUserInfo>,
+    pub created_at: DateTime<Utc>,
+    /// The revision which this revision restored, if it was created by a revert
+    pub reverted_from: Option<u64>,
+    /// The differences from the previous revision of the word
+    pub diff: Vec<ItemDiff>,
+}
+
+/// Runs `change`, which modifies the given words, recording a revision of each of them before and
+/// after it is made.
+pub fn with_revisions<R>(
+    db: &impl ModeratorAccessDb,
+    word_ids: &[u64],
+    suggesting_user: NonZeroU64,
+    reviewing_user: NonZeroU64,
+    change: impl FnOnce() -> R,
+) -> R {
+    for word_id in word_ids {
+        WordRevision::record_baseline(db, *word_id);
+    }
+
+    let ret = change();
+
+    for word_id in word_ids {
+        WordRevision::record(db, *word_id, suggesting_user, reviewing_user);
+    }
+
+    ret
+}
+
+impl WordRevision {
+    /// Records the current state of the word without attributing it to anyone, if it differs from
+    /// the latest revision. This makes sure that the state before the first tracked change to a
+    /// word is kept.
+    pub fn record_baseline(db: &impl ModeratorAccessDb, word_id: u64) {
+        Self::insert(db, word_id, None, None);
+    }
+
+    /// Records the current state of the word after an accepted change, if it differs from the
+    /// latest revision.
+    pub fn record(
+        db: &impl ModeratorAccessDb,
+        word_id: u64,
+        suggesting_user: NonZeroU64,
+        reviewing_user: NonZeroU64,
+    ) {
+        Self::insert(db, word_id, Some((suggesting_user, reviewing_user)), None);
+    }
+
+    #[instrument(name = "Record word revision", fields(recorded), skip(db))]
+    fn insert(
+        db: &impl ModeratorAccessDb,
+        word_id: u64,
+        users: Option<(NonZeroU64, NonZeroU64)>,
+        reverted_from: Option<u64>,
+    ) {
+        const SELECT_LATEST: &str = "
+            SELECT snapshot FROM word_revisions WHERE word_id = ?1
+                ORDER BY revision_id DESC LIMIT 1;
+        ";
+        const INSERT: &str = "
+            INSERT INTO word_revisions (
+                word_id, suggesting_user, reviewing_user, created_at, reverted_from, snapshot
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6);
+        ";
+
+        // The word has been deleted, so there is nothing to record
+        let Some(snapshot) = RevisionSnapshot::fetch(db, word_id) else {
+            Span::current().record("recorded", false);
+            return;
+        };
+        let snapshot = serde_json::to_string(&snapshot).unwrap();
+
+        let conn = db.get().unwrap();
+        let latest: Option<String> = conn
+            .prepare(SELECT_LATEST)
+            .unwrap()
+            .query_row(params![word_id], |row| row.get("snapshot"))
+            .optional()
+            .unwrap();
+
+        if latest.as_ref() == Some(&snapshot) {
+            Span::current().record("recorded", false);
+            return;
+        }
+
+        conn.prepare(INSERT)
+            .unwrap()
+            .execute(params![
+                word_id,
+                users.map(|(suggesting, _)| suggesting.get()),
+                users.map(|(_, reviewing)| reviewing.get()),
+                Utc::now(),
+                reverted_from,
+                snapshot,
+            ])
+            .unwrap();
+
+        Span::current().record("recorded", true);
+    }
+
+    /// The id of the latest revision of the word, if any have been recorded
+    #[instrument(level = "trace", name = "Fetch latest revision id", skip(db))]
+    pub fn latest_id(db: &impl PublicAccessDb, word_id: u64) -> Option<u64> {
+        const SELECT: &str = "SELECT MAX(revision_id) FROM word_revisions WHERE word_id = ?1;";
+
+        let conn = db.get().unwrap();
+        let latest = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query_row(params![word_id], |row| row.get(0))
+            .unwrap();
+        latest
+    }
+
+    #[instrument(
+        level = "trace",
+        name = "Fetch revision snapshot",
+        fields(found),
+        skip(db)
+    )]
+    fn fetch_snapshot(
+        db: &impl PublicAccessDb,
+        revision_id: u64,
+    ) -> Option<(u64, RevisionSnapshot)> {
+        const SELECT: &str = "SELECT word_id, snapshot FROM word_revisions WHERE revision_id = ?1;";
+
+        let conn = db.get().unwrap();
+        let opt: Option<(u64, String)> = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query_row(params![revision_id], |row| {
+                Ok((row.get("word_id")?, row.get("snapshot")?))
+            })
+            .optional()
+            .unwrap();
+
+        Span::current().record("found", opt.is_some());
+
+        opt.map(|(word_id, json)| (word_id, serde_json::from_str(&json).unwrap()))
+    }
+
+    /// Fetches all revisions of a word, newest first.
+    #[instrument(name = "Fetch word revisions", fields(results), skip(db, i18n_info))]
+    pub fn fetch_all_for_word(
+        db: &impl PublicAccessDb,
+        i18n_info: &I18nInfo,
+        word_id: u64,
+    ) -> Vec<WordRevision> {
+        const SELECT: &str = "
+            SELECT word_revisions.revision_id, word_revisions.created_at,
+                   word_revisions.reverted_from, word_revisions.snapshot,
+                   word_revisions.suggesting_user, suggestor.username,
+                   suggestor.display_name, word_revisions.reviewing_user,
+                   reviewer.username AS reviewer_username,
+                   reviewer.display_name AS reviewer_display_name
+            FROM word_revisions
+            LEFT OUTER JOIN users AS suggestor ON word_revisions.suggesting_user = suggestor.user_id
+            LEFT OUTER JOIN users AS reviewer ON word_revisions.reviewing_user = reviewer.user_id
+            WHERE word_revisions.word_id = ?1
+            ORDER BY word_revisions.revision_id ASC;
+        ";
+
+        let datasets: HashMap<u64, Dataset> = Dataset::fetch_all(db)
+            .into_iter()
+            .map(|d| (d.id, d))
+            .collect();
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+        let rows = query.query(params![word_id]).unwrap();
+
+        let rows: Vec<(WordRevision, BTreeMap<(ChangeTarget, u64), ChangeSnapshot>)> = rows
+            .map(|row| {
+                let json: String = row.get("snapshot")?;
+                let items = serde_json::from_str::<RevisionSnapshot>(&json)
+                    .unwrap()
+                    .into_items();
+
+                Ok((WordRevision::from_row(row, word_id, Vec::new()), items))
+            })
+            .collect()
+            .unwrap();
+
+        let linked_words =
+            LinkedWordHits::fetch(db, rows.iter().flat_map(|(_, items)| items.values()));
+
+        let mut previous = BTreeMap::new();
+        let mut revisions: Vec<WordRevision> = rows
+            .into_iter()
+            .map(|(mut revision, items)| {
+                revision.diff = diff_items(
+                    db,
+                    i18n_info,
+                    &datasets,
+                    &linked_words,
+                    word_id,
+                    &previous,
+                    &items,
+                );
+                previous = items;
+                revision
+            })
+            .collect();
+
+        revisions.reverse();
+
+        Span::current().record("results", revisions.len());
+
+        revisions
+    }
+
+    fn from_row(row: &Row<'_>, word_id: u64, diff: Vec<ItemDiff>) -> WordRevision {
+        let user = |id: &str, username: &str, display_name: &str| {
+            row.get::<&str, Option<u64>>(id)
+                .unwrap()
+                .and_then(NonZeroU64::new)
+                .map(|id| PublicUserInfo {
+                    id,
+                    username: row.get(username).unwrap(),
+                    display_name: row.get(display_name).unwrap(),
+                })
+        };
+
+        WordRevision {
+            revision_id: row.get("revision_id").unwrap(),
+            word_id,
+            suggesting_user: user("suggesting_user", "username", "display_name"),
+            reviewing_user: user(
+                "reviewing_user",
+                "reviewer_username",
+                "reviewer_display_name",
+            ),
+            created_at: row.get("created_at").unwrap(),
+            reverted_from: row.get("reverted_from").unwrap(),
+            diff,
+        }
+    }
+
+    /// Restores a word and its associated items to how they were at the given revision, which
+    /// itself creates a new revision. Linked words whose other word no longer exists, and
+    /// attributions to datasets or labels which no longer exist, cannot be restored and are
+    /// skipped.
+    ///
+    /// Returns `false` if the revision does not exist or is not a revision of the given word.
+    #[instrument(name = "Revert word to revision", skip(db, tantivy))]
+    pub fn revert(
+        db: &impl ModeratorAccessDb,
+        tantivy: Arc<TantivyClient>,
+        word_id: u64,
+        revision_id: u64,
+        reviewer: NonZeroU64,
+    ) -> bool {
+        let snapshot = match Self::fetch_snapshot(db, revision_id) {
+            Some((revision_word_id, snapshot)) if revision_word_id == word_id => snapshot,
+            _ => return false,
+        };
+
+        let mut current = RevisionSnapshot::fetch(db, word_id)
+            .map(RevisionSnapshot::into_items)
+            .unwrap_or_default();
+        let existed = !current.is_empty();
+
+        let document = WordDocument::from(&snapshot.word);
+
+        let target = snapshot.into_items();
+
+        // Words on the other side of links being restored or removed are changed too
+        let mut other_words: BTreeSet<u64> = current
+            .values()
+            .chain(target.values())
+            .filter_map(|item| match item {
+                ChangeSnapshot::LinkedWord(l) => Some([l.first, l.second]),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        other_words.remove(&word_id);
+
+        for id in std::iter::once(word_id).chain(other_words.iter().copied()) {
+            WordRevision::record_baseline(db, id);
+        }
+
+        for (key, item) in target {
+            let old = current.remove(&key);
+
+            if restore_item(db, &item) {
+                WordChange::record(db, reviewer, reviewer, old, Some(item));
+            }
+        }
+
+        // Anything left was added after the revision
+        for item in current.into_values() {
+            remove_item(db, &item);
+            WordChange::record(db, reviewer, reviewer, Some(item), None);
+        }
+
+        Self::insert(db, word_id, Some((reviewer, reviewer)), Some(revision_id));
+
+        for id in other_words {
+            WordRevision::record(db, id, reviewer, reviewer);
+        }
+
+        let document = document.with_senses_and_labels_from_db(db);
+        if existed {
+            block_on(async move { tantivy.edit_word(document).await });
+        } else {
+            block_on(async move { tantivy.add_new_word(document).await });
+        }
+
+        true
+    }
+}
+
+fn diff_items(
+    db: &impl PublicAccessDb,
+    i18n_info: &I18nInfo,
+    datasets: &HashMap<u64, Dataset>,
+    linked_words: &LinkedWordHits,
+    word_id: u64,
+    old: &BTreeMap<(ChangeTarget, u64), ChangeSnapshot>,
+    new: &BTreeMap<(ChangeTarget, u64), ChangeSnapshot>,
+) -> Vec<ItemDiff> {
+    let keys: BTreeSet<&(ChangeTarget, u64)> = old.keys().chain(new.keys()).collect();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let (old, new) = (old.get(key), new.get(key));
+            let kind = match (old, new) {
+                (None, _) => ChangeKind::Added,
+                (_, None) => ChangeKind::Deleted,
+                _ => ChangeKind::Edited,
+            };
+
+            let fields = |s: Option<&ChangeSnapshot>| {
+                s.map(|s| s.fields(db, i18n_info, datasets, linked_words, word_id))
+            };
+            let fields = FieldDiff::between(fields(old), fields(new));
+
+            (!fields.is_empty()).then_some(ItemDiff {
+                target: key.0,
+                kind,
+                fields,
+            })
+        })
+        .collect()
+}
+
+/// Writes the item back into the database, returning whether it could be restored.
+#[instrument(level = "trace", name = "Restore item", fields(restored), skip_all)]
+pub fn restore_item(db: &impl ModeratorAccessDb, item: &ChangeSnapshot) -> bool {
+    const UPSERT_WORD: &str = "
+        INSERT INTO words (
+            word_id, english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
+            is_inchoative, is_informal, transitivity, followed_by, noun_class, note,
+            etymology_language, etymology_form, etymology_note, literal_translation
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+            ON CONFLICT(word_id) DO UPDATE SET
+                english = excluded.english,
+                xhosa = excluded.xhosa,
+                part_of_speech = excluded.part_of_speech,
+                xhosa_tone_markings = excluded.xhosa_tone_markings,
+                infinitive = excluded.infinitive,
+                is_plural = excluded.is_plural,
+                noun_class = excluded.noun_class,
+                is_inchoative = excluded.is_inchoative,
+                is_informal = excluded.is_informal,
+                transitivity = excluded.transitivity,
+                followed_by = excluded.followed_by,
+                note = excluded.note,
+                etymology_language = excluded.etymology_language,
+                etymology_form = excluded.etymology_form,
+                etymology_note = excluded.etymology_note,
+                literal_translation = excluded.literal_translation;
+    ";
+    const UPSERT_EXAMPLE: &str = "
+        INSERT INTO examples (example_id, word_id, english, xhosa) VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(example_id) DO UPDATE SET
+                english = excluded.english,
+                xhosa = excluded.xhosa;
+    ";
+    const UPSERT_LINKED_WORD: &str = "
+        INSERT INTO linked_words (link_id, link_type, first_word_id, second_word_id)
+            SELECT ?1, ?2, ?3, ?4
+            WHERE EXISTS (SELECT 1 FROM words WHERE word_id = ?3)
+                AND EXISTS (SELECT 1 FROM words WHERE word_id = ?4)
+            ON CONFLICT(link_id) DO UPDATE SET
+                link_type = excluded.link_type;
+    ";
+    const INSERT_DATASET_ATTRIBUTION: &str = "
+        INSERT INTO dataset_attributions (word_id, dataset_id)
+            SELECT ?1, dataset_id FROM datasets WHERE dataset_id = ?2;
+    ";
+    const UPSERT_SENSE: &str = "
+        INSERT INTO senses (sense_id, word_id, sense_order, english, is_informal)
+            SELECT ?1, ?2, ?3, ?4, ?5
+            WHERE EXISTS (SELECT 1 FROM words WHERE word_id = ?2)
+            ON CONFLICT(sense_id) DO UPDATE SET
+                sense_order = excluded.sense_order,
+                english = excluded.english,
+                is_informal = excluded.is_informal;
+    ";
+    const CLEAR_SENSE_EXAMPLES: &str = "DELETE FROM example_senses WHERE sense_id = ?1;";
+    const CLEAR_SENSE_LINKED_WORDS: &str = "DELETE FROM linked_word_senses WHERE sense_id = ?1;";
+    const CLEAR_SENSE_LABELS: &str = "DELETE FROM sense_labels WHERE sense_id = ?1;";
+    const INSERT_SENSE_LABEL: &str = "
+        INSERT INTO sense_labels (sense_id, label_id)
+            SELECT ?1, label_id FROM labels WHERE label_id = ?2;
+    ";
+    const INSERT_LABEL: &str = "
+        INSERT INTO word_labels (word_id, label_id)
+            SELECT ?1, label_id FROM labels WHERE label_id = ?2;
+    ";
+    const UPSERT_CITATION: &str = "
+        INSERT INTO citations (citation_id, reference_id, word_id, sense_id, example_id, pages)
+            SELECT ?1, ?2, ?3, ?4, ?5, ?6
+            WHERE EXISTS (SELECT 1 FROM bibliography WHERE reference_id = ?2)
+                AND EXISTS (SELECT 1 FROM words WHERE word_id = ?3)
+                AND (?4 IS NULL OR EXISTS (SELECT 1 FROM senses WHERE sense_id = ?4))
+                AND (?5 IS NULL OR EXISTS (SELECT 1 FROM examples WHERE example_id = ?5))
+            ON CONFLICT(citation_id) DO UPDATE SET
+                reference_id = excluded.reference_id,
+                sense_id = excluded.sense_id,
+                example_id = excluded.example_id,
+                pages = excluded.pages;
+    ";
+
+    let conn = db.get().unwrap();
+    let modified_rows = match item {
+        ChangeSnapshot::Word(w) => conn
+            .prepare(UPSERT_WORD)
+            .unwrap()
+            .execute(params![
+                w.word_id,
+                w.english,
+                w.xhosa,
+                w.part_of_speech,
+                w.xhosa_tone_markings,
+                w.infinitive,
+                w.is_plural,
+                w.is_inchoative,
+                w.is_informal,
+                w.transitivity,
+                w.followed_by.clone().unwrap_or_default(),
+                w.noun_class.map(|x| x as u8),
+                w.note,
+                w.etymology_language,
+                w.etymology_form,
+                w.etymology_note,
+                w.literal_translation,
+            ])
+            .unwrap(),
+        ChangeSnapshot::Example(e) => conn
+            .prepare(UPSERT_EXAMPLE)
+            .unwrap()
+            .execute(params![e.example_id, e.word_id, e.english, e.xhosa])
+            .unwrap(),
+        ChangeSnapshot::LinkedWord(l) => conn
+            .prepare(UPSERT_LINKED_WORD)
+            .unwrap()
+            .execute(params![l.link_id, l.link_type, l.first, l.second])
+            .unwrap(),
+        // An attribution which is already present is ignored by the table
+        ChangeSnapshot::DatasetAttribution(d) => conn
+            .prepare(INSERT_DATASET_ATTRIBUTION)
+            .unwrap()
+            .execute(params![d.word_id, d.dataset_id])
+            .unwrap(),
+        // Examples and linked words which no longer exist are skipped when reassigning them
+        ChangeSnapshot::Sense(s) => {
+            let modified_rows = conn
+                .prepare(UPSERT_SENSE)
+                .unwrap()
+                .execute(params![
+                    s.sense_id,
+                    s.word_id,
+                    s.sense_order,
+                    s.english,
+                    s.is_informal
+                ])
+                .unwrap();
+
+            if modified_rows == 1 {
+                conn.prepare(CLEAR_SENSE_EXAMPLES)
+                    .unwrap()
+                    .execute(params![s.sense_id])
+                    .unwrap();
+                conn.prepare(CLEAR_SENSE_LINKED_WORDS)
+                    .unwrap()
+                    .execute(params![s.sense_id])
+                    .unwrap();
+                conn.prepare(CLEAR_SENSE_LABELS)
+                    .unwrap()
+                    .execute(params![s.sense_id])
+                    .unwrap();
+
+                for example_id in &s.examples {
+                    senses::assign_example(db, *example_id, Some(s.sense_id));
+                }
+
+                for link_id in &s.linked_words {
+                    senses::assign_linked_word(db, *link_id, s.word_id, Some(s.sense_id));
+                }
+
+                for label_id in &s.labels {
+                    conn.prepare(INSERT_SENSE_LABEL)
+                        .unwrap()
+                        .execute(params![s.sense_id, label_id])
+                        .unwrap();
+                }
+            }
+
+            modified_rows
+        }
+        // Likewise for labels, and labels which no longer exist are skipped
+        ChangeSnapshot::Label(l) => conn
+            .prepare(INSERT_LABEL)
+            .unwrap()
+            .execute(params![l.word_id, l.label_id])
+            .unwrap(),
+        // Citations of references, senses or examples which no longer exist are skipped
+        ChangeSnapshot::Citation(c) => conn
+            .prepare(UPSERT_CITATION)
+            .unwrap()
+            .execute(params![
+                c.citation_id,
+                c.reference_id,
+                c.word_id,
+                c.sense_id,
+                c.example_id,
+                c.pages
+            ])
+            .unwrap(),
+    };
+
+    Span::current().record("restored", modified_rows == 1);
+
+    modified_rows == 1
+}
+
+#[instrument(level = "trace", name = "Remove item", skip_all)]
+fn remove_item(db: &impl ModeratorAccessDb, item: &ChangeSnapshot) {
+    const DELETE_EXAMPLE: &str = "DELETE FROM examples WHERE example_id = ?1;";
+    const DELETE_LINKED_WORD: &str = "DELETE FROM linked_words WHERE link_id = ?1;";
+    const DELETE_DATASET_ATTRIBUTION: &str =
+        "DELETE FROM dataset_attributions WHERE word_id = ?1 AND dataset_id = ?2;";
+    const DELETE_SENSE: &str = "DELETE FROM senses WHERE sense_id = ?1;";
+    const DELETE_LABEL: &str = "DELETE FROM word_labels WHERE word_id = ?1 AND label_id = ?2;";
+    const DELETE_CITATION: &str = "DELETE FROM citations WHERE citation_id = ?1;";
+
+    let conn = db.get().unwrap();
+    match item {
+        // The revision always contains the word itself, so it is never left over
+        ChangeSnapshot::Word(w) => unreachable!("Word {} missing from revision", w.word_id),
+        ChangeSnapshot::Example(e) => conn
+            .prepare(DELETE_EXAMPLE)
+            .unwrap()
+            .execute(params![e.example_id])
+            .unwrap(),
+        ChangeSnapshot::LinkedWord(l) => conn
+            .prepare(DELETE_LINKED_WORD)
+            .unwrap()
+            .execute(params![l.link_id])
+            .unwrap(),
+        ChangeSnapshot::DatasetAttribution(d) => conn
+            .prepare(DELETE_DATASET_ATTRIBUTION)
+            .unwrap()
+            .execute(params![d.word_id, d.dataset_id])
+            .unwrap(),
+        ChangeSnapshot::Sense(s) => conn
+            .prepare(DELETE_SENSE)
+            .unwrap()
+            .execute(params![s.sense_id])
+            .unwrap(),
+        ChangeSnapshot::Label(l) => conn
+            .prepare(DELETE_LABEL)
+            .unwrap()
+            .execute(params![l.word_id, l.label_id])
+            .unwrap(),
+        ChangeSnapshot::Citation(c) => conn
+            .prepare(DELETE_CITATION)
+            .unwrap()
+            .execute(params![c.citation_id])
+            .unwrap(),
+    };
+}
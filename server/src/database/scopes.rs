@@ -0,0 +1,369 @@
+//! Scopes which limit a moderator to the words in certain datasets or with certain labels, e.g. a
+//! medical terminologist who only moderates the medical glossary. Moderators without any scopes
+//! may moderate the whole dictionary.
+
+use crate::database::outcomes::{SuggestionSummary, SuggestionType};
+use fallible_iterator::FallibleIterator;
+use isixhosa_common::database::{ModeratorAccessDb, WordOrSuggestionId};
+use rusqlite::{params, OptionalExtension};
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use std::num::NonZeroU64;
+use tabled::Tabled;
+use tracing::{instrument, Span};
+
+#[derive(Debug, Clone, Tabled)]
+pub struct ModeratorScope {
+    #[tabled(rename = "ID")]
+    pub scope_id: u64,
+    #[tabled(rename = "Email address")]
+    pub email: String,
+    #[tabled(rename = "Scope")]
+    pub target: ScopeTarget,
+}
+
+/// What a scope limits a moderator to. The name is `None` if the dataset or label was deleted.
+#[derive(Debug, Clone)]
+pub enum ScopeTarget {
+    Dataset { id: u64, name: Option<String> },
+    Label { id: u64, name: Option<String> },
+}
+
+impl Display for ScopeTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (kind, id, name) = match self {
+            ScopeTarget::Dataset { id, name } => ("Dataset", id, name),
+            ScopeTarget::Label { id, name } => ("Label", id, name),
+        };
+
+        write!(f, "{kind} {id} ({})", name.as_deref().unwrap_or("deleted"))
+    }
+}
+
+impl ModeratorScope {
+    #[instrument(level = "info", name = "Fetch all moderator scopes", skip_all)]
+    pub fn fetch_all(db: &impl ModeratorAccessDb) -> Vec<ModeratorScope> {
+        const SELECT: &str = "
+            SELECT moderator_scopes.scope_id, users.email, moderator_scopes.dataset_id,
+                   datasets.name AS dataset_name, moderator_scopes.label_id,
+                   labels.name AS label_name
+            FROM moderator_scopes
+            INNER JOIN users ON moderator_scopes.user_id = users.user_id
+            LEFT JOIN datasets ON moderator_scopes.dataset_id = datasets.dataset_id
+            LEFT JOIN labels ON moderator_scopes.label_id = labels.label_id
+            ORDER BY users.email, moderator_scopes.scope_id;
+        ";
+
+        let conn = db.get().unwrap();
+        let scopes: Vec<ModeratorScope> = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query(params![])
+            .unwrap()
+            .map(|row| {
+                let target = match row.get("dataset_id")? {
+                    Some(id) => ScopeTarget::Dataset {
+                        id,
+                        name: row.get("dataset_name")?,
+                    },
+                    None => ScopeTarget::Label {
+                        id: row.get("label_id")?,
+                        name: row.get("label_name")?,
+                    },
+                };
+
+                Ok(ModeratorScope {
+                    scope_id: row.get("scope_id")?,
+                    email: row.get("email")?,
+                    target,
+                })
+            })
+            .collect()
+            .unwrap();
+        scopes
+    }
+
+    /// Limits the moderator with the given email to the dataset or label, on top of any scopes
+    /// they already have. Returns `false` if there is no such user.
+    #[instrument(level = "info", name = "Add moderator scope", skip(db))]
+    pub fn add_by_email(
+        db: &impl ModeratorAccessDb,
+        email: String,
+        dataset_id: Option<u64>,
+        label_id: Option<u64>,
+    ) -> bool {
+        const INSERT: &str = "
+            INSERT INTO moderator_scopes (user_id, dataset_id, label_id)
+                SELECT user_id, ?2, ?3 FROM users WHERE email = ?1;
+        ";
+
+        let conn = db.get().unwrap();
+        let changed = conn
+            .prepare(INSERT)
+            .unwrap()
+            .execute(params![email, dataset_id, label_id])
+            .unwrap();
+
+        changed != 0
+    }
+
+    /// Removes all scopes of the moderator with the given email, so that they may moderate the
+    /// whole dictionary again. Returns `false` if they had no scopes.
+    #[instrument(level = "info", name = "Clear moderator scopes", skip(db))]
+    pub fn clear_by_email(db: &impl ModeratorAccessDb, email: String) -> bool {
+        const DELETE: &str = "
+            DELETE FROM moderator_scopes
+                WHERE user_id IN (SELECT user_id FROM users WHERE email = ?1);
+        ";
+
+        let conn = db.get().unwrap();
+        let changed = conn
+            .prepare(DELETE)
+            .unwrap()
+            .execute(params![email])
+            .unwrap();
+
+        changed != 0
+    }
+}
+
+/// The datasets and labels a moderator is limited to
+#[derive(Clone, Debug)]
+pub struct ModeratorScopes {
+    datasets: HashSet<u64>,
+    labels: HashSet<u64>,
+}
+
+impl ModeratorScopes {
+    /// Returns `None` if the moderator may moderate the whole dictionary
+    #[instrument(name = "Fetch moderator scopes", fields(scoped), skip(db))]
+    pub fn fetch_for_moderator(
+        db: &impl ModeratorAccessDb,
+        moderator: NonZeroU64,
+    ) -> Option<ModeratorScopes> {
+        const SELECT: &str =
+            "SELECT dataset_id, label_id FROM moderator_scopes WHERE user_id = ?1;";
+
+        let conn = db.get().unwrap();
+        let rows: Vec<(Option<u64>, Option<u64>)> = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query(params![moderator.get()])
+            .unwrap()
+            .map(|row| Ok((row.get("dataset_id")?, row.get("label_id")?)))
+            .collect()
+            .unwrap();
+
+        Span::current().record("scoped", !rows.is_empty());
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        Some(ModeratorScopes {
+            datasets: rows.iter().filter_map(|(dataset, _)| *dataset).collect(),
+            labels: rows.iter().filter_map(|(_, label)| *label).collect(),
+        })
+    }
+
+    /// Whether the word is in one of the scopes
+    pub fn allows_word(&self, db: &impl ModeratorAccessDb, word: WordOrSuggestionId) -> bool {
+        word_in_any(db, word, &self.datasets, &self.labels)
+    }
+
+    /// An SQL condition for whether the word is in one of the scopes, as with
+    /// [`word_in_any_condition`]
+    pub fn condition(&self, existing_word_id: &str, suggested_word_id: &str) -> String {
+        word_in_any_condition(
+            existing_word_id,
+            suggested_word_id,
+            &self.datasets,
+            &self.labels,
+        )
+    }
+
+    /// Whether the word which the suggestion is for is in one of the scopes. Returns `false` if
+    /// the suggestion does not exist.
+    pub fn allows_suggestion(
+        &self,
+        db: &impl ModeratorAccessDb,
+        suggestion_type: SuggestionType,
+        suggestion_id: u64,
+    ) -> bool {
+        suggestion_word(db, suggestion_type, suggestion_id)
+            .is_some_and(|word| self.allows_word(db, word))
+    }
+}
+
+/// Whether the word is in any of the datasets or has any of the labels. For a suggested word, the
+/// datasets and labels suggested for it count, so that e.g. scoped moderators can review words
+/// being added to their dataset. For an existing word only those it actually has count, so that
+/// suggesting a dataset or label for a word does not bring it into a moderator's scope.
+#[instrument(
+    name = "Check if word is in datasets or labels",
+    fields(found),
+    skip(db)
+)]
+pub fn word_in_any(
+    db: &impl ModeratorAccessDb,
+    word: WordOrSuggestionId,
+    datasets: &HashSet<u64>,
+    labels: &HashSet<u64>,
+) -> bool {
+    const DATASETS_OF_EXISTING: &str = "
+        SELECT dataset_id FROM dataset_attributions WHERE word_id = ?1;
+    ";
+    const LABELS_OF_EXISTING: &str = "
+        SELECT label_id FROM word_labels WHERE word_id = ?1
+        UNION
+        SELECT sense_labels.label_id FROM sense_labels
+            INNER JOIN senses ON sense_labels.sense_id = senses.sense_id
+            WHERE senses.word_id = ?1;
+    ";
+    const DATASETS_OF_SUGGESTED: &str = "
+        SELECT dataset_id FROM dataset_attribution_suggestions
+            WHERE suggested_word_id = ?1 AND NOT is_delete;
+    ";
+    const LABELS_OF_SUGGESTED: &str = "
+        SELECT label_id FROM label_suggestions
+            WHERE suggested_word_id = ?1 AND NOT is_delete;
+    ";
+
+    let (select_datasets, select_labels) = match word {
+        WordOrSuggestionId::ExistingWord { .. } => (DATASETS_OF_EXISTING, LABELS_OF_EXISTING),
+        WordOrSuggestionId::Suggested { .. } => (DATASETS_OF_SUGGESTED, LABELS_OF_SUGGESTED),
+    };
+
+    let conn = db.get().unwrap();
+    let any_in = |select: &str, scope: &HashSet<u64>| -> bool {
+        if scope.is_empty() {
+            return false;
+        }
+
+        let any: bool = conn
+            .prepare(select)
+            .unwrap()
+            .query(params![word.inner()])
+            .unwrap()
+            .map(|row| row.get(0))
+            .any(|id: u64| Ok(scope.contains(&id)))
+            .unwrap();
+        any
+    };
+
+    let found = any_in(select_datasets, datasets) || any_in(select_labels, labels);
+    Span::current().record("found", found);
+    found
+}
+
+/// An SQL condition for whether a word is in any of the datasets or has any of the labels, counting
+/// the same datasets and labels as [`word_in_any`] does. The arguments are the SQL expressions for
+/// the id of the word if it exists and of the word suggestion if it is a new word, one of which is
+/// `NULL`.
+pub fn word_in_any_condition(
+    existing_word_id: &str,
+    suggested_word_id: &str,
+    datasets: &HashSet<u64>,
+    labels: &HashSet<u64>,
+) -> String {
+    let list = |ids: &HashSet<u64>| {
+        ids.iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (datasets, labels) = (list(datasets), list(labels));
+
+    format!(
+        "(
+            EXISTS (
+                SELECT 1 FROM dataset_attributions
+                    WHERE word_id = {existing_word_id} AND dataset_id IN ({datasets})
+            ) OR EXISTS (
+                SELECT 1 FROM word_labels
+                    WHERE word_id = {existing_word_id} AND label_id IN ({labels})
+            ) OR EXISTS (
+                SELECT 1 FROM sense_labels
+                    INNER JOIN senses ON sense_labels.sense_id = senses.sense_id
+                    WHERE senses.word_id = {existing_word_id}
+                        AND sense_labels.label_id IN ({labels})
+            ) OR EXISTS (
+                SELECT 1 FROM dataset_attribution_suggestions
+                    WHERE suggested_word_id = {suggested_word_id} AND NOT is_delete
+                        AND dataset_id IN ({datasets})
+            ) OR EXISTS (
+                SELECT 1 FROM label_suggestions
+                    WHERE suggested_word_id = {suggested_word_id} AND NOT is_delete
+                        AND label_id IN ({labels})
+            )
+        )"
+    )
+}
+
+/// Whether the moderator may review the suggestion, which they may do for any suggestion if they
+/// have no scopes
+pub fn may_moderate(
+    db: &impl ModeratorAccessDb,
+    moderator: NonZeroU64,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+) -> bool {
+    match ModeratorScopes::fetch_for_moderator(db, moderator) {
+        Some(scopes) => scopes.allows_suggestion(db, suggestion_type, suggestion_id),
+        None => true,
+    }
+}
+
+/// Whether the moderator may change the existing word directly, which they may do for any word if
+/// they have no scopes
+pub fn may_moderate_word(db: &impl ModeratorAccessDb, moderator: NonZeroU64, word_id: u64) -> bool {
+    match ModeratorScopes::fetch_for_moderator(db, moderator) {
+        Some(scopes) => scopes.allows_word(db, WordOrSuggestionId::existing(word_id)),
+        None => true,
+    }
+}
+
+/// Whether the moderator may moderate the whole dictionary. Scoped moderators may not use tools
+/// which act on words that are not in the dictionary, such as restoring words from the trash.
+pub fn is_unscoped(db: &impl ModeratorAccessDb, moderator: NonZeroU64) -> bool {
+    ModeratorScopes::fetch_for_moderator(db, moderator).is_none()
+}
+
+/// The existing or suggested word which the suggestion is for
+#[instrument(level = "trace", name = "Fetch word of suggestion", skip(db))]
+fn suggestion_word(
+    db: &impl ModeratorAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+) -> Option<WordOrSuggestionId> {
+    let summary = SuggestionSummary::fetch(db, suggestion_type, suggestion_id)?;
+
+    if let Some(word_id) = summary.word_id {
+        return Some(WordOrSuggestionId::existing(word_id));
+    }
+
+    let table = match suggestion_type {
+        SuggestionType::Word => return Some(WordOrSuggestionId::suggested(suggestion_id)),
+        SuggestionType::Sense => "sense_suggestions",
+        SuggestionType::Example => "example_suggestions",
+        SuggestionType::LinkedWord => "linked_word_suggestions",
+        SuggestionType::DatasetAttribution => "dataset_attribution_suggestions",
+        SuggestionType::Label => "label_suggestions",
+        // Deletions are always of existing items
+        SuggestionType::WordDeletion
+        | SuggestionType::ExampleDeletion
+        | SuggestionType::LinkedWordDeletion => return None,
+    };
+
+    let select = format!("SELECT suggested_word_id FROM {table} WHERE suggestion_id = ?1;");
+
+    let conn = db.get().unwrap();
+    let suggested_word_id: Option<u64> = conn
+        .prepare(&select)
+        .unwrap()
+        .query_row(params![suggestion_id], |row| row.get(0))
+        .optional()
+        .unwrap()
+        .flatten();
+    suggested_word_id.map(WordOrSuggestionId::suggested)
+}
@@ -0,0 +1,211 @@
+//! Assigning examples and linked words to the senses of a word.
+
+use crate::database::changes::{ChangeSnapshot, WordChange};
+use isixhosa_common::database::ModeratorAccessDb;
+use isixhosa_common::types::ExistingSense;
+use rusqlite::{params, OptionalExtension};
+use std::num::NonZeroU64;
+use tracing::instrument;
+
+/// Assigns the example to the given sense, or removes it from its sense if `None`. Nothing is
+/// assigned if the example or sense does not exist.
+#[instrument(level = "trace", name = "Assign example to sense", skip(db))]
+pub fn assign_example(db: &impl ModeratorAccessDb, example_id: u64, sense_id: Option<u64>) {
+    const DELETE: &str = "DELETE FROM example_senses WHERE example_id = ?1;";
+    const UPSERT: &str = "
+        INSERT INTO example_senses (example_id, sense_id)
+            SELECT ?1, ?2
+            WHERE EXISTS (SELECT 1 FROM examples WHERE example_id = ?1)
+                AND EXISTS (SELECT 1 FROM senses WHERE sense_id = ?2)
+            ON CONFLICT(example_id) DO UPDATE SET
+                sense_id = excluded.sense_id;
+    ";
+
+    let conn = db.get().unwrap();
+    match sense_id {
+        Some(sense_id) => conn
+            .prepare(UPSERT)
+            .unwrap()
+            .execute(params![example_id, sense_id])
+            .unwrap(),
+        None => conn
+            .prepare(DELETE)
+            .unwrap()
+            .execute(params![example_id])
+            .unwrap(),
+    };
+}
+
+/// Assigns the linked word to the given sense of `word_id`, or removes it from any sense of
+/// `word_id` if `None`. The sense which the link belongs to on the other word is left alone.
+#[instrument(level = "trace", name = "Assign linked word to sense", skip(db))]
+pub fn assign_linked_word(
+    db: &impl ModeratorAccessDb,
+    link_id: u64,
+    word_id: u64,
+    sense_id: Option<u64>,
+) {
+    const DELETE: &str = "
+        DELETE FROM linked_word_senses
+            WHERE link_id = ?1 AND sense_id IN (SELECT sense_id FROM senses WHERE word_id = ?2);
+    ";
+    const INSERT: &str = "
+        INSERT INTO linked_word_senses (link_id, sense_id)
+            SELECT ?1, ?2
+            WHERE EXISTS (SELECT 1 FROM linked_words WHERE link_id = ?1)
+                AND EXISTS (SELECT 1 FROM senses WHERE sense_id = ?2 AND word_id = ?3);
+    ";
+
+    let conn = db.get().unwrap();
+    conn.prepare(DELETE)
+        .unwrap()
+        .execute(params![link_id, word_id])
+        .unwrap();
+
+    if let Some(sense_id) = sense_id {
+        conn.prepare(INSERT)
+            .unwrap()
+            .execute(params![link_id, sense_id, word_id])
+            .unwrap();
+    }
+}
+
+/// Applies the sense suggested for an example suggestion, if there is one, to the accepted example.
+/// If the suggested sense has not been accepted yet, the example is left without a sense.
+#[instrument(level = "trace", name = "Apply suggested sense of example", skip(db))]
+pub fn apply_example_suggestion(
+    db: &impl ModeratorAccessDb,
+    example_suggestion_id: u64,
+    example_id: u64,
+    suggesting_user: NonZeroU64,
+    reviewer: NonZeroU64,
+) {
+    const SELECT: &str = "
+        SELECT existing_sense_id, sense_suggestion_id FROM example_sense_suggestions
+            WHERE example_suggestion_id = ?1;
+    ";
+
+    let conn = db.get().unwrap();
+    let suggested: Option<(Option<u64>, Option<u64>)> = conn
+        .prepare(SELECT)
+        .unwrap()
+        .query_row(params![example_suggestion_id], |row| {
+            Ok((
+                row.get("existing_sense_id")?,
+                row.get("sense_suggestion_id")?,
+            ))
+        })
+        .optional()
+        .unwrap();
+
+    let new = match suggested {
+        Some((Some(sense_id), _)) => Some(sense_id),
+        Some((None, None)) => None,
+        _ => return,
+    };
+
+    let old = ExistingSense::fetch_id_for_example(db, example_id);
+    record_sense_changes(db, [old, new], suggesting_user, reviewer, || {
+        assign_example(db, example_id, new)
+    });
+}
+
+/// Applies the sense suggested for a linked word suggestion, if there is one, to the accepted link.
+/// If the suggested sense has not been accepted yet, the link is left without a sense.
+#[instrument(
+    level = "trace",
+    name = "Apply suggested sense of linked word",
+    skip(db)
+)]
+pub fn apply_linked_word_suggestion(
+    db: &impl ModeratorAccessDb,
+    link_suggestion_id: u64,
+    link_id: u64,
+    suggesting_user: NonZeroU64,
+    reviewer: NonZeroU64,
+) {
+    const SELECT: &str = "
+        SELECT linked_word_sense_suggestions.existing_word_id, existing_sense_id,
+               sense_suggestion_id, senses.word_id
+            FROM linked_word_sense_suggestions
+            LEFT JOIN senses ON existing_sense_id = senses.sense_id
+            WHERE link_suggestion_id = ?1;
+    ";
+
+    let conn = db.get().unwrap();
+    let suggested: Option<(Option<u64>, Option<u64>, Option<u64>, Option<u64>)> = conn
+        .prepare(SELECT)
+        .unwrap()
+        .query_row(params![link_suggestion_id], |row| {
+            Ok((
+                row.get("existing_word_id")?,
+                row.get("existing_sense_id")?,
+                row.get("sense_suggestion_id")?,
+                row.get("word_id")?,
+            ))
+        })
+        .optional()
+        .unwrap();
+
+    let (word_id, new) = match suggested {
+        Some((_, Some(sense_id), _, Some(word_id))) => (word_id, Some(sense_id)),
+        Some((Some(word_id), None, None, _)) => (word_id, None),
+        _ => return,
+    };
+
+    let old = ExistingSense::fetch_id_for_linked_word(db, link_id, word_id);
+    record_sense_changes(db, [old, new], suggesting_user, reviewer, || {
+        assign_linked_word(db, link_id, word_id, new)
+    });
+}
+
+/// Points the examples, linked words and labels suggested to belong to a suggested sense to the
+/// sense which was created when it was accepted.
+#[instrument(level = "trace", name = "Resolve suggested sense", skip(db))]
+pub fn resolve_sense_suggestion(
+    db: &impl ModeratorAccessDb,
+    sense_suggestion_id: u64,
+    sense_id: u64,
+) {
+    const UPDATES: [&str; 3] = [
+        "UPDATE example_sense_suggestions SET existing_sense_id = ?2, sense_suggestion_id = NULL
+            WHERE sense_suggestion_id = ?1;",
+        "UPDATE linked_word_sense_suggestions SET existing_sense_id = ?2, sense_suggestion_id = NULL
+            WHERE sense_suggestion_id = ?1;",
+        "UPDATE label_suggestions SET existing_sense_id = ?2, sense_suggestion_id = NULL
+            WHERE sense_suggestion_id = ?1;",
+    ];
+
+    let conn = db.get().unwrap();
+    for update in UPDATES {
+        conn.prepare(update)
+            .unwrap()
+            .execute(params![sense_suggestion_id, sense_id])
+            .unwrap();
+    }
+}
+
+fn record_sense_changes(
+    db: &impl ModeratorAccessDb,
+    senses: [Option<u64>; 2],
+    suggesting_user: NonZeroU64,
+    reviewer: NonZeroU64,
+    assign: impl FnOnce(),
+) {
+    if senses[0] == senses[1] {
+        return;
+    }
+
+    let senses: Vec<(u64, Option<ChangeSnapshot>)> = senses
+        .into_iter()
+        .flatten()
+        .map(|id| (id, ChangeSnapshot::fetch_sense(db, id)))
+        .collect();
+
+    assign();
+
+    for (id, old) in senses {
+        let new = ChangeSnapshot::fetch_sense(db, id);
+        WordChange::record(db, suggesting_user, reviewer, old, new);
+    }
+}
@@ -0,0 +1,368 @@
+//! Splitting a word which conflates two meanings into two separate words.
+
+use crate::database::changes::{ChangeSnapshot, WordChange};
+use crate::database::revisions::WordRevision;
+use crate::database::senses;
+use crate::export::{DatasetAttributionRecord, LabelAttributionRecord, WordRecord};
+use crate::search::{TantivyClient, WordDocument};
+use futures::executor::block_on;
+use isixhosa_common::database::ModeratorAccessDb;
+use isixhosa_common::language::WordLinkType;
+use isixhosa_common::types::{
+    Citation, ExistingExample, ExistingLinkedWord, ExistingSense, ExistingWord,
+};
+use rusqlite::params;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use tracing::{instrument, Span};
+
+/// How a word should be split. The chosen examples and linked words move to the new word, and
+/// everything else stays with the original.
+#[derive(Debug)]
+pub struct SplitWord {
+    pub original_english: String,
+    pub new_english: String,
+    /// Must be either [`WordLinkType::Related`] or [`WordLinkType::Confusable`]
+    pub link_type: WordLinkType,
+    pub new_examples: Vec<u64>,
+    pub new_linked_words: Vec<u64>,
+}
+
+/// Splits the word into two words with all of the same grammatical information. User and dataset
+/// attributions, word labels and citations of the word as a whole are copied to the new word, and
+/// the two words are linked to each other. Senses stay with the original word, so moved examples
+/// and linked words are removed from them. Citations of moved examples move with them.
+///
+/// Returns the ID of the new word, or `None` if the word does not exist or the split is invalid.
+#[instrument(name = "Split word", fields(new_word_id), skip(db, tantivy))]
+pub fn split_word(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    word_id: u64,
+    split: SplitWord,
+    reviewer: NonZeroU64,
+) -> Option<u64> {
+    let valid_link_type = matches!(
+        split.link_type,
+        WordLinkType::Related | WordLinkType::Confusable
+    );
+    let english_given =
+        !split.original_english.trim().is_empty() && !split.new_english.trim().is_empty();
+
+    if !valid_link_type || !english_given {
+        return None;
+    }
+
+    let word = ExistingWord::fetch_alone(db, word_id)?;
+
+    let examples: Vec<ExistingExample> = ExistingExample::fetch_all_for_word(db, word_id)
+        .into_iter()
+        .filter(|e| split.new_examples.contains(&e.example_id))
+        .collect();
+    let linked_words: Vec<ExistingLinkedWord> = ExistingLinkedWord::fetch_all_for_word(db, word_id)
+        .into_iter()
+        .filter(|l| split.new_linked_words.contains(&l.link_id))
+        .collect();
+
+    // Words on the other side of moved links are changed too
+    let mut changed_words: Vec<u64> = linked_words
+        .iter()
+        .map(|l| l.other.id)
+        .chain([word_id])
+        .collect();
+    changed_words.sort_unstable();
+    changed_words.dedup();
+
+    for id in &changed_words {
+        WordRevision::record_baseline(db, *id);
+    }
+
+    let original_senses = ExistingSense::fetch_all_for_word(db, word_id);
+
+    let mut record = WordRecord::from(word);
+    if record.english != split.original_english.trim() {
+        let old = ChangeSnapshot::fetch_word(db, word_id);
+        update_english(db, word_id, split.original_english.trim());
+        let new = ChangeSnapshot::fetch_word(db, word_id);
+        WordChange::record(db, reviewer, reviewer, old, new);
+    }
+
+    record.english = split.new_english.trim().to_owned();
+    let new_word_id = insert_word(db, &record);
+    record.word_id = new_word_id;
+    let new = ChangeSnapshot::fetch_word(db, new_word_id);
+    WordChange::record(db, reviewer, reviewer, None, new);
+
+    copy_attributions(db, word_id, new_word_id, reviewer);
+    move_examples(db, examples, new_word_id, reviewer);
+    move_linked_words(db, linked_words, word_id, new_word_id, reviewer);
+    link_words(db, split.link_type, word_id, new_word_id, reviewer);
+
+    for sense in original_senses {
+        let new = ChangeSnapshot::fetch_sense(db, sense.sense_id);
+        WordChange::record(
+            db,
+            reviewer,
+            reviewer,
+            Some(ChangeSnapshot::Sense(sense)),
+            new,
+        );
+    }
+
+    changed_words.push(new_word_id);
+    for id in changed_words {
+        WordRevision::record(db, id, reviewer, reviewer);
+    }
+
+    let original = ExistingWord::fetch_alone(db, word_id).unwrap();
+    let original =
+        WordDocument::from(&WordRecord::from(original)).with_senses_and_labels_from_db(db);
+    let new = WordDocument::from(&record).with_senses_and_labels_from_db(db);
+    block_on(async move {
+        tantivy.edit_word(original).await;
+        tantivy.add_new_word(new).await;
+    });
+
+    Span::current().record("new_word_id", new_word_id);
+    Some(new_word_id)
+}
+
+fn update_english(db: &impl ModeratorAccessDb, word_id: u64, english: &str) {
+    const UPDATE: &str = "UPDATE words SET english = ?2 WHERE word_id = ?1;";
+
+    db.get()
+        .unwrap()
+        .prepare(UPDATE)
+        .unwrap()
+        .execute(params![word_id, english])
+        .unwrap();
+}
+
+/// Inserts the word as a new word, ignoring its ID. Returns the ID of the new word.
+pub fn insert_word(db: &impl ModeratorAccessDb, w: &WordRecord) -> u64 {
+    const INSERT: &str = "
+        INSERT INTO words (
+            english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
+            is_inchoative, is_informal, transitivity, followed_by, noun_class, note,
+            etymology_language, etymology_form, etymology_note, literal_translation
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+            RETURNING word_id;
+    ";
+
+    let id: i64 = db
+        .get()
+        .unwrap()
+        .prepare(INSERT)
+        .unwrap()
+        .query_row(
+            params![
+                w.english,
+                w.xhosa,
+                w.part_of_speech,
+                w.xhosa_tone_markings,
+                w.infinitive,
+                w.is_plural,
+                w.is_inchoative,
+                w.is_informal,
+                w.transitivity,
+                w.followed_by.clone().unwrap_or_default(),
+                w.noun_class.map(|x| x as u8),
+                w.note,
+                w.etymology_language,
+                w.etymology_form,
+                w.etymology_note,
+                w.literal_translation,
+            ],
+            |row| row.get("word_id"),
+        )
+        .unwrap();
+
+    id as u64
+}
+
+fn copy_attributions(db: &impl ModeratorAccessDb, from: u64, to: u64, reviewer: NonZeroU64) {
+    const COPY_USERS: &str = "
+        INSERT INTO user_attributions (word_id, user_id)
+            SELECT ?2, user_id FROM user_attributions WHERE word_id = ?1;
+    ";
+    const COPY_DATASETS: &str = "
+        INSERT INTO dataset_attributions (word_id, dataset_id)
+            SELECT ?2, dataset_id FROM dataset_attributions WHERE word_id = ?1
+            RETURNING word_id, dataset_id;
+    ";
+    const COPY_LABELS: &str = "
+        INSERT INTO word_labels (word_id, label_id)
+            SELECT ?2, label_id FROM word_labels WHERE word_id = ?1
+            RETURNING word_id, label_id;
+    ";
+    const COPY_CITATIONS: &str = "
+        INSERT INTO citations (reference_id, word_id, sense_id, example_id, pages)
+            SELECT reference_id, ?2, NULL, NULL, pages FROM citations
+                WHERE word_id = ?1 AND sense_id IS NULL AND example_id IS NULL
+            RETURNING citation_id;
+    ";
+
+    let conn = db.get().unwrap();
+    conn.prepare(COPY_USERS)
+        .unwrap()
+        .execute(params![from, to])
+        .unwrap();
+
+    let datasets: Vec<u64> = conn
+        .prepare(COPY_DATASETS)
+        .unwrap()
+        .query_map(params![from, to], |row| row.get("dataset_id"))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    for dataset_id in datasets {
+        let record = ChangeSnapshot::DatasetAttribution(DatasetAttributionRecord {
+            word_id: to,
+            dataset_id,
+        });
+        WordChange::record(db, reviewer, reviewer, None, Some(record));
+    }
+
+    let labels: Vec<u64> = conn
+        .prepare(COPY_LABELS)
+        .unwrap()
+        .query_map(params![from, to], |row| row.get("label_id"))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    for label_id in labels {
+        let record = ChangeSnapshot::Label(LabelAttributionRecord {
+            word_id: to,
+            label_id,
+        });
+        WordChange::record(db, reviewer, reviewer, None, Some(record));
+    }
+
+    let citations: Vec<u64> = conn
+        .prepare(COPY_CITATIONS)
+        .unwrap()
+        .query_map(params![from, to], |row| row.get("citation_id"))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    for citation_id in citations {
+        let new = ChangeSnapshot::fetch_citation(db, citation_id);
+        WordChange::record(db, reviewer, reviewer, None, new);
+    }
+}
+
+fn move_examples(
+    db: &impl ModeratorAccessDb,
+    examples: Vec<ExistingExample>,
+    to: u64,
+    reviewer: NonZeroU64,
+) {
+    const UPDATE: &str = "UPDATE examples SET word_id = ?2 WHERE example_id = ?1;";
+    const UPDATE_CITATION: &str = "UPDATE citations SET word_id = ?2 WHERE citation_id = ?1;";
+
+    let citations = examples
+        .first()
+        .map(|e| Citation::fetch_all_for_word(db, e.word_id))
+        .unwrap_or_default();
+
+    for example in examples {
+        let example_id = example.example_id;
+
+        db.get()
+            .unwrap()
+            .prepare(UPDATE)
+            .unwrap()
+            .execute(params![example_id, to])
+            .unwrap();
+        senses::assign_example(db, example_id, None);
+
+        let new = ChangeSnapshot::fetch_example(db, example_id);
+        WordChange::record(
+            db,
+            reviewer,
+            reviewer,
+            Some(ChangeSnapshot::Example(example)),
+            new,
+        );
+
+        for citation in citations
+            .iter()
+            .filter(|c| c.example_id == Some(example_id))
+        {
+            db.get()
+                .unwrap()
+                .prepare(UPDATE_CITATION)
+                .unwrap()
+                .execute(params![citation.citation_id, to])
+                .unwrap();
+
+            let new = ChangeSnapshot::fetch_citation(db, citation.citation_id);
+            WordChange::record(
+                db,
+                reviewer,
+                reviewer,
+                Some(ChangeSnapshot::Citation(citation.clone())),
+                new,
+            );
+        }
+    }
+}
+
+fn move_linked_words(
+    db: &impl ModeratorAccessDb,
+    linked_words: Vec<ExistingLinkedWord>,
+    from: u64,
+    to: u64,
+    reviewer: NonZeroU64,
+) {
+    // Keeps the direction of the link the same
+    const UPDATE: &str = "
+        UPDATE linked_words SET
+            first_word_id = CASE first_word_id WHEN ?2 THEN ?3 ELSE first_word_id END,
+            second_word_id = CASE second_word_id WHEN ?2 THEN ?3 ELSE second_word_id END
+        WHERE link_id = ?1;
+    ";
+
+    for link in linked_words {
+        let old = ChangeSnapshot::fetch_linked_word(db, link.link_id);
+
+        db.get()
+            .unwrap()
+            .prepare(UPDATE)
+            .unwrap()
+            .execute(params![link.link_id, from, to])
+            .unwrap();
+        senses::assign_linked_word(db, link.link_id, from, None);
+
+        let new = ChangeSnapshot::fetch_linked_word(db, link.link_id);
+        WordChange::record(db, reviewer, reviewer, old, new);
+    }
+}
+
+/// Links the two words and records the change
+pub fn link_words(
+    db: &impl ModeratorAccessDb,
+    link_type: WordLinkType,
+    first: u64,
+    second: u64,
+    reviewer: NonZeroU64,
+) {
+    const INSERT: &str = "
+        INSERT INTO linked_words (link_type, first_word_id, second_word_id) VALUES (?1, ?2, ?3)
+            RETURNING link_id;
+    ";
+
+    let link_id: i64 = db
+        .get()
+        .unwrap()
+        .prepare(INSERT)
+        .unwrap()
+        .query_row(params![link_type, first, second], |row| row.get("link_id"))
+        .unwrap();
+
+    let new = ChangeSnapshot::fetch_linked_word(db, link_id as u64);
+    WordChange::record(db, reviewer, reviewer, None, new);
+}
@@ -1,24 +1,35 @@
 use crate::auth::FullUser;
+use crate::database::conflicts;
+use crate::database::outcomes::SuggestionType;
+use crate::database::queue;
 use crate::database::suggestion::{
-    DatasetAttributionSuggestion, SuggestedExample, SuggestedLinkedWord, SuggestedWord,
+    DatasetAttributionSuggestion, LabelSuggestion, SenseRefId, SuggestedExample,
+    SuggestedLinkedWord, SuggestedSense, SuggestedWord,
 };
 use crate::database::WordId;
 use crate::database::WordOrSuggestionId;
 use crate::i18n::{FromWithI18n, I18nInfo};
 use crate::search::{TantivyClient, WordDocument};
 use crate::serialization::{deserialize_checkbox, false_fn};
-use crate::spawn_blocking_child;
+use crate::{find_duplicates, spawn_blocking_child};
 use futures::executor::block_on;
 use isixhosa::noun::NounClass;
+use isixhosa_common::conjugation::VerbStem;
 use isixhosa_common::database::UserAccessDb;
 use isixhosa_common::format::DisplayHtml;
-use isixhosa_common::language::{ConjunctionFollowedBy, PartOfSpeech, Transitivity, WordLinkType};
-use isixhosa_common::types::{Dataset, ExistingExample, ExistingLinkedWord, ExistingWord, WordHit};
+use isixhosa_common::language::{
+    ConjunctionFollowedBy, EtymologyLanguage, GrammarWarning, NounClassExt, PartOfSpeech,
+    Transitivity, WordLinkType,
+};
+use isixhosa_common::noun_forms::has_class_prefix;
+use isixhosa_common::types::{
+    Dataset, ExistingExample, ExistingLinkedWord, ExistingSense, ExistingWord, Label, WordHit,
+};
 use rusqlite::types::{ToSqlOutput, Value};
 use rusqlite::{params, ToSql};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_with::{serde_as, NoneAsEmptyString};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::iter;
 use std::num::NonZeroU64;
 use std::sync::Arc;
@@ -55,12 +66,63 @@ where
     }
 }
 
+/// Checks the grammatical information of a word for likely mistakes: a noun prefix which does not
+/// match the noun class, a plural flag which does not match the class, and an infinitive which is
+/// malformed or not formed from the verb.
+pub fn grammar_warnings(
+    xhosa: &str,
+    part_of_speech: Option<PartOfSpeech>,
+    noun_class: Option<NounClass>,
+    infinitive: &str,
+    is_plural: bool,
+) -> Vec<GrammarWarning> {
+    let mut warnings = Vec::new();
+
+    match (part_of_speech, noun_class) {
+        (Some(PartOfSpeech::Noun), Some(class)) => {
+            if !has_class_prefix(xhosa, class) {
+                warnings.push(GrammarWarning::NounPrefixMismatch);
+            }
+
+            let plural_class = !class.to_prefixes().selected_singular;
+            if is_plural != plural_class {
+                warnings.push(GrammarWarning::PluralMismatch);
+            }
+        }
+        (Some(PartOfSpeech::Verb), _) if !infinitive.trim().is_empty() => {
+            match VerbStem::from_infinitive(infinitive) {
+                None => warnings.push(GrammarWarning::InvalidInfinitive),
+                Some(stem) => {
+                    // Verbs are usually written as their stem, e.g. -bona or (i)-azi, but may be
+                    // written as the infinitive itself
+                    let headword = xhosa
+                        .trim()
+                        .to_lowercase()
+                        .replace("(i)", "")
+                        .replace('-', "");
+                    let headword_stem = VerbStem::from_infinitive(&headword);
+                    let matches = headword == stem.stem()
+                        || headword_stem.as_ref().map(VerbStem::stem) == Some(stem.stem());
+
+                    if !headword.contains(' ') && !matches {
+                        warnings.push(GrammarWarning::InfinitiveMismatch);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    warnings
+}
+
+/// Returns the id of the word deletion suggestion
 #[instrument(level = "trace", name = "Suggest word deletion", skip(db))]
 pub async fn suggest_word_deletion(
     suggesting_user: &FullUser,
     word_id: WordId,
     db: &impl UserAccessDb,
-) {
+) -> u64 {
     const STATEMENT: &str =
         "INSERT INTO word_deletion_suggestions (word_id, reason, suggesting_user) VALUES (?1, ?2, ?3);";
 
@@ -71,13 +133,15 @@ pub async fn suggest_word_deletion(
         let conn = db.get().unwrap();
         conn.prepare(STATEMENT)
             .unwrap()
-            .execute(params![word_id.0, "No reason given", user_id])
-            .unwrap();
+            .insert(params![word_id.0, "No reason given", user_id])
+            .unwrap() as u64
     })
     .await
     .unwrap()
 }
 
+/// Returns the suggestions which were added or edited by the submission, in the order that they
+/// were made.
 #[instrument(
     name = "Process word submission",
     fields(suggestion_id, changes),
@@ -89,14 +153,17 @@ pub async fn submit_suggestion(
     suggesting_user: &FullUser,
     db: &impl UserAccessDb,
     i18n_info: I18nInfo,
-) {
+) -> Vec<(SuggestionType, u64)> {
     // Intentionally suggesting_user is not set to excluded
     const INSERT_SUGGESTION: &str = "
         INSERT INTO word_suggestions (
             suggestion_id, suggesting_user, existing_word_id, changes_summary, english, xhosa,
             part_of_speech, xhosa_tone_markings, infinitive, is_plural, is_inchoative, is_informal,
-            transitivity, followed_by, noun_class, note
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+            transitivity, followed_by, noun_class, note, etymology_language, etymology_form,
+            etymology_note, literal_translation
+        ) VALUES (
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20
+        )
             ON CONFLICT(suggestion_id) DO UPDATE SET
                 existing_word_id = excluded.existing_word_id,
                 changes_summary = excluded.changes_summary,
@@ -111,7 +178,11 @@ pub async fn submit_suggestion(
                 transitivity = excluded.transitivity,
                 followed_by = excluded.followed_by,
                 noun_class = excluded.noun_class,
-                note = excluded.note
+                note = excluded.note,
+                etymology_language = excluded.etymology_language,
+                etymology_form = excluded.etymology_form,
+                etymology_note = excluded.etymology_note,
+                literal_translation = excluded.literal_translation
             RETURNING suggestion_id;
         ";
 
@@ -125,6 +196,7 @@ pub async fn submit_suggestion(
 
     spawn_blocking_child(move || {
         let conn = db.get().unwrap();
+        let mut submitted = Vec::new();
 
         let orig = WordFormTemplate::fetch_from_db(&db, &i18n_info, w.existing_id, None)
             .unwrap_or_default();
@@ -160,7 +232,23 @@ pub async fn submit_suggestion(
             diff_with_sentinel(w.transitivity, orig.transitivity),
             diff(w.followed_by.clone(), &orig.followed_by, use_submitted),
             diff_with_sentinel(w.noun_class, orig.noun_class),
-            diff(w.note.clone(), &orig.note, use_submitted)
+            diff(w.note.clone(), &orig.note, use_submitted),
+            diff_with_sentinel(w.etymology_language, orig.etymology_language),
+            diff(
+                w.etymology_form.clone(),
+                &orig.etymology_form,
+                use_submitted
+            ),
+            diff(
+                w.etymology_note.clone(),
+                &orig.etymology_note,
+                use_submitted
+            ),
+            diff(
+                w.literal_translation.clone(),
+                &orig.literal_translation,
+                use_submitted
+            ),
         ];
 
         let orig_suggestion =
@@ -178,6 +266,12 @@ pub async fn submit_suggestion(
                 .unwrap()
                 .query_row(params, |row| row.get("suggestion_id"))
                 .unwrap();
+
+            if let Some(word_id) = w.existing_id {
+                conflicts::record_base(&db, suggested_word_id as u64, word_id);
+            }
+
+            submitted.push((SuggestionType::Word, suggested_word_id as u64));
             Some(suggested_word_id)
         } else {
             w.suggestion_id.map(|id| id as i64)
@@ -189,6 +283,32 @@ pub async fn submit_suggestion(
 
         let suggested_word_id_if_new = suggested_word_id.filter(|_| w.existing_id.is_none());
 
+        // Senses are removed from the submission as they are processed
+        let sense_labels: Vec<(String, Vec<u64>)> = w
+            .senses
+            .iter()
+            .map(|s| (s.key.clone(), s.labels.clone()))
+            .collect();
+
+        let senses = process_senses(
+            &mut w,
+            &db,
+            suggesting_user,
+            suggested_word_id_if_new,
+            &changes_summary,
+            &mut submitted,
+        );
+        let labels_changed = process_labels(
+            &w,
+            &db,
+            &senses,
+            &sense_labels,
+            suggesting_user,
+            suggested_word_id_if_new,
+            &changes_summary,
+            &mut submitted,
+        );
+
         // Don't need to index non-new word suggestions
         if let Some(suggested_word_id) = suggested_word_id_if_new {
             let doc = WordDocument {
@@ -202,12 +322,30 @@ pub async fn submit_suggestion(
                 suggesting_user: Some(suggesting_user),
                 noun_class: w.noun_class,
                 is_informal: w.is_informal,
-            };
+                senses: Vec::new(),
+                labels: Vec::new(),
+                rank_weight: 1.0,
+            }
+            .with_senses_and_labels_from_db(&db);
 
             if orig_suggestion.is_none() {
-                block_on(async move { tantivy.add_new_word(doc).await });
-            } else if matches!(orig_suggestion, Some(o) if w.has_any_changes_in_word(&o)) {
-                block_on(async move { tantivy.edit_word(doc).await });
+                block_on(tantivy.add_new_word(doc));
+            } else if matches!(
+                orig_suggestion,
+                Some(o) if labels_changed
+                    || w.has_any_changes_in_word(&o)
+                    || o.sense_glosses() != doc.senses
+            ) {
+                block_on(tantivy.edit_word(doc));
+            }
+
+            // Recorded now so that the moderation queue can be filtered by it without searching
+            if any_changes {
+                let id = suggested_word_id as u64;
+                if let Some(word) = SuggestedWord::fetch_alone(&db, id) {
+                    let found = block_on(find_duplicates(&tantivy, i18n_info.clone(), &word));
+                    queue::record_duplicates(&db, id, &found);
+                }
             }
         }
 
@@ -215,16 +353,20 @@ pub async fn submit_suggestion(
             &mut w,
             &db,
             &i18n_info,
+            &senses,
             suggesting_user,
             suggested_word_id_if_new,
             &changes_summary,
+            &mut submitted,
         );
         process_examples(
             &mut w,
             &db,
+            &senses,
             suggesting_user,
             suggested_word_id_if_new,
             &changes_summary,
+            &mut submitted,
         );
         process_datasets(
             &mut w,
@@ -232,10 +374,236 @@ pub async fn submit_suggestion(
             suggesting_user,
             suggested_word_id_if_new,
             &changes_summary,
-        )
+            &mut submitted,
+        );
+
+        submitted
     })
     .await
-    .unwrap();
+    .unwrap()
+}
+
+/// Returns the senses which the keys used in the form refer to, so that examples and linked words
+/// can be assigned to them.
+#[instrument(
+    name = "Process sense submissions",
+    fields(
+        suggested_word_id = suggested_word_id_if_new,
+        existing_word_id = w.existing_id,
+        added,
+        edited,
+        deleted,
+        skipped,
+    ),
+    skip_all
+)]
+fn process_senses(
+    w: &mut WordSubmission,
+    db: &impl UserAccessDb,
+    suggesting_user: NonZeroU64,
+    suggested_word_id_if_new: Option<i64>,
+    changes_summary: &str,
+    submitted: &mut Vec<(SuggestionType, u64)>,
+) -> HashMap<String, SenseRefId> {
+    const INSERT_SENSE_SUGGESTION: &str = "
+        INSERT INTO sense_suggestions (
+            suggestion_id, suggesting_user, existing_sense_id, changes_summary, suggested_word_id,
+            existing_word_id, is_delete, sense_order, english, is_informal
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, FALSE, ?7, ?8, ?9)
+            ON CONFLICT(suggestion_id) DO UPDATE SET
+                changes_summary = excluded.changes_summary,
+                suggested_word_id = excluded.suggested_word_id,
+                existing_word_id = excluded.existing_word_id,
+                sense_order = excluded.sense_order,
+                english = excluded.english,
+                is_informal = excluded.is_informal
+            RETURNING suggestion_id;
+        ";
+
+    const DELETE_SENSE_SUGGESTION: &str = "DELETE FROM sense_suggestions WHERE suggestion_id = ?1;";
+
+    const SUGGEST_SENSE_DELETION: &str = "
+            INSERT INTO sense_suggestions (
+                suggesting_user, existing_sense_id, changes_summary, existing_word_id, is_delete
+            ) VALUES (?1, ?2, ?3, ?4, TRUE);
+        ";
+
+    let conn = db.get().unwrap();
+    let mut upsert_sense = conn.prepare(INSERT_SENSE_SUGGESTION).unwrap();
+    let mut delete_suggested_sense = conn.prepare(DELETE_SENSE_SUGGESTION).unwrap();
+    let mut suggest_sense_deletion = conn.prepare(SUGGEST_SENSE_DELETION).unwrap();
+
+    let use_submitted = w.existing_id.is_none() && w.suggestion_id.is_none();
+    let existing_id = w.existing_id;
+    let senses = &mut w.senses;
+
+    for sense in senses.iter_mut() {
+        sense.english = sense.english.trim().to_owned();
+    }
+
+    // A sense with its gloss cleared is treated as removed
+    senses.retain(|sense| !sense.english.is_empty());
+    senses.sort_by_key(|sense| sense.order);
+
+    for (i, sense) in senses.iter_mut().enumerate() {
+        sense.order = i as u32 + 1;
+    }
+
+    let mut keys = HashMap::new();
+    let [mut deleted, mut edited, mut skipped] = [0u32; 3];
+
+    let mut upsert = |new: &SenseSubmission, old: Option<&ExistingSense>| -> u64 {
+        upsert_sense
+            .query_row(
+                params![
+                    new.suggestion_id,
+                    suggesting_user.get(),
+                    new.existing_id,
+                    changes_summary,
+                    suggested_word_id_if_new,
+                    existing_id,
+                    diff_opt(new.order, &old.map(|o| o.sense_order), use_submitted),
+                    diff_opt(
+                        new.english.clone(),
+                        &old.map(|o| o.english.clone()),
+                        use_submitted
+                    ),
+                    diff_opt(new.is_informal, &old.map(|o| o.is_informal), use_submitted),
+                ],
+                |row| row.get("suggestion_id"),
+            )
+            .unwrap()
+    };
+
+    match (w.suggestion_id, w.existing_id) {
+        (Some(suggested), None) => {
+            for prev in SuggestedSense::fetch_all_for_suggestion(db, suggested) {
+                if let Some(i) = senses
+                    .iter()
+                    .position(|new| new.suggestion_id == Some(prev.suggestion_id))
+                {
+                    let new = senses.remove(i);
+                    edited += 1;
+                    submitted.push((SuggestionType::Sense, upsert(&new, None)));
+                    keys.insert(new.key, SenseRefId::Suggested(prev.suggestion_id));
+                } else {
+                    deleted += 1;
+                    delete_suggested_sense
+                        .execute(params![prev.suggestion_id])
+                        .unwrap();
+                }
+            }
+        }
+        (_, Some(existing)) => {
+            for prev in ExistingSense::fetch_all_for_word(db, existing) {
+                if let Some(i) = senses
+                    .iter()
+                    .position(|new| new.existing_id == Some(prev.sense_id))
+                {
+                    let new = senses.remove(i);
+
+                    if new.has_any_changes(&prev) {
+                        edited += 1;
+                        submitted.push((SuggestionType::Sense, upsert(&new, Some(&prev))));
+                    } else {
+                        skipped += 1;
+                    }
+
+                    keys.insert(new.key, SenseRefId::Existing(prev.sense_id));
+                } else {
+                    deleted += 1;
+                    let id = suggest_sense_deletion
+                        .insert(params![
+                            suggesting_user.get(),
+                            prev.sense_id,
+                            changes_summary,
+                            existing
+                        ])
+                        .unwrap();
+                    submitted.push((SuggestionType::Sense, id as u64));
+                }
+            }
+        }
+        (None, None) => {}
+    }
+
+    // Newly added senses
+    for new in senses.iter() {
+        let id = upsert(new, None);
+        submitted.push((SuggestionType::Sense, id));
+        keys.insert(new.key.clone(), SenseRefId::Suggested(id));
+    }
+
+    let span = Span::current();
+    span.record("added", senses.len());
+    span.record("edited", edited);
+    span.record("deleted", deleted);
+    span.record("skipped", skipped);
+
+    keys
+}
+
+/// Suggests the sense which an example belongs to. `None` means that it belongs to no sense.
+fn suggest_example_sense(
+    db: &impl UserAccessDb,
+    example_suggestion_id: u64,
+    sense: Option<SenseRefId>,
+) {
+    const UPSERT: &str = "
+        INSERT INTO example_sense_suggestions (
+            example_suggestion_id, existing_sense_id, sense_suggestion_id
+        ) VALUES (?1, ?2, ?3)
+            ON CONFLICT(example_suggestion_id) DO UPDATE SET
+                existing_sense_id = excluded.existing_sense_id,
+                sense_suggestion_id = excluded.sense_suggestion_id;
+    ";
+
+    let (existing, suggested) = sense_columns(sense);
+    db.get()
+        .unwrap()
+        .prepare(UPSERT)
+        .unwrap()
+        .execute(params![example_suggestion_id, existing, suggested])
+        .unwrap();
+}
+
+/// Suggests the sense of `word_id` which a linked word belongs to. `None` means that it belongs to
+/// no sense of that word.
+fn suggest_linked_word_sense(
+    db: &impl UserAccessDb,
+    link_suggestion_id: u64,
+    word_id: Option<u64>,
+    sense: Option<SenseRefId>,
+) {
+    const UPSERT: &str = "
+        INSERT INTO linked_word_sense_suggestions (
+            link_suggestion_id, existing_word_id, existing_sense_id, sense_suggestion_id
+        ) VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(link_suggestion_id) DO UPDATE SET
+                existing_word_id = excluded.existing_word_id,
+                existing_sense_id = excluded.existing_sense_id,
+                sense_suggestion_id = excluded.sense_suggestion_id;
+    ";
+
+    let (existing, suggested) = sense_columns(sense);
+    db.get()
+        .unwrap()
+        .prepare(UPSERT)
+        .unwrap()
+        .execute(params![link_suggestion_id, word_id, existing, suggested])
+        .unwrap();
+}
+
+fn sense_columns(sense: Option<SenseRefId>) -> (Option<u64>, Option<u64>) {
+    match sense {
+        Some(SenseRefId::Existing(id)) => (Some(id), None),
+        Some(SenseRefId::Suggested(id)) => (None, Some(id)),
+        None => (None, None),
+    }
+}
+
+fn resolve_sense(key: &Option<String>, senses: &HashMap<String, SenseRefId>) -> Option<SenseRefId> {
+    key.as_ref().and_then(|key| senses.get(key)).copied()
 }
 
 #[instrument(
@@ -250,13 +618,16 @@ pub async fn submit_suggestion(
     ),
     skip_all
 )]
+#[allow(clippy::too_many_arguments)]
 fn process_linked_words(
     w: &mut WordSubmission,
     db: &impl UserAccessDb,
     i18n_info: &I18nInfo,
+    senses: &HashMap<String, SenseRefId>,
     suggesting_user: NonZeroU64,
     suggested_word_id_if_new: Option<i64>,
     changes_summary: &str,
+    submitted: &mut Vec<(SuggestionType, u64)>,
 ) {
     const INSERT_LINKED_WORD_SUGGESTION: &str = "
         INSERT INTO linked_word_suggestions (
@@ -270,7 +641,8 @@ fn process_linked_words(
                 second_suggested_word_id = excluded.second_suggested_word_id,
                 link_type = excluded.link_type,
                 first_existing_word_id = excluded.first_existing_word_id,
-                second_existing_word_id = excluded.second_existing_word_id;
+                second_existing_word_id = excluded.second_existing_word_id
+            RETURNING suggestion_id;
         ";
 
     const DELETE_LINKED_WORD_SUGGESTION: &str =
@@ -293,10 +665,25 @@ fn process_linked_words(
 
     let [mut deleted, mut edited, mut skipped] = [0u32; 3];
 
+    let this_part_of_speech = w.part_of_speech;
+    let mut valid_links = Vec::new();
+
+    // Returns the id of the suggestion if the link was suggested
     let mut maybe_insert_link = |new: LinkedWordSubmission, old: Option<ExistingLinkedWord>| {
-        if !new.has_any_changes(&old) {
+        if !new.is_valid(db, this_part_of_speech, &mut valid_links) {
+            skipped += 1;
+            return None;
+        }
+
+        let sense = resolve_sense(&new.sense, senses);
+        let old_sense = old.as_ref().and_then(|old| {
+            ExistingSense::fetch_id_for_linked_word(db, old.link_id, existing_word_id?)
+                .map(SenseRefId::Existing)
+        });
+
+        if !new.has_any_changes(&old) && sense == old_sense {
             skipped += 1;
-            return;
+            return None;
         } else {
             edited += 1;
         }
@@ -330,28 +717,36 @@ fn process_linked_words(
             }
         };
 
+        // The submitted link type is relative to this word, but is stored relative to the first
+        let link_type = match (&old, existing_word_id) {
+            (Some(old), Some(this_id)) if this_id != old.first_word_id => new.link_type.inverse(),
+            _ => new.link_type,
+        };
+
         let first_existing = first.and_then(WordOrSuggestionId::into_existing);
         let first_suggested = first.and_then(WordOrSuggestionId::into_suggested);
         let second_existing = second.and_then(WordOrSuggestionId::into_existing);
         let second_suggested = second.and_then(WordOrSuggestionId::into_suggested);
 
-        upsert_suggested_link
-            .execute(params![
-                new.suggestion_id,
-                suggesting_user.get(),
-                new.existing_id,
-                changes_summary,
-                first_suggested,
-                second_suggested,
-                diff_opt(
-                    new.link_type,
-                    &old.as_ref().map(|o| o.link_type),
-                    use_submitted
-                ),
-                first_existing,
-                second_existing,
-            ])
+        let suggestion_id: u64 = upsert_suggested_link
+            .query_row(
+                params![
+                    new.suggestion_id,
+                    suggesting_user.get(),
+                    new.existing_id,
+                    changes_summary,
+                    first_suggested,
+                    second_suggested,
+                    diff_opt(link_type, &old.as_ref().map(|o| o.link_type), use_submitted),
+                    first_existing,
+                    second_existing,
+                ],
+                |row| row.get("suggestion_id"),
+            )
             .unwrap();
+
+        suggest_linked_word_sense(db, suggestion_id, existing_word_id, sense);
+        Some(suggestion_id)
     };
 
     let linked_words = &mut w.linked_words.0;
@@ -373,7 +768,8 @@ fn process_linked_words(
                             existing_word_id.unwrap(),
                         )
                     });
-                    maybe_insert_link(new, old);
+                    let id = maybe_insert_link(new, old);
+                    submitted.extend(id.map(|id| (SuggestionType::LinkedWord, id)));
                 } else {
                     deleted += 1;
                     delete_suggested_link
@@ -390,16 +786,18 @@ fn process_linked_words(
                     .position(|new| new.existing_id == Some(prev.link_id))
                 {
                     let new = linked_words.remove(i);
-                    maybe_insert_link(new, Some(prev));
+                    let id = maybe_insert_link(new, Some(prev));
+                    submitted.extend(id.map(|id| (SuggestionType::LinkedWord, id)));
                 } else {
                     deleted += 1;
-                    suggest_link_deletion
-                        .execute(params![
+                    let id = suggest_link_deletion
+                        .insert(params![
                             prev.link_id,
                             w.changes_summary,
                             suggesting_user.get()
                         ])
                         .unwrap();
+                    submitted.push((SuggestionType::LinkedWordDeletion, id as u64));
                 }
             }
         }
@@ -408,23 +806,36 @@ fn process_linked_words(
     }
 
     // Newly added linked words
+    w.linked_words
+        .0
+        .retain(|new| new.is_valid(db, this_part_of_speech, &mut valid_links));
+
     for new in &w.linked_words.0 {
         let other_existing = new.other.into_existing();
         let other_suggested = new.other.into_suggested();
 
-        upsert_suggested_link
-            .execute(params![
-                new.suggestion_id,
-                suggesting_user.get(),
-                new.existing_id,
-                changes_summary,
-                suggested_word_id_if_new,
-                other_suggested,
-                new.link_type,
-                w.existing_id,
-                other_existing,
-            ])
+        let suggestion_id: u64 = upsert_suggested_link
+            .query_row(
+                params![
+                    new.suggestion_id,
+                    suggesting_user.get(),
+                    new.existing_id,
+                    changes_summary,
+                    suggested_word_id_if_new,
+                    other_suggested,
+                    new.link_type,
+                    w.existing_id,
+                    other_existing,
+                ],
+                |row| row.get("suggestion_id"),
+            )
             .unwrap();
+        submitted.push((SuggestionType::LinkedWord, suggestion_id));
+
+        let sense = resolve_sense(&new.sense, senses);
+        if sense.is_some() {
+            suggest_linked_word_sense(db, suggestion_id, w.existing_id, sense);
+        }
     }
 
     let span = Span::current();
@@ -449,9 +860,11 @@ fn process_linked_words(
 fn process_examples(
     w: &mut WordSubmission,
     db: &impl UserAccessDb,
+    senses: &HashMap<String, SenseRefId>,
     suggesting_user: NonZeroU64,
     suggested_word_id_if_new: Option<i64>,
     changes_summary: &str,
+    submitted: &mut Vec<(SuggestionType, u64)>,
 ) {
     const INSERT_EXAMPLE_SUGGESTION: &str = "
         INSERT INTO example_suggestions (
@@ -463,7 +876,8 @@ fn process_examples(
                 suggested_word_id = excluded.suggested_word_id,
                 existing_word_id = excluded.existing_word_id,
                 english = excluded.english,
-                xhosa = excluded.xhosa;
+                xhosa = excluded.xhosa
+            RETURNING suggestion_id;
         ";
 
     const DELETE_EXAMPLE_SUGGESTION: &str =
@@ -485,34 +899,46 @@ fn process_examples(
 
     let [mut deleted, mut edited, mut skipped] = [0u32; 3];
 
+    // Returns the id of the suggestion if the example was suggested
     let mut maybe_insert_example = |new: ExampleSubmission, old: Option<ExistingExample>| {
-        if !new.has_any_changes(&old) {
+        let sense = resolve_sense(&new.sense, senses);
+        let old_sense = old.as_ref().and_then(|old| {
+            ExistingSense::fetch_id_for_example(db, old.example_id).map(SenseRefId::Existing)
+        });
+
+        if !new.has_any_changes(&old) && sense == old_sense {
             skipped += 1;
-            return;
+            return None;
         } else {
             edited += 1;
         }
 
-        upsert_example
-            .execute(params![
-                new.suggestion_id,
-                suggesting_user.get(),
-                new.existing_id,
-                changes_summary,
-                suggested_word_id_if_new,
-                existing_id,
-                diff_opt(
-                    new.english,
-                    &old.as_ref().map(|o| o.english.clone()),
-                    use_submitted
-                ),
-                diff_opt(
-                    new.xhosa,
-                    &old.as_ref().map(|o| o.xhosa.clone()),
-                    use_submitted
-                ),
-            ])
+        let suggestion_id: u64 = upsert_example
+            .query_row(
+                params![
+                    new.suggestion_id,
+                    suggesting_user.get(),
+                    new.existing_id,
+                    changes_summary,
+                    suggested_word_id_if_new,
+                    existing_id,
+                    diff_opt(
+                        new.english,
+                        &old.as_ref().map(|o| o.english.clone()),
+                        use_submitted
+                    ),
+                    diff_opt(
+                        new.xhosa,
+                        &old.as_ref().map(|o| o.xhosa.clone()),
+                        use_submitted
+                    ),
+                ],
+                |row| row.get("suggestion_id"),
+            )
             .unwrap();
+
+        suggest_example_sense(db, suggestion_id, sense);
+        Some(suggestion_id)
     };
 
     match (w.suggestion_id, w.existing_id) {
@@ -526,7 +952,8 @@ fn process_examples(
                     let old = new
                         .existing_id
                         .and_then(|id| ExistingExample::fetch(db, id));
-                    maybe_insert_example(new, old);
+                    let id = maybe_insert_example(new, old);
+                    submitted.extend(id.map(|id| (SuggestionType::Example, id)));
                 } else {
                     deleted += 1;
                     delete_suggested_example
@@ -544,16 +971,20 @@ fn process_examples(
                     .filter(|new| !(new.english.is_empty() && new.xhosa.is_empty()));
 
                 match new {
-                    Some(new) => maybe_insert_example(new, Some(prev)),
+                    Some(new) => {
+                        let id = maybe_insert_example(new, Some(prev));
+                        submitted.extend(id.map(|id| (SuggestionType::Example, id)));
+                    }
                     None => {
                         deleted += 1;
-                        suggest_example_deletion
-                            .execute(params![
+                        let id = suggest_example_deletion
+                            .insert(params![
                                 prev.example_id,
                                 w.changes_summary,
                                 suggesting_user.get()
                             ])
                             .unwrap();
+                        submitted.push((SuggestionType::ExampleDeletion, id as u64));
                     }
                 }
             }
@@ -569,28 +1000,30 @@ fn process_examples(
             continue;
         }
 
-        const PUNCTUATION: [char; 4] = ['.', '?', '!', '"'];
-
-        if !new.english.ends_with(&PUNCTUATION[..]) {
-            new.english.push('.');
-        }
+        punctuate(&mut new.english);
+        punctuate(&mut new.xhosa);
+
+        let suggestion_id: u64 = upsert_example
+            .query_row(
+                params![
+                    new.suggestion_id,
+                    suggesting_user.get(),
+                    new.existing_id,
+                    changes_summary,
+                    suggested_word_id_if_new,
+                    w.existing_id,
+                    new.english,
+                    new.xhosa
+                ],
+                |row| row.get("suggestion_id"),
+            )
+            .unwrap();
+        submitted.push((SuggestionType::Example, suggestion_id));
 
-        if !new.xhosa.ends_with(&PUNCTUATION[..]) {
-            new.xhosa.push('.');
+        let sense = resolve_sense(&new.sense, senses);
+        if sense.is_some() {
+            suggest_example_sense(db, suggestion_id, sense);
         }
-
-        upsert_example
-            .execute(params![
-                new.suggestion_id,
-                suggesting_user.get(),
-                new.existing_id,
-                changes_summary,
-                suggested_word_id_if_new,
-                w.existing_id,
-                new.english,
-                new.xhosa
-            ])
-            .unwrap();
     }
 
     let span = Span::current();
@@ -600,12 +1033,140 @@ fn process_examples(
     span.record("skipped", skipped);
 }
 
+/// Ends an example sentence with a full stop if it has no final punctuation
+fn punctuate(sentence: &mut String) {
+    const PUNCTUATION: [char; 4] = ['.', '?', '!', '"'];
+
+    if !sentence.ends_with(&PUNCTUATION[..]) {
+        sentence.push('.');
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ExampleSuggestionEdit {
+    pub english: String,
+    pub xhosa: String,
+    pub changes_summary: String,
+
+    // Used only in moderation page
+    #[serde(default)]
+    pub suggestion_anchor_ord: Option<u32>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LinkedWordSuggestionEdit {
+    /// Relative to the first word of the link
+    pub link_type: WordLinkType,
+    pub changes_summary: String,
+
+    // Used only in moderation page
+    #[serde(default)]
+    pub suggestion_anchor_ord: Option<u32>,
+}
+
+/// Edits a pending example suggestion in place, outside of the word it belongs to. As in a word
+/// submission, fields which are the same as the existing example are stored as unchanged. Returns
+/// `false` if the edit is invalid.
+#[instrument(
+    name = "Edit suggested example",
+    fields(suggestion_id = suggestion.suggestion_id),
+    skip_all
+)]
+pub fn edit_example_suggestion(
+    db: &impl UserAccessDb,
+    suggestion: &SuggestedExample,
+    edit: ExampleSuggestionEdit,
+) -> bool {
+    const UPDATE: &str = "
+        UPDATE example_suggestions SET english = ?1, xhosa = ?2, changes_summary = ?3
+            WHERE suggestion_id = ?4;
+    ";
+
+    let (mut english, mut xhosa) = (edit.english.trim().to_owned(), edit.xhosa.trim().to_owned());
+
+    if english.is_empty() || xhosa.is_empty() || edit.changes_summary.trim().is_empty() {
+        return false;
+    }
+
+    punctuate(&mut english);
+    punctuate(&mut xhosa);
+
+    let old = suggestion
+        .existing_example_id
+        .and_then(|id| ExistingExample::fetch(db, id));
+    let use_submitted = old.is_none();
+
+    let conn = db.get().unwrap();
+    conn.prepare(UPDATE)
+        .unwrap()
+        .execute(params![
+            diff_opt(
+                english,
+                &old.as_ref().map(|o| o.english.clone()),
+                use_submitted
+            ),
+            diff_opt(xhosa, &old.as_ref().map(|o| o.xhosa.clone()), use_submitted),
+            edit.changes_summary.trim(),
+            suggestion.suggestion_id,
+        ])
+        .unwrap();
+
+    true
+}
+
+/// Edits the link type of a pending linked word suggestion in place. The linked words themselves
+/// can't be changed, as a link to a different word is a different suggestion. Returns `false` if
+/// the link type doesn't make sense for the parts of speech of the two words.
+#[instrument(
+    name = "Edit suggested linked word",
+    fields(suggestion_id = suggestion.suggestion_id),
+    skip_all
+)]
+pub fn edit_linked_word_suggestion(
+    db: &impl UserAccessDb,
+    suggestion: &SuggestedLinkedWord,
+    edit: LinkedWordSuggestionEdit,
+) -> bool {
+    const UPDATE: &str = "
+        UPDATE linked_word_suggestions SET link_type = ?1, changes_summary = ?2
+            WHERE suggestion_id = ?3;
+    ";
+
+    let (first, second) = (
+        &suggestion.first.current().1,
+        &suggestion.second.current().1,
+    );
+
+    if !edit
+        .link_type
+        .is_valid_between(first.part_of_speech, second.part_of_speech)
+        || edit.changes_summary.trim().is_empty()
+    {
+        return false;
+    }
+
+    let old = suggestion.link_type.old().copied();
+
+    let conn = db.get().unwrap();
+    conn.prepare(UPDATE)
+        .unwrap()
+        .execute(params![
+            diff_opt(edit.link_type, &old, false),
+            edit.changes_summary.trim(),
+            suggestion.suggestion_id,
+        ])
+        .unwrap();
+
+    true
+}
+
 pub fn process_datasets(
     w: &mut WordSubmission,
     db: &impl UserAccessDb,
     suggesting_user: NonZeroU64,
     suggested_word_id_if_new: Option<i64>,
     changes_summary: &str,
+    submitted: &mut Vec<(SuggestionType, u64)>,
 ) {
     const INSERT_SUGGESTION: &str = "
         INSERT INTO dataset_attribution_suggestions
@@ -639,8 +1200,8 @@ pub fn process_datasets(
 
             // We add suggest datasets not already present in the suggestion...
             for dataset in add {
-                insert_suggestion
-                    .execute(params![
+                let id = insert_suggestion
+                    .insert(params![
                         dataset,
                         suggesting_user,
                         changes_summary,
@@ -649,6 +1210,7 @@ pub fn process_datasets(
                         false // This is _not_ a deletion
                     ])
                     .unwrap();
+                submitted.push((SuggestionType::DatasetAttribution, id as u64));
             }
 
             // ... and just delete the suggestions for those that do not appear in the updated edit
@@ -679,8 +1241,8 @@ pub fn process_datasets(
                 .zip(iter::repeat(true));
 
             for (dataset, is_delete) in remove.chain(add) {
-                insert_suggestion
-                    .execute(params![
+                let id = insert_suggestion
+                    .insert(params![
                         dataset,
                         suggesting_user,
                         changes_summary,
@@ -689,11 +1251,161 @@ pub fn process_datasets(
                         is_delete
                     ])
                     .unwrap();
+                submitted.push((SuggestionType::DatasetAttribution, id as u64));
             }
         }
     }
 }
 
+/// Suggests adding and removing labels of the word and its senses. Returns whether any
+/// suggestions were added or removed.
+#[instrument(
+    name = "Process label submissions",
+    fields(
+        suggested_word_id = suggested_word_id_if_new,
+        existing_word_id = w.existing_id,
+        added,
+        removed,
+    ),
+    skip_all
+)]
+#[allow(clippy::too_many_arguments)]
+fn process_labels(
+    w: &WordSubmission,
+    db: &impl UserAccessDb,
+    senses: &HashMap<String, SenseRefId>,
+    sense_labels: &[(String, Vec<u64>)],
+    suggesting_user: NonZeroU64,
+    suggested_word_id_if_new: Option<i64>,
+    changes_summary: &str,
+    submitted: &mut Vec<(SuggestionType, u64)>,
+) -> bool {
+    const INSERT_SUGGESTION: &str = "
+        INSERT INTO label_suggestions (
+            label_id, suggesting_user, changes_summary, existing_word_id, suggested_word_id,
+            existing_sense_id, sense_suggestion_id, is_delete
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);
+    ";
+
+    // Delete a given suggestion from the DB - not the same as _suggesting_ a deletion
+    const DELETE_SUGGESTION: &str = "DELETE FROM label_suggestions WHERE suggestion_id = ?1;";
+
+    // `None` for the word as a whole, otherwise the sense which is labelled
+    let suggested_labels: HashSet<(Option<SenseRefId>, u64)> = w
+        .labels
+        .iter()
+        .map(|label| (None, *label))
+        .chain(sense_labels.iter().flat_map(|(key, labels)| {
+            let sense = senses.get(key).copied();
+            labels
+                .iter()
+                .filter(move |_| sense.is_some())
+                .map(move |label| (sense, *label))
+        }))
+        .collect();
+
+    let conn = db.get().unwrap();
+    let mut insert_suggestion = conn.prepare(INSERT_SUGGESTION).unwrap();
+    let mut delete_suggestion = conn.prepare(DELETE_SUGGESTION).unwrap();
+
+    let mut insert = |(sense, label): &(Option<SenseRefId>, u64), is_delete: bool| {
+        let (existing_sense, suggested_sense) = match sense {
+            Some(SenseRefId::Existing(id)) => (Some(*id), None),
+            Some(SenseRefId::Suggested(id)) => (None, Some(*id)),
+            None => (None, None),
+        };
+
+        let id = insert_suggestion
+            .insert(params![
+                label,
+                suggesting_user,
+                changes_summary,
+                w.existing_id,
+                suggested_word_id_if_new,
+                existing_sense,
+                suggested_sense,
+                is_delete
+            ])
+            .unwrap();
+        submitted.push((SuggestionType::Label, id as u64));
+    };
+
+    let (added, removed) = match suggested_word_id_if_new {
+        // This is part of a suggestion for a new word
+        Some(suggested_id) => {
+            let current: HashMap<(Option<SenseRefId>, u64), u64> =
+                LabelSuggestion::fetch_all_for_suggestion(db, suggested_id as u64)
+                    .into_iter()
+                    .map(|l| ((l.sense.map(|s| s.id), l.label.id), l.suggestion_id))
+                    .collect();
+
+            // We add suggest labels not already present in the suggestion...
+            let add: Vec<_> = suggested_labels
+                .iter()
+                .filter(|key| !current.contains_key(key))
+                .collect();
+            for &key in &add {
+                insert(key, false);
+            }
+
+            // ... and just delete the suggestions for those that do not appear in the updated edit
+            let remove: Vec<u64> = current
+                .iter()
+                .filter(|(key, _)| !suggested_labels.contains(key))
+                .map(|(_, suggestion_id)| *suggestion_id)
+                .collect();
+            for suggestion_id in &remove {
+                delete_suggestion.execute(params![suggestion_id]).unwrap();
+            }
+
+            (add.len(), remove.len())
+        }
+        // This is part of an edit to an existing word
+        None => {
+            let word_id = w.existing_id.unwrap();
+            let remaining_senses: HashSet<SenseRefId> = senses.values().copied().collect();
+
+            // Labels of senses which are being deleted don't need to be removed as well
+            let current: HashSet<(Option<SenseRefId>, u64)> =
+                Label::fetch_all_for_word(db, word_id)
+                    .into_iter()
+                    .map(|l| (None, l.id))
+                    .chain(
+                        ExistingSense::fetch_all_for_word(db, word_id)
+                            .into_iter()
+                            .map(|s| (SenseRefId::Existing(s.sense_id), s.labels))
+                            .filter(|(sense, _)| remaining_senses.contains(sense))
+                            .flat_map(|(sense, labels)| {
+                                labels.into_iter().map(move |label| (Some(sense), label))
+                            }),
+                    )
+                    .collect();
+
+            // We suggest adding labels which are not on the existing word...
+            let add: Vec<_> = suggested_labels.difference(&current).collect();
+
+            // ...and suggest removing those that are not in the updated edit
+            let remove: Vec<_> = current.difference(&suggested_labels).collect();
+
+            for &key in &remove {
+                insert(key, true);
+            }
+
+            for &key in &add {
+                insert(key, false);
+            }
+
+            (add.len(), remove.len())
+        }
+    };
+
+    let span = Span::current();
+    span.record("added", added);
+    span.record("removed", removed);
+
+    added + removed > 0
+}
+
 #[derive(Default, Debug)]
 pub struct WordFormTemplate {
     pub english: String,
@@ -708,9 +1420,16 @@ pub struct WordFormTemplate {
     pub noun_class: Option<NounClass>,
     pub note: String,
     pub is_informal: bool,
+    pub etymology_language: Option<EtymologyLanguage>,
+    pub etymology_form: String,
+    pub etymology_note: String,
+    pub literal_translation: String,
+    pub senses: Vec<SenseTemplate>,
     pub examples: Vec<ExampleTemplate>,
     pub linked_words: Vec<LinkedWordTemplate>,
     pub datasets: Vec<u64>,
+    /// The labels of the word as a whole
+    pub labels: Vec<u64>,
 }
 
 impl WordFormTemplate {
@@ -725,16 +1444,22 @@ impl WordFormTemplate {
             (Some(existing), Some(suggestion)) => {
                 let suggested_word = SuggestedWord::fetch_full(db, i18n, suggestion)?;
                 let mut template = WordFormTemplate::from_with_i18n(suggested_word, i18n);
+                let senses = ExistingSense::fetch_all_for_word(db, existing);
                 template.examples.extend(
                     ExistingExample::fetch_all_for_word(db, existing)
                         .into_iter()
-                        .map(Into::into),
+                        .map(|e| ExampleTemplate::from(e).with_sense_in(&senses)),
                 );
                 template.linked_words.extend(
                     ExistingLinkedWord::fetch_all_for_word(db, existing)
                         .into_iter()
-                        .map(|l| LinkedWordTemplate::from_with_i18n(l, i18n)),
+                        .map(|l| {
+                            LinkedWordTemplate::from_with_i18n(l, i18n).with_sense_in(&senses)
+                        }),
                 );
+                template
+                    .senses
+                    .extend(senses.into_iter().map(SenseTemplate::from));
                 Some(template)
             }
             (_, Some(suggestion)) => {
@@ -748,11 +1473,29 @@ impl WordFormTemplate {
             _ => None,
         }
     }
+
+    fn sense_glosses(&self) -> Vec<String> {
+        self.senses.iter().map(|s| s.english.clone()).collect()
+    }
 }
 
 impl FromWithI18n<SuggestedWord> for WordFormTemplate {
     fn from_with_i18n(w: SuggestedWord, i18n: &I18nInfo) -> Self {
         let this_id = w.this_id();
+
+        let (word_labels, sense_labels): (Vec<_>, Vec<_>) = w
+            .labels
+            .into_iter()
+            .filter(|l| !l.is_delete)
+            .partition(|l| l.sense.is_none());
+        let labels_of = |key: &str| -> Vec<u64> {
+            sense_labels
+                .iter()
+                .filter(|l| l.sense.as_ref().map(|s| s.id.form_key()).as_deref() == Some(key))
+                .map(|l| l.label.id)
+                .collect()
+        };
+
         WordFormTemplate {
             english: w.english.current().clone(),
             xhosa: w.xhosa.current().clone(),
@@ -766,6 +1509,20 @@ impl FromWithI18n<SuggestedWord> for WordFormTemplate {
             noun_class: *w.noun_class.current(),
             note: w.note.current().clone(),
             is_informal: *w.is_informal.current(),
+            etymology_language: *w.etymology_language.current(),
+            etymology_form: w.etymology_form.current().clone(),
+            etymology_note: w.etymology_note.current().clone(),
+            literal_translation: w.literal_translation.current().clone(),
+            senses: w
+                .senses
+                .into_iter()
+                .filter(|s| !s.is_delete)
+                .map(SenseTemplate::from)
+                .map(|mut s| {
+                    s.labels = labels_of(&s.key);
+                    s
+                })
+                .collect(),
             examples: w.examples.into_iter().map(Into::into).collect(),
             linked_words: w
                 .linked_words
@@ -773,6 +1530,7 @@ impl FromWithI18n<SuggestedWord> for WordFormTemplate {
                 .map(|s| LinkedWordTemplate::from_suggested(s, this_id, i18n))
                 .collect(),
             datasets: w.datasets.into_iter().map(|d| d.dataset.id).collect(),
+            labels: word_labels.into_iter().map(|l| l.label.id).collect(),
         }
     }
 }
@@ -792,13 +1550,65 @@ impl FromWithI18n<ExistingWord> for WordFormTemplate {
             noun_class: w.noun_class,
             note: w.note,
             is_informal: w.is_informal,
-            examples: w.examples.into_iter().map(Into::into).collect(),
+            etymology_language: w.etymology_language,
+            etymology_form: w.etymology_form,
+            etymology_note: w.etymology_note,
+            literal_translation: w.literal_translation,
+            examples: w
+                .examples
+                .into_iter()
+                .map(|e| ExampleTemplate::from(e).with_sense_in(&w.senses))
+                .collect(),
             linked_words: w
                 .linked_words
                 .into_iter()
-                .map(|e| LinkedWordTemplate::from_with_i18n(e, i18n))
+                .map(|e| LinkedWordTemplate::from_with_i18n(e, i18n).with_sense_in(&w.senses))
                 .collect(),
             datasets: w.datasets.into_iter().map(|d| d.id).collect(),
+            labels: w.labels.into_iter().map(|l| l.id).collect(),
+            senses: w.senses.into_iter().map(SenseTemplate::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SenseTemplate {
+    pub suggestion_id: Option<u64>,
+    pub existing_id: Option<u64>,
+    /// The key which examples and linked words use to refer to this sense in the form
+    pub key: String,
+    pub english: String,
+    pub is_informal: bool,
+    pub labels: Vec<u64>,
+}
+
+impl From<SuggestedSense> for SenseTemplate {
+    fn from(sense: SuggestedSense) -> Self {
+        let key = match sense.existing_sense_id {
+            Some(existing) => SenseRefId::Existing(existing),
+            None => SenseRefId::Suggested(sense.suggestion_id),
+        };
+
+        SenseTemplate {
+            suggestion_id: Some(sense.suggestion_id),
+            existing_id: sense.existing_sense_id,
+            key: key.form_key(),
+            english: sense.english.current().clone(),
+            is_informal: *sense.is_informal.current(),
+            labels: Vec::new(),
+        }
+    }
+}
+
+impl From<ExistingSense> for SenseTemplate {
+    fn from(sense: ExistingSense) -> Self {
+        SenseTemplate {
+            suggestion_id: None,
+            existing_id: Some(sense.sense_id),
+            key: SenseRefId::Existing(sense.sense_id).form_key(),
+            english: sense.english,
+            is_informal: sense.is_informal,
+            labels: sense.labels,
         }
     }
 }
@@ -809,6 +1619,20 @@ pub struct ExampleTemplate {
     pub existing_id: Option<u64>,
     pub english: String,
     pub xhosa: String,
+    /// The key of the sense which this example belongs to
+    pub sense: Option<String>,
+}
+
+impl ExampleTemplate {
+    fn with_sense_in(mut self, senses: &[ExistingSense]) -> Self {
+        self.sense = self.existing_id.and_then(|id| {
+            senses
+                .iter()
+                .find(|s| s.examples.contains(&id))
+                .map(|s| SenseRefId::Existing(s.sense_id).form_key())
+        });
+        self
+    }
 }
 
 impl From<SuggestedExample> for ExampleTemplate {
@@ -818,6 +1642,7 @@ impl From<SuggestedExample> for ExampleTemplate {
             existing_id: ex.existing_example_id,
             english: ex.english.current().clone(),
             xhosa: ex.xhosa.current().clone(),
+            sense: ex.sense.current().as_ref().map(|s| s.id.form_key()),
         }
     }
 }
@@ -829,6 +1654,7 @@ impl From<ExistingExample> for ExampleTemplate {
             existing_id: Some(ex.example_id),
             english: ex.english,
             xhosa: ex.xhosa,
+            sense: None,
         }
     }
 }
@@ -840,9 +1666,21 @@ pub struct LinkedWordTemplate {
     pub link_type: WordLinkType,
     pub other: WordHit,
     pub other_rendered_plaintext: String,
+    /// The key of the sense of this word which the link belongs to
+    pub sense: Option<String>,
 }
 
 impl LinkedWordTemplate {
+    fn with_sense_in(mut self, senses: &[ExistingSense]) -> Self {
+        self.sense = self.existing_id.and_then(|id| {
+            senses
+                .iter()
+                .find(|s| s.linked_words.contains(&id))
+                .map(|s| SenseRefId::Existing(s.sense_id).form_key())
+        });
+        self
+    }
+
     fn from_suggested(
         suggestion: SuggestedLinkedWord,
         this_id: WordOrSuggestionId,
@@ -852,9 +1690,10 @@ impl LinkedWordTemplate {
         LinkedWordTemplate {
             suggestion_id: Some(suggestion.suggestion_id),
             existing_id: suggestion.existing_linked_word_id,
-            link_type: *suggestion.link_type.current(),
+            link_type: *suggestion.link_type_for(this_id).current(),
             other_rendered_plaintext: other.to_plaintext(i18n).to_string(),
             other,
+            sense: suggestion.sense.current().as_ref().map(|s| s.id.form_key()),
         }
     }
 }
@@ -864,9 +1703,10 @@ impl FromWithI18n<ExistingLinkedWord> for LinkedWordTemplate {
         LinkedWordTemplate {
             suggestion_id: None,
             existing_id: Some(link.link_id),
-            link_type: link.link_type,
+            link_type: link.relative_link_type(),
             other_rendered_plaintext: link.other.to_plaintext(i18n).to_string(),
             other: link.other,
+            sense: None,
         }
     }
 }
@@ -877,6 +1717,7 @@ pub struct LinkedWordSubmission {
     existing_id: Option<u64>,
     link_type: WordLinkType,
     other: WordOrSuggestionId,
+    sense: Option<String>,
 }
 
 impl LinkedWordSubmission {
@@ -884,11 +1725,36 @@ impl LinkedWordSubmission {
         match o {
             Some(o) => {
                 WordOrSuggestionId::existing(o.other.id) != self.other
-                    || o.link_type != self.link_type
+                    || o.relative_link_type() != self.link_type
             }
             None => true,
         }
     }
+
+    /// Checks that the link type makes sense for the parts of speech of the two words, and that it
+    /// doesn't contradict an earlier valid link to the same word, which is then added to `valid`.
+    fn is_valid(
+        &self,
+        db: &impl UserAccessDb,
+        this_part_of_speech: Option<PartOfSpeech>,
+        valid: &mut Vec<(WordOrSuggestionId, WordLinkType)>,
+    ) -> bool {
+        let other_part_of_speech =
+            WordHit::fetch_from_db(db, self.other).and_then(|hit| hit.part_of_speech);
+
+        let is_valid = self
+            .link_type
+            .is_valid_between(this_part_of_speech, other_part_of_speech)
+            && !valid
+                .iter()
+                .any(|(other, ty)| *other == self.other && self.link_type.contradicts(*ty));
+
+        if is_valid {
+            valid.push((self.other, self.link_type));
+        }
+
+        is_valid
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -905,6 +1771,8 @@ impl<'de> Deserialize<'de> for LinkedWordList {
             existing_id: Option<String>,
             link_type: String,
             other: String,
+            #[serde(default)]
+            sense: Option<String>,
         }
 
         #[derive(Deserialize, Debug)]
@@ -939,6 +1807,7 @@ impl<'de> Deserialize<'de> for LinkedWordList {
                         existing_id,
                         link_type,
                         other,
+                        sense: raw.sense.filter(|key| !key.is_empty()),
                     })
                 })
                 .collect(),
@@ -979,15 +1848,40 @@ pub struct WordSubmission {
     #[serde(deserialize_with = "deserialize_checkbox")]
     pub is_informal: bool,
 
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    pub etymology_language: Option<EtymologyLanguage>,
+    #[serde(default)]
+    etymology_form: String,
+    #[serde(default)]
+    etymology_note: String,
+
+    #[serde(default)]
+    literal_translation: String,
+
+    #[serde(default)]
+    senses: Vec<SenseSubmission>,
     #[serde(default)]
     examples: Vec<ExampleSubmission>,
     #[serde(default)]
     linked_words: LinkedWordList,
     #[serde(default)]
     datasets: Vec<u64>,
+    #[serde(default)]
+    labels: Vec<u64>,
 }
 
 impl WordSubmission {
+    pub fn grammar_warnings(&self) -> Vec<GrammarWarning> {
+        grammar_warnings(
+            &self.xhosa,
+            self.part_of_speech,
+            self.noun_class,
+            &self.infinitive,
+            self.is_plural,
+        )
+    }
+
     fn has_any_changes_in_word(&self, o: &WordFormTemplate) -> bool {
         self.english != o.english
             || self.xhosa != o.xhosa
@@ -1000,15 +1894,46 @@ impl WordSubmission {
             || self.transitivity != o.transitivity
             || self.noun_class != o.noun_class
             || self.part_of_speech != o.part_of_speech
+            || self.etymology_language != o.etymology_language
+            || self.etymology_form != o.etymology_form
+            || self.etymology_note != o.etymology_note
+            || self.literal_translation != o.literal_translation
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct SenseSubmission {
+    suggestion_id: Option<u64>,
+    existing_id: Option<u64>,
+    /// The key which examples and linked words use to refer to this sense in the form
+    key: String,
+    order: u32,
+    english: String,
+    #[serde(default = "false_fn")]
+    #[serde(deserialize_with = "deserialize_checkbox")]
+    is_informal: bool,
+    #[serde(default)]
+    labels: Vec<u64>,
+}
+
+impl SenseSubmission {
+    fn has_any_changes(&self, o: &ExistingSense) -> bool {
+        o.sense_order != self.order
+            || o.english != self.english
+            || o.is_informal != self.is_informal
     }
 }
 
+#[serde_as]
 #[derive(Deserialize, Clone, Debug)]
 struct ExampleSubmission {
     suggestion_id: Option<u64>,
     existing_id: Option<u64>,
     english: String,
     xhosa: String,
+    #[serde(default)]
+    #[serde_as(as = "NoneAsEmptyString")]
+    sense: Option<String>,
 }
 
 impl ExampleSubmission {
@@ -1,5 +1,10 @@
+use crate::database::changes::{ChangeSnapshot, WordChange};
+use crate::database::revisions::{with_revisions, WordRevision};
+use crate::database::senses;
+use crate::database::submit;
 use crate::database::WordId;
 use crate::database::{add_user_attribution, WordOrSuggestionId};
+use crate::export::{DatasetAttributionRecord, LabelAttributionRecord};
 use crate::i18n::I18nInfo;
 use crate::search::{TantivyClient, WordDocument};
 use crate::DebugExt;
@@ -10,18 +15,27 @@ use isixhosa::noun::NounClass;
 use isixhosa_common::database::{ModeratorAccessDb, UserAccessDb};
 use isixhosa_common::format::{DisplayHtml, HtmlFormatter, HyperlinkWrapper, NounClassInHit};
 use isixhosa_common::i18n::TranslationKey;
-use isixhosa_common::language::{ConjunctionFollowedBy, PartOfSpeech, Transitivity, WordLinkType};
+use isixhosa_common::language::{
+    ConjunctionFollowedBy, EtymologyLanguage, GrammarWarning, PartOfSpeech, Transitivity,
+    WordLinkType,
+};
 use isixhosa_common::serialization::WithDeleteSentinel;
-use isixhosa_common::types::{Dataset, ExistingExample, ExistingWord, PublicUserInfo, WordHit};
+use isixhosa_common::types::{
+    Dataset, ExistingExample, ExistingSense, ExistingWord, Label, PublicUserInfo, WordHit,
+};
 use num_enum::TryFromPrimitive;
 use rusqlite::types::FromSql;
 use rusqlite::{params, OptionalExtension, Params, Row};
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug};
+use std::num::NonZeroU64;
 use std::sync::Arc;
 use tracing::{instrument, Span};
 
+/// Filters dataset attribution and label suggestions to those with ids in the JSON array `?1`
+const WITH_IDS: &str = "WHERE suggestion_id IN (SELECT value FROM json_each(?1))";
+
 #[derive(Clone, Debug)]
 pub struct SuggestedWord {
     pub suggestion_id: u64,
@@ -46,12 +60,30 @@ pub struct SuggestedWord {
 
     pub is_informal: MaybeEdited<bool>,
 
+    pub etymology_language: MaybeEdited<Option<EtymologyLanguage>>,
+    pub etymology_form: MaybeEdited<String>,
+    pub etymology_note: MaybeEdited<String>,
+
+    pub literal_translation: MaybeEdited<String>,
+
+    pub senses: Vec<SuggestedSense>,
     pub examples: Vec<SuggestedExample>,
     pub linked_words: Vec<SuggestedLinkedWord>,
     pub datasets: Vec<DatasetAttributionSuggestion>,
+    pub labels: Vec<LabelSuggestion>,
 }
 
 impl SuggestedWord {
+    pub fn grammar_warnings(&self) -> Vec<GrammarWarning> {
+        submit::grammar_warnings(
+            self.xhosa.current(),
+            *self.part_of_speech.current(),
+            *self.noun_class.current(),
+            self.infinitive.current(),
+            *self.is_plural.current(),
+        )
+    }
+
     pub fn this_id(&self) -> WordOrSuggestionId {
         if let Some(word_id) = self.word_id {
             WordOrSuggestionId::existing(word_id)
@@ -60,37 +92,60 @@ impl SuggestedWord {
         }
     }
 
+    /// Sets the fields of the word to what they would be if the suggestion were accepted. Senses,
+    /// examples, linked words, datasets and labels are left as they are.
+    pub fn apply_fields(&self, word: &mut ExistingWord) {
+        word.english = self.english.current().clone();
+        word.xhosa = self.xhosa.current().clone();
+        word.part_of_speech = *self.part_of_speech.current();
+        word.xhosa_tone_markings = self.xhosa_tone_markings.current().clone();
+        word.infinitive = self.infinitive.current().clone();
+        word.is_plural = *self.is_plural.current();
+        word.is_inchoative = *self.is_inchoative.current();
+        word.transitivity = *self.transitivity.current();
+        word.followed_by = self.followed_by.current().clone();
+        word.noun_class = *self.noun_class.current();
+        word.note = self.note.current().clone();
+        word.is_informal = *self.is_informal.current();
+        word.etymology_language = *self.etymology_language.current();
+        word.etymology_form = self.etymology_form.current().clone();
+        word.etymology_note = self.etymology_note.current().clone();
+        word.literal_translation = self.literal_translation.current().clone();
+    }
+
+    /// Returns the suggested edits to the given existing words without senses, examples and linked
+    /// words populated. These can be fetched afterwards with [`SuggestedWord::fetch_associated`] for
+    /// the words which need them.
     #[instrument(
         level = "info",
-        name = "Fetch all suggested words",
+        name = "Fetch suggested edits to existing words",
         fields(results),
         skip(db)
     )]
-    pub fn fetch_all_full(db: &impl ModeratorAccessDb, i18n_info: &I18nInfo) -> Vec<SuggestedWord> {
+    pub fn fetch_alone_for_existing_words(
+        db: &impl ModeratorAccessDb,
+        word_ids: &[u64],
+    ) -> Vec<SuggestedWord> {
         const SELECT_SUGGESTIONS: &str = "
             SELECT
                 suggestion_id, suggesting_user, existing_word_id, changes_summary,
                 english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
-                is_inchoative, is_informal, transitivity, followed_by, noun_class, note, username, display_name
+                is_inchoative, is_informal, transitivity, followed_by, noun_class, note,
+                etymology_language, etymology_form, etymology_note, literal_translation, username,
+                display_name
             FROM word_suggestions
             INNER JOIN users ON word_suggestions.suggesting_user = users.user_id
+            WHERE existing_word_id IN (SELECT value FROM json_each(?1))
             ORDER BY suggestion_id;";
 
+        let ids = serde_json::to_string(word_ids).unwrap();
         let conn = db.get().unwrap();
 
         let mut query = conn.prepare(SELECT_SUGGESTIONS).unwrap();
-        let suggestions = query.query(params![]).unwrap();
+        let suggestions = query.query(params![ids]).unwrap();
 
         let results: Vec<_> = suggestions
-            .map(|row| {
-                let mut w = SuggestedWord::from_row_fetch_original(row, db);
-                w.examples = SuggestedExample::fetch_all_for_suggestion(db, w.suggestion_id);
-                w.linked_words =
-                    SuggestedLinkedWord::fetch_all_for_suggestion(db, i18n_info, w.suggestion_id);
-                w.datasets =
-                    DatasetAttributionSuggestion::fetch_all_for_suggestion(db, w.suggestion_id);
-                Ok(w)
-            })
+            .map(|row| Ok(SuggestedWord::from_row_fetch_original(row, db)))
             .collect()
             .unwrap();
 
@@ -99,7 +154,7 @@ impl SuggestedWord {
         results
     }
 
-    /// Returns the suggested word without examples and linked words populated.
+    /// Returns the suggested word without senses, examples and linked words populated.
     #[instrument(
         level = "trace",
         name = "Fetch just suggested word",
@@ -111,7 +166,8 @@ impl SuggestedWord {
             SELECT
                 suggestion_id, existing_word_id, changes_summary, english, xhosa, part_of_speech,
                 xhosa_tone_markings, infinitive, is_plural, is_inchoative, is_informal, transitivity,
-                followed_by, noun_class, note, username, display_name, suggesting_user
+                followed_by, noun_class, note, etymology_language, etymology_form, etymology_note,
+                literal_translation, username, display_name, suggesting_user
             FROM word_suggestions
             INNER JOIN users ON word_suggestions.suggesting_user = users.user_id
             WHERE suggestion_id = ?1;
@@ -133,7 +189,7 @@ impl SuggestedWord {
         word
     }
 
-    /// Returns the suggested word with examples and linked words populated.
+    /// Returns the suggested word with senses, examples and linked words populated.
     #[instrument(name = "Fetch full suggested word", fields(found), skip(db))]
     pub fn fetch_full(
         db: &impl UserAccessDb,
@@ -142,9 +198,7 @@ impl SuggestedWord {
     ) -> Option<SuggestedWord> {
         let mut word = SuggestedWord::fetch_alone(db, id);
         if let Some(w) = word.as_mut() {
-            w.examples = SuggestedExample::fetch_all_for_suggestion(db, id);
-            w.linked_words = SuggestedLinkedWord::fetch_all_for_suggestion(db, i18n_info, id);
-            w.datasets = DatasetAttributionSuggestion::fetch_all_for_suggestion(db, id);
+            w.fetch_associated(db, i18n_info);
         }
 
         Span::current().record("found", word.is_some());
@@ -152,6 +206,16 @@ impl SuggestedWord {
         word
     }
 
+    /// Populates the senses, examples, linked words, datasets and labels suggested with the word
+    pub fn fetch_associated(&mut self, db: &impl UserAccessDb, i18n_info: &I18nInfo) {
+        let id = self.suggestion_id;
+        self.senses = SuggestedSense::fetch_all_for_suggestion(db, id);
+        self.examples = SuggestedExample::fetch_all_for_suggestion(db, id);
+        self.linked_words = SuggestedLinkedWord::fetch_all_for_suggestion(db, i18n_info, id);
+        self.datasets = DatasetAttributionSuggestion::fetch_all_for_suggestion(db, id);
+        self.labels = LabelSuggestion::fetch_all_for_suggestion(db, id);
+    }
+
     /// Does not delete suggestion.
     #[instrument(
         name = "Accept just suggested word",
@@ -163,12 +227,17 @@ impl SuggestedWord {
         ),
         skip_all
     )]
-    pub fn accept_just_word_suggestion(&self, db: &impl ModeratorAccessDb) -> u64 {
+    pub fn accept_just_word_suggestion(
+        &self,
+        db: &impl ModeratorAccessDb,
+        reviewer: NonZeroU64,
+    ) -> u64 {
         const INSERT: &str = "
             INSERT INTO words (
                 word_id, english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
-                is_inchoative, is_informal, transitivity, followed_by, noun_class, note
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+                is_inchoative, is_informal, transitivity, followed_by, noun_class, note,
+                etymology_language, etymology_form, etymology_note, literal_translation
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
                 ON CONFLICT(word_id) DO UPDATE SET
                     english = excluded.english,
                     xhosa = excluded.xhosa,
@@ -181,10 +250,18 @@ impl SuggestedWord {
                     is_informal = excluded.is_informal,
                     transitivity = excluded.transitivity,
                     followed_by = excluded.followed_by,
-                    note = excluded.note
+                    note = excluded.note,
+                    etymology_language = excluded.etymology_language,
+                    etymology_form = excluded.etymology_form,
+                    etymology_note = excluded.etymology_note,
+                    literal_translation = excluded.literal_translation
                 RETURNING word_id;
         ";
 
+        let old = self
+            .word_id
+            .and_then(|id| ChangeSnapshot::fetch_word(db, id));
+
         let conn = db.get().unwrap();
         let params = params![
             self.word_id,
@@ -200,6 +277,10 @@ impl SuggestedWord {
             self.followed_by.current().clone().unwrap_or_default(),
             self.noun_class.current().map(|x| x as u8),
             self.note.current(),
+            self.etymology_language.current(),
+            self.etymology_form.current(),
+            self.etymology_note.current(),
+            self.literal_translation.current(),
         ];
 
         let id: i64 = conn
@@ -211,28 +292,62 @@ impl SuggestedWord {
 
         add_user_attribution(db, &self.suggesting_user, WordId(id));
 
+        let new = ChangeSnapshot::fetch_word(db, id);
+        WordChange::record(db, self.suggesting_user.id, reviewer, old, new);
+
         Span::current().record("accepted_id", id);
 
         id
     }
 
+    /// Accepts the word suggestion along with everything suggested with it, returning the id of
+    /// the accepted word.
     #[instrument(name = "Accept whole word suggestion", skip_all)]
     pub fn accept_whole_word_suggestion(
         self,
         db: &impl ModeratorAccessDb,
         tantivy: Arc<TantivyClient>,
-    ) {
+        reviewer: NonZeroU64,
+    ) -> u64 {
         let word_suggestion_id = self.suggestion_id;
-        let new_word_id = self.accept_just_word_suggestion(db);
+
+        // Existing words on the other side of the suggested links are changed too
+        let mut changed_words: Vec<u64> = self
+            .linked_words
+            .iter()
+            .flat_map(|l| [l.first.current().0, l.second.current().0])
+            .filter_map(WordOrSuggestionId::into_existing)
+            .chain(self.word_id)
+            .collect();
+        changed_words.sort_unstable();
+        changed_words.dedup();
+
+        for word_id in &changed_words {
+            WordRevision::record_baseline(db, *word_id);
+        }
+
+        let new_word_id = self.accept_just_word_suggestion(db, reviewer);
+
+        // Senses go first, so that examples and links can be assigned to them
+        for mut sense in self.senses.into_iter() {
+            sense.word_or_suggested_id = WordOrSuggestionId::existing(new_word_id);
+            sense.apply(db, reviewer);
+        }
 
         for mut example in self.examples.into_iter() {
             example.word_or_suggested_id = WordOrSuggestionId::existing(new_word_id);
-            example.accept(db);
+            example.apply(db, reviewer);
         }
 
         for mut dataset_attrib in self.datasets.into_iter() {
             dataset_attrib.word_or_suggestion_id = WordOrSuggestionId::existing(new_word_id);
-            dataset_attrib.accept(db);
+            dataset_attrib.apply(db, reviewer);
+        }
+
+        // Fetched again, as labels for suggested senses now point to the accepted senses
+        for mut label in LabelSuggestion::fetch_all_for_suggestion(db, word_suggestion_id) {
+            label.word_or_suggestion_id = WordOrSuggestionId::existing(new_word_id);
+            label.apply(db, reviewer);
         }
 
         let old = WordOrSuggestionId::suggested(self.suggestion_id);
@@ -246,12 +361,20 @@ impl SuggestedWord {
             }
 
             if l.first.current().0.is_existing() && l.second.current().0.is_existing() {
-                l.accept(db);
+                l.apply(db, reviewer);
             } else {
                 l.update_first_and_second(db);
             }
         }
 
+        if self.word_id.is_none() {
+            changed_words.push(new_word_id);
+        }
+
+        for word_id in changed_words {
+            WordRevision::record(db, word_id, self.suggesting_user.id, reviewer);
+        }
+
         let document = WordDocument {
             id: WordOrSuggestionId::existing(new_word_id),
             english: self.english.current().clone(),
@@ -263,7 +386,11 @@ impl SuggestedWord {
             suggesting_user: None,
             noun_class: *self.noun_class.current(),
             is_informal: *self.is_informal.current(),
-        };
+            senses: Vec::new(),
+            labels: Vec::new(),
+            rank_weight: 1.0,
+        }
+        .with_senses_and_labels_from_db(db);
 
         let tantivy_clone = tantivy.clone();
         SuggestedWord::delete(db, tantivy_clone, word_suggestion_id);
@@ -273,6 +400,8 @@ impl SuggestedWord {
         } else {
             block_on(async move { tantivy.edit_word(document).await });
         }
+
+        new_word_id
     }
 
     #[instrument(name = "Delete word suggestion", fields(found), skip(db, tantivy))]
@@ -337,9 +466,31 @@ impl SuggestedWord {
             ),
             note: MaybeEdited::from_row("note", row, e.map(|e| e.note.clone())),
             is_informal: MaybeEdited::from_row("is_informal", row, e.map(|e| e.is_plural)),
+            etymology_language: MaybeEdited::from_row_with_sentinel(
+                "etymology_language",
+                row,
+                e.and_then(|e| e.etymology_language),
+            ),
+            etymology_form: MaybeEdited::from_row(
+                "etymology_form",
+                row,
+                e.map(|e| e.etymology_form.clone()),
+            ),
+            etymology_note: MaybeEdited::from_row(
+                "etymology_note",
+                row,
+                e.map(|e| e.etymology_note.clone()),
+            ),
+            literal_translation: MaybeEdited::from_row(
+                "literal_translation",
+                row,
+                e.map(|e| e.literal_translation.clone()),
+            ),
+            senses: vec![],
             examples: vec![],
             linked_words: vec![],
             datasets: vec![],
+            labels: vec![],
         }
     }
 
@@ -380,16 +531,18 @@ pub struct SuggestedExample {
 
     pub english: MaybeEdited<String>,
     pub xhosa: MaybeEdited<String>,
+    pub sense: MaybeEdited<Option<SenseRef>>,
 }
 
 impl SuggestedExample {
     #[instrument(
-        name = "Fetch all suggested examples for existing words",
+        name = "Fetch suggested examples for existing words",
         fields(results),
         skip(db)
     )]
-    pub fn fetch_all_for_existing_words(
+    pub fn fetch_for_existing_words(
         db: &impl ModeratorAccessDb,
+        suggestion_ids: &[u64],
     ) -> impl Iterator<Item = (WordId, Vec<SuggestedExample>)> {
         const SELECT: &str = "
             SELECT words.word_id,
@@ -400,12 +553,15 @@ impl SuggestedExample {
                    example_suggestions.suggesting_user
             FROM example_suggestions
             INNER JOIN users ON example_suggestions.suggesting_user = users.user_id
-            INNER JOIN words ON example_suggestions.existing_word_id = words.word_id;
+            INNER JOIN words ON example_suggestions.existing_word_id = words.word_id
+            WHERE example_suggestions.suggestion_id IN (SELECT value FROM json_each(?1))
+            ORDER BY example_suggestions.suggestion_id;
         ";
 
+        let ids = serde_json::to_string(suggestion_ids).unwrap();
         let conn = db.get().unwrap();
         let mut query = conn.prepare(SELECT).unwrap();
-        let examples = query.query(params![]).unwrap();
+        let examples = query.query(params![ids]).unwrap();
 
         let mut map: HashMap<WordId, Vec<SuggestedExample>> = HashMap::new();
 
@@ -488,6 +644,18 @@ impl SuggestedExample {
         ex
     }
 
+    pub fn accept(&self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) -> i64 {
+        let word_ids: Vec<u64> = self
+            .word_or_suggested_id
+            .into_existing()
+            .into_iter()
+            .collect();
+        with_revisions(db, &word_ids, self.suggesting_user.id, reviewer, || {
+            self.apply(db, reviewer)
+        })
+    }
+
+    /// Accepts the example without recording a revision of the word it belongs to
     #[instrument(
         name = "Accept suggested example",
         fields(
@@ -497,7 +665,7 @@ impl SuggestedExample {
         ),
         skip_all,
     )]
-    pub fn accept(&self, db: &impl ModeratorAccessDb) -> i64 {
+    fn apply(&self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) -> i64 {
         const INSERT: &str = "
             INSERT INTO examples (example_id, word_id, english, xhosa) VALUES (?1, ?2, ?3, ?4)
                 ON CONFLICT(example_id) DO UPDATE SET
@@ -506,6 +674,10 @@ impl SuggestedExample {
                 RETURNING example_id;
         ";
 
+        let old = self
+            .existing_example_id
+            .and_then(|id| ChangeSnapshot::fetch_example(db, id));
+
         let conn = db.get().unwrap();
         let word = match self.word_or_suggested_id {
             WordOrSuggestionId::ExistingWord { existing_id } => existing_id,
@@ -518,13 +690,25 @@ impl SuggestedExample {
             self.xhosa.current()
         ];
 
-        let id = conn
+        let id: i64 = conn
             .prepare(INSERT)
             .unwrap()
             .query_row(params, |row| row.get("example_id"))
             .unwrap();
 
         add_user_attribution(db, &self.suggesting_user, WordId(word));
+
+        let new = ChangeSnapshot::fetch_example(db, id as u64);
+        WordChange::record(db, self.suggesting_user.id, reviewer, old, new);
+
+        senses::apply_example_suggestion(
+            db,
+            self.suggestion_id,
+            id as u64,
+            self.suggesting_user.id,
+            reviewer,
+        );
+
         SuggestedExample::delete(db, self.suggestion_id);
 
         Span::current().record("accepted_id", id);
@@ -544,22 +728,388 @@ impl SuggestedExample {
     }
 
     fn from_row_fetch_original(row: &Row<'_>, db: &impl UserAccessDb) -> Self {
+        const SELECT_SENSE: &str = "
+            SELECT existing_sense_id, sense_suggestion_id FROM example_sense_suggestions
+                WHERE example_suggestion_id = ?1;
+        ";
+
         let existing_id = row.get::<&str, Option<i64>>("existing_example_id").unwrap();
         let e = existing_id.and_then(|id| ExistingExample::fetch(db, id as u64));
         let e = e.as_ref();
 
+        let suggestion_id: u64 = row.get("suggestion_id").unwrap();
+        let suggested_sense = db
+            .get()
+            .unwrap()
+            .prepare(SELECT_SENSE)
+            .unwrap()
+            .query_row(params![suggestion_id], |row| {
+                Ok((
+                    row.get("existing_sense_id")?,
+                    row.get("sense_suggestion_id")?,
+                ))
+            })
+            .optional()
+            .unwrap();
+        let current_sense =
+            existing_id.and_then(|id| ExistingSense::fetch_id_for_example(db, id as u64));
+
         SuggestedExample {
             suggesting_user: PublicUserInfo::try_from(row).unwrap(),
             changes_summary: row.get("changes_summary").unwrap(),
-            suggestion_id: row.get("suggestion_id").unwrap(),
+            suggestion_id,
             existing_example_id: row.get("existing_example_id").unwrap(),
             word_or_suggested_id: row.try_into().unwrap(),
             english: MaybeEdited::from_row("english", row, e.map(|e| e.english.clone())),
             xhosa: MaybeEdited::from_row("xhosa", row, e.map(|e| e.xhosa.clone())),
+            sense: SenseRef::maybe_edited(db, current_sense, suggested_sense),
         }
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct SuggestedSense {
+    pub changes_summary: String,
+    pub suggesting_user: PublicUserInfo,
+
+    pub suggestion_id: u64,
+    pub existing_sense_id: Option<u64>,
+    pub word_or_suggested_id: WordOrSuggestionId,
+    pub is_delete: bool,
+
+    pub sense_order: MaybeEdited<u32>,
+    pub english: MaybeEdited<String>,
+    pub is_informal: MaybeEdited<bool>,
+}
+
+impl SuggestedSense {
+    pub fn sense_order_text(&self) -> MaybeEdited<String> {
+        self.sense_order.map(|order| order.to_string())
+    }
+
+    pub fn informal_text(&self) -> MaybeEdited<TranslationKey<'static>> {
+        text_if_bool(
+            TranslationKey::new("informal.in-word-result"),
+            TranslationKey::new("informal.non"),
+            self.is_informal,
+            false,
+        )
+    }
+
+    #[instrument(
+        name = "Fetch suggested senses for existing words",
+        fields(results),
+        skip(db)
+    )]
+    pub fn fetch_for_existing_words(
+        db: &impl ModeratorAccessDb,
+        suggestion_ids: &[u64],
+    ) -> impl Iterator<Item = (WordId, Vec<SuggestedSense>)> {
+        const SELECT: &str = "
+            SELECT words.word_id,
+                   sense_suggestions.suggestion_id, sense_suggestions.existing_word_id,
+                   sense_suggestions.suggested_word_id, sense_suggestions.existing_sense_id,
+                   sense_suggestions.changes_summary, sense_suggestions.is_delete,
+                   sense_suggestions.sense_order, sense_suggestions.english,
+                   sense_suggestions.is_informal, users.username, users.display_name,
+                   sense_suggestions.suggesting_user
+            FROM sense_suggestions
+            INNER JOIN users ON sense_suggestions.suggesting_user = users.user_id
+            INNER JOIN words ON sense_suggestions.existing_word_id = words.word_id
+            WHERE sense_suggestions.suggestion_id IN (SELECT value FROM json_each(?1))
+            ORDER BY sense_suggestions.suggestion_id;
+        ";
+
+        let ids = serde_json::to_string(suggestion_ids).unwrap();
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+        let senses = query.query(params![ids]).unwrap();
+
+        let mut map: HashMap<WordId, Vec<SuggestedSense>> = HashMap::new();
+
+        senses
+            .map(|row| {
+                Ok((
+                    WordId(row.get::<&str, u64>("word_id")?),
+                    SuggestedSense::from_row_fetch_original(row, db),
+                ))
+            })
+            .for_each(|(word_id, sense)| {
+                map.entry(word_id)
+                    .or_insert_with(|| Vec::with_capacity(1))
+                    .push(sense);
+                Ok(())
+            })
+            .unwrap();
+
+        Span::current().record("results", map.len());
+
+        map.into_iter()
+    }
+
+    #[instrument(
+        level = "trace",
+        name = "Fetch all suggested senses for suggested word",
+        fields(results),
+        skip(db)
+    )]
+    pub fn fetch_all_for_suggestion(
+        db: &impl UserAccessDb,
+        suggested_word_id: u64,
+    ) -> Vec<SuggestedSense> {
+        const SELECT: &str = "
+            SELECT
+                suggestion_id, existing_word_id, suggested_word_id, existing_sense_id,
+                changes_summary, is_delete, sense_order, english, is_informal, username,
+                display_name, suggesting_user
+            FROM sense_suggestions
+            INNER JOIN users ON sense_suggestions.suggesting_user = users.user_id
+            WHERE suggested_word_id = ?1;
+        ";
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(SELECT).unwrap();
+        let senses = query.query(params![suggested_word_id]).unwrap();
+
+        let mut senses: Vec<_> = senses
+            .map(|row| Ok(SuggestedSense::from_row_fetch_original(row, db)))
+            .collect()
+            .unwrap();
+        senses.sort_by_key(|s| (*s.sense_order.current(), s.suggestion_id));
+
+        Span::current().record("results", senses.len());
+
+        senses
+    }
+
+    #[instrument(name = "Fetch suggested sense", fields(found), skip(db))]
+    pub fn fetch(db: &impl UserAccessDb, suggestion_id: u64) -> Option<SuggestedSense> {
+        const SELECT: &str = "
+            SELECT
+                suggestion_id, existing_word_id, suggested_word_id, existing_sense_id,
+                changes_summary, is_delete, sense_order, english, is_informal, username,
+                display_name, suggesting_user
+            FROM sense_suggestions
+            INNER JOIN users ON sense_suggestions.suggesting_user = users.user_id
+            WHERE suggestion_id = ?1;
+        ";
+
+        let conn = db.get().unwrap();
+        let sense = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query_row(params![suggestion_id], |row| {
+                Ok(Self::from_row_fetch_original(row, db))
+            })
+            .optional()
+            .unwrap();
+
+        Span::current().record("found", sense.is_some());
+
+        sense
+    }
+
+    pub fn accept(&self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) -> Option<i64> {
+        let word_ids: Vec<u64> = self
+            .word_or_suggested_id
+            .into_existing()
+            .into_iter()
+            .collect();
+        with_revisions(db, &word_ids, self.suggesting_user.id, reviewer, || {
+            self.apply(db, reviewer)
+        })
+    }
+
+    /// Accepts the sense without recording a revision of the word it belongs to. Returns the id of
+    /// the sense, or `None` if it was deleted.
+    #[instrument(
+        name = "Accept suggested sense",
+        fields(
+            suggestion_id = self.suggestion_id,
+            word_id = ?self.word_or_suggested_id,
+            accepted_id,
+        ),
+        skip_all,
+    )]
+    fn apply(&self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) -> Option<i64> {
+        const INSERT: &str = "
+            INSERT INTO senses (sense_id, word_id, sense_order, english, is_informal)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT(sense_id) DO UPDATE SET
+                    sense_order = excluded.sense_order,
+                    english = excluded.english,
+                    is_informal = excluded.is_informal
+                RETURNING sense_id;
+        ";
+        const DELETE: &str = "DELETE FROM senses WHERE sense_id = ?1;";
+
+        let old = self
+            .existing_sense_id
+            .and_then(|id| ChangeSnapshot::fetch_sense(db, id));
+
+        let conn = db.get().unwrap();
+        let word = match self.word_or_suggested_id {
+            WordOrSuggestionId::ExistingWord { existing_id } => existing_id,
+            _ => panic!("No existing word for suggested sense {:#?}", self),
+        };
+
+        let id = match (self.is_delete, self.existing_sense_id) {
+            (true, Some(existing)) => {
+                conn.prepare(DELETE)
+                    .unwrap()
+                    .execute(params![existing])
+                    .unwrap();
+                None
+            }
+            _ => {
+                let params = params![
+                    self.existing_sense_id,
+                    word,
+                    self.sense_order.current(),
+                    self.english.current(),
+                    self.is_informal.current(),
+                ];
+
+                let id: i64 = conn
+                    .prepare(INSERT)
+                    .unwrap()
+                    .query_row(params, |row| row.get("sense_id"))
+                    .unwrap();
+                senses::resolve_sense_suggestion(db, self.suggestion_id, id as u64);
+                Some(id)
+            }
+        };
+
+        add_user_attribution(db, &self.suggesting_user, WordId(word));
+
+        let new = id.and_then(|id| ChangeSnapshot::fetch_sense(db, id as u64));
+        WordChange::record(db, self.suggesting_user.id, reviewer, old, new);
+
+        SuggestedSense::delete(db, self.suggestion_id);
+
+        Span::current().record("accepted_id", id);
+
+        id
+    }
+
+    #[instrument(name = "Delete suggested sense", fields(found), skip(db))]
+    pub fn delete(db: &impl ModeratorAccessDb, id: u64) -> bool {
+        const DELETE: &str = "DELETE FROM sense_suggestions WHERE suggestion_id = ?1;";
+
+        let conn = db.get().unwrap();
+        let modified_rows = conn.prepare(DELETE).unwrap().execute(params![id]).unwrap();
+        let found = modified_rows == 1;
+        Span::current().record("found", found);
+        found
+    }
+
+    fn from_row_fetch_original(row: &Row<'_>, db: &impl UserAccessDb) -> Self {
+        let existing_id = row.get::<&str, Option<i64>>("existing_sense_id").unwrap();
+        let e = existing_id.and_then(|id| ExistingSense::fetch(db, id as u64));
+        let e = e.as_ref();
+
+        SuggestedSense {
+            suggesting_user: PublicUserInfo::try_from(row).unwrap(),
+            changes_summary: row.get("changes_summary").unwrap(),
+            suggestion_id: row.get("suggestion_id").unwrap(),
+            existing_sense_id: row.get("existing_sense_id").unwrap(),
+            word_or_suggested_id: row.try_into().unwrap(),
+            is_delete: row.get("is_delete").unwrap(),
+            sense_order: MaybeEdited::from_row("sense_order", row, e.map(|e| e.sense_order)),
+            english: MaybeEdited::from_row("english", row, e.map(|e| e.english.clone())),
+            is_informal: MaybeEdited::from_row("is_informal", row, e.map(|e| e.is_informal)),
+        }
+    }
+}
+
+/// Which sense an example or linked word belongs to, as referred to in a suggestion
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SenseRefId {
+    Existing(u64),
+    Suggested(u64),
+}
+
+impl SenseRefId {
+    fn from_columns(existing: Option<u64>, suggested: Option<u64>) -> Option<SenseRefId> {
+        existing
+            .map(SenseRefId::Existing)
+            .or(suggested.map(SenseRefId::Suggested))
+    }
+
+    /// The key by which the sense is referred to in the submit form
+    pub fn form_key(&self) -> String {
+        match self {
+            SenseRefId::Existing(id) => format!("existing-{id}"),
+            SenseRefId::Suggested(id) => format!("suggested-{id}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SenseRef {
+    pub id: SenseRefId,
+    pub english: String,
+}
+
+impl PartialEq for SenseRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for SenseRef {}
+
+impl SenseRef {
+    #[instrument(level = "trace", name = "Fetch sense reference", skip(db))]
+    fn fetch(db: &impl UserAccessDb, id: SenseRefId) -> Option<SenseRef> {
+        const SELECT_SUGGESTED: &str = "
+            SELECT COALESCE(sense_suggestions.english, senses.english) AS english
+                FROM sense_suggestions
+                LEFT JOIN senses ON sense_suggestions.existing_sense_id = senses.sense_id
+                WHERE suggestion_id = ?1;
+        ";
+
+        let english = match id {
+            SenseRefId::Existing(id) => ExistingSense::fetch(db, id).map(|s| s.english),
+            SenseRefId::Suggested(id) => db
+                .get()
+                .unwrap()
+                .prepare(SELECT_SUGGESTED)
+                .unwrap()
+                .query_row(params![id], |row| row.get("english"))
+                .optional()
+                .unwrap(),
+        };
+
+        english.map(|english| SenseRef { id, english })
+    }
+
+    /// Works out the old and new sense from the sense which the item currently belongs to, and
+    /// the suggested sense (if any) read from a sense suggestion side table.
+    fn maybe_edited(
+        db: &impl UserAccessDb,
+        current: Option<u64>,
+        suggested: Option<(Option<u64>, Option<u64>)>,
+    ) -> MaybeEdited<Option<SenseRef>> {
+        let old = current.and_then(|id| SenseRef::fetch(db, SenseRefId::Existing(id)));
+        let new = suggested.map(|(existing, suggested)| {
+            SenseRefId::from_columns(existing, suggested).and_then(|id| SenseRef::fetch(db, id))
+        });
+
+        match new {
+            Some(new) if new != old && old.is_none() => MaybeEdited::New(new),
+            Some(new) if new != old => MaybeEdited::Edited { old, new },
+            _ => MaybeEdited::Old(old),
+        }
+    }
+}
+
+impl<L: Loader + 'static> DisplayHtml<L> for SenseRef {
+    fn fmt(&self, f: &mut HtmlFormatter<L>) -> fmt::Result {
+        DisplayHtml::fmt(&self.english, f)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SuggestedLinkedWord {
     pub changes_summary: String,
@@ -570,15 +1120,17 @@ pub struct SuggestedLinkedWord {
     pub first: MaybeEdited<(WordOrSuggestionId, WordHit)>,
     pub second: MaybeEdited<(WordOrSuggestionId, WordHit)>,
     pub link_type: MaybeEdited<WordLinkType>,
+    /// The sense of the word from whose page the link was suggested
+    pub sense: MaybeEdited<Option<SenseRef>>,
 }
 
 impl SuggestedLinkedWord {
-    #[instrument(name = "Fetch suggested linked word", skip(db))]
+    #[instrument(name = "Fetch suggested linked word", fields(found), skip(db))]
     pub fn fetch(
         db: &impl UserAccessDb,
         _i18n_info: I18nInfo,
         suggestion: u64,
-    ) -> SuggestedLinkedWord {
+    ) -> Option<SuggestedLinkedWord> {
         const SELECT_SUGGESTION: &str = "
             SELECT linked_word_suggestions.suggestion_id, linked_word_suggestions.link_type,
                    linked_word_suggestions.changes_summary, linked_word_suggestions.existing_linked_word_id,
@@ -600,7 +1152,11 @@ impl SuggestedLinkedWord {
             .query_row(params![suggestion], |row| {
                 Ok(SuggestedLinkedWord::from_row_populate_both(row, db))
             })
+            .optional()
             .unwrap();
+
+        Span::current().record("found", s.is_some());
+
         s
     }
 
@@ -641,12 +1197,13 @@ impl SuggestedLinkedWord {
 
     /// Each link shows up once and only once.
     #[instrument(
-        name = "Fetch all suggested linked words for existing words",
+        name = "Fetch suggested linked words for existing words",
         fields(results),
         skip_all
     )]
-    pub fn fetch_all_for_existing_words(
+    pub fn fetch_for_existing_words(
         db: &impl ModeratorAccessDb,
+        suggestion_ids: &[u64],
     ) -> impl Iterator<Item = (WordId, Vec<SuggestedLinkedWord>)> {
         const SELECT: &str = "
             SELECT words.word_id,
@@ -672,12 +1229,15 @@ impl SuggestedLinkedWord {
 
             WHERE
                 linked_word_suggestions.suggested_word_id IS NULL AND
-                linked_word_suggestions.second_suggested_word_id IS NULL;
+                linked_word_suggestions.second_suggested_word_id IS NULL AND
+                linked_word_suggestions.suggestion_id IN (SELECT value FROM json_each(?1))
+            ORDER BY linked_word_suggestions.suggestion_id;
         ";
 
+        let ids = serde_json::to_string(suggestion_ids).unwrap();
         let conn = db.get().unwrap();
         let mut query = conn.prepare(SELECT).unwrap();
-        let examples = query.query(params![]).unwrap();
+        let examples = query.query(params![ids]).unwrap();
 
         let mut map: HashMap<WordId, Vec<SuggestedLinkedWord>> = HashMap::new();
 
@@ -710,6 +1270,17 @@ impl SuggestedLinkedWord {
     }
 
     // TODO(error handling)
+    pub fn accept(&self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) -> i64 {
+        let word_ids: Vec<u64> = [self.first.current().0, self.second.current().0]
+            .into_iter()
+            .filter_map(WordOrSuggestionId::into_existing)
+            .collect();
+        with_revisions(db, &word_ids, self.suggesting_user.id, reviewer, || {
+            self.apply(db, reviewer)
+        })
+    }
+
+    /// Accepts the linked word without recording a revision of either word
     #[instrument(
         name = "Accept suggested linked word",
         fields(
@@ -719,7 +1290,7 @@ impl SuggestedLinkedWord {
         )
         skip_all,
     )]
-    pub fn accept(&self, db: &impl ModeratorAccessDb) -> i64 {
+    fn apply(&self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) -> i64 {
         const INSERT: &str = "
             INSERT INTO linked_words (link_id, link_type, first_word_id, second_word_id)
                 VALUES (?1, ?2, ?3, ?4)
@@ -728,6 +1299,10 @@ impl SuggestedLinkedWord {
                 RETURNING link_id;
         ";
 
+        let old = self
+            .existing_linked_word_id
+            .and_then(|id| ChangeSnapshot::fetch_linked_word(db, id));
+
         let conn = db.get().unwrap();
 
         let get_existing = |a: &MaybeEdited<(_, _)>| match a.current().0 {
@@ -744,7 +1319,7 @@ impl SuggestedLinkedWord {
             second,
         ];
 
-        let id = conn
+        let id: i64 = conn
             .prepare(INSERT)
             .unwrap()
             .query_row(params, |row| row.get("link_id"))
@@ -752,6 +1327,18 @@ impl SuggestedLinkedWord {
 
         add_user_attribution(db, &self.suggesting_user, WordId(first));
         add_user_attribution(db, &self.suggesting_user, WordId(second));
+
+        let new = ChangeSnapshot::fetch_linked_word(db, id as u64);
+        WordChange::record(db, self.suggesting_user.id, reviewer, old, new);
+
+        senses::apply_linked_word_suggestion(
+            db,
+            self.suggestion_id,
+            id as u64,
+            self.suggesting_user.id,
+            reviewer,
+        );
+
         SuggestedLinkedWord::delete(db, self.suggestion_id);
 
         Span::current().record("accepted_id", id);
@@ -806,6 +1393,11 @@ impl SuggestedLinkedWord {
     fn from_row_populate_both(row: &Row<'_>, db: &impl UserAccessDb) -> Self {
         const SELECT: &str =
             "SELECT link_type, first_word_id, second_word_id FROM linked_words WHERE link_id = ?1;";
+        const SELECT_SENSE: &str = "
+            SELECT existing_word_id, existing_sense_id, sense_suggestion_id
+                FROM linked_word_sense_suggestions
+                WHERE link_suggestion_id = ?1;
+        ";
 
         let conn = db.get().unwrap();
         let existing_id = row
@@ -879,10 +1471,33 @@ impl SuggestedLinkedWord {
 
         let (first, second) = (next(other_first), next(other_second));
 
-        let suggestion_id = row.get("suggestion_id").unwrap();
+        let suggestion_id: u64 = row.get("suggestion_id").unwrap();
 
         Span::current().record("suggestion_id", suggestion_id);
 
+        let suggested_sense: Option<(Option<u64>, Option<u64>, Option<u64>)> = conn
+            .prepare(SELECT_SENSE)
+            .unwrap()
+            .query_row(params![suggestion_id], |row| {
+                Ok((
+                    row.get("existing_word_id")?,
+                    row.get("existing_sense_id")?,
+                    row.get("sense_suggestion_id")?,
+                ))
+            })
+            .optional()
+            .unwrap();
+        let current_sense = suggested_sense
+            .and_then(|(word_id, ..)| Some((existing_id? as u64, word_id?)))
+            .and_then(|(link_id, word_id)| {
+                ExistingSense::fetch_id_for_linked_word(db, link_id, word_id)
+            });
+        let sense = SenseRef::maybe_edited(
+            db,
+            current_sense,
+            suggested_sense.map(|(_, existing, suggested)| (existing, suggested)),
+        );
+
         SuggestedLinkedWord {
             suggesting_user: PublicUserInfo::try_from(row).unwrap(),
             changes_summary: row.get("changes_summary").unwrap(),
@@ -891,6 +1506,16 @@ impl SuggestedLinkedWord {
             first,
             second,
             link_type: MaybeEdited::from_row("link_type", row, other_type),
+            sense,
+        }
+    }
+
+    /// The link type describing the word with the given id in relation to the other word.
+    pub fn link_type_for(&self, this_id: WordOrSuggestionId) -> MaybeEdited<WordLinkType> {
+        if this_id == self.second.current().0 && this_id != self.first.current().0 {
+            self.link_type.map(WordLinkType::inverse)
+        } else {
+            self.link_type
         }
     }
 
@@ -995,14 +1620,16 @@ impl DatasetAttributionSuggestion {
     }
 
     #[instrument(
-        name = "Fetch all dataset attribution suggestions",
+        name = "Fetch dataset attribution suggestions with ids",
         fields(results),
         skip(db)
     )]
-    pub fn fetch_all(
+    pub fn fetch_with_ids(
         db: &impl ModeratorAccessDb,
+        suggestion_ids: &[u64],
     ) -> impl Iterator<Item = (WordOrSuggestionId, Vec<Self>)> {
-        Self::fetch_with_filter(db, "", params![])
+        let ids = serde_json::to_string(suggestion_ids).unwrap();
+        Self::fetch_with_filter(db, WITH_IDS, params![ids])
     }
 
     #[instrument(
@@ -1019,6 +1646,19 @@ impl DatasetAttributionSuggestion {
         vec
     }
 
+    pub fn accept(self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) {
+        let word_ids: Vec<u64> = self
+            .word_or_suggestion_id
+            .into_existing()
+            .into_iter()
+            .collect();
+        let suggesting_user = self.suggesting_user.id;
+        with_revisions(db, &word_ids, suggesting_user, reviewer, || {
+            self.apply(db, reviewer)
+        })
+    }
+
+    /// Accepts the attribution without recording a revision of the word
     #[instrument(
         name = "Accept suggested dataset attribution",
         fields(
@@ -1026,7 +1666,7 @@ impl DatasetAttributionSuggestion {
         ),
         skip_all,
     )]
-    pub fn accept(self, db: &impl ModeratorAccessDb) {
+    fn apply(self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) {
         const INSERT: &str =
             "INSERT INTO dataset_attributions (dataset_id, word_id) VALUES (?1, ?2)
                 ON CONFLICT DO NOTHING;";
@@ -1036,7 +1676,8 @@ impl DatasetAttributionSuggestion {
         let word_id = self.word_or_suggestion_id.into_existing().unwrap();
         let sql = if self.is_delete { DELETE } else { INSERT };
 
-        db.get()
+        let modified_rows = db
+            .get()
             .unwrap()
             .prepare(sql)
             .unwrap()
@@ -1044,6 +1685,19 @@ impl DatasetAttributionSuggestion {
             .unwrap();
 
         add_user_attribution(db, &self.suggesting_user, WordId(word_id));
+
+        if modified_rows == 1 {
+            let record = ChangeSnapshot::DatasetAttribution(DatasetAttributionRecord {
+                word_id,
+                dataset_id: self.dataset.id,
+            });
+            let (old, new) = if self.is_delete {
+                (Some(record), None)
+            } else {
+                (None, Some(record))
+            };
+            WordChange::record(db, self.suggesting_user.id, reviewer, old, new);
+        }
         DatasetAttributionSuggestion::delete(db, self.suggestion_id);
     }
 
@@ -1060,6 +1714,208 @@ impl DatasetAttributionSuggestion {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct LabelSuggestion {
+    pub suggestion_id: u64,
+    pub word_or_suggestion_id: WordOrSuggestionId,
+    pub suggesting_user: PublicUserInfo,
+    pub label: Label,
+    pub changes_summary: String,
+    pub is_delete: bool,
+    /// The sense which is labelled, or `None` if the label is for the word as a whole
+    pub sense: Option<SenseRef>,
+}
+
+impl LabelSuggestion {
+    fn from_row(row: &Row<'_>, db: &impl UserAccessDb) -> Result<Self, rusqlite::Error> {
+        let sense = SenseRefId::from_columns(
+            row.get("existing_sense_id")?,
+            row.get("sense_suggestion_id")?,
+        );
+
+        Ok(LabelSuggestion {
+            suggestion_id: row.get("suggestion_id")?,
+            word_or_suggestion_id: WordOrSuggestionId::try_from(row)?,
+            suggesting_user: PublicUserInfo::try_from(row)?,
+            label: Label::try_from(row)?,
+            changes_summary: row.get("changes_summary")?,
+            is_delete: row.get("is_delete")?,
+            sense: sense.and_then(|id| SenseRef::fetch(db, id)),
+        })
+    }
+
+    fn fetch_with_filter(
+        db: &impl UserAccessDb,
+        where_clause: &'static str,
+        params: impl Params,
+    ) -> impl Iterator<Item = (WordOrSuggestionId, Vec<Self>)> {
+        let select = format!(
+            "SELECT labels.label_id, labels.kind, labels.name, labels.description,
+                    labels.rank_weight, existing_word_id, suggested_word_id, existing_sense_id,
+                    sense_suggestion_id, suggestion_id, changes_summary, is_delete,
+                    users.username, users.display_name, label_suggestions.suggesting_user
+            FROM labels
+            INNER JOIN label_suggestions ON labels.label_id = label_suggestions.label_id
+            INNER JOIN users ON label_suggestions.suggesting_user = users.user_id
+                {where_clause}
+            ORDER BY suggestion_id;
+            "
+        );
+
+        let conn = db.get().unwrap();
+        let mut query = conn.prepare(&select).unwrap();
+        let suggestions = query.query(params).unwrap();
+
+        let mut map: HashMap<WordOrSuggestionId, Vec<Self>> = HashMap::new();
+
+        suggestions
+            .map(|row| {
+                Ok((
+                    WordOrSuggestionId::try_from(row)?,
+                    LabelSuggestion::from_row(row, db)?,
+                ))
+            })
+            .for_each(|(word_id, suggestion)| {
+                map.entry(word_id)
+                    .or_insert_with(|| Vec::with_capacity(1))
+                    .push(suggestion);
+                Ok(())
+            })
+            .unwrap();
+
+        Span::current().record("results", map.len());
+
+        map.into_iter()
+    }
+
+    #[instrument(name = "Fetch label suggestion by id", fields(suggestion_id), skip(db))]
+    pub fn fetch_by_id(db: &impl ModeratorAccessDb, suggestion_id: u64) -> Option<LabelSuggestion> {
+        LabelSuggestion::fetch_with_filter(db, "WHERE suggestion_id = ?1", params![suggestion_id])
+            .next()?
+            .1
+            .into_iter()
+            .next()
+    }
+
+    #[instrument(name = "Fetch label suggestions with ids", fields(results), skip(db))]
+    pub fn fetch_with_ids(
+        db: &impl ModeratorAccessDb,
+        suggestion_ids: &[u64],
+    ) -> impl Iterator<Item = (WordOrSuggestionId, Vec<Self>)> {
+        let ids = serde_json::to_string(suggestion_ids).unwrap();
+        Self::fetch_with_filter(db, WITH_IDS, params![ids])
+    }
+
+    #[instrument(
+        name = "Fetch all label suggestions for suggestion",
+        fields(results),
+        skip(db)
+    )]
+    pub fn fetch_all_for_suggestion(db: &impl UserAccessDb, suggestion: u64) -> Vec<Self> {
+        let params = params![suggestion];
+        let all = Self::fetch_with_filter(db, "WHERE suggested_word_id = ?1", params);
+
+        #[allow(clippy::let_and_return)] // Needed due to lifetime issues
+        let vec = all.map(|(_, word)| word).next().unwrap_or_default();
+        vec
+    }
+
+    pub fn accept(self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) {
+        let word_ids: Vec<u64> = self
+            .word_or_suggestion_id
+            .into_existing()
+            .into_iter()
+            .collect();
+        let suggesting_user = self.suggesting_user.id;
+        with_revisions(db, &word_ids, suggesting_user, reviewer, || {
+            self.apply(db, reviewer)
+        })
+    }
+
+    /// Accepts the label without recording a revision of the word. If the label is for a sense
+    /// which has not been accepted yet, nothing is applied.
+    #[instrument(
+        name = "Accept suggested label",
+        fields(
+            suggestion_id = self.suggestion_id,
+        ),
+        skip_all,
+    )]
+    fn apply(self, db: &impl ModeratorAccessDb, reviewer: NonZeroU64) {
+        const INSERT_WORD: &str = "INSERT INTO word_labels (word_id, label_id) VALUES (?1, ?2)
+            ON CONFLICT DO NOTHING;";
+        const DELETE_WORD: &str = "DELETE FROM word_labels WHERE word_id = ?1 AND label_id = ?2;";
+        const INSERT_SENSE: &str = "INSERT INTO sense_labels (sense_id, label_id) VALUES (?1, ?2)
+            ON CONFLICT DO NOTHING;";
+        const DELETE_SENSE: &str =
+            "DELETE FROM sense_labels WHERE sense_id = ?1 AND label_id = ?2;";
+
+        let word_id = self.word_or_suggestion_id.into_existing().unwrap();
+        let conn = db.get().unwrap();
+
+        match self.sense.map(|s| s.id) {
+            None => {
+                let sql = if self.is_delete {
+                    DELETE_WORD
+                } else {
+                    INSERT_WORD
+                };
+                let modified_rows = conn
+                    .prepare(sql)
+                    .unwrap()
+                    .execute(params![word_id, self.label.id])
+                    .unwrap();
+
+                if modified_rows == 1 {
+                    let record = ChangeSnapshot::Label(LabelAttributionRecord {
+                        word_id,
+                        label_id: self.label.id,
+                    });
+                    let (old, new) = if self.is_delete {
+                        (Some(record), None)
+                    } else {
+                        (None, Some(record))
+                    };
+                    WordChange::record(db, self.suggesting_user.id, reviewer, old, new);
+                }
+            }
+            Some(SenseRefId::Existing(sense_id)) => {
+                let old = ChangeSnapshot::fetch_sense(db, sense_id);
+                let sql = if self.is_delete {
+                    DELETE_SENSE
+                } else {
+                    INSERT_SENSE
+                };
+                let modified_rows = conn
+                    .prepare(sql)
+                    .unwrap()
+                    .execute(params![sense_id, self.label.id])
+                    .unwrap();
+
+                if modified_rows == 1 {
+                    let new = ChangeSnapshot::fetch_sense(db, sense_id);
+                    WordChange::record(db, self.suggesting_user.id, reviewer, old, new);
+                }
+            }
+            Some(SenseRefId::Suggested(_)) => {}
+        }
+
+        add_user_attribution(db, &self.suggesting_user, WordId(word_id));
+        LabelSuggestion::delete(db, self.suggestion_id);
+    }
+
+    #[instrument(name = "Delete suggested label", fields(found), skip(db))]
+    pub fn delete(db: &impl ModeratorAccessDb, id: u64) -> bool {
+        const DELETE: &str = "DELETE FROM label_suggestions WHERE suggestion_id = ?1;";
+
+        let conn = db.get().unwrap();
+        let modified_rows = conn.prepare(DELETE).unwrap().execute(params![id]).unwrap();
+        let found = modified_rows == 1;
+        Span::current().record("found", found);
+        found
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum MaybeEdited<T> {
     Edited { old: T, new: T },
@@ -1086,6 +1942,14 @@ impl<T> MaybeEdited<T> {
             MaybeEdited::New(new) => new,
         }
     }
+
+    /// The value before the suggestion, if it edits an existing one
+    pub fn old(&self) -> Option<&T> {
+        match self {
+            MaybeEdited::Edited { old, .. } | MaybeEdited::Old(old) => Some(old),
+            MaybeEdited::New(_) => None,
+        }
+    }
 }
 
 impl<T: PartialEq> MaybeEdited<T> {
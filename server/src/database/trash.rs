@@ -0,0 +1,687 @@
+//! Deleted words, examples, and linked words. Deleting an item moves a snapshot of it into the
+//! trash, from which moderators can restore it until it is purged after [`TRASH_RETENTION_DAYS`].
+//! A deleted word's pending suggestions are kept in the trash along with it, as they would
+//! otherwise be deleted with the word.
+
+use crate::database::changes::{ChangeSnapshot, ChangeTarget, LinkedWordHits, WordChange};
+use crate::database::outcomes::SuggestionType;
+use crate::database::revisions::{restore_item, with_revisions, RevisionSnapshot, WordRevision};
+use crate::export::LinkedWordRecord;
+use crate::i18n::I18nInfo;
+use crate::search::{TantivyClient, WordDocument};
+use chrono::{DateTime, Duration, Utc};
+use fallible_iterator::FallibleIterator;
+use futures::executor::block_on;
+use isixhosa_common::database::{ModeratorAccessDb, WordOrSuggestionId};
+use isixhosa_common::types::{Dataset, ExistingExample, ExistingWord, PublicUserInfo, WordHit};
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{params, params_from_iter, ErrorCode, OptionalExtension, Row, ToSql};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use tracing::{instrument, warn, Span};
+
+/// How long deleted items are kept in the trash before they are permanently deleted
+pub const TRASH_RETENTION_DAYS: u64 = 30;
+
+/// Everything needed to restore a deleted item, stored as JSON.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TrashedItem {
+    /// A word along with everything that was deleted along with it
+    Word {
+        snapshot: RevisionSnapshot,
+        contributors: Vec<u64>,
+        /// The word's pending suggestions, along with their discussions and reviews
+        #[serde(default)]
+        suggestions: Vec<TrashedRows>,
+    },
+    Example(ExistingExample),
+    LinkedWord(LinkedWordRecord),
+}
+
+/// Rows of a table, stored column by column so that they can be inserted again unchanged
+#[derive(Serialize, Deserialize)]
+struct TrashedRows {
+    table: String,
+    rows: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// The tables of pending suggestions which are deleted along with a word and the conditions
+/// selecting them, given the word's id. Each table comes after the tables which it references, so
+/// that the rows can be inserted again in order. The suggestion to delete the word is left out, as
+/// it is dealt with by the word being deleted.
+fn pending_suggestion_tables() -> [(Option<SuggestionType>, &'static str, String); 11] {
+    let word_suggestions = "SELECT suggestion_id FROM word_suggestions WHERE existing_word_id = ?1";
+    let senses = "SELECT sense_id FROM senses WHERE word_id = ?1";
+    let examples = "SELECT example_id FROM examples WHERE word_id = ?1";
+    let links = "SELECT link_id FROM linked_words WHERE first_word_id = ?1 OR second_word_id = ?1";
+
+    let sense_suggestions = format!(
+        "existing_word_id = ?1 OR suggested_word_id IN ({word_suggestions})
+            OR existing_sense_id IN ({senses})"
+    );
+    let example_suggestions = format!(
+        "existing_word_id = ?1 OR suggested_word_id IN ({word_suggestions})
+            OR existing_example_id IN ({examples})"
+    );
+    let linked_word_suggestions = format!(
+        "first_existing_word_id = ?1 OR second_existing_word_id = ?1
+            OR existing_linked_word_id IN ({links})
+            OR suggested_word_id IN ({word_suggestions})
+            OR second_suggested_word_id IN ({word_suggestions})"
+    );
+
+    [
+        (
+            Some(SuggestionType::Word),
+            "word_suggestions",
+            "existing_word_id = ?1".to_owned(),
+        ),
+        (
+            None,
+            "word_suggestion_bases",
+            format!("suggestion_id IN ({word_suggestions})"),
+        ),
+        (
+            Some(SuggestionType::Sense),
+            "sense_suggestions",
+            sense_suggestions.clone(),
+        ),
+        (
+            Some(SuggestionType::Example),
+            "example_suggestions",
+            example_suggestions.clone(),
+        ),
+        (
+            None,
+            "example_sense_suggestions",
+            format!(
+                "example_suggestion_id IN (
+                    SELECT suggestion_id FROM example_suggestions WHERE {example_suggestions}
+                ) OR sense_suggestion_id IN (
+                    SELECT suggestion_id FROM sense_suggestions WHERE {sense_suggestions}
+                ) OR existing_sense_id IN ({senses})"
+            ),
+        ),
+        (
+            Some(SuggestionType::ExampleDeletion),
+            "example_deletion_suggestions",
+            format!("example_id IN ({examples})"),
+        ),
+        (
+            Some(SuggestionType::LinkedWord),
+            "linked_word_suggestions",
+            linked_word_suggestions.clone(),
+        ),
+        (
+            None,
+            "linked_word_sense_suggestions",
+            format!(
+                "link_suggestion_id IN (
+                    SELECT suggestion_id FROM linked_word_suggestions
+                        WHERE {linked_word_suggestions}
+                ) OR sense_suggestion_id IN (
+                    SELECT suggestion_id FROM sense_suggestions WHERE {sense_suggestions}
+                ) OR existing_word_id = ?1 OR existing_sense_id IN ({senses})"
+            ),
+        ),
+        (
+            Some(SuggestionType::LinkedWordDeletion),
+            "linked_word_deletion_suggestions",
+            format!("linked_word_id IN ({links})"),
+        ),
+        (
+            Some(SuggestionType::Label),
+            "label_suggestions",
+            format!(
+                "existing_word_id = ?1 OR suggested_word_id IN ({word_suggestions})
+                    OR existing_sense_id IN ({senses})
+                    OR sense_suggestion_id IN (
+                        SELECT suggestion_id FROM sense_suggestions WHERE {sense_suggestions}
+                    )"
+            ),
+        ),
+        (
+            Some(SuggestionType::DatasetAttribution),
+            "dataset_attribution_suggestions",
+            format!("existing_word_id = ?1 OR suggested_word_id IN ({word_suggestions})"),
+        ),
+    ]
+}
+
+/// Tables which refer to suggestions by their type and id rather than by a foreign key, along with
+/// how conflicts are resolved when their rows are inserted again. The time a suggestion was
+/// submitted is replaced, as it is recorded again when the suggestion is inserted.
+const SUGGESTION_KEYED_TABLES: [(&str, &str); 6] = [
+    ("suggestion_submissions", "REPLACE"),
+    ("suggestions_needing_info", "IGNORE"),
+    ("suggestion_comments", "IGNORE"),
+    ("suggestion_approvals", "IGNORE"),
+    ("suggestion_votes", "IGNORE"),
+    ("reviewer_edited_suggestions", "IGNORE"),
+];
+
+impl TrashedItem {
+    fn target(&self) -> ChangeTarget {
+        match self {
+            TrashedItem::Word { .. } => ChangeTarget::Word,
+            TrashedItem::Example(_) => ChangeTarget::Example,
+            TrashedItem::LinkedWord(_) => ChangeTarget::LinkedWord,
+        }
+    }
+
+    fn item_id(&self) -> u64 {
+        match self {
+            TrashedItem::Word { snapshot, .. } => snapshot.word.word_id,
+            TrashedItem::Example(e) => e.example_id,
+            TrashedItem::LinkedWord(l) => l.link_id,
+        }
+    }
+
+    fn word_id(&self) -> u64 {
+        match self {
+            TrashedItem::Word { snapshot, .. } => snapshot.word.word_id,
+            TrashedItem::Example(e) => e.word_id,
+            TrashedItem::LinkedWord(l) => l.first,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TrashEntry {
+    pub trash_id: u64,
+    pub target: ChangeTarget,
+    pub word_id: u64,
+    /// `None` if the word has been deleted
+    pub word: Option<WordHit>,
+    /// The fields of the deleted item, alongside the translation keys of their names
+    pub fields: Vec<(&'static str, String)>,
+    /// `None` if the user has since been deleted
+    pub suggesting_user: Option<PublicUserInfo>,
+    pub reviewing_user: Option<PublicUserInfo>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashEntry {
+    /// When the item will be permanently deleted
+    pub fn purged_at(&self) -> DateTime<Utc> {
+        self.deleted_at + Duration::days(TRASH_RETENTION_DAYS as i64)
+    }
+}
+
+#[instrument(name = "Move word to trash", fields(found), skip(db))]
+pub fn trash_word(
+    db: &impl ModeratorAccessDb,
+    word_id: u64,
+    suggesting_user: NonZeroU64,
+    reviewing_user: NonZeroU64,
+) -> bool {
+    const SELECT_CONTRIBUTORS: &str = "SELECT user_id FROM user_attributions WHERE word_id = ?1;";
+
+    let Some(snapshot) = RevisionSnapshot::fetch(db, word_id) else {
+        Span::current().record("found", false);
+        return false;
+    };
+
+    let conn = db.get().unwrap();
+    let mut query = conn.prepare(SELECT_CONTRIBUTORS).unwrap();
+    let contributors: Vec<u64> = query
+        .query(params![word_id])
+        .unwrap()
+        .map(|row| row.get("user_id"))
+        .collect()
+        .unwrap();
+
+    let item = TrashedItem::Word {
+        snapshot,
+        contributors,
+        suggestions: fetch_pending_suggestions(db, word_id),
+    };
+    insert(db, &item, suggesting_user, reviewing_user);
+    ExistingWord::delete(db, word_id);
+
+    Span::current().record("found", true);
+    true
+}
+
+#[instrument(name = "Move example to trash", fields(found), skip(db))]
+pub fn trash_example(
+    db: &impl ModeratorAccessDb,
+    example_id: u64,
+    suggesting_user: NonZeroU64,
+    reviewing_user: NonZeroU64,
+) -> bool {
+    const DELETE: &str = "DELETE FROM examples WHERE example_id = ?1;";
+
+    let Some(example) = ExistingExample::fetch(db, example_id) else {
+        Span::current().record("found", false);
+        return false;
+    };
+
+    insert(
+        db,
+        &TrashedItem::Example(example),
+        suggesting_user,
+        reviewing_user,
+    );
+
+    let conn = db.get().unwrap();
+    conn.prepare(DELETE)
+        .unwrap()
+        .execute(params![example_id])
+        .unwrap();
+
+    Span::current().record("found", true);
+    true
+}
+
+#[instrument(name = "Move linked word to trash", fields(found), skip(db))]
+pub fn trash_linked_word(
+    db: &impl ModeratorAccessDb,
+    link_id: u64,
+    suggesting_user: NonZeroU64,
+    reviewing_user: NonZeroU64,
+) -> bool {
+    const DELETE: &str = "DELETE FROM linked_words WHERE link_id = ?1;";
+
+    let Some(ChangeSnapshot::LinkedWord(link)) = ChangeSnapshot::fetch_linked_word(db, link_id)
+    else {
+        Span::current().record("found", false);
+        return false;
+    };
+
+    insert(
+        db,
+        &TrashedItem::LinkedWord(link),
+        suggesting_user,
+        reviewing_user,
+    );
+
+    let conn = db.get().unwrap();
+    conn.prepare(DELETE)
+        .unwrap()
+        .execute(params![link_id])
+        .unwrap();
+
+    Span::current().record("found", true);
+    true
+}
+
+/// Fetches the rows of the word's pending suggestions, followed by the rows which refer to them by
+/// their type and id.
+fn fetch_pending_suggestions(db: &impl ModeratorAccessDb, word_id: u64) -> Vec<TrashedRows> {
+    let mut suggestion_ids = Vec::new();
+    let mut tables: Vec<TrashedRows> = pending_suggestion_tables()
+        .into_iter()
+        .map(|(suggestion_type, table, condition)| {
+            let select = format!("SELECT * FROM {table} WHERE {condition} ORDER BY rowid;");
+            let rows = fetch_rows(db, &select, params![word_id]);
+
+            if let Some(suggestion_type) = suggestion_type {
+                suggestion_ids.extend(
+                    rows.iter()
+                        .map(|row| (suggestion_type, row["suggestion_id"].as_u64().unwrap())),
+                );
+            }
+
+            TrashedRows {
+                table: table.to_owned(),
+                rows,
+            }
+        })
+        .collect();
+
+    for (table, _) in SUGGESTION_KEYED_TABLES {
+        let select = format!(
+            "SELECT * FROM {table} WHERE suggestion_type = ?1 AND suggestion_id = ?2 ORDER BY rowid;"
+        );
+        let rows = suggestion_ids
+            .iter()
+            .flat_map(|(ty, id)| fetch_rows(db, &select, params![ty, id]))
+            .collect();
+
+        tables.push(TrashedRows {
+            table: table.to_owned(),
+            rows,
+        });
+    }
+
+    tables
+}
+
+fn fetch_rows(
+    db: &impl ModeratorAccessDb,
+    select: &str,
+    params: &[&dyn ToSql],
+) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    let conn = db.get().unwrap();
+    let mut query = conn.prepare(select).unwrap();
+    let columns: Vec<String> = query.column_names().into_iter().map(String::from).collect();
+
+    query
+        .query(params)
+        .unwrap()
+        .map(|row| {
+            columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    let value = match row.get_ref(i)? {
+                        ValueRef::Null => serde_json::Value::Null,
+                        ValueRef::Integer(v) => v.into(),
+                        ValueRef::Real(v) => v.into(),
+                        ValueRef::Text(v) => String::from_utf8_lossy(v).into(),
+                        ValueRef::Blob(v) => v.into(),
+                    };
+
+                    Ok((column.clone(), value))
+                })
+                .collect()
+        })
+        .collect()
+        .unwrap()
+}
+
+/// Inserts the rows of the word's pending suggestions again. Rows which refer to users or items
+/// which have since been deleted cannot be restored and are skipped, along with the rows which
+/// refer to them in turn.
+fn restore_pending_suggestions(db: &impl ModeratorAccessDb, suggestions: Vec<TrashedRows>) {
+    let conn = db.get().unwrap();
+
+    for TrashedRows { table, rows } in suggestions {
+        let conflict = SUGGESTION_KEYED_TABLES
+            .iter()
+            .find(|(keyed, _)| *keyed == table)
+            .map_or("IGNORE", |(_, conflict)| *conflict);
+
+        for row in rows {
+            let columns: Vec<&str> = row.keys().map(String::as_str).collect();
+            let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{i}")).collect();
+            let insert = format!(
+                "INSERT OR {conflict} INTO {table} ({}) VALUES ({});",
+                columns.join(", "),
+                placeholders.join(", "),
+            );
+
+            let values = row.values().map(|value| match value {
+                serde_json::Value::Null => Value::Null,
+                serde_json::Value::Bool(v) => Value::Integer(*v as i64),
+                serde_json::Value::Number(v) => v
+                    .as_i64()
+                    .map(Value::Integer)
+                    .unwrap_or_else(|| Value::Real(v.as_f64().unwrap())),
+                serde_json::Value::String(v) => Value::Text(v.clone()),
+                serde_json::Value::Array(v) => {
+                    Value::Blob(v.iter().map(|b| b.as_u64().unwrap() as u8).collect())
+                }
+                serde_json::Value::Object(_) => unreachable!("Rows only contain SQL values"),
+            });
+
+            // A row whose user, word, example or link is gone fails a foreign key constraint
+            match conn.execute(&insert, params_from_iter(values)) {
+                Ok(_) => {}
+                Err(e) if e.sqlite_error_code() == Some(ErrorCode::ConstraintViolation) => {}
+                Err(e) => warn!("Failed to restore a pending suggestion row of {table}: {e:?}"),
+            }
+        }
+    }
+}
+
+fn insert(
+    db: &impl ModeratorAccessDb,
+    item: &TrashedItem,
+    suggesting_user: NonZeroU64,
+    reviewing_user: NonZeroU64,
+) {
+    const INSERT: &str = "
+        INSERT INTO trash (
+            item_type, item_id, word_id, suggesting_user, reviewing_user, deleted_at, snapshot
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);
+    ";
+
+    db.get()
+        .unwrap()
+        .prepare(INSERT)
+        .unwrap()
+        .execute(params![
+            item.target(),
+            item.item_id(),
+            item.word_id(),
+            suggesting_user.get(),
+            reviewing_user.get(),
+            Utc::now(),
+            serde_json::to_string(item).unwrap(),
+        ])
+        .unwrap();
+}
+
+/// Fetches everything in the trash, most recently deleted first.
+#[instrument(name = "Fetch all trash", fields(results), skip_all)]
+pub fn fetch_all(db: &impl ModeratorAccessDb, i18n_info: &I18nInfo) -> Vec<TrashEntry> {
+    const SELECT: &str = "
+        SELECT trash.trash_id, trash.item_type, trash.word_id, trash.deleted_at, trash.snapshot,
+               trash.suggesting_user, suggestor.username, suggestor.display_name,
+               trash.reviewing_user, reviewer.username AS reviewer_username,
+               reviewer.display_name AS reviewer_display_name
+        FROM trash
+        LEFT OUTER JOIN users AS suggestor ON trash.suggesting_user = suggestor.user_id
+        LEFT OUTER JOIN users AS reviewer ON trash.reviewing_user = reviewer.user_id
+        ORDER BY trash.trash_id DESC;
+    ";
+
+    let datasets: HashMap<u64, Dataset> = Dataset::fetch_all(db)
+        .into_iter()
+        .map(|d| (d.id, d))
+        .collect();
+
+    let conn = db.get().unwrap();
+    let mut query = conn.prepare(SELECT).unwrap();
+    let rows = query.query(params![]).unwrap();
+
+    let rows: Vec<(TrashEntry, ChangeSnapshot)> =
+        rows.map(|row| Ok(from_row(row, db))).collect().unwrap();
+
+    let linked_words = LinkedWordHits::fetch(db, rows.iter().map(|(_, snapshot)| snapshot));
+
+    let entries: Vec<TrashEntry> = rows
+        .into_iter()
+        .map(|(mut entry, snapshot)| {
+            entry.fields = snapshot.fields(db, i18n_info, &datasets, &linked_words, entry.word_id);
+            entry
+        })
+        .collect();
+
+    Span::current().record("results", entries.len());
+
+    entries
+}
+
+/// Reads an entry from the row along with the snapshot of its item. Its fields are left empty until
+/// the snapshot is rendered.
+fn from_row(row: &Row<'_>, db: &impl ModeratorAccessDb) -> (TrashEntry, ChangeSnapshot) {
+    let user = |id: &str, username: &str, display_name: &str| {
+        row.get::<&str, Option<u64>>(id)
+            .unwrap()
+            .and_then(NonZeroU64::new)
+            .map(|id| PublicUserInfo {
+                id,
+                username: row.get(username).unwrap(),
+                display_name: row.get(display_name).unwrap(),
+            })
+    };
+
+    let word_id: u64 = row.get("word_id").unwrap();
+    let json: String = row.get("snapshot").unwrap();
+    let snapshot = match serde_json::from_str::<TrashedItem>(&json).unwrap() {
+        TrashedItem::Word { snapshot, .. } => ChangeSnapshot::Word(snapshot.word),
+        TrashedItem::Example(e) => ChangeSnapshot::Example(e),
+        TrashedItem::LinkedWord(l) => ChangeSnapshot::LinkedWord(l),
+    };
+
+    let entry = TrashEntry {
+        trash_id: row.get("trash_id").unwrap(),
+        target: row.get("item_type").unwrap(),
+        word_id,
+        word: WordHit::fetch_from_db(db, WordOrSuggestionId::existing(word_id)),
+        fields: Vec::new(),
+        suggesting_user: user("suggesting_user", "username", "display_name"),
+        reviewing_user: user(
+            "reviewing_user",
+            "reviewer_username",
+            "reviewer_display_name",
+        ),
+        deleted_at: row.get("deleted_at").unwrap(),
+    };
+
+    (entry, snapshot)
+}
+
+/// Restores an item from the trash. A deleted word is restored along with everything that was
+/// deleted with it and is added back to the search index. Examples can only be restored if their
+/// word exists, and linked words if both of their words exist.
+///
+/// Returns whether the item could be restored.
+#[instrument(name = "Restore from trash", fields(restored), skip(db, tantivy))]
+pub fn restore(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    trash_id: u64,
+    reviewer: NonZeroU64,
+) -> bool {
+    const SELECT: &str = "SELECT snapshot FROM trash WHERE trash_id = ?1;";
+
+    let json: Option<String> = db
+        .get()
+        .unwrap()
+        .prepare(SELECT)
+        .unwrap()
+        .query_row(params![trash_id], |row| row.get("snapshot"))
+        .optional()
+        .unwrap();
+
+    let Some(json) = json else {
+        Span::current().record("restored", false);
+        return false;
+    };
+
+    let word_exists = |id: u64| ExistingWord::fetch_alone(db, id).is_some();
+
+    let restored = match serde_json::from_str::<TrashedItem>(&json).unwrap() {
+        TrashedItem::Word {
+            snapshot,
+            contributors,
+            suggestions,
+        } => {
+            let word_id = snapshot.word.word_id;
+
+            if word_exists(word_id) {
+                false
+            } else {
+                restore_word(db, tantivy, snapshot, contributors, suggestions, reviewer);
+                true
+            }
+        }
+        TrashedItem::Example(example) => {
+            let word_id = example.word_id;
+
+            word_exists(word_id)
+                && with_revisions(db, &[word_id], reviewer, reviewer, || {
+                    restore_item_recording_change(db, ChangeSnapshot::Example(example), reviewer)
+                })
+        }
+        TrashedItem::LinkedWord(link) => {
+            let word_ids = [link.first, link.second];
+
+            with_revisions(db, &word_ids, reviewer, reviewer, || {
+                restore_item_recording_change(db, ChangeSnapshot::LinkedWord(link), reviewer)
+            })
+        }
+    };
+
+    if restored {
+        delete(db, trash_id);
+    }
+
+    Span::current().record("restored", restored);
+    restored
+}
+
+fn restore_word(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    snapshot: RevisionSnapshot,
+    contributors: Vec<u64>,
+    suggestions: Vec<TrashedRows>,
+    reviewer: NonZeroU64,
+) {
+    // Skips users who have since been deleted
+    const INSERT_CONTRIBUTOR: &str = "
+        INSERT INTO user_attributions (word_id, user_id)
+            SELECT ?1, user_id FROM users WHERE user_id = ?2;
+    ";
+
+    let word_id = snapshot.word.word_id;
+    let document = WordDocument::from(&snapshot.word);
+
+    let other_words: Vec<u64> = snapshot
+        .linked_words
+        .iter()
+        .map(|l| {
+            if l.first == word_id {
+                l.second
+            } else {
+                l.first
+            }
+        })
+        .collect();
+
+    with_revisions(db, &other_words, reviewer, reviewer, || {
+        for item in snapshot.into_items().into_values() {
+            restore_item_recording_change(db, item, reviewer);
+        }
+    });
+
+    let conn = db.get().unwrap();
+    let mut insert = conn.prepare(INSERT_CONTRIBUTOR).unwrap();
+    for user in contributors {
+        insert.execute(params![word_id, user]).unwrap();
+    }
+
+    restore_pending_suggestions(db, suggestions);
+
+    WordRevision::record(db, word_id, reviewer, reviewer);
+
+    let document = document.with_senses_and_labels_from_db(db);
+    block_on(async move { tantivy.add_new_word(document).await });
+}
+
+fn restore_item_recording_change(
+    db: &impl ModeratorAccessDb,
+    item: ChangeSnapshot,
+    reviewer: NonZeroU64,
+) -> bool {
+    let restored = restore_item(db, &item);
+
+    if restored {
+        WordChange::record(db, reviewer, reviewer, None, Some(item));
+    }
+
+    restored
+}
+
+#[instrument(name = "Delete trash entry", fields(found), skip(db))]
+fn delete(db: &impl ModeratorAccessDb, trash_id: u64) -> bool {
+    const DELETE: &str = "DELETE FROM trash WHERE trash_id = ?1;";
+
+    let conn = db.get().unwrap();
+    let modified_rows = conn
+        .prepare(DELETE)
+        .unwrap()
+        .execute(params![trash_id])
+        .unwrap();
+    let found = modified_rows == 1;
+    Span::current().record("found", found);
+    found
+}
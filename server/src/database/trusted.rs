@@ -0,0 +1,223 @@
+//! A log of the suggestions made by trusted contributors. These are applied as soon as they are
+//! made, so moderators review them afterwards instead, and revert them if needs be.
+
+use crate::database::changes::{ChangeSnapshot, WordChange};
+use crate::database::outcomes::{SuggestionSummary, SuggestionType};
+use crate::database::revisions::WordRevision;
+use crate::database::trash;
+use crate::search::TantivyClient;
+use chrono::{DateTime, Utc};
+use fallible_iterator::FallibleIterator;
+use futures::executor::block_on;
+use isixhosa_common::database::{ModeratorAccessDb, WordOrSuggestionId};
+use isixhosa_common::types::{PublicUserInfo, WordHit};
+use rusqlite::{params, OptionalExtension, Row};
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use tracing::{instrument, Span};
+
+#[derive(Debug)]
+pub struct TrustedEdit {
+    pub edit_id: u64,
+    pub suggestion_type: SuggestionType,
+    pub word_id: u64,
+    /// `None` if the word does not exist anymore
+    pub word: Option<WordHit>,
+    pub suggesting_user: PublicUserInfo,
+    pub summary: String,
+    pub changes_summary: String,
+    /// The revision of the word from before the edit, or `None` if the edit added the word
+    pub revision_before: Option<u64>,
+    /// The latest revision of the word once the edit was applied
+    pub revision_after: Option<u64>,
+    /// Whether the word has been changed since the edit. Reverting the edit would undo those
+    /// changes too, so it can then only be undone by hand.
+    pub superseded: bool,
+    pub applied_at: DateTime<Utc>,
+}
+
+impl TrustedEdit {
+    #[instrument(
+        name = "Log trusted edit",
+        fields(
+            suggestion_type = ?summary.suggestion_type,
+            suggestion_id = summary.suggestion_id,
+        ),
+        skip(db, summary)
+    )]
+    pub fn insert(
+        db: &impl ModeratorAccessDb,
+        summary: &SuggestionSummary,
+        word_id: u64,
+        revision_before: Option<u64>,
+        revision_after: Option<u64>,
+    ) {
+        const INSERT: &str = "
+            INSERT INTO trusted_edits (
+                suggestion_type, word_id, suggesting_user, summary, changes_summary,
+                revision_before, revision_after, applied_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);
+        ";
+
+        let conn = db.get().unwrap();
+        conn.prepare(INSERT)
+            .unwrap()
+            .execute(params![
+                summary.suggestion_type,
+                word_id,
+                summary.suggesting_user.get(),
+                summary.summary,
+                summary.changes_summary,
+                revision_before,
+                revision_after,
+                Utc::now(),
+            ])
+            .unwrap();
+    }
+
+    /// Fetches all edits which no moderator has reviewed yet, oldest first
+    #[instrument(name = "Fetch unreviewed trusted edits", fields(results), skip(db))]
+    pub fn fetch_all_unreviewed(db: &impl ModeratorAccessDb) -> Vec<TrustedEdit> {
+        let select = Self::select_where("WHERE reviewed_at IS NULL ORDER BY edit_id");
+
+        let conn = db.get().unwrap();
+        let edits: Vec<TrustedEdit> = conn
+            .prepare(&select)
+            .unwrap()
+            .query(params![])
+            .unwrap()
+            .map(|row| Ok(Self::from_row(db, row)))
+            .collect()
+            .unwrap();
+
+        Span::current().record("results", edits.len());
+
+        edits
+    }
+
+    #[instrument(name = "Fetch unreviewed trusted edit", fields(found), skip(db))]
+    pub fn fetch_unreviewed(db: &impl ModeratorAccessDb, edit_id: u64) -> Option<TrustedEdit> {
+        let select = Self::select_where("WHERE reviewed_at IS NULL AND edit_id = ?1");
+
+        let conn = db.get().unwrap();
+        let edit = conn
+            .prepare(&select)
+            .unwrap()
+            .query_row(params![edit_id], |row| Ok(Self::from_row(db, row)))
+            .optional()
+            .unwrap();
+
+        Span::current().record("found", edit.is_some());
+
+        edit
+    }
+
+    fn select_where(condition: &str) -> String {
+        format!(
+            "
+            SELECT trusted_edits.edit_id, trusted_edits.suggestion_type, trusted_edits.word_id,
+                   trusted_edits.suggesting_user, users.username, users.display_name,
+                   trusted_edits.summary, trusted_edits.changes_summary,
+                   trusted_edits.revision_before, trusted_edits.revision_after,
+                   trusted_edits.applied_at
+            FROM trusted_edits
+            INNER JOIN users ON trusted_edits.suggesting_user = users.user_id
+            {condition};
+            "
+        )
+    }
+
+    fn from_row(db: &impl ModeratorAccessDb, row: &Row<'_>) -> TrustedEdit {
+        let word_id = row.get("word_id").unwrap();
+        let revision_before: Option<u64> = row.get("revision_before").unwrap();
+        let revision_after: Option<u64> = row.get("revision_after").unwrap();
+        let latest = WordRevision::latest_id(db, word_id);
+
+        TrustedEdit {
+            edit_id: row.get("edit_id").unwrap(),
+            suggestion_type: row.get("suggestion_type").unwrap(),
+            word_id,
+            word: WordHit::fetch_from_db(db, WordOrSuggestionId::existing(word_id)),
+            suggesting_user: PublicUserInfo {
+                id: NonZeroU64::new(row.get("suggesting_user").unwrap()).unwrap(),
+                username: row.get("username").unwrap(),
+                display_name: row.get("display_name").unwrap(),
+            },
+            summary: row.get("summary").unwrap(),
+            changes_summary: row.get("changes_summary").unwrap(),
+            revision_before,
+            revision_after,
+            superseded: latest > revision_after.or(revision_before),
+            applied_at: row.get("applied_at").unwrap(),
+        }
+    }
+
+    /// Marks the edit as reviewed by the moderator, keeping it as it is. Returns `false` if the
+    /// edit does not exist or was already reviewed.
+    #[instrument(name = "Approve trusted edit", skip(db))]
+    pub fn approve(db: &impl ModeratorAccessDb, edit_id: u64, reviewer: NonZeroU64) -> bool {
+        Self::mark_reviewed(db, edit_id, reviewer, false)
+    }
+
+    /// Undoes the edit by restoring its word to the revision from before it was applied, or by
+    /// moving the word to the trash if the edit added it. Returns `false` if the edit could not be
+    /// reverted, including if the word has been changed since, as those changes would be undone
+    /// too.
+    #[instrument(name = "Revert trusted edit", fields(reverted), skip(db, tantivy))]
+    pub fn revert(
+        db: &impl ModeratorAccessDb,
+        tantivy: Arc<TantivyClient>,
+        edit_id: u64,
+        reviewer: NonZeroU64,
+    ) -> bool {
+        let Some(edit) = Self::fetch_unreviewed(db, edit_id).filter(|edit| !edit.superseded) else {
+            Span::current().record("reverted", false);
+            return false;
+        };
+
+        let reverted = match edit.revision_before {
+            Some(revision) => WordRevision::revert(db, tantivy, edit.word_id, revision, reviewer),
+            None => {
+                let suggesting_user = edit.suggesting_user.id;
+                let old = ChangeSnapshot::fetch_word(db, edit.word_id);
+
+                // Keep the state of the word before it was deleted in its history too
+                WordRevision::record_baseline(db, edit.word_id);
+                let trashed = trash::trash_word(db, edit.word_id, suggesting_user, reviewer);
+
+                if trashed {
+                    WordChange::record(db, suggesting_user, reviewer, old, None);
+
+                    let id = WordOrSuggestionId::existing(edit.word_id);
+                    block_on(async move { tantivy.delete_word(id).await });
+                }
+
+                trashed
+            }
+        };
+
+        Span::current().record("reverted", reverted);
+
+        reverted && Self::mark_reviewed(db, edit_id, reviewer, true)
+    }
+
+    fn mark_reviewed(
+        db: &impl ModeratorAccessDb,
+        edit_id: u64,
+        reviewer: NonZeroU64,
+        reverted: bool,
+    ) -> bool {
+        const UPDATE: &str = "
+            UPDATE trusted_edits SET reviewing_user = ?1, reviewed_at = ?2, reverted = ?3
+                WHERE edit_id = ?4 AND reviewed_at IS NULL;
+        ";
+
+        let conn = db.get().unwrap();
+        let changed = conn
+            .prepare(UPDATE)
+            .unwrap()
+            .execute(params![reviewer.get(), Utc::now(), reverted, edit_id])
+            .unwrap();
+        changed == 1
+    }
+}
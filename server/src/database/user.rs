@@ -28,6 +28,8 @@ impl TryFrom<&Row<'_>> for FullUser {
                 Permissions::Administrator
             } else if row.get("is_moderator")? {
                 Permissions::Moderator
+            } else if row.get("is_trusted_contributor")? {
+                Permissions::TrustedContributor
             } else {
                 Permissions::User
             },
@@ -43,7 +45,10 @@ impl FullUser {
         const SELECT: &str = "
             SELECT
                 user_id, username, display_name, email, is_moderator, is_administrator, locked,
-                language
+                language, EXISTS(
+                    SELECT 1 FROM trusted_contributors
+                    WHERE trusted_contributors.user_id = users.user_id
+                ) AS is_trusted_contributor
             FROM users
             WHERE user_id = ?1;
         ";
@@ -72,7 +77,10 @@ impl FullUser {
         const SELECT: &str = "
             SELECT
                 user_id, username, display_name, email, is_moderator, is_administrator, locked,
-                language
+                language, EXISTS(
+                    SELECT 1 FROM trusted_contributors
+                    WHERE trusted_contributors.user_id = users.user_id
+                ) AS is_trusted_contributor
             FROM users
             WHERE oidc_id = ?1;
         ";
@@ -97,7 +105,10 @@ impl FullUser {
         const SELECT: &str = "
             SELECT
                 user_id, username, display_name, email, is_moderator, is_administrator, locked,
-                language
+                language, EXISTS(
+                    SELECT 1 FROM trusted_contributors
+                    WHERE trusted_contributors.user_id = users.user_id
+                ) AS is_trusted_contributor
             FROM users;
         ";
 
@@ -116,6 +127,8 @@ impl FullUser {
         users
     }
 
+    /// Sets the user's role. Trusted contributors are kept in their own table, as they are
+    /// otherwise regular users.
     #[instrument(level = "info", name = "Set user role", skip(db))]
     pub fn set_role_by_email(
         db: &impl ModeratorAccessDb,
@@ -124,9 +137,17 @@ impl FullUser {
     ) -> bool {
         const UPDATE: &str =
             "UPDATE users SET is_moderator = ?1, is_administrator = ?2 WHERE email = ?3;";
+        const INSERT_TRUSTED: &str = "
+            INSERT INTO trusted_contributors (user_id, trusted_at, automatic)
+                SELECT user_id, ?2, FALSE FROM users WHERE email = ?1;
+        ";
+        const DELETE_TRUSTED: &str = "
+            DELETE FROM trusted_contributors
+                WHERE user_id = (SELECT user_id FROM users WHERE email = ?1);
+        ";
 
         let (is_mod, is_admin) = match role {
-            Permissions::User => (false, false),
+            Permissions::User | Permissions::TrustedContributor => (false, false),
             Permissions::Moderator => (true, false),
             Permissions::Administrator => (true, true),
         };
@@ -140,9 +161,76 @@ impl FullUser {
             .execute(params![is_mod, is_admin, email])
             .unwrap();
 
+        if role == Permissions::TrustedContributor {
+            conn.prepare(INSERT_TRUSTED)
+                .unwrap()
+                .execute(params![email, Utc::now()])
+                .unwrap();
+        } else {
+            conn.prepare(DELETE_TRUSTED)
+                .unwrap()
+                .execute(params![email])
+                .unwrap();
+        }
+
         changed != 0
     }
 
+    /// Makes every regular user who has had at least `min_accepted` suggestions accepted by
+    /// moderators, and at least `min_acceptance_rate` of all their reviewed suggestions accepted,
+    /// a trusted contributor. Returns the users who were promoted.
+    #[instrument(
+        level = "info",
+        name = "Promote trusted contributors",
+        fields(promoted),
+        skip(db)
+    )]
+    pub fn promote_trusted_contributors(
+        db: &impl ModeratorAccessDb,
+        min_accepted: u32,
+        min_acceptance_rate: f64,
+    ) -> Vec<FullUser> {
+        // Suggestions which trusted contributors applied themselves do not count. An outcome of 3
+        // means that the suggestion was rejected.
+        const SELECT: &str = "
+            SELECT suggesting_user FROM suggestion_outcomes
+            INNER JOIN users ON suggestion_outcomes.suggesting_user = users.user_id
+            WHERE reviewing_user != suggesting_user AND NOT users.is_moderator
+                AND NOT users.locked
+                AND suggesting_user NOT IN (SELECT user_id FROM trusted_contributors)
+            GROUP BY suggesting_user
+            HAVING SUM(outcome != 3) >= ?1 AND SUM(outcome != 3) >= ?2 * COUNT(*);
+        ";
+        const INSERT: &str = "
+            INSERT INTO trusted_contributors (user_id, trusted_at, automatic)
+                VALUES (?1, ?2, TRUE);
+        ";
+
+        let conn = db.get().unwrap();
+        let user_ids: Vec<u64> = conn
+            .prepare(SELECT)
+            .unwrap()
+            .query(params![min_accepted, min_acceptance_rate])
+            .unwrap()
+            .map(|row| row.get("suggesting_user"))
+            .collect()
+            .unwrap();
+
+        let mut insert = conn.prepare(INSERT).unwrap();
+        for user_id in &user_ids {
+            insert.execute(params![user_id, Utc::now()]).unwrap();
+        }
+
+        let promoted: Vec<FullUser> = user_ids
+            .into_iter()
+            .filter_map(|id| FullUser::fetch_by_id(db, id))
+            .collect();
+
+        Span::current().record("promoted", promoted.len());
+
+        promoted
+    }
+
     #[instrument(level = "info", name = "Set user to locked", skip(db))]
     pub fn set_locked_by_email(db: &impl ModeratorAccessDb, email: String, locked: bool) -> bool {
         const UPDATE: &str = "UPDATE users SET locked = ?1 WHERE email = ?2;";
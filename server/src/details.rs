@@ -1,13 +1,16 @@
 use crate::auth::with_any_auth;
+use crate::database::merge;
 use crate::i18n::I18nInfo;
 use crate::i18n::SiteContext;
 use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
 use isixhosa_common::auth::Auth;
 use isixhosa_common::database::{DbBase, PublicAccessDb};
+use isixhosa_common::language::GrammarWarning;
 use isixhosa_common::templates::{WordChangeMethod, WordDetails};
 use isixhosa_common::types::ExistingWord;
 use std::sync::Arc;
 use tracing::instrument;
+use warp::http::Uri;
 use warp::{Filter, Rejection, Reply};
 
 pub fn details(
@@ -18,31 +21,51 @@ pub fn details(
         .and(warp::path::end())
         .and(warp::get())
         .and(warp::any().map(|| None)) // previous_success is None
+        .and(warp::any().map(Vec::new)) // grammar_warnings is empty
         .and(with_any_auth(db, site_ctx))
         .and_then(word)
         .debug_boxed()
 }
 
-#[instrument(name = "Display word details page", skip(auth, db, previous_success))]
+#[instrument(
+    name = "Display word details page",
+    skip(auth, db, previous_success, grammar_warnings)
+)]
 pub async fn word(
     word_id: u64,
     previous_success: Option<WordChangeMethod>,
+    grammar_warnings: Vec<GrammarWarning>,
     auth: Auth,
     i18n_info: I18nInfo,
     db: impl PublicAccessDb,
 ) -> Result<impl Reply, Rejection> {
     let db = db.clone();
-    let word = spawn_blocking_child(move || ExistingWord::fetch_full(&db, word_id))
-        .await
-        .unwrap();
-    Ok(match word {
-        Some(word) => WordDetails {
+    let (word, redirect) = spawn_blocking_child(move || {
+        let word = ExistingWord::fetch_full(&db, word_id);
+        let redirect = word
+            .is_none()
+            .then(|| merge::fetch_redirect(&db, word_id))
+            .flatten();
+        (word, redirect)
+    })
+    .await
+    .unwrap();
+
+    Ok(match (word, redirect) {
+        (Some(word), _) => WordDetails {
             auth,
             i18n_info,
             word,
             previous_success,
+            grammar_warnings,
+            preview: false,
         }
         .into_response(),
-        None => NotFound { auth, i18n_info }.into_response(),
+        (None, Some(to)) => {
+            // The word was merged into another one
+            let uri: Uri = format!("/word/{to}").parse().unwrap();
+            warp::redirect(uri).into_response()
+        }
+        (None, None) => NotFound { auth, i18n_info }.into_response(),
     })
 }
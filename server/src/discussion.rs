@@ -0,0 +1,225 @@
+use crate::auth::{with_moderator_auth, with_user_auth, FullUser};
+use crate::database::discussion::{self, SuggestionComment, ThreadedComment};
+use crate::database::outcomes::{SuggestionSummary, SuggestionType};
+use crate::database::scopes;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::{Auth, Permissions};
+use isixhosa_common::database::{DbBase, ModeratorAccessDb, UserAccessDb};
+use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr, NoneAsEmptyString};
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::http::Uri;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "discussion.askama.html")]
+struct DiscussionPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    suggestion: SuggestionSummary,
+    thread: Vec<ThreadedComment>,
+    needs_info: bool,
+    previous_success: Option<bool>,
+}
+
+impl DiscussionPage {
+    fn url(&self) -> String {
+        discussion_url(
+            self.suggestion.suggestion_type,
+            self.suggestion.suggestion_id,
+        )
+    }
+
+    fn is_moderator(&self) -> bool {
+        self.auth.has_moderator_permissions()
+    }
+
+    fn is_suggesting_user(&self, author: &NonZeroU64) -> bool {
+        *author == self.suggestion.suggesting_user
+    }
+}
+
+#[serde_as]
+#[derive(Deserialize, Debug)]
+struct CommentForm {
+    body: String,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    parent: Option<u64>,
+}
+
+#[serde_as]
+#[derive(Deserialize, Debug)]
+struct NeedsInfoForm {
+    #[serde_as(as = "DisplayFromStr")]
+    needs_info: bool,
+}
+
+pub fn discussion_url(suggestion_type: SuggestionType, suggestion_id: u64) -> String {
+    format!(
+        "/suggestions/{}/{}/discussion",
+        suggestion_type.name(),
+        suggestion_id
+    )
+}
+
+pub fn discussion(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let page = warp::get()
+        .and(warp::path![SuggestionType / u64 / "discussion"])
+        .and(warp::path::end())
+        .and(with_user_auth(db.clone(), site_ctx.clone()))
+        .and_then(discussion_page);
+
+    let post_comment = warp::post()
+        .and(warp::path![SuggestionType / u64 / "discussion"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::form())
+        .and(with_user_auth(db.clone(), site_ctx.clone()))
+        .and_then(post_comment);
+
+    let needs_info = warp::post()
+        .and(warp::path![SuggestionType / u64 / "needs_info"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(4 * 1024))
+        .and(body::form())
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(set_needs_info);
+
+    warp::path("suggestions")
+        .and(page.or(post_comment).or(needs_info))
+        .debug_boxed()
+}
+
+/// Discussions can be seen and posted in by the user who made the suggestion and by moderators
+fn may_discuss(user: &FullUser, suggestion: &SuggestionSummary) -> bool {
+    user.id == suggestion.suggesting_user || user.permissions.contains(Permissions::Moderator)
+}
+
+async fn discussion_page(
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    render_discussion(db, i18n_info, user, suggestion_type, suggestion_id, None).await
+}
+
+#[instrument(name = "Display suggestion discussion", skip(db, i18n_info, user))]
+async fn render_discussion(
+    db: impl UserAccessDb,
+    i18n_info: I18nInfo,
+    user: FullUser,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    previous_success: Option<bool>,
+) -> Result<warp::reply::Response, Rejection> {
+    let user_clone = user.clone();
+    let discussion = spawn_blocking_child(move || {
+        let suggestion = SuggestionSummary::fetch(&db, suggestion_type, suggestion_id)
+            .filter(|suggestion| may_discuss(&user_clone, suggestion))?;
+        let thread = SuggestionComment::fetch_thread(&db, suggestion_type, suggestion_id);
+        let needs_info = discussion::needs_info(&db, suggestion_type, suggestion_id);
+        Some((suggestion, thread, needs_info))
+    })
+    .await
+    .unwrap();
+
+    Ok(match discussion {
+        Some((suggestion, thread, needs_info)) => DiscussionPage {
+            auth: user.into(),
+            i18n_info,
+            suggestion,
+            thread,
+            needs_info,
+            previous_success,
+        }
+        .into_response(),
+        None => NotFound {
+            auth: user.into(),
+            i18n_info,
+        }
+        .into_response(),
+    })
+}
+
+#[instrument(name = "Post comment on suggestion", skip(form, user, i18n_info, db))]
+async fn post_comment(
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    form: CommentForm,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let user_clone = user.clone();
+    let posted = spawn_blocking_child(move || {
+        SuggestionSummary::fetch(&db_clone, suggestion_type, suggestion_id)
+            .filter(|suggestion| may_discuss(&user_clone, suggestion))
+            .map(|suggestion| {
+                SuggestionComment::post(
+                    &db_clone,
+                    &suggestion,
+                    user_clone.id,
+                    form.parent,
+                    &form.body,
+                )
+            })
+    })
+    .await
+    .unwrap();
+
+    match posted {
+        // Redirect so that refreshing the page does not post the comment again
+        Some(true) => {
+            let uri: Uri = discussion_url(suggestion_type, suggestion_id)
+                .parse()
+                .unwrap();
+            Ok(warp::redirect::see_other(uri).into_response())
+        }
+        _ => render_discussion(db, i18n_info, user, suggestion_type, suggestion_id, posted).await,
+    }
+}
+
+#[instrument(name = "Set suggestion needs info", skip(user, _i18n_info, db))]
+async fn set_needs_info(
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    form: NeedsInfoForm,
+    user: FullUser,
+    _i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let moderator = user.id;
+    spawn_blocking_child(move || {
+        if SuggestionSummary::fetch(&db, suggestion_type, suggestion_id).is_none()
+            || !scopes::may_moderate(&db, moderator, suggestion_type, suggestion_id)
+        {
+            return;
+        }
+
+        if form.needs_info {
+            discussion::set_needs_info(&db, suggestion_type, suggestion_id, moderator);
+        } else {
+            discussion::clear_needs_info(&db, suggestion_type, suggestion_id);
+        }
+    })
+    .await
+    .unwrap();
+
+    let uri: Uri = discussion_url(suggestion_type, suggestion_id)
+        .parse()
+        .unwrap();
+    Ok(warp::redirect::see_other(uri))
+}
@@ -7,12 +7,14 @@ use tracing::instrument;
 use warp::{body, Filter, Rejection, Reply};
 
 use crate::auth::{with_user_auth, FullUser};
+use crate::database::outcomes::SuggestionType;
 use crate::database::submit::{submit_suggestion, suggest_word_deletion, WordSubmission};
 use crate::details::word;
 use crate::i18n::I18nInfo;
 use crate::search::TantivyClient;
 use crate::serialization::qs_form;
 use crate::submit::edit_word_page;
+use crate::trusted;
 use crate::DebugBoxedExt;
 
 pub fn edit(
@@ -27,12 +29,15 @@ pub fn edit(
         .and(with_user_auth(db.clone(), site_ctx.clone()))
         .and_then(edit_word_page);
 
+    let db_clone = db.clone();
+    let tantivy_clone = tantivy.clone();
     let submit_form = warp::post()
         .and(warp::path![u64])
         .and(warp::path::end())
         .and(body::content_length_limit(64 * 1024))
         .and(qs_form())
-        .and(warp::any().map(move || tantivy.clone()))
+        .and(warp::any().map(move || db_clone.clone()))
+        .and(warp::any().map(move || tantivy_clone.clone()))
         .and(with_user_auth(db.clone(), site_ctx.clone()))
         .and_then(submit_suggestion_reply);
 
@@ -43,9 +48,12 @@ pub fn edit(
         .and(with_user_auth(db.clone(), site_ctx.clone()))
         .and_then(edit_word_page);
 
+    let db_clone = db.clone();
     let delete_redirect = warp::post()
         .and(warp::path![u64 / "delete"])
         .and(warp::path::end())
+        .and(warp::any().map(move || db_clone.clone()))
+        .and(warp::any().map(move || tantivy.clone()))
         .and(with_user_auth(db, site_ctx))
         .and_then(delete_word_reply);
 
@@ -63,26 +71,42 @@ pub fn edit(
 async fn submit_suggestion_reply(
     id: u64,
     w: WordSubmission,
+    db_base: DbBase,
     tantivy: Arc<TantivyClient>,
     user: FullUser,
     i18n_info: I18nInfo,
     db: impl UserAccessDb,
 ) -> Result<impl Reply, Rejection> {
-    submit_suggestion(w, tantivy, &user, &db, i18n_info.clone()).await;
-    word(id, Some(WordChangeMethod::Edit), user.into(), i18n_info, db).await
+    let grammar_warnings = w.grammar_warnings();
+    let submitted = submit_suggestion(w, tantivy.clone(), &user, &db, i18n_info.clone()).await;
+    trusted::apply_if_trusted(db_base, tantivy, &user, i18n_info.clone(), submitted).await;
+    word(
+        id,
+        Some(WordChangeMethod::Edit),
+        grammar_warnings,
+        user.into(),
+        i18n_info,
+        db,
+    )
+    .await
 }
 
 #[instrument(name = "Suggest to delete word", skip(user, db))]
 async fn delete_word_reply(
     id: u64,
+    db_base: DbBase,
+    tantivy: Arc<TantivyClient>,
     user: FullUser,
     i18n_info: I18nInfo,
     db: impl UserAccessDb,
 ) -> Result<impl Reply, Rejection> {
-    suggest_word_deletion(&user, WordId(id), &db).await;
+    let deletion = suggest_word_deletion(&user, WordId(id), &db).await;
+    let submitted = vec![(SuggestionType::WordDeletion, deletion)];
+    trusted::apply_if_trusted(db_base, tantivy, &user, i18n_info.clone(), submitted).await;
     word(
         id,
         Some(WordChangeMethod::Delete),
+        Vec::new(),
         user.into(),
         i18n_info,
         db,
@@ -1,14 +1,21 @@
 //! This script is called daily to back up the database and sweep unused login tokens.
 
+use crate::database::trash::TRASH_RETENTION_DAYS;
 use crate::i18n::{I18nInfo, EN_ZA};
+use crate::search::WordDocument;
 use crate::{set_up_db, CliArgs, Config};
 use anyhow::Result;
 use chrono::Utc;
 use fallible_iterator::FallibleIterator;
 use genanki_rs::{Deck, Field, Model, ModelType, Note, Template};
 use isixhosa::noun::NounClass;
+use isixhosa_common::conjugation::{Polarity, SubjectConcord, Tense};
+use isixhosa_common::database::WordOrSuggestionId;
 use isixhosa_common::format::DisplayHtml;
-use isixhosa_common::language::{ConjunctionFollowedBy, PartOfSpeech, Transitivity, WordLinkType};
+use isixhosa_common::language::{
+    ConjunctionFollowedBy, EtymologyLanguage, LabelKind, PartOfSpeech, ReferenceKind, Transitivity,
+    WordLinkType,
+};
 use isixhosa_common::types::{ExistingExample, ExistingWord};
 use rusqlite::backup::Backup;
 use rusqlite::{params, OptionalExtension};
@@ -32,6 +39,10 @@ pub fn restore(cfg: Config) -> Result<()> {
     restore_words(&cfg, &conn)?;
     restore_examples(&cfg, &conn)?;
     restore_linked_words(&cfg, &conn)?;
+    restore_senses(&cfg, &conn)?;
+    restore_labels(&cfg, &conn)?;
+    restore_references(&cfg, &conn)?;
+    restore_conjugation_overrides(&cfg, &conn)?;
     restore_contributions(&cfg, &conn)?;
 
     // Force reindex on next start
@@ -44,6 +55,7 @@ pub fn restore(cfg: Config) -> Result<()> {
 pub fn run_daily_tasks(cfg: &Config, args: &CliArgs) -> Result<()> {
     let conn = Connection::open(&cfg.database_path)?;
     sweep_tokens(&conn)?;
+    sweep_trash(&conn)?;
     export(cfg, &args.site, &conn)
 }
 
@@ -60,6 +72,10 @@ fn export(cfg: &Config, site: &str, src: &Connection) -> Result<()> {
     write_words(cfg, site, &dest)?;
     write_examples(cfg, &dest)?;
     write_linked_words(cfg, &dest)?;
+    write_senses(cfg, &dest)?;
+    write_labels(cfg, &dest)?;
+    write_references(cfg, &dest)?;
+    write_conjugation_overrides(cfg, &dest)?;
     write_users(cfg, &dest)?;
     write_contributions(cfg, &dest)?;
     write_datasets(cfg, &dest)?;
@@ -109,6 +125,17 @@ pub struct WordRecord {
     pub followed_by: Option<ConjunctionFollowedBy>,
     pub noun_class: Option<NounClass>,
     pub note: String,
+
+    // Exports and snapshots from before etymologies existed have none
+    #[serde(default)]
+    pub etymology_language: Option<EtymologyLanguage>,
+    #[serde(default)]
+    pub etymology_form: String,
+    #[serde(default)]
+    pub etymology_note: String,
+
+    #[serde(default)]
+    pub literal_translation: String,
 }
 
 impl WordRecord {
@@ -314,6 +341,32 @@ impl From<ExistingWord> for WordRecord {
             followed_by: w.followed_by,
             noun_class: w.noun_class,
             note: w.note,
+            etymology_language: w.etymology_language,
+            etymology_form: w.etymology_form,
+            etymology_note: w.etymology_note,
+            literal_translation: w.literal_translation,
+        }
+    }
+}
+
+impl From<&WordRecord> for WordDocument {
+    fn from(w: &WordRecord) -> Self {
+        WordDocument {
+            id: WordOrSuggestionId::existing(w.word_id),
+            english: w.english.clone(),
+            xhosa: w.xhosa.clone(),
+            part_of_speech: w.part_of_speech,
+            is_plural: w.is_plural,
+            is_inchoative: w.is_inchoative,
+            transitivity: w.transitivity,
+            suggesting_user: None,
+            noun_class: w.noun_class,
+            is_informal: w.is_informal,
+            // Senses and labels are not part of the word record, see
+            // `WordDocument::with_senses_and_labels_from_db`
+            senses: Vec::new(),
+            labels: Vec::new(),
+            rank_weight: 1.0,
         }
     }
 }
@@ -339,6 +392,195 @@ impl TryFrom<&Row<'_>> for LinkedWordRecord {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct SenseRecord {
+    pub sense_id: u64,
+    pub word_id: u64,
+    pub sense_order: u32,
+    pub english: String,
+    pub is_informal: bool,
+}
+
+impl TryFrom<&Row<'_>> for SenseRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(SenseRecord {
+            sense_id: row.get("sense_id")?,
+            word_id: row.get("word_id")?,
+            sense_order: row.get("sense_order")?,
+            english: row.get("english")?,
+            is_informal: row.get("is_informal")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExampleSenseRecord {
+    pub example_id: u64,
+    pub sense_id: u64,
+}
+
+impl TryFrom<&Row<'_>> for ExampleSenseRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(ExampleSenseRecord {
+            example_id: row.get("example_id")?,
+            sense_id: row.get("sense_id")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LinkedWordSenseRecord {
+    pub link_id: u64,
+    pub sense_id: u64,
+}
+
+impl TryFrom<&Row<'_>> for LinkedWordSenseRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(LinkedWordSenseRecord {
+            link_id: row.get("link_id")?,
+            sense_id: row.get("sense_id")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LabelRecord {
+    pub label_id: u64,
+    pub kind: LabelKind,
+    pub name: String,
+    pub description: String,
+    pub rank_weight: f64,
+}
+
+impl TryFrom<&Row<'_>> for LabelRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(LabelRecord {
+            label_id: row.get("label_id")?,
+            kind: row.get("kind")?,
+            name: row.get("name")?,
+            description: row.get("description")?,
+            rank_weight: row.get("rank_weight")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq)]
+pub struct LabelAttributionRecord {
+    pub word_id: u64,
+    pub label_id: u64,
+}
+
+impl TryFrom<&Row<'_>> for LabelAttributionRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(LabelAttributionRecord {
+            word_id: row.get("word_id")?,
+            label_id: row.get("label_id")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SenseLabelRecord {
+    pub sense_id: u64,
+    pub label_id: u64,
+}
+
+impl TryFrom<&Row<'_>> for SenseLabelRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(SenseLabelRecord {
+            sense_id: row.get("sense_id")?,
+            label_id: row.get("label_id")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReferenceRecord {
+    pub reference_id: u64,
+    pub kind: ReferenceKind,
+    pub author: String,
+    pub title: String,
+    pub year: Option<u32>,
+    pub publisher: Option<String>,
+    pub url: Option<String>,
+}
+
+impl TryFrom<&Row<'_>> for ReferenceRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(ReferenceRecord {
+            reference_id: row.get("reference_id")?,
+            kind: row.get("kind")?,
+            author: row.get("author")?,
+            title: row.get("title")?,
+            year: row.get("year")?,
+            publisher: row.get("publisher")?,
+            url: row.get("url")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CitationRecord {
+    pub citation_id: u64,
+    pub reference_id: u64,
+    pub word_id: u64,
+    pub sense_id: Option<u64>,
+    pub example_id: Option<u64>,
+    pub pages: String,
+}
+
+impl TryFrom<&Row<'_>> for CitationRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(CitationRecord {
+            citation_id: row.get("citation_id")?,
+            reference_id: row.get("reference_id")?,
+            word_id: row.get("word_id")?,
+            sense_id: row.get("sense_id")?,
+            example_id: row.get("example_id")?,
+            pages: row.get("pages")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConjugationOverrideRecord {
+    pub word_id: u64,
+    pub tense: Tense,
+    pub polarity: Polarity,
+    pub subject: SubjectConcord,
+    pub form: String,
+}
+
+impl TryFrom<&Row<'_>> for ConjugationOverrideRecord {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(ConjugationOverrideRecord {
+            word_id: row.get("word_id")?,
+            tense: row.get("tense")?,
+            polarity: row.get("polarity")?,
+            subject: row.get("subject")?,
+            form: row.get("form")?,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ContributionRecord {
     pub word_id: u64,
@@ -437,7 +679,8 @@ fn write_words(cfg: &Config, site: &str, conn: &Connection) -> Result<()> {
     const SELECT_WORDS: &str = "
         SELECT
             word_id, english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
-            is_inchoative, is_informal, transitivity, followed_by, noun_class, note
+            is_inchoative, is_informal, transitivity, followed_by, noun_class, note,
+            etymology_language, etymology_form, etymology_note, literal_translation
         FROM words
         ORDER BY word_id;
     ";
@@ -508,8 +751,9 @@ fn restore_words(cfg: &Config, conn: &Connection) -> Result<()> {
     const INSERT: &str = "
         INSERT INTO words (
             word_id, english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
-            is_inchoative, is_informal, transitivity, followed_by, noun_class, note
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13);
+            is_inchoative, is_informal, transitivity, followed_by, noun_class, note,
+            etymology_language, etymology_form, etymology_note, literal_translation
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17);
     ";
 
     let mut csv = csv_reader(cfg, "words.csv")?;
@@ -531,7 +775,11 @@ fn restore_words(cfg: &Config, conn: &Connection) -> Result<()> {
             w.transitivity,
             w.followed_by.unwrap_or_default(),
             w.noun_class.map(|x| x as u8),
-            w.note
+            w.note,
+            w.etymology_language,
+            w.etymology_form,
+            w.etymology_note,
+            w.literal_translation,
         ])?;
     }
 
@@ -608,6 +856,293 @@ fn restore_linked_words(cfg: &Config, conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+#[allow(clippy::redundant_closure)] // "implementation of FnOnce is not general enough"
+fn write_senses(cfg: &Config, conn: &Connection) -> Result<()> {
+    const SELECT_SENSES: &str = "
+        SELECT sense_id, word_id, sense_order, english, is_informal
+        FROM senses
+        ORDER BY sense_id;
+    ";
+
+    const SELECT_EXAMPLE_SENSES: &str =
+        "SELECT example_id, sense_id FROM example_senses ORDER BY example_id;";
+
+    const SELECT_LINKED_WORD_SENSES: &str =
+        "SELECT link_id, sense_id FROM linked_word_senses ORDER BY link_id, sense_id;";
+
+    let mut csv = csv_writer(cfg, "senses.csv")?;
+    conn.prepare(SELECT_SENSES)?
+        .query(params![])?
+        .map(|row| SenseRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|sense| csv.serialize(sense).map_err(Into::into))?;
+
+    let mut csv = csv_writer(cfg, "example_senses.csv")?;
+    conn.prepare(SELECT_EXAMPLE_SENSES)?
+        .query(params![])?
+        .map(|row| ExampleSenseRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|rec| csv.serialize(rec).map_err(Into::into))?;
+
+    let mut csv = csv_writer(cfg, "linked_word_senses.csv")?;
+    conn.prepare(SELECT_LINKED_WORD_SENSES)?
+        .query(params![])?
+        .map(|row| LinkedWordSenseRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|rec| csv.serialize(rec).map_err(Into::into))
+}
+
+fn restore_senses(cfg: &Config, conn: &Connection) -> Result<()> {
+    const INSERT_SENSE: &str = "
+        INSERT INTO senses (sense_id, word_id, sense_order, english, is_informal)
+        VALUES (?1, ?2, ?3, ?4, ?5);
+    ";
+
+    const INSERT_EXAMPLE_SENSE: &str =
+        "INSERT INTO example_senses (example_id, sense_id) VALUES (?1, ?2);";
+
+    const INSERT_LINKED_WORD_SENSE: &str =
+        "INSERT INTO linked_word_senses (link_id, sense_id) VALUES (?1, ?2);";
+
+    // Exports from before senses existed don't have these files
+    if !cfg.plaintext_export_path.join("senses.csv").exists() {
+        return Ok(());
+    }
+
+    let mut csv = csv_reader(cfg, "senses.csv")?;
+    let mut insert = conn.prepare(INSERT_SENSE)?;
+
+    for res in csv.deserialize() {
+        let s: SenseRecord = res?;
+        insert.execute(params![
+            s.sense_id,
+            s.word_id,
+            s.sense_order,
+            s.english,
+            s.is_informal
+        ])?;
+    }
+
+    let mut csv = csv_reader(cfg, "example_senses.csv")?;
+    let mut insert = conn.prepare(INSERT_EXAMPLE_SENSE)?;
+
+    for res in csv.deserialize() {
+        let e: ExampleSenseRecord = res?;
+        insert.execute(params![e.example_id, e.sense_id])?;
+    }
+
+    let mut csv = csv_reader(cfg, "linked_word_senses.csv")?;
+    let mut insert = conn.prepare(INSERT_LINKED_WORD_SENSE)?;
+
+    for res in csv.deserialize() {
+        let l: LinkedWordSenseRecord = res?;
+        insert.execute(params![l.link_id, l.sense_id])?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_closure)] // "implementation of FnOnce is not general enough"
+fn write_labels(cfg: &Config, conn: &Connection) -> Result<()> {
+    const SELECT_LABELS: &str = "
+        SELECT label_id, kind, name, description, rank_weight FROM labels ORDER BY label_id;
+    ";
+
+    const SELECT_WORD_LABELS: &str =
+        "SELECT word_id, label_id FROM word_labels ORDER BY word_id, label_id;";
+
+    const SELECT_SENSE_LABELS: &str =
+        "SELECT sense_id, label_id FROM sense_labels ORDER BY sense_id, label_id;";
+
+    let mut csv = csv_writer(cfg, "labels.csv")?;
+    conn.prepare(SELECT_LABELS)?
+        .query(params![])?
+        .map(|row| LabelRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|label| csv.serialize(label).map_err(Into::into))?;
+
+    let mut csv = csv_writer(cfg, "word_labels.csv")?;
+    conn.prepare(SELECT_WORD_LABELS)?
+        .query(params![])?
+        .map(|row| LabelAttributionRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|rec| csv.serialize(rec).map_err(Into::into))?;
+
+    let mut csv = csv_writer(cfg, "sense_labels.csv")?;
+    conn.prepare(SELECT_SENSE_LABELS)?
+        .query(params![])?
+        .map(|row| SenseLabelRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|rec| csv.serialize(rec).map_err(Into::into))
+}
+
+fn restore_labels(cfg: &Config, conn: &Connection) -> Result<()> {
+    const INSERT_LABEL: &str = "
+        INSERT INTO labels (label_id, kind, name, description, rank_weight)
+        VALUES (?1, ?2, ?3, ?4, ?5);
+    ";
+
+    const INSERT_WORD_LABEL: &str = "INSERT INTO word_labels (word_id, label_id) VALUES (?1, ?2);";
+
+    const INSERT_SENSE_LABEL: &str =
+        "INSERT INTO sense_labels (sense_id, label_id) VALUES (?1, ?2);";
+
+    // Exports from before labels existed don't have these files
+    if !cfg.plaintext_export_path.join("labels.csv").exists() {
+        return Ok(());
+    }
+
+    let mut csv = csv_reader(cfg, "labels.csv")?;
+    let mut insert = conn.prepare(INSERT_LABEL)?;
+
+    for res in csv.deserialize() {
+        let l: LabelRecord = res?;
+        insert.execute(params![
+            l.label_id,
+            l.kind,
+            l.name,
+            l.description,
+            l.rank_weight
+        ])?;
+    }
+
+    let mut csv = csv_reader(cfg, "word_labels.csv")?;
+    let mut insert = conn.prepare(INSERT_WORD_LABEL)?;
+
+    for res in csv.deserialize() {
+        let l: LabelAttributionRecord = res?;
+        insert.execute(params![l.word_id, l.label_id])?;
+    }
+
+    let mut csv = csv_reader(cfg, "sense_labels.csv")?;
+    let mut insert = conn.prepare(INSERT_SENSE_LABEL)?;
+
+    for res in csv.deserialize() {
+        let l: SenseLabelRecord = res?;
+        insert.execute(params![l.sense_id, l.label_id])?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_closure)] // "implementation of FnOnce is not general enough"
+fn write_references(cfg: &Config, conn: &Connection) -> Result<()> {
+    const SELECT_REFERENCES: &str = "
+        SELECT reference_id, kind, author, title, year, publisher, url FROM bibliography
+            ORDER BY reference_id;
+    ";
+
+    const SELECT_CITATIONS: &str = "
+        SELECT citation_id, reference_id, word_id, sense_id, example_id, pages FROM citations
+            ORDER BY citation_id;
+    ";
+
+    let mut csv = csv_writer(cfg, "references.csv")?;
+    conn.prepare(SELECT_REFERENCES)?
+        .query(params![])?
+        .map(|row| ReferenceRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|reference| csv.serialize(reference).map_err(Into::into))?;
+
+    let mut csv = csv_writer(cfg, "citations.csv")?;
+    conn.prepare(SELECT_CITATIONS)?
+        .query(params![])?
+        .map(|row| CitationRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|citation| csv.serialize(citation).map_err(Into::into))
+}
+
+fn restore_references(cfg: &Config, conn: &Connection) -> Result<()> {
+    const INSERT_REFERENCE: &str = "
+        INSERT INTO bibliography (reference_id, kind, author, title, year, publisher, url)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);
+    ";
+
+    const INSERT_CITATION: &str = "
+        INSERT INTO citations (citation_id, reference_id, word_id, sense_id, example_id, pages)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6);
+    ";
+
+    // Exports from before references existed don't have these files
+    if !cfg.plaintext_export_path.join("references.csv").exists() {
+        return Ok(());
+    }
+
+    let mut csv = csv_reader(cfg, "references.csv")?;
+    let mut insert = conn.prepare(INSERT_REFERENCE)?;
+
+    for res in csv.deserialize() {
+        let r: ReferenceRecord = res?;
+        insert.execute(params![
+            r.reference_id,
+            r.kind,
+            r.author,
+            r.title,
+            r.year,
+            r.publisher,
+            r.url
+        ])?;
+    }
+
+    let mut csv = csv_reader(cfg, "citations.csv")?;
+    let mut insert = conn.prepare(INSERT_CITATION)?;
+
+    for res in csv.deserialize() {
+        let c: CitationRecord = res?;
+        insert.execute(params![
+            c.citation_id,
+            c.reference_id,
+            c.word_id,
+            c.sense_id,
+            c.example_id,
+            c.pages
+        ])?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::redundant_closure)] // "implementation of FnOnce is not general enough"
+fn write_conjugation_overrides(cfg: &Config, conn: &Connection) -> Result<()> {
+    const SELECT_OVERRIDES: &str = "
+        SELECT word_id, tense, polarity, subject, form FROM conjugation_overrides
+            ORDER BY word_id, tense, polarity, subject;
+    ";
+
+    let mut csv = csv_writer(cfg, "conjugation_overrides.csv")?;
+    conn.prepare(SELECT_OVERRIDES)?
+        .query(params![])?
+        .map(|row| ConjugationOverrideRecord::try_from(row))
+        .map_err(|e| -> anyhow::Error { e.into() })
+        .for_each(|o| csv.serialize(o).map_err(Into::into))
+}
+
+fn restore_conjugation_overrides(cfg: &Config, conn: &Connection) -> Result<()> {
+    const INSERT_OVERRIDE: &str = "
+        INSERT INTO conjugation_overrides (word_id, tense, polarity, subject, form)
+        VALUES (?1, ?2, ?3, ?4, ?5);
+    ";
+
+    // Exports from before conjugation overrides existed don't have this file
+    if !cfg
+        .plaintext_export_path
+        .join("conjugation_overrides.csv")
+        .exists()
+    {
+        return Ok(());
+    }
+
+    let mut csv = csv_reader(cfg, "conjugation_overrides.csv")?;
+    let mut insert = conn.prepare(INSERT_OVERRIDE)?;
+
+    for res in csv.deserialize() {
+        let o: ConjugationOverrideRecord = res?;
+        insert.execute(params![o.word_id, o.tense, o.polarity, o.subject, o.form])?;
+    }
+
+    Ok(())
+}
+
 #[allow(clippy::redundant_closure)] // "implementation of FnOnce is not general enough"
 fn write_contributions(cfg: &Config, conn: &Connection) -> Result<()> {
     const SELECT: &str = "
@@ -723,3 +1258,12 @@ fn sweep_tokens(conn: &Connection) -> Result<()> {
 
     Ok(())
 }
+
+fn sweep_trash(conn: &Connection) -> Result<()> {
+    const DELETE: &str = "DELETE FROM trash WHERE JULIANDAY(?1) - JULIANDAY(deleted_at) > ?2;";
+
+    conn.prepare(DELETE)?
+        .execute(params![Utc::now(), TRASH_RETENTION_DAYS as f64])?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::auth::with_any_auth;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, PublicAccessDb};
+use isixhosa_common::types::ExistingWord;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "expressions.askama.html")]
+struct Expressions {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    expressions: Vec<ExistingWord>,
+}
+
+pub fn expressions(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    warp::path("expressions")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_any_auth(db, site_ctx))
+        .and_then(expressions_page)
+        .debug_boxed()
+}
+
+#[instrument(name = "Display expressions page", skip(auth, db))]
+async fn expressions_page(
+    auth: Auth,
+    i18n_info: I18nInfo,
+    db: impl PublicAccessDb,
+) -> Result<impl Reply, Rejection> {
+    spawn_blocking_child(move || {
+        let expressions = ExistingWord::fetch_all_expressions(&db);
+
+        Ok(Expressions {
+            auth,
+            i18n_info,
+            expressions,
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,65 @@
+use crate::auth::with_any_auth;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
+use askama::Template;
+use isixhosa::noun::NounClass;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::concord::ConcordTable;
+use isixhosa_common::database::{DbBase, PublicAccessDb};
+use isixhosa_common::format::{DisplayHtml, NounClassSelect};
+use isixhosa_common::types::WordHit;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "noun_class.askama.html")]
+struct NounClassPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    noun_class: NounClass,
+    concords: ConcordTable,
+    nouns: Vec<WordHit>,
+}
+
+pub fn grammar(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    warp::path!("grammar" / "noun-class" / String)
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_any_auth(db, site_ctx))
+        .and_then(noun_class_page)
+        .debug_boxed()
+}
+
+#[instrument(name = "Display noun class page", skip(auth, db))]
+async fn noun_class_page(
+    number: String,
+    auth: Auth,
+    i18n_info: I18nInfo,
+    db: impl PublicAccessDb,
+) -> Result<impl Reply, Rejection> {
+    // Classes are identified by their number as written in grammars, e.g. 1a
+    let Some(noun_class) = NounClass::iter().find(|c| c.to_number() == number.as_str()) else {
+        return Ok(NotFound { auth, i18n_info }.into_response());
+    };
+
+    spawn_blocking_child(move || {
+        let nouns = WordHit::fetch_all_in_noun_class(&db, noun_class);
+
+        Ok(NounClassPage {
+            auth,
+            i18n_info,
+            noun_class,
+            concords: ConcordTable::for_noun_class(noun_class),
+            nouns,
+        }
+        .into_response())
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,106 @@
+use crate::auth::{with_any_auth, with_moderator_auth, FullUser};
+use crate::database::revisions::WordRevision;
+use crate::database::scopes;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::search::TantivyClient;
+use crate::{spawn_blocking_child, DebugBoxedExt};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, ModeratorAccessDb, PublicAccessDb, WordOrSuggestionId};
+use isixhosa_common::format::DisplayHtml;
+use isixhosa_common::types::WordHit;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "word_history.askama.html")]
+struct WordHistory {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    word_id: u64,
+    /// `None` if the word has been deleted
+    word: Option<WordHit>,
+    revisions: Vec<WordRevision>,
+    previous_success: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RevertForm {
+    revision: u64,
+}
+
+pub fn history(
+    db: DbBase,
+    tantivy: Arc<TantivyClient>,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let show_history = warp::get()
+        .and(warp::path![u64 / "history"])
+        .and(warp::path::end())
+        .and(warp::any().map(|| None)) // previous_success is None
+        .and(with_any_auth(db.clone(), site_ctx.clone()))
+        .and_then(history_page);
+
+    let revert = warp::post()
+        .and(warp::path![u64 / "history"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(4 * 1024))
+        .and(body::form::<RevertForm>())
+        .and(warp::any().map(move || tantivy.clone()))
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(revert_to_revision);
+
+    warp::path("word")
+        .and(show_history.or(revert))
+        .debug_boxed()
+}
+
+#[instrument(name = "Display word history page", skip(auth, db))]
+async fn history_page(
+    word_id: u64,
+    previous_success: Option<bool>,
+    auth: Auth,
+    i18n_info: I18nInfo,
+    db: impl PublicAccessDb,
+) -> Result<impl Reply, Rejection> {
+    spawn_blocking_child(move || {
+        let word = WordHit::fetch_from_db(&db, WordOrSuggestionId::existing(word_id));
+        let revisions = WordRevision::fetch_all_for_word(&db, &i18n_info, word_id);
+
+        Ok(WordHistory {
+            auth,
+            i18n_info,
+            word_id,
+            word,
+            revisions,
+            previous_success,
+        })
+    })
+    .await
+    .unwrap()
+}
+
+#[instrument(name = "Revert word to revision", skip(tantivy, user, db))]
+async fn revert_to_revision(
+    word_id: u64,
+    form: RevertForm,
+    tantivy: Arc<TantivyClient>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let success = spawn_blocking_child(move || {
+        scopes::may_moderate_word(&db_clone, reviewer, word_id)
+            && WordRevision::revert(&db_clone, tantivy, word_id, form.revision, reviewer)
+    })
+    .await
+    .unwrap();
+
+    history_page(word_id, Some(success), user.into(), i18n_info, db).await
+}
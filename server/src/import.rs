@@ -89,8 +89,9 @@ pub fn insert_suggested_word_with_info(
 ) -> i64 {
     const INSERT: &str = "INSERT INTO word_suggestions
         (english, xhosa, xhosa_tone_markings, infinitive, is_plural, is_inchoative, is_informal,
-        followed_by, note, part_of_speech, noun_class, transitivity, changes_summary, suggesting_user)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14);";
+        followed_by, note, part_of_speech, noun_class, transitivity, changes_summary, suggesting_user,
+        etymology_form, etymology_note, literal_translation)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17);";
 
     let mut insert = conn.prepare(INSERT).unwrap();
 
@@ -110,6 +111,9 @@ pub fn insert_suggested_word_with_info(
             transitivity,
             "",
             1, // Just use user #1
+            "",
+            "",
+            "",
         ])
         .unwrap();
     conn.last_insert_rowid()
@@ -1,18 +1,14 @@
 // Soon after launch, perhaps before:
-// - informal/archaic meanings
-// - standalone example & linked word suggestion editing
 // - forum for xhosa questions
 // - error handling - dont crash always probably & on panic, always crash (viz. tokio workers)!
 // - better search engine optimisation
 // - cache control headers/etags
-// - attributions - references
 // - learn page with additional resources/links page
 
 // Well after launch:
 // - rate limiting
 // - integration testing
 // - tracing for logging over log: open telemetry/ELK stack or similar?
-// - conjugation tables
 // - user profiles showing statistics (for mods primarily but maybe can publicise it?)
 // - semantic fields/categories linking related words to browse all at once
 // - grammar notes
@@ -29,23 +25,36 @@ use askama::Template;
 use auth::auth;
 use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
+use conjugation::conjugation;
+use counterpart::counterpart;
 use details::details;
+use discussion::discussion;
 use edit::edit;
+use expressions::expressions;
 use fluent_templates::Loader;
 use futures::StreamExt;
+use grammar::grammar;
+use history::history;
 use isixhosa_click_macros::I18nTemplate;
 use isixhosa_common::auth::{Auth, Permissions};
+use isixhosa_common::database::db_impl::DbImpl;
 use isixhosa_common::database::{with_public_db, DbBase, ModeratorAccessDb, PublicAccessDb};
 use isixhosa_common::format::DisplayHtml;
-use isixhosa_common::types::{Dataset, ExistingWord, WordHit};
+use isixhosa_common::types::{Dataset, ExistingWord, Label, WordHit};
+use merge::merge;
 use moderation::moderation;
+use my_suggestions::my_suggestions;
 use opentelemetry::{global, KeyValue};
 use opentelemetry_sdk::Resource;
 use percent_encoding::NON_ALPHANUMERIC;
+use preview::preview;
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::{params, Connection};
+use recent_changes::recent_changes;
+use references::references;
+use rusqlite::Connection;
 use serde::Deserialize;
+use split::split;
 use std::collections::HashSet;
 use std::convert::Infallible;
 use std::fmt::Debug;
@@ -54,10 +63,14 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use submit::submit;
+use suggestion_edit::suggestion_edit;
 use tokio::task::JoinHandle;
 use tracing::{debug, info, instrument, Span};
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, EnvFilter, Layer, Registry};
+use trash::trash;
+use trusted::trusted_edits;
+use voting::voting;
 use walkdir::DirEntry;
 use warp::filters::compression::gzip;
 #[cfg(debug_assertions)]
@@ -77,18 +90,34 @@ pub use isixhosa_common::{i18n_args, icon};
 mod admin;
 mod auth;
 mod config;
+mod conjugation;
+mod counterpart;
 mod database;
 mod details;
+mod discussion;
 mod edit;
 mod export;
+mod expressions;
+mod grammar;
+mod history;
 mod i18n;
 mod import;
+mod merge;
 mod moderation;
+mod my_suggestions;
+mod preview;
+mod recent_changes;
+mod references;
 mod search;
 mod serialization;
 mod session;
+mod split;
 mod submit;
+mod suggestion_edit;
+mod trash;
+mod trusted;
 mod user_management;
+mod voting;
 
 use crate::admin::admin;
 use crate::i18n::I18nInfo;
@@ -171,6 +200,35 @@ enum UserCommand {
     List,
     /// Logs out all users
     LogoutAll,
+    /// Makes users whose suggestions are reliably accepted trusted contributors, so that their
+    /// suggestions are applied immediately and only reviewed afterwards.
+    PromoteTrusted {
+        /// The least number of suggestions the user must have had accepted by moderators
+        #[arg(long, default_value_t = 25)]
+        min_accepted: u32,
+        /// The least fraction of the user's reviewed suggestions which must have been accepted
+        #[arg(long, default_value_t = 0.9)]
+        min_acceptance_rate: f64,
+    },
+    /// Limit a moderator to the words in a dataset or with a label. A moderator with several scopes
+    /// may moderate the words in any of them, and one with none may moderate all words.
+    AddScope {
+        /// The moderator's email
+        user: String,
+        /// The id of the dataset
+        #[arg(long, conflicts_with = "label", required_unless_present = "label")]
+        dataset: Option<u64>,
+        /// The id of the label
+        #[arg(long)]
+        label: Option<u64>,
+    },
+    /// Remove all of a moderator's scopes, so that they may moderate all words again.
+    ClearScopes {
+        /// The moderator's email
+        user: String,
+    },
+    /// List the scopes of all moderators
+    ListScopes,
 }
 
 fn main() -> Result<()> {
@@ -393,9 +451,18 @@ async fn handle_error(err: Rejection) -> Result<Response, Rejection> {
     }
 }
 
+/// Changes made to tables after they were first created, in the order they were made. The
+/// `user_version` of the database is the number of these which have been applied to it.
+const MIGRATIONS: [&str; 2] = [
+    include_str!("sql/migrations/1.sql"),
+    include_str!("sql/migrations/2.sql"),
+];
+
+/// Creates the tables and applies any migrations, returning whether any were applied. The search
+/// index must be rebuilt after a migration, as migrations may change what is indexed.
 #[instrument("Set up database PRAGMAs and tables", skip_all)]
-pub fn set_up_db(conn: &Connection) -> Result<()> {
-    const CREATIONS: [&str; 15] = [
+pub fn set_up_db(conn: &Connection) -> Result<bool> {
+    const CREATIONS: [&str; 44] = [
         include_str!("sql/users.sql"),
         include_str!("sql/words.sql"),
         include_str!("sql/user_attributions.sql"),
@@ -411,6 +478,36 @@ pub fn set_up_db(conn: &Connection) -> Result<()> {
         include_str!("sql/datasets.sql"),
         include_str!("sql/dataset_attributions.sql"),
         include_str!("sql/dataset_attribution_suggestions.sql"),
+        include_str!("sql/word_changes.sql"),
+        include_str!("sql/word_revisions.sql"),
+        include_str!("sql/trash.sql"),
+        include_str!("sql/word_redirects.sql"),
+        include_str!("sql/senses.sql"),
+        include_str!("sql/example_senses.sql"),
+        include_str!("sql/linked_word_senses.sql"),
+        include_str!("sql/sense_suggestions.sql"),
+        include_str!("sql/example_sense_suggestions.sql"),
+        include_str!("sql/linked_word_sense_suggestions.sql"),
+        include_str!("sql/labels.sql"),
+        include_str!("sql/word_labels.sql"),
+        include_str!("sql/sense_labels.sql"),
+        include_str!("sql/label_suggestions.sql"),
+        include_str!("sql/bibliography.sql"),
+        include_str!("sql/citations.sql"),
+        include_str!("sql/conjugation_overrides.sql"),
+        include_str!("sql/suggestion_outcomes.sql"),
+        include_str!("sql/reviewer_edited_suggestions.sql"),
+        include_str!("sql/suggestion_comments.sql"),
+        include_str!("sql/suggestions_needing_info.sql"),
+        include_str!("sql/review_policies.sql"),
+        include_str!("sql/suggestion_approvals.sql"),
+        include_str!("sql/suggestion_votes.sql"),
+        include_str!("sql/trusted_contributors.sql"),
+        include_str!("sql/trusted_edits.sql"),
+        include_str!("sql/moderator_scopes.sql"),
+        include_str!("sql/suggestion_submissions.sql"),
+        include_str!("sql/word_suggestion_bases.sql"),
+        include_str!("sql/word_suggestion_duplicates.sql"),
     ];
 
     // See https://github.com/the-lean-crate/criner/discussions/5
@@ -423,11 +520,40 @@ pub fn set_up_db(conn: &Connection) -> Result<()> {
     ",
     )?;
 
+    let is_new: bool = conn.query_row(
+        "SELECT NOT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'words');",
+        [],
+        |row| row.get(0),
+    )?;
+
     for creation in &CREATIONS {
-        conn.execute(creation, params![])?;
+        conn.execute_batch(creation)?;
     }
 
-    Ok(())
+    migrate_db(conn, is_new)
+}
+
+/// Applies the migrations which have not yet been applied to the database, returning whether any
+/// were. A new database is created with every column already, so none are applied to it.
+#[instrument("Migrate database", skip(conn))]
+fn migrate_db(conn: &Connection, is_new: bool) -> Result<bool> {
+    if is_new {
+        conn.pragma_update(None, "user_version", MIGRATIONS.len())?;
+        return Ok(false);
+    }
+
+    let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    for (applied, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        info!("Applying database migration {}", applied + 1);
+
+        let transaction = conn.unchecked_transaction()?;
+        transaction.execute_batch(migration)?;
+        transaction.pragma_update(None, "user_version", applied + 1)?;
+        transaction.commit()?;
+    }
+
+    Ok(version < MIGRATIONS.len())
 }
 
 // I cannot be bothered trying to find the right type
@@ -469,23 +595,22 @@ async fn server(cfg: Config, args: CliArgs) -> Result<()> {
     let manager = SqliteConnectionManager::file(&cfg.database_path);
     let pool = Pool::new(manager)?;
     let pool_clone = pool.clone();
-    spawn_blocking_child(move || set_up_db(&*pool_clone.get()?)).await??;
+    let migrated = spawn_blocking_child(move || set_up_db(&*pool_clone.get()?)).await??;
 
     let tantivy = TantivyClient::start(&cfg.tantivy_path, pool.clone()).await?;
 
+    if migrated {
+        info!("Reindexing database after migration");
+        tantivy.reindex_database(DbImpl(pool.clone())).await;
+    }
+
     let tantivy_cloned = tantivy.clone();
     let with_tantivy = warp::any().map(move || tantivy_cloned.clone());
     let db = DbBase::new(pool);
     let site_ctx = Arc::new(i18n::load(args.site.clone(), &cfg));
 
     let search = {
-        let search_page =
-            with_any_auth(db.clone(), site_ctx.clone()).map(|auth, i18n_info, _db| Search {
-                auth,
-                i18n_info,
-                hits: Default::default(),
-                query: Default::default(),
-            });
+        let search_page = with_any_auth(db.clone(), site_ctx.clone()).and_then(search_page);
 
         let query_search = path::end()
             .and(warp::query())
@@ -676,8 +801,24 @@ async fn server(cfg: Config, args: CliArgs) -> Result<()> {
         .debug_boxed()
         .or(submit(db.clone(), tantivy.clone(), site_ctx.clone()))
         .or(moderation(db.clone(), tantivy.clone(), site_ctx.clone()))
-        .or(admin(db.clone(), site_ctx.clone()))
+        .or(trash(db.clone(), tantivy.clone(), site_ctx.clone()))
+        .or(trusted_edits(db.clone(), tantivy.clone(), site_ctx.clone()))
+        .or(admin(db.clone(), tantivy.clone(), site_ctx.clone()))
         .or(details(db.clone(), site_ctx.clone()))
+        .or(preview(db.clone(), site_ctx.clone()))
+        .or(recent_changes(db.clone(), site_ctx.clone()))
+        .or(expressions(db.clone(), site_ctx.clone()))
+        .or(grammar(db.clone(), site_ctx.clone()))
+        .or(history(db.clone(), tantivy.clone(), site_ctx.clone()))
+        .or(merge(db.clone(), tantivy.clone(), site_ctx.clone()))
+        .or(split(db.clone(), tantivy.clone(), site_ctx.clone()))
+        .or(references(db.clone(), site_ctx.clone()))
+        .or(conjugation(db.clone(), site_ctx.clone()))
+        .or(counterpart(db.clone(), tantivy.clone(), site_ctx.clone()))
+        .or(suggestion_edit(db.clone(), site_ctx.clone()))
+        .or(my_suggestions(db.clone(), site_ctx.clone()))
+        .or(discussion(db.clone(), site_ctx.clone()))
+        .or(voting(db.clone(), site_ctx.clone()))
         .or(edit(db.clone(), tantivy, site_ctx.clone()))
         .or(auth(db.clone(), &cfg, site_ctx.clone()).await)
         .debug_boxed()
@@ -752,6 +893,9 @@ struct SearchQuery {
     query: String,
     #[serde(default = "false_fn")]
     raw: bool,
+    /// Only include words with this label
+    #[serde(default)]
+    label: Option<u64>,
 }
 
 #[derive(Template, I18nTemplate, Clone, Debug)]
@@ -810,6 +954,25 @@ struct Search {
     i18n_info: I18nInfo,
     hits: Vec<WordHit>,
     query: String,
+    labels: Vec<Label>,
+    label: Option<u64>,
+}
+
+async fn search_page(
+    auth: Auth,
+    i18n_info: I18nInfo,
+    db: impl PublicAccessDb,
+) -> Result<impl Reply, Rejection> {
+    Ok(Search {
+        auth,
+        i18n_info,
+        hits: Default::default(),
+        query: Default::default(),
+        labels: spawn_blocking_child(move || Label::fetch_all(&db))
+            .await
+            .unwrap(),
+        label: None,
+    })
 }
 
 #[instrument(
@@ -817,6 +980,7 @@ struct Search {
     fields(
         query = %query.query,
         raw = %query.raw,
+        label = ?query.label,
     ),
     skip_all,
 )]
@@ -825,13 +989,14 @@ async fn query_search(
     tantivy: Arc<TantivyClient>,
     auth: Auth,
     i18n_info: I18nInfo,
-    _db: impl PublicAccessDb,
+    db: impl PublicAccessDb,
 ) -> Result<impl Reply, Rejection> {
     let results = tantivy
         .search(
             query.query.clone(),
             IncludeResults::AcceptedOnly,
             false,
+            query.label.into_iter().collect(),
             i18n_info.clone(),
         )
         .await
@@ -843,6 +1008,10 @@ async fn query_search(
             i18n_info,
             query: query.query,
             hits: results,
+            labels: spawn_blocking_child(move || Label::fetch_all(&db))
+                .await
+                .unwrap(),
+            label: query.label,
         };
 
         Ok(askama_warp::reply(&template))
@@ -870,36 +1039,53 @@ async fn duplicate_search(
 ) -> Result<impl Reply, Rejection> {
     let suggestion = SuggestedWord::fetch_alone(&db, query.suggestion.get());
 
-    let include = IncludeResults::AcceptedAndAllSuggestions;
-    let res = match suggestion.filter(|w| w.word_id.is_none()) {
-        Some(w) => {
-            let english = tantivy
-                .search(w.english.current().clone(), include, true, i18n.clone())
-                .await
-                .unwrap();
-            let xhosa = tantivy
-                .search(w.xhosa.current().clone(), include, true, i18n)
-                .await
-                .unwrap();
-
-            let mut results: HashSet<JsWordHit> =
-                HashSet::with_capacity(english.len() + xhosa.len());
-            results.extend(english);
-            results.extend(xhosa);
-            // Exclude this suggestion and the original of this suggestion (the word being edited)
-            results.retain(|res| {
-                let is_this_suggestion = res.id == query.suggestion.get() && res.is_suggestion;
-                let is_original = Some(res.id) == w.word_id && !res.is_suggestion;
-                !(is_this_suggestion || is_original)
-            });
-            results
-        }
+    let res = match suggestion {
+        Some(w) => find_duplicates(&tantivy, i18n, &w).await,
         None => HashSet::new(),
     };
 
     Ok(reply::json(&res))
 }
 
+/// Searches for words and suggestions which could be duplicates of the suggested word. Only new
+/// words are checked, so suggested edits to existing words have no duplicates.
+pub async fn find_duplicates(
+    tantivy: &TantivyClient,
+    i18n: I18nInfo,
+    w: &SuggestedWord,
+) -> HashSet<JsWordHit> {
+    if w.word_id.is_some() {
+        return HashSet::new();
+    }
+
+    let include = IncludeResults::AcceptedAndAllSuggestions;
+    let english = tantivy
+        .search(
+            w.english.current().clone(),
+            include,
+            true,
+            Vec::new(),
+            i18n.clone(),
+        )
+        .await
+        .unwrap();
+    let xhosa = tantivy
+        .search(w.xhosa.current().clone(), include, true, Vec::new(), i18n)
+        .await
+        .unwrap();
+
+    let mut results: HashSet<JsWordHit> = HashSet::with_capacity(english.len() + xhosa.len());
+    results.extend(english);
+    results.extend(xhosa);
+    // Exclude this suggestion and the original of this suggestion (the word being edited)
+    results.retain(|res| {
+        let is_this_suggestion = res.id == w.suggestion_id && res.is_suggestion;
+        let is_original = Some(res.id) == w.word_id && !res.is_suggestion;
+        !(is_this_suggestion || is_original)
+    });
+    results
+}
+
 #[instrument(
     name = "Begin live search websocket connection",
     fields(include_own_suggestions = %params.include_own_suggestions.unwrap_or_default()),
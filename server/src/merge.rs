@@ -0,0 +1,118 @@
+use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::merge::merge_words;
+use crate::database::scopes;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::search::TantivyClient;
+use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, ModeratorAccessDb, WordOrSuggestionId};
+use isixhosa_common::format::DisplayHtml;
+use isixhosa_common::types::WordHit;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::http::Uri;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "merge.askama.html")]
+struct MergeWord {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    word: WordHit,
+    previous_success: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+struct MergeForm {
+    into: u64,
+}
+
+pub fn merge(
+    db: DbBase,
+    tantivy: Arc<TantivyClient>,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let merge_page = warp::get()
+        .and(warp::path![u64 / "merge"])
+        .and(warp::path::end())
+        .and(warp::any().map(|| None)) // previous_success is None
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(merge_word_page);
+
+    let submit_merge = warp::post()
+        .and(warp::path![u64 / "merge"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(4 * 1024))
+        .and(body::form::<MergeForm>())
+        .and(warp::any().map(move || tantivy.clone()))
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(submit_merge_form);
+
+    warp::path("word")
+        .and(merge_page.or(submit_merge))
+        .debug_boxed()
+}
+
+#[instrument(name = "Display merge word page", skip(user, db))]
+async fn merge_word_page(
+    word_id: u64,
+    previous_success: Option<bool>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let word = spawn_blocking_child(move || {
+        WordHit::fetch_from_db(&db, WordOrSuggestionId::existing(word_id))
+    })
+    .await
+    .unwrap();
+
+    Ok(match word {
+        Some(word) => MergeWord {
+            auth: user.into(),
+            i18n_info,
+            word,
+            previous_success,
+        }
+        .into_response(),
+        None => NotFound {
+            auth: user.into(),
+            i18n_info,
+        }
+        .into_response(),
+    })
+}
+
+#[instrument(name = "Merge word", skip(tantivy, user, db))]
+async fn submit_merge_form(
+    word_id: u64,
+    form: MergeForm,
+    tantivy: Arc<TantivyClient>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let into = form.into;
+    let merged = spawn_blocking_child(move || {
+        scopes::may_moderate_word(&db_clone, reviewer, word_id)
+            && scopes::may_moderate_word(&db_clone, reviewer, into)
+            && merge_words(&db_clone, tantivy, word_id, into, reviewer)
+    })
+    .await
+    .unwrap();
+
+    if merged {
+        let uri: Uri = format!("/word/{into}").parse().unwrap();
+        Ok(warp::redirect::see_other(uri).into_response())
+    } else {
+        merge_word_page(word_id, Some(false), user, i18n_info, db)
+            .await
+            .map(Reply::into_response)
+    }
+}
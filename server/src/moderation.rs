@@ -1,32 +1,53 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU64;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::conflicts::{self, Conflict};
 use crate::database::deletion::{
     ExampleDeletionSuggestion, LinkedWordDeletionSuggestion, WordDeletionSuggestion,
 };
-use crate::database::submit::{submit_suggestion, WordSubmission};
+use crate::database::discussion::{self, DiscussionStatus};
+use crate::database::outcomes::{
+    RejectionReason, SuggestionOutcome, SuggestionSummary, SuggestionType,
+};
+use crate::database::queue::{self, QueueQuery, QueueSection};
+use crate::database::review::{self, AppliedPolicy, ReviewTally};
+use crate::database::scopes::{self, ModeratorScopes};
+use crate::database::submit::{
+    edit_example_suggestion, edit_linked_word_suggestion, submit_suggestion, ExampleSuggestionEdit,
+    LinkedWordSuggestionEdit, WordSubmission,
+};
 use crate::database::suggestion::{
-    DatasetAttributionSuggestion, SuggestedExample, SuggestedLinkedWord, SuggestedWord,
+    DatasetAttributionSuggestion, LabelSuggestion, SuggestedExample, SuggestedLinkedWord,
+    SuggestedSense, SuggestedWord,
 };
+use crate::export::WordRecord;
 use crate::i18n::I18nInfo;
 use crate::i18n::SiteContext;
-use crate::search::TantivyClient;
+use crate::search::{IndexBatch, TantivyClient, WordDocument};
 use crate::serialization::qs_form;
 use crate::submit::edit_suggestion_page;
-use crate::{spawn_blocking_child, DebugBoxedExt};
+use crate::suggestion_edit::{edit_example_suggestion_page, edit_linked_word_suggestion_page};
+use crate::{find_duplicates, spawn_blocking_child, DebugBoxedExt};
 use askama::Template;
+use futures::executor::block_on;
 use isixhosa_click_macros::I18nTemplate;
 use isixhosa_common::auth::Auth;
 use isixhosa_common::database::WordId;
-use isixhosa_common::database::{DbBase, ModeratorAccessDb, WordOrSuggestionId};
+use isixhosa_common::database::{DbBase, DbTransaction, ModeratorAccessDb, WordOrSuggestionId};
 use isixhosa_common::format::DisplayHtml;
-use isixhosa_common::types::{ExistingWord, WordHit};
+use isixhosa_common::types::{Dataset, ExistingWord, WordHit};
 use serde::Deserialize;
-use serde_with::{serde_as, DisplayFromStr};
+use serde_with::{serde_as, DisplayFromStr, NoneAsEmptyString};
 use tracing::{error, instrument, Span};
+use url::form_urlencoded;
 use warp::{body, Filter, Rejection, Reply};
 
+/// How many words, deletions and words with associated edits are shown on each page of the queue
+const QUEUE_PAGE_SIZE: usize = 25;
+
 #[derive(Template, I18nTemplate, Debug)]
 #[template(path = "moderation.askama.html")]
 struct ModerationTemplate {
@@ -34,8 +55,21 @@ struct ModerationTemplate {
     i18n_info: I18nInfo,
     previous_success: Option<Success>,
     word_suggestions: Vec<SuggestedWord>,
+    /// The ids of the suggested new words on this page which have possible duplicates
+    with_duplicates: HashSet<u64>,
     word_deletions: Vec<WordDeletionSuggestion>,
     word_associated_edits: Vec<(WordHit, WordAssociatedEdits)>,
+    /// Whether the moderator is limited to certain datasets and labels
+    scoped: bool,
+    queue: QueueFilter,
+    /// The number of items in the queue once filtered, across all pages
+    total: usize,
+    pages: usize,
+    datasets: Vec<Dataset>,
+    discussions: HashMap<(SuggestionType, u64), DiscussionStatus>,
+    reviews: HashMap<(SuggestionType, u64), ReviewTally>,
+    /// The conflicts of the suggested edits to existing words on this page, by suggestion id
+    conflicts: HashMap<u64, Vec<Conflict>>,
 }
 
 impl ModerationTemplate {
@@ -45,6 +79,52 @@ impl ModerationTemplate {
             && self.word_associated_edits.is_empty()
     }
 
+    fn discussion(&self, suggestion_type: &str, suggestion_id: &u64) -> DiscussionStatus {
+        suggestion_type
+            .parse()
+            .ok()
+            .and_then(|ty| self.discussions.get(&(ty, *suggestion_id)).copied())
+            .unwrap_or_default()
+    }
+
+    fn review(&self, suggestion_type: &str, suggestion_id: &u64) -> ReviewTally {
+        suggestion_type
+            .parse()
+            .ok()
+            .and_then(|ty| self.reviews.get(&(ty, *suggestion_id)).copied())
+            .unwrap_or_default()
+    }
+
+    fn conflicts(&self, suggestion_id: &u64) -> &[Conflict] {
+        self.conflicts
+            .get(suggestion_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Whether the word was changed since the suggestion was made, so that it must be rebased
+    /// before it can be accepted
+    fn needs_rebase(&self, suggestion_id: &u64) -> bool {
+        self.conflicts(suggestion_id)
+            .iter()
+            .any(|conflict| conflict.other.is_none())
+    }
+
+    fn pending_approval(&self) -> Option<(u32, u32)> {
+        self.previous_success
+            .as_ref()
+            .and_then(|success| success.pending_approval)
+    }
+
+    /// The query string of this page of the queue, so that forms return to it
+    fn queue_query(&self) -> String {
+        self.queue.to_query(self.queue.page)
+    }
+
+    fn page_query(&self, page: &usize) -> String {
+        self.queue.to_query(*page)
+    }
+
     fn prev_action_method(&self) -> &'static str {
         match &self.previous_success {
             None => "other",
@@ -58,35 +138,176 @@ impl ModerationTemplate {
     }
 }
 
+/// How the moderation queue is filtered, sorted and paged. This is the query string of the
+/// moderation page, and is kept in the URLs of the forms on it so that moderators stay on the same
+/// page of the queue after acting on a suggestion.
+#[serde_as]
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+struct QueueFilter {
+    #[serde_as(as = "NoneAsEmptyString")]
+    change_type: Option<SuggestionType>,
+    /// The username of the suggesting user
+    user: String,
+    #[serde_as(as = "NoneAsEmptyString")]
+    dataset: Option<u64>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    older_than_days: Option<u32>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    newer_than_days: Option<u32>,
+    /// Whether new words must have possible duplicates, or must not have any
+    #[serde_as(as = "NoneAsEmptyString")]
+    duplicates: Option<bool>,
+    /// Text which the English or isiXhosa of the word must contain
+    #[serde(rename = "q")]
+    search: String,
+    sort: QueueSort,
+    /// Zero-based
+    page: usize,
+}
+
+#[derive(Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum QueueSort {
+    #[default]
+    Oldest,
+    Newest,
+}
+
+impl QueueSort {
+    fn name(&self) -> &'static str {
+        match self {
+            QueueSort::Oldest => "oldest",
+            QueueSort::Newest => "newest",
+        }
+    }
+}
+
+impl QueueFilter {
+    /// Builds the query string for the given page of the queue with this filter, including the `?`
+    fn to_query(&self, page: usize) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+
+        if let Some(change_type) = self.change_type {
+            query.append_pair("change_type", change_type.name());
+        }
+        if !self.user.is_empty() {
+            query.append_pair("user", &self.user);
+        }
+        if let Some(dataset) = self.dataset {
+            query.append_pair("dataset", &dataset.to_string());
+        }
+        if let Some(days) = self.older_than_days {
+            query.append_pair("older_than_days", &days.to_string());
+        }
+        if let Some(days) = self.newer_than_days {
+            query.append_pair("newer_than_days", &days.to_string());
+        }
+        if let Some(duplicates) = self.duplicates {
+            query.append_pair("duplicates", &duplicates.to_string());
+        }
+        if !self.search.is_empty() {
+            query.append_pair("q", &self.search);
+        }
+        if self.sort != QueueSort::default() {
+            query.append_pair("sort", self.sort.name());
+        }
+        if page != 0 {
+            query.append_pair("page", &page.to_string());
+        }
+
+        let query = query.finish();
+        if query.is_empty() {
+            query
+        } else {
+            format!("?{query}")
+        }
+    }
+
+    fn is_filtered(&self) -> bool {
+        self.change_type.is_some()
+            || !self.user.is_empty()
+            || self.dataset.is_some()
+            || self.older_than_days.is_some()
+            || self.newer_than_days.is_some()
+            || self.duplicates.is_some()
+            || !self.search.is_empty()
+    }
+
+    /// The query for the queue with this filter, limited to the scopes of the moderator
+    fn query<'a>(&'a self, scopes: Option<&'a ModeratorScopes>) -> QueueQuery<'a> {
+        QueueQuery {
+            change_type: self.change_type,
+            user: &self.user,
+            dataset: self.dataset,
+            older_than_days: self.older_than_days,
+            newer_than_days: self.newer_than_days,
+            duplicates: self.duplicates,
+            search: &self.search,
+            newest_first: self.sort == QueueSort::Newest,
+            scopes,
+        }
+    }
+}
+
 /// Edits that are associated to a word but not of the word itself, e.g. examples
 #[derive(Default, Debug)]
 pub struct WordAssociatedEdits {
+    sense_suggestions: Vec<SuggestedSense>,
     example_suggestions: Vec<SuggestedExample>,
     example_deletion_suggestions: Vec<ExampleDeletionSuggestion>,
     linked_word_suggestions: Vec<SuggestedLinkedWord>,
     linked_word_deletion_suggestions: Vec<LinkedWordDeletionSuggestion>,
     dataset_attribution_suggestions: Vec<DatasetAttributionSuggestion>,
+    label_suggestions: Vec<LabelSuggestion>,
 }
 
 impl WordAssociatedEdits {
+    /// Fetches the given suggestions for the given existing words, in the order of the words
     #[instrument(
-        name = "Fetch all word associated edits",
+        name = "Fetch word associated edits",
         fields(relevant_words),
-        skip(db)
+        skip(db, suggestions)
     )]
-    pub fn fetch_all(
+    pub fn fetch_for_words(
         db: &impl ModeratorAccessDb,
         i18n_info: &I18nInfo,
+        word_ids: &[u64],
+        suggestions: &HashSet<(SuggestionType, u64)>,
     ) -> Vec<(WordHit, WordAssociatedEdits)> {
-        let example_suggestions = SuggestedExample::fetch_all_for_existing_words(db);
-        let example_deletions = ExampleDeletionSuggestion::fetch_all(db);
-        let dataset_attribution_suggestions = DatasetAttributionSuggestion::fetch_all(db);
-        let linked_word_suggestions = SuggestedLinkedWord::fetch_all_for_existing_words(db);
-        let linked_word_deletion_suggestions =
-            LinkedWordDeletionSuggestion::fetch_all(db, i18n_info.clone());
+        let ids = |suggestion_type: SuggestionType| -> Vec<u64> {
+            suggestions
+                .iter()
+                .filter(|(ty, _)| *ty == suggestion_type)
+                .map(|(_, id)| *id)
+                .collect()
+        };
+
+        let sense_suggestions =
+            SuggestedSense::fetch_for_existing_words(db, &ids(SuggestionType::Sense));
+        let example_suggestions =
+            SuggestedExample::fetch_for_existing_words(db, &ids(SuggestionType::Example));
+        let example_deletions =
+            ExampleDeletionSuggestion::fetch_with_ids(db, &ids(SuggestionType::ExampleDeletion));
+        let dataset_attribution_suggestions = DatasetAttributionSuggestion::fetch_with_ids(
+            db,
+            &ids(SuggestionType::DatasetAttribution),
+        );
+        let label_suggestions = LabelSuggestion::fetch_with_ids(db, &ids(SuggestionType::Label));
+        let linked_word_suggestions =
+            SuggestedLinkedWord::fetch_for_existing_words(db, &ids(SuggestionType::LinkedWord));
+        let linked_word_deletion_suggestions = LinkedWordDeletionSuggestion::fetch_with_ids(
+            db,
+            i18n_info.clone(),
+            &ids(SuggestionType::LinkedWordDeletion),
+        );
 
         let mut map: HashMap<WordId, WordAssociatedEdits> = HashMap::new();
 
+        for (id, suggestions) in sense_suggestions {
+            map.entry(id).or_default().sense_suggestions = suggestions;
+        }
+
         for (id, suggestions) in example_suggestions {
             map.entry(id).or_default().example_suggestions = suggestions;
         }
@@ -103,6 +324,12 @@ impl WordAssociatedEdits {
             }
         }
 
+        for (id, suggestions) in label_suggestions {
+            if let Some(id) = id.into_existing() {
+                map.entry(WordId(id)).or_default().label_suggestions = suggestions;
+            }
+        }
+
         for (id, suggestions) in linked_word_suggestions {
             map.entry(id).or_default().linked_word_suggestions = suggestions;
         }
@@ -111,14 +338,19 @@ impl WordAssociatedEdits {
             map.entry(id).or_default().linked_word_deletion_suggestions = deletions;
         }
 
-        let mut vec: Vec<(WordHit, WordAssociatedEdits)> = map
-            .into_iter()
-            .map(|(id, assoc)| (WordHit::fetch_from_db(db, id.into()).unwrap(), assoc))
+        let vec: Vec<(WordHit, WordAssociatedEdits)> = word_ids
+            .iter()
+            .filter_map(|id| {
+                let assoc = map.remove(&WordId(*id))?;
+                Some((
+                    WordHit::fetch_from_db(db, WordId(*id).into()).unwrap(),
+                    assoc,
+                ))
+            })
             .collect();
 
         Span::current().record("relevant_words", vec.len());
 
-        vec.sort_by_key(|(hit, _)| hit.id);
         vec
     }
 
@@ -129,6 +361,51 @@ impl WordAssociatedEdits {
     fn linked_words_is_empty(&self) -> bool {
         self.linked_word_suggestions.is_empty() && self.linked_word_deletion_suggestions.is_empty()
     }
+
+    fn park_needing_info(
+        &mut self,
+        discussions: &HashMap<(SuggestionType, u64), DiscussionStatus>,
+    ) {
+        park_needing_info(
+            &mut self.example_suggestions,
+            discussions,
+            SuggestionType::Example,
+            |ex| ex.suggestion_id,
+        );
+        park_needing_info(
+            &mut self.example_deletion_suggestions,
+            discussions,
+            SuggestionType::ExampleDeletion,
+            |del| del.suggestion_id,
+        );
+        park_needing_info(
+            &mut self.linked_word_suggestions,
+            discussions,
+            SuggestionType::LinkedWord,
+            |l| l.suggestion_id,
+        );
+        park_needing_info(
+            &mut self.linked_word_deletion_suggestions,
+            discussions,
+            SuggestionType::LinkedWordDeletion,
+            |del| del.suggestion_id,
+        );
+    }
+}
+
+/// Moves suggestions which are waiting on more information from the suggesting user to the end of
+/// the list, so that moderators see the ones they can review now first
+fn park_needing_info<T>(
+    suggestions: &mut [T],
+    discussions: &HashMap<(SuggestionType, u64), DiscussionStatus>,
+    suggestion_type: SuggestionType,
+    suggestion_id: impl Fn(&T) -> u64,
+) {
+    suggestions.sort_by_key(|suggestion| {
+        discussions
+            .get(&(suggestion_type, suggestion_id(suggestion)))
+            .is_some_and(|status| status.needs_info)
+    });
 }
 
 #[derive(Debug)]
@@ -136,9 +413,24 @@ struct Success {
     success: bool,
     method: Option<Method>,
     next_suggestion: Option<u32>,
+    /// Set when an approval was recorded but the suggestion needs more before it is accepted, as
+    /// the number of approvals so far and the number required
+    pending_approval: Option<(u32, u32)>,
+    /// What happened to each suggestion of a bulk action. Empty for other actions.
+    bulk: Vec<BulkItemResult>,
+}
+
+impl Success {
+    /// The number of suggestions of a bulk action which were accepted or rejected
+    fn bulk_done(&self) -> usize {
+        self.bulk
+            .iter()
+            .filter(|result| matches!(result.outcome, BulkOutcome::Done))
+            .count()
+    }
 }
 
-#[derive(Deserialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 enum Method {
     Edit,
@@ -146,12 +438,19 @@ enum Method {
     Reject,
 }
 
+#[serde_as]
 #[derive(Deserialize, Debug)]
 struct Action {
     #[serde(flatten)]
     suggestion: ActionTarget,
     method: Method,
     suggestion_anchor_ord: u32,
+    /// Only given when rejecting
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    rejection_reason: Option<RejectionReason>,
+    #[serde(default)]
+    reviewer_comment: String,
 }
 
 #[serde_as]
@@ -161,11 +460,100 @@ struct Action {
 enum ActionTarget {
     WordDeletion(#[serde_as(as = "DisplayFromStr")] u64),
     Word(#[serde_as(as = "DisplayFromStr")] u64),
+    Sense(#[serde_as(as = "DisplayFromStr")] u64),
     Example(#[serde_as(as = "DisplayFromStr")] u64),
     ExampleDeletion(#[serde_as(as = "DisplayFromStr")] u64),
     LinkedWord(#[serde_as(as = "DisplayFromStr")] u64),
     LinkedWordDeletion(#[serde_as(as = "DisplayFromStr")] u64),
     DatasetAttribution(#[serde_as(as = "DisplayFromStr")] u64),
+    Label(#[serde_as(as = "DisplayFromStr")] u64),
+}
+
+impl ActionTarget {
+    fn suggestion(&self) -> (SuggestionType, u64) {
+        match *self {
+            ActionTarget::WordDeletion(id) => (SuggestionType::WordDeletion, id),
+            ActionTarget::Word(id) => (SuggestionType::Word, id),
+            ActionTarget::Sense(id) => (SuggestionType::Sense, id),
+            ActionTarget::Example(id) => (SuggestionType::Example, id),
+            ActionTarget::ExampleDeletion(id) => (SuggestionType::ExampleDeletion, id),
+            ActionTarget::LinkedWord(id) => (SuggestionType::LinkedWord, id),
+            ActionTarget::LinkedWordDeletion(id) => (SuggestionType::LinkedWordDeletion, id),
+            ActionTarget::DatasetAttribution(id) => (SuggestionType::DatasetAttribution, id),
+            ActionTarget::Label(id) => (SuggestionType::Label, id),
+        }
+    }
+}
+
+/// Accepts or rejects all of the selected suggestions at once, e.g. for suggestions from a dataset
+/// import which were reviewed elsewhere
+#[serde_as]
+#[derive(Deserialize, Debug)]
+struct BulkAction {
+    method: Method,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default)]
+    selected: Vec<SelectedSuggestion>,
+    /// Only given when rejecting
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    rejection_reason: Option<RejectionReason>,
+    #[serde(default)]
+    reviewer_comment: String,
+}
+
+/// A suggestion selected for a bulk action, given as `<suggestion type>:<suggestion id>`
+#[derive(Copy, Clone, Debug)]
+struct SelectedSuggestion(SuggestionType, u64);
+
+impl FromStr for SelectedSuggestion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (suggestion_type, suggestion_id) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid selected suggestion {s}"))?;
+
+        Ok(SelectedSuggestion(
+            suggestion_type.parse().map_err(|e| e.to_string())?,
+            suggestion_id.parse().map_err(|e| e.to_string())?,
+        ))
+    }
+}
+
+#[derive(Debug)]
+struct BulkItemResult {
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    /// `None` if the suggestion does not exist
+    summary: Option<String>,
+    outcome: BulkOutcome,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum BulkOutcome {
+    Done,
+    /// The approval was recorded, but the suggestion needs more before it is accepted. Holds the
+    /// number of approvals so far and the number required.
+    ApprovalRecorded(u32, u32),
+    NotFound,
+    /// The suggestion is outside of the moderator's scopes
+    NotAllowed,
+    /// The word was changed since the suggestion was made, so it must be rebased first
+    NeedsRebase,
+    Failed,
+}
+
+impl BulkOutcome {
+    fn succeeded(&self) -> bool {
+        matches!(self, BulkOutcome::Done | BulkOutcome::ApprovalRecorded(..))
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct RebaseAction {
+    suggestion: u64,
+    suggestion_anchor_ord: u32,
 }
 
 pub fn moderation(
@@ -177,30 +565,56 @@ pub fn moderation(
 
     let show_all = warp::get()
         .and(warp::any().map(|| None)) // previous_success is None
+        .and(warp::query())
+        .and(with_tantivy.clone())
         .and(with_moderator_auth(db.clone(), site_ctx.clone()))
         .and_then(moderation_template);
 
     let process_one = warp::post()
         .and(with_tantivy.clone())
+        .and(warp::query())
         .and(body::form::<Action>())
         .and(with_moderator_auth(db.clone(), site_ctx.clone()))
         .and_then(process_one);
 
     let submit_edit = warp::post()
         .and(body::content_length_limit(64 * 1024))
-        .and(with_tantivy)
+        .and(with_tantivy.clone())
+        .and(warp::query())
         .and(qs_form())
         .and(with_moderator_auth(db.clone(), site_ctx.clone()))
         .and_then(edit_suggestion_form);
 
+    let submit_example_edit = warp::post()
+        .and(warp::path!["example" / u64])
+        .and(body::content_length_limit(64 * 1024))
+        .and(warp::query())
+        .and(with_tantivy.clone())
+        .and(body::form())
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(example_edit_form);
+
+    let submit_linked_word_edit = warp::post()
+        .and(warp::path!["linked_word" / u64])
+        .and(body::content_length_limit(64 * 1024))
+        .and(warp::query())
+        .and(with_tantivy.clone())
+        .and(body::form())
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(linked_word_edit_form);
+
     let edit_failed = warp::any()
         .and(warp::any().map(|| {
             Some(Success {
                 success: false,
                 method: Some(Method::Edit),
                 next_suggestion: None,
+                pending_approval: None,
+                bulk: Vec::new(),
             })
         }))
+        .and(warp::any().map(QueueFilter::default))
+        .and(with_tantivy.clone())
         .and(with_moderator_auth(db.clone(), site_ctx.clone()))
         .and_then(moderation_template);
 
@@ -211,36 +625,134 @@ pub fn moderation(
                 success: false,
                 method: None,
                 next_suggestion: None,
+                pending_approval: None,
+                bulk: Vec::new(),
             })
         }))
+        .and(warp::any().map(QueueFilter::default))
+        .and(with_tantivy)
         .and(with_moderator_auth(db, site_ctx.clone()))
         .and_then(moderation_template);
 
+    let process_bulk = warp::post()
+        .and(warp::path!["bulk"])
+        .and(body::content_length_limit(64 * 1024))
+        .and(with_tantivy.clone())
+        .and(warp::query())
+        .and(qs_form())
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(process_bulk);
+
+    let rebase = warp::post()
+        .and(warp::path!["rebase"])
+        .and(body::content_length_limit(4 * 1024))
+        .and(with_tantivy.clone())
+        .and(warp::query())
+        .and(body::form())
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(rebase_suggestion);
+
     let root = warp::path::end().and(show_all.or(process_one).or(other_failed));
-    let submit_edit = warp::path("edit")
-        .and(warp::path::end())
-        .and(submit_edit.or(edit_failed));
+    let submit_edit = warp::path("edit").and(
+        warp::path::end()
+            .and(submit_edit.or(edit_failed))
+            .or(submit_example_edit)
+            .or(submit_linked_word_edit),
+    );
 
     warp::path("moderation")
-        .and(root.or(submit_edit))
+        .and(root.or(submit_edit).or(process_bulk).or(rebase))
         .debug_boxed()
 }
 
 #[instrument(name = "Display moderation template", skip_all)]
 async fn moderation_template(
     previous_success: Option<Success>,
+    mut queue: QueueFilter,
+    tantivy: Arc<TantivyClient>,
     user: FullUser,
     i18n_info: I18nInfo,
     db: impl ModeratorAccessDb,
 ) -> Result<impl Reply, Rejection> {
     spawn_blocking_child(move || {
+        let scopes = ModeratorScopes::fetch_for_moderator(&db, user.id);
+
+        let query = queue.query(scopes.as_ref());
+        let total = queue::count(&db, &query);
+        let pages = total.div_ceil(QUEUE_PAGE_SIZE).max(1);
+        let page = queue.page.min(pages - 1);
+
+        let items = queue::fetch_page(&db, &query, page * QUEUE_PAGE_SIZE, QUEUE_PAGE_SIZE);
+        let in_section = |section: QueueSection| -> Vec<u64> {
+            items
+                .iter()
+                .filter(|(s, _)| *s == section)
+                .map(|(_, id)| *id)
+                .collect()
+        };
+
+        let word_suggestions: Vec<SuggestedWord> = in_section(QueueSection::Words)
+            .into_iter()
+            .filter_map(|id| {
+                let mut word = SuggestedWord::fetch_alone(&db, id)?;
+                word.fetch_associated(&db, &i18n_info);
+                Some(word)
+            })
+            .collect();
+
+        // Whether new words have duplicates is recorded when they are submitted, but not for those
+        // edited since, so those on this page are checked again
+        let new_word_ids: Vec<u64> = word_suggestions
+            .iter()
+            .filter(|word| word.word_id.is_none())
+            .map(|word| word.suggestion_id)
+            .collect();
+        let mut duplicate_flags = queue::fetch_duplicate_flags(&db, &new_word_ids);
+        for word in &word_suggestions {
+            if word.word_id.is_none() && !duplicate_flags.contains_key(&word.suggestion_id) {
+                let found = block_on(find_duplicates(&tantivy, i18n_info.clone(), word));
+                queue::record_duplicates(&db, word.suggestion_id, &found);
+                duplicate_flags.insert(word.suggestion_id, !found.is_empty());
+            }
+        }
+        let with_duplicates = duplicate_flags
+            .into_iter()
+            .filter_map(|(id, has_duplicates)| has_duplicates.then_some(id))
+            .collect();
+
+        let deletion_ids = in_section(QueueSection::WordDeletions);
+        let mut word_deletions = WordDeletionSuggestion::fetch_with_ids(&db, &deletion_ids);
+        word_deletions.sort_by_key(|d| deletion_ids.iter().position(|id| *id == d.suggestion_id));
+
+        let word_ids = in_section(QueueSection::WordAssociatedEdits);
+        let edits = queue::fetch_associated_edits(&db, &query, &word_ids);
+        let mut word_associated_edits =
+            WordAssociatedEdits::fetch_for_words(&db, &i18n_info, &word_ids, &edits);
+
+        let discussions = discussion::fetch_all_statuses(&db);
+        for (_, assoc) in &mut word_associated_edits {
+            assoc.park_needing_info(&discussions);
+        }
+
+        let conflicts = conflicts::fetch_all_for(&db, &i18n_info, &word_suggestions);
+        queue.page = page;
+
         Ok(ModerationTemplate {
             auth: user.into(),
             i18n_info: i18n_info.clone(),
             previous_success,
-            word_suggestions: SuggestedWord::fetch_all_full(&db, &i18n_info),
-            word_deletions: WordDeletionSuggestion::fetch_all(&db),
-            word_associated_edits: WordAssociatedEdits::fetch_all(&db, &i18n_info),
+            word_suggestions,
+            with_duplicates,
+            word_deletions,
+            word_associated_edits,
+            scoped: scopes.is_some(),
+            queue,
+            total,
+            pages,
+            datasets: Dataset::fetch_all(&db),
+            discussions,
+            reviews: review::fetch_all_tallies(&db),
+            conflicts,
         })
     })
     .await
@@ -257,19 +769,60 @@ async fn moderation_template(
 )]
 async fn edit_suggestion_form(
     tantivy: Arc<TantivyClient>,
+    queue: QueueFilter,
     submission: WordSubmission,
     user: FullUser,
     i18n_info: I18nInfo,
     db: impl ModeratorAccessDb,
 ) -> Result<impl Reply, Rejection> {
     let next_suggestion = submission.suggestion_anchor_ord;
-    submit_suggestion(submission, tantivy, &user, &db, i18n_info.clone()).await;
+
+    if let Some(suggestion_id) = submission.suggestion_id {
+        let db_clone = db.clone();
+        let reviewer = user.id;
+        let allowed = spawn_blocking_child(move || {
+            let allowed =
+                scopes::may_moderate(&db_clone, reviewer, SuggestionType::Word, suggestion_id);
+
+            if allowed {
+                record_reviewer_edit(&db_clone, SuggestionType::Word, suggestion_id, reviewer);
+            }
+
+            allowed
+        })
+        .await
+        .unwrap();
+
+        if !allowed {
+            return moderation_template(
+                Some(Success {
+                    success: false,
+                    method: Some(Method::Edit),
+                    next_suggestion,
+                    pending_approval: None,
+                    bulk: Vec::new(),
+                }),
+                queue,
+                tantivy,
+                user,
+                i18n_info,
+                db,
+            )
+            .await;
+        }
+    }
+
+    submit_suggestion(submission, tantivy.clone(), &user, &db, i18n_info.clone()).await;
     moderation_template(
         Some(Success {
             success: true,
             method: Some(Method::Edit),
             next_suggestion,
+            pending_approval: None,
+            bulk: Vec::new(),
         }),
+        queue,
+        tantivy,
         user,
         i18n_info,
         db,
@@ -277,17 +830,142 @@ async fn edit_suggestion_form(
     .await
 }
 
+#[instrument(name = "Process example suggestion edit form", skip(tantivy, user, db))]
+async fn example_edit_form(
+    suggestion_id: u64,
+    queue: QueueFilter,
+    tantivy: Arc<TantivyClient>,
+    edit: ExampleSuggestionEdit,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let next_suggestion = edit.suggestion_anchor_ord;
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let success = spawn_blocking_child(move || {
+        if !scopes::may_moderate(&db_clone, reviewer, SuggestionType::Example, suggestion_id) {
+            return false;
+        }
+
+        let success = SuggestedExample::fetch(&db_clone, suggestion_id)
+            .map(|example| edit_example_suggestion(&db_clone, &example, edit))
+            .unwrap_or(false);
+
+        if success {
+            record_reviewer_edit(&db_clone, SuggestionType::Example, suggestion_id, reviewer);
+        }
+
+        success
+    })
+    .await
+    .unwrap();
+
+    moderation_template(
+        Some(Success {
+            success,
+            method: Some(Method::Edit),
+            next_suggestion,
+            pending_approval: None,
+            bulk: Vec::new(),
+        }),
+        queue,
+        tantivy,
+        user,
+        i18n_info,
+        db,
+    )
+    .await
+}
+
+#[instrument(
+    name = "Process linked word suggestion edit form",
+    skip(tantivy, user, db)
+)]
+async fn linked_word_edit_form(
+    suggestion_id: u64,
+    queue: QueueFilter,
+    tantivy: Arc<TantivyClient>,
+    edit: LinkedWordSuggestionEdit,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let next_suggestion = edit.suggestion_anchor_ord;
+    let db_clone = db.clone();
+    let i18n_info_clone = i18n_info.clone();
+    let reviewer = user.id;
+    let success = spawn_blocking_child(move || {
+        if !scopes::may_moderate(
+            &db_clone,
+            reviewer,
+            SuggestionType::LinkedWord,
+            suggestion_id,
+        ) {
+            return false;
+        }
+
+        let success = SuggestedLinkedWord::fetch(&db_clone, i18n_info_clone, suggestion_id)
+            .map(|link| edit_linked_word_suggestion(&db_clone, &link, edit))
+            .unwrap_or(false);
+
+        if success {
+            record_reviewer_edit(
+                &db_clone,
+                SuggestionType::LinkedWord,
+                suggestion_id,
+                reviewer,
+            );
+        }
+
+        success
+    })
+    .await
+    .unwrap();
+
+    moderation_template(
+        Some(Success {
+            success,
+            method: Some(Method::Edit),
+            next_suggestion,
+            pending_approval: None,
+            bulk: Vec::new(),
+        }),
+        queue,
+        tantivy,
+        user,
+        i18n_info,
+        db,
+    )
+    .await
+}
+
+/// Marks the suggestion as edited by the reviewer, unless they are also the one who suggested it
+fn record_reviewer_edit(
+    db: &impl ModeratorAccessDb,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    reviewer: NonZeroU64,
+) {
+    let suggestion = SuggestionSummary::fetch(db, suggestion_type, suggestion_id);
+
+    if suggestion.is_some_and(|s| s.suggesting_user != reviewer) {
+        SuggestionOutcome::record_reviewer_edit(db, suggestion_type, suggestion_id);
+    }
+}
+
 async fn accept_suggested_word(
     db: &impl ModeratorAccessDb,
     i18n_info: I18nInfo,
     tantivy: Arc<TantivyClient>,
     suggestion: u64,
+    reviewer: NonZeroU64,
 ) -> bool {
     let db = db.clone();
     spawn_blocking_child(move || {
         SuggestedWord::fetch_full(&db, &i18n_info, suggestion)
             .unwrap()
-            .accept_whole_word_suggestion(&db, tantivy);
+            .accept_whole_word_suggestion(&db, tantivy, reviewer);
     })
     .await
     .unwrap();
@@ -310,14 +988,14 @@ async fn accept_deletion(
     db: &impl ModeratorAccessDb,
     tantivy: Arc<TantivyClient>,
     suggestion: u64,
+    reviewer: NonZeroU64,
 ) -> bool {
-    let word_id = WordDeletionSuggestion::fetch_word_id_for_suggestion(db, suggestion);
-    Span::current().record("word_id", word_id);
     let db = db.clone();
-
-    spawn_blocking_child(move || ExistingWord::delete(&db, word_id))
-        .await
-        .unwrap();
+    let word_id =
+        spawn_blocking_child(move || WordDeletionSuggestion::accept(&db, suggestion, reviewer))
+            .await
+            .unwrap();
+    Span::current().record("word_id", word_id);
 
     tantivy
         .delete_word(WordOrSuggestionId::existing(word_id))
@@ -335,12 +1013,51 @@ async fn reject_deletion(db: &impl ModeratorAccessDb, suggestion: u64) -> bool {
     true
 }
 
-async fn accept_suggested_example(db: &impl ModeratorAccessDb, suggestion: u64) -> bool {
+async fn accept_suggested_sense(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    suggestion: u64,
+    reviewer: NonZeroU64,
+) -> bool {
+    let db = db.clone();
+    let document = spawn_blocking_child(move || {
+        let sense = SuggestedSense::fetch(&db, suggestion).unwrap();
+        sense.accept(&db, reviewer);
+
+        // Sense glosses are indexed with the word, so it needs to be reindexed
+        let word = sense
+            .word_or_suggested_id
+            .into_existing()
+            .and_then(|id| ExistingWord::fetch_alone(&db, id))?;
+        Some(WordDocument::from(&WordRecord::from(word)).with_senses_and_labels_from_db(&db))
+    })
+    .await
+    .unwrap();
+
+    if let Some(document) = document {
+        tantivy.edit_word(document).await;
+    }
+
+    true
+}
+
+async fn reject_suggested_sense(db: &impl ModeratorAccessDb, suggestion: u64) -> bool {
+    let db = db.clone();
+    spawn_blocking_child(move || SuggestedSense::delete(&db, suggestion))
+        .await
+        .unwrap()
+}
+
+async fn accept_suggested_example(
+    db: &impl ModeratorAccessDb,
+    suggestion: u64,
+    reviewer: NonZeroU64,
+) -> bool {
     let db = db.clone();
     spawn_blocking_child(move || {
         SuggestedExample::fetch(&db, suggestion)
             .unwrap()
-            .accept(&db)
+            .accept(&db, reviewer)
     })
     .await
     .unwrap();
@@ -355,9 +1072,13 @@ async fn reject_suggested_example(db: &impl ModeratorAccessDb, suggestion: u64)
         .unwrap()
 }
 
-async fn accept_example_deletion(db: &impl ModeratorAccessDb, suggestion: u64) -> bool {
+async fn accept_example_deletion(
+    db: &impl ModeratorAccessDb,
+    suggestion: u64,
+    reviewer: NonZeroU64,
+) -> bool {
     let db = db.clone();
-    spawn_blocking_child(move || ExampleDeletionSuggestion::accept(&db, suggestion))
+    spawn_blocking_child(move || ExampleDeletionSuggestion::accept(&db, suggestion, reviewer))
         .await
         .unwrap();
 
@@ -377,10 +1098,13 @@ async fn accept_linked_word(
     db: &impl ModeratorAccessDb,
     i18n_info: I18nInfo,
     suggestion: u64,
+    reviewer: NonZeroU64,
 ) -> bool {
     let db = db.clone();
     spawn_blocking_child(move || {
-        SuggestedLinkedWord::fetch(&db, i18n_info, suggestion).accept(&db)
+        SuggestedLinkedWord::fetch(&db, i18n_info, suggestion)
+            .unwrap()
+            .accept(&db, reviewer)
     })
     .await
     .unwrap();
@@ -395,9 +1119,13 @@ async fn reject_linked_word(db: &impl ModeratorAccessDb, suggestion: u64) -> boo
     true
 }
 
-async fn accept_linked_word_deletion(db: &impl ModeratorAccessDb, suggestion: u64) -> bool {
+async fn accept_linked_word_deletion(
+    db: &impl ModeratorAccessDb,
+    suggestion: u64,
+    reviewer: NonZeroU64,
+) -> bool {
     let db = db.clone();
-    spawn_blocking_child(move || LinkedWordDeletionSuggestion::accept(&db, suggestion))
+    spawn_blocking_child(move || LinkedWordDeletionSuggestion::accept(&db, suggestion, reviewer))
         .await
         .unwrap();
     true
@@ -414,12 +1142,13 @@ async fn reject_linked_word_deletion(db: &impl ModeratorAccessDb, suggestion: u6
 async fn accept_dataset_attribution_suggestion(
     db: &impl ModeratorAccessDb,
     suggestion: u64,
+    reviewer: NonZeroU64,
 ) -> bool {
     let db = db.clone();
     spawn_blocking_child(move || {
         DatasetAttributionSuggestion::fetch_by_id(&db, suggestion)
             .unwrap()
-            .accept(&db)
+            .accept(&db, reviewer)
     })
     .await
     .unwrap();
@@ -436,15 +1165,188 @@ async fn reject_dataset_attribution_suggestion(
         .unwrap()
 }
 
+async fn accept_label_suggestion(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    suggestion: u64,
+    reviewer: NonZeroU64,
+) -> bool {
+    let db = db.clone();
+    let document = spawn_blocking_child(move || {
+        let label = LabelSuggestion::fetch_by_id(&db, suggestion).unwrap();
+        let word_id = label.word_or_suggestion_id.into_existing();
+        label.accept(&db, reviewer);
+
+        // Labels are indexed with the word for filtering and ranking, so it needs to be reindexed
+        let word = word_id.and_then(|id| ExistingWord::fetch_alone(&db, id))?;
+        Some(WordDocument::from(&WordRecord::from(word)).with_senses_and_labels_from_db(&db))
+    })
+    .await
+    .unwrap();
+
+    if let Some(document) = document {
+        tantivy.edit_word(document).await;
+    }
+
+    true
+}
+
+async fn reject_label_suggestion(db: &impl ModeratorAccessDb, suggestion: u64) -> bool {
+    let db = db.clone();
+    spawn_blocking_child(move || LabelSuggestion::delete(&db, suggestion))
+        .await
+        .unwrap()
+}
+
+/// Accepts a suggestion in the same way as accepting it from the moderation page does, without
+/// recording its outcome. Returns whether it was accepted.
+pub async fn accept_suggestion(
+    db: &impl ModeratorAccessDb,
+    i18n_info: I18nInfo,
+    tantivy: Arc<TantivyClient>,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    reviewer: NonZeroU64,
+) -> bool {
+    match suggestion_type {
+        SuggestionType::Word => {
+            accept_suggested_word(db, i18n_info, tantivy, suggestion_id, reviewer).await
+        }
+        SuggestionType::WordDeletion => accept_deletion(db, tantivy, suggestion_id, reviewer).await,
+        SuggestionType::Sense => accept_suggested_sense(db, tantivy, suggestion_id, reviewer).await,
+        SuggestionType::Example => accept_suggested_example(db, suggestion_id, reviewer).await,
+        SuggestionType::ExampleDeletion => {
+            accept_example_deletion(db, suggestion_id, reviewer).await
+        }
+        SuggestionType::LinkedWord => {
+            accept_linked_word(db, i18n_info, suggestion_id, reviewer).await
+        }
+        SuggestionType::LinkedWordDeletion => {
+            accept_linked_word_deletion(db, suggestion_id, reviewer).await
+        }
+        SuggestionType::DatasetAttribution => {
+            accept_dataset_attribution_suggestion(db, suggestion_id, reviewer).await
+        }
+        SuggestionType::Label => {
+            accept_label_suggestion(db, tantivy, suggestion_id, reviewer).await
+        }
+    }
+}
+
+/// Rejects a suggestion in the same way as rejecting it from the moderation page does, without
+/// recording its outcome. Returns whether it was rejected.
+async fn reject_suggestion(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+) -> bool {
+    match suggestion_type {
+        SuggestionType::Word => reject_suggested_word(db, tantivy, suggestion_id).await,
+        SuggestionType::WordDeletion => reject_deletion(db, suggestion_id).await,
+        SuggestionType::Sense => reject_suggested_sense(db, suggestion_id).await,
+        SuggestionType::Example => reject_suggested_example(db, suggestion_id).await,
+        SuggestionType::ExampleDeletion => reject_example_deletion(db, suggestion_id).await,
+        SuggestionType::LinkedWord => reject_linked_word(db, suggestion_id).await,
+        SuggestionType::LinkedWordDeletion => reject_linked_word_deletion(db, suggestion_id).await,
+        SuggestionType::DatasetAttribution => {
+            reject_dataset_attribution_suggestion(db, suggestion_id).await
+        }
+        SuggestionType::Label => reject_label_suggestion(db, suggestion_id).await,
+    }
+}
+
 #[instrument(name = "Process moderation page action", skip(user, db, tantivy))]
 async fn process_one(
     tantivy: Arc<TantivyClient>,
+    queue: QueueFilter,
     params: Action,
     user: FullUser,
     i18n_info: I18nInfo,
     db: impl ModeratorAccessDb,
 ) -> Result<impl Reply, Rejection> {
     let db_clone = db.clone();
+    let tantivy_clone = tantivy.clone();
+    let reviewer = user.id;
+
+    let (suggestion_type, suggestion_id) = params.suggestion.suggestion();
+    let is_accept = params.method == Method::Accept;
+    let allowed = {
+        let (db, i18n_info) = (db.clone(), i18n_info.clone());
+        spawn_blocking_child(move || {
+            scopes::may_moderate(&db, reviewer, suggestion_type, suggestion_id)
+                && !(is_accept
+                    && suggestion_type == SuggestionType::Word
+                    && conflicts::needs_rebase(&db, &i18n_info, suggestion_id))
+        })
+        .await
+        .unwrap()
+    };
+
+    if !allowed {
+        return moderation_template(
+            Some(Success {
+                success: false,
+                method: Some(params.method),
+                next_suggestion: Some(params.suggestion_anchor_ord),
+                pending_approval: None,
+                bulk: Vec::new(),
+            }),
+            queue,
+            tantivy_clone,
+            user,
+            i18n_info,
+            db_clone,
+        )
+        .await
+        .map(Reply::into_response);
+    }
+
+    // Fetched before the suggestion is accepted or rejected, as it is then deleted
+    let summary = if params.method == Method::Edit {
+        None
+    } else {
+        let db = db.clone();
+        spawn_blocking_child(move || SuggestionSummary::fetch(&db, suggestion_type, suggestion_id))
+            .await
+            .unwrap()
+    };
+
+    // Suggestions which need several approvals are only accepted once the last one is given
+    if let Some(summary) = summary.clone().filter(|_| params.method == Method::Accept) {
+        let db = db.clone();
+        let (approvals, required) = spawn_blocking_child(move || {
+            let policy = AppliedPolicy::for_suggestion(&db, &summary);
+            let approvals = review::approve(
+                &db,
+                summary.suggestion_type,
+                summary.suggestion_id,
+                reviewer,
+            );
+            (approvals, policy.required_approvals)
+        })
+        .await
+        .unwrap();
+
+        if approvals < required {
+            return moderation_template(
+                Some(Success {
+                    success: true,
+                    method: Some(Method::Accept),
+                    next_suggestion: Some(params.suggestion_anchor_ord),
+                    pending_approval: Some((approvals, required)),
+                    bulk: Vec::new(),
+                }),
+                queue,
+                tantivy_clone,
+                user,
+                i18n_info,
+                db_clone,
+            )
+            .await
+            .map(Reply::into_response);
+        }
+    }
 
     let edit_unsupported = || {
         error!("Got request to edit word or example deletion suggestion, but this makes no sense!");
@@ -454,7 +1356,7 @@ async fn process_one(
     let success = match params.suggestion {
         ActionTarget::WordDeletion(suggestion) => match params.method {
             Method::Edit => edit_unsupported(),
-            Method::Accept => accept_deletion(&db, tantivy, suggestion).await,
+            Method::Accept => accept_deletion(&db, tantivy, suggestion, reviewer).await,
             Method::Reject => reject_deletion(&db, suggestion).await,
         },
         ActionTarget::Word(suggestion) => match params.method {
@@ -465,48 +1367,102 @@ async fn process_one(
                     user,
                     suggestion,
                     params.suggestion_anchor_ord,
+                    queue.to_query(queue.page),
                 )
                 .await
                 .map(Reply::into_response)
             }
             Method::Accept => {
-                accept_suggested_word(&db, i18n_info.clone(), tantivy, suggestion).await
+                accept_suggested_word(&db, i18n_info.clone(), tantivy, suggestion, reviewer).await
             }
             Method::Reject => reject_suggested_word(&db, tantivy, suggestion).await,
         },
+        ActionTarget::Sense(suggestion) => match params.method {
+            Method::Edit => edit_unsupported(),
+            Method::Accept => accept_suggested_sense(&db, tantivy, suggestion, reviewer).await,
+            Method::Reject => reject_suggested_sense(&db, suggestion).await,
+        },
         ActionTarget::Example(suggestion) => match params.method {
-            Method::Edit => todo!("Example standalone editing"),
-            Method::Accept => accept_suggested_example(&db, suggestion).await,
+            Method::Edit => {
+                return edit_example_suggestion_page(
+                    db,
+                    i18n_info,
+                    user,
+                    suggestion,
+                    Some(params.suggestion_anchor_ord),
+                    queue.to_query(queue.page),
+                    None,
+                )
+                .await
+                .map(Reply::into_response)
+            }
+            Method::Accept => accept_suggested_example(&db, suggestion, reviewer).await,
             Method::Reject => reject_suggested_example(&db, suggestion).await,
         },
         ActionTarget::ExampleDeletion(suggestion) => match params.method {
             Method::Edit => edit_unsupported(),
-            Method::Accept => accept_example_deletion(&db, suggestion).await,
+            Method::Accept => accept_example_deletion(&db, suggestion, reviewer).await,
             Method::Reject => reject_example_deletion(&db, suggestion).await,
         },
         ActionTarget::LinkedWord(suggestion) => match params.method {
-            Method::Edit => todo!("Linked word standalone editing"),
-            Method::Accept => accept_linked_word(&db, i18n_info.clone(), suggestion).await,
+            Method::Edit => {
+                return edit_linked_word_suggestion_page(
+                    db,
+                    i18n_info,
+                    user,
+                    suggestion,
+                    Some(params.suggestion_anchor_ord),
+                    queue.to_query(queue.page),
+                    None,
+                )
+                .await
+                .map(Reply::into_response)
+            }
+            Method::Accept => {
+                accept_linked_word(&db, i18n_info.clone(), suggestion, reviewer).await
+            }
             Method::Reject => reject_linked_word(&db, suggestion).await,
         },
         ActionTarget::LinkedWordDeletion(suggestion) => match params.method {
             Method::Edit => edit_unsupported(),
-            Method::Accept => accept_linked_word_deletion(&db, suggestion).await,
+            Method::Accept => accept_linked_word_deletion(&db, suggestion, reviewer).await,
             Method::Reject => reject_linked_word_deletion(&db, suggestion).await,
         },
         ActionTarget::DatasetAttribution(suggestion) => match params.method {
             Method::Edit => edit_unsupported(),
-            Method::Accept => accept_dataset_attribution_suggestion(&db, suggestion).await,
+            Method::Accept => {
+                accept_dataset_attribution_suggestion(&db, suggestion, reviewer).await
+            }
             Method::Reject => reject_dataset_attribution_suggestion(&db, suggestion).await,
         },
+        ActionTarget::Label(suggestion) => match params.method {
+            Method::Edit => edit_unsupported(),
+            Method::Accept => accept_label_suggestion(&db, tantivy, suggestion, reviewer).await,
+            Method::Reject => reject_label_suggestion(&db, suggestion).await,
+        },
     };
 
+    if let Some(summary) = summary.filter(|_| success) {
+        let db = db_clone.clone();
+        let accepted = params.method == Method::Accept;
+        let (reason, comment) = (params.rejection_reason, params.reviewer_comment);
+        spawn_blocking_child(move || {
+            SuggestionOutcome::record(&db, &summary, reviewer, accepted, reason, &comment)
+        })
+        .await
+        .unwrap();
+    }
+
     moderation_template(
         Some(Success {
             success,
             method: Some(params.method),
             next_suggestion: params.suggestion_anchor_ord.checked_sub(1),
+            pending_approval: None,
+            bulk: Vec::new(),
         }),
+        queue,
+        tantivy_clone,
         user,
         i18n_info,
         db_clone,
@@ -514,3 +1470,265 @@ async fn process_one(
     .await
     .map(Reply::into_response)
 }
+
+#[instrument(
+    name = "Process bulk moderation action",
+    fields(method = ?action.method, selected = action.selected.len()),
+    skip_all
+)]
+async fn process_bulk(
+    tantivy: Arc<TantivyClient>,
+    queue: QueueFilter,
+    action: BulkAction,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let reviewer = user.id;
+    let mut results = Vec::with_capacity(action.selected.len());
+
+    // The search index is committed once for the whole batch rather than once per suggestion
+    let index = tantivy.batch();
+
+    for SelectedSuggestion(suggestion_type, suggestion_id) in action.selected {
+        let db_clone = db.clone();
+        let (summary, allowed) = spawn_blocking_child(move || {
+            let summary = SuggestionSummary::fetch(&db_clone, suggestion_type, suggestion_id);
+            let allowed = scopes::may_moderate(&db_clone, reviewer, suggestion_type, suggestion_id);
+            (summary, allowed)
+        })
+        .await
+        .unwrap();
+
+        let outcome = match summary.clone() {
+            None => BulkOutcome::NotFound,
+            Some(_) if !allowed => BulkOutcome::NotAllowed,
+            Some(summary) => {
+                // Each suggestion is processed in a task of its own, so that if one fails the rest
+                // are still processed
+                let item = tokio::spawn(process_bulk_item(
+                    db.clone(),
+                    index.client().batch(),
+                    i18n_info.clone(),
+                    summary,
+                    action.method,
+                    reviewer,
+                    action.rejection_reason,
+                    action.reviewer_comment.clone(),
+                ));
+
+                item.await.unwrap_or_else(|err| {
+                    error!("Bulk moderation of {suggestion_type:?} {suggestion_id} failed: {err}");
+                    BulkOutcome::Failed
+                })
+            }
+        };
+
+        results.push(BulkItemResult {
+            suggestion_type,
+            suggestion_id,
+            summary: summary.map(|summary| summary.summary),
+            outcome,
+        });
+    }
+
+    index.commit().await;
+
+    let success = results.iter().all(|result| result.outcome.succeeded());
+
+    moderation_template(
+        Some(Success {
+            success,
+            method: Some(action.method),
+            next_suggestion: None,
+            pending_approval: None,
+            bulk: results,
+        }),
+        queue,
+        tantivy,
+        user,
+        i18n_info,
+        db,
+    )
+    .await
+}
+
+/// Processes one suggestion of a bulk action in a database transaction of its own, which is only
+/// committed if it succeeds. Its changes to the search index are likewise only added to the bulk
+/// action's batch once the transaction has been committed.
+///
+/// The transaction is begun, used and committed on one blocking thread, so that the write lock is
+/// not held while the task waits on the runtime.
+#[allow(clippy::too_many_arguments)]
+async fn process_bulk_item(
+    db: impl ModeratorAccessDb,
+    index: IndexBatch,
+    i18n_info: I18nInfo,
+    summary: SuggestionSummary,
+    method: Method,
+    reviewer: NonZeroU64,
+    rejection_reason: Option<RejectionReason>,
+    reviewer_comment: String,
+) -> BulkOutcome {
+    let (suggestion_type, suggestion_id) = (summary.suggestion_type, summary.suggestion_id);
+    let tantivy = index.client();
+
+    let outcome = spawn_blocking_child(move || {
+        let transaction = DbTransaction::begin(&db);
+
+        let outcome = block_on(apply_bulk_item(
+            transaction.clone(),
+            tantivy,
+            i18n_info,
+            summary,
+            method,
+            reviewer,
+            rejection_reason,
+            reviewer_comment,
+        ));
+
+        // Otherwise, the transaction is rolled back once it is dropped
+        if !outcome.succeeded() {
+            return outcome;
+        }
+
+        match transaction.commit() {
+            Ok(()) => outcome,
+            Err(err) => {
+                error!(
+                    "Committing bulk moderation of {suggestion_type:?} {suggestion_id} failed: {err}"
+                );
+                BulkOutcome::Failed
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    if outcome.succeeded() {
+        index.commit().await;
+    } else {
+        index.discard();
+    }
+
+    outcome
+}
+
+/// Accepts or rejects one suggestion of a bulk action, including recording the approval and the
+/// outcome as the moderation page does for a single suggestion
+#[allow(clippy::too_many_arguments)]
+async fn apply_bulk_item(
+    db: impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    i18n_info: I18nInfo,
+    summary: SuggestionSummary,
+    method: Method,
+    reviewer: NonZeroU64,
+    rejection_reason: Option<RejectionReason>,
+    reviewer_comment: String,
+) -> BulkOutcome {
+    let (suggestion_type, suggestion_id) = (summary.suggestion_type, summary.suggestion_id);
+
+    let done = match method {
+        Method::Accept => {
+            let (db_clone, i18n_clone) = (db.clone(), i18n_info.clone());
+            let needs_rebase = suggestion_type == SuggestionType::Word
+                && spawn_blocking_child(move || {
+                    conflicts::needs_rebase(&db_clone, &i18n_clone, suggestion_id)
+                })
+                .await
+                .unwrap();
+
+            if needs_rebase {
+                return BulkOutcome::NeedsRebase;
+            }
+
+            let db_clone = db.clone();
+            let summary = summary.clone();
+            let (approvals, required) = spawn_blocking_child(move || {
+                let policy = AppliedPolicy::for_suggestion(&db_clone, &summary);
+                let approvals =
+                    review::approve(&db_clone, suggestion_type, suggestion_id, reviewer);
+                (approvals, policy.required_approvals)
+            })
+            .await
+            .unwrap();
+
+            if approvals < required {
+                return BulkOutcome::ApprovalRecorded(approvals, required);
+            }
+
+            accept_suggestion(
+                &db,
+                i18n_info,
+                tantivy,
+                suggestion_type,
+                suggestion_id,
+                reviewer,
+            )
+            .await
+        }
+        Method::Reject => reject_suggestion(&db, tantivy, suggestion_type, suggestion_id).await,
+        Method::Edit => {
+            error!("Got request to edit suggestions in bulk, but this makes no sense!");
+            false
+        }
+    };
+
+    if !done {
+        return BulkOutcome::Failed;
+    }
+
+    let accepted = method == Method::Accept;
+    spawn_blocking_child(move || {
+        SuggestionOutcome::record(
+            &db,
+            &summary,
+            reviewer,
+            accepted,
+            rejection_reason,
+            &reviewer_comment,
+        )
+    })
+    .await
+    .unwrap();
+
+    BulkOutcome::Done
+}
+
+#[instrument(name = "Rebase word suggestion", skip(tantivy, user, db))]
+async fn rebase_suggestion(
+    tantivy: Arc<TantivyClient>,
+    queue: QueueFilter,
+    action: RebaseAction,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let suggestion_id = action.suggestion;
+
+    let success = spawn_blocking_child(move || {
+        scopes::may_moderate(&db_clone, reviewer, SuggestionType::Word, suggestion_id)
+            && conflicts::rebase(&db_clone, suggestion_id)
+    })
+    .await
+    .unwrap();
+
+    moderation_template(
+        Some(Success {
+            success,
+            method: None,
+            next_suggestion: Some(action.suggestion_anchor_ord),
+            pending_approval: None,
+            bulk: Vec::new(),
+        }),
+        queue,
+        tantivy,
+        user,
+        i18n_info,
+        db,
+    )
+    .await
+}
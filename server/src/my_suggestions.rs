@@ -0,0 +1,68 @@
+use crate::auth::{with_user_auth, FullUser};
+use crate::database::discussion;
+use crate::database::outcomes::{SuggestionOutcome, SuggestionSummary};
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, UserAccessDb};
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "my_suggestions.askama.html")]
+struct MySuggestionsPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    /// Pending suggestions, and whether a moderator is waiting on more information about them
+    pending: Vec<(SuggestionSummary, bool)>,
+    reviewed: Vec<SuggestionOutcome>,
+}
+
+pub fn my_suggestions(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    warp::get()
+        .and(warp::path!("suggestions"))
+        .and(warp::path::end())
+        .and(with_user_auth(db, site_ctx))
+        .and_then(my_suggestions_page)
+        .debug_boxed()
+}
+
+#[instrument(name = "Display my suggestions page", skip_all)]
+async fn my_suggestions_page(
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let user_id = user.id;
+    let (pending, reviewed) = spawn_blocking_child(move || {
+        let pending = SuggestionSummary::fetch_all_pending_for_user(&db, user_id)
+            .into_iter()
+            .map(|suggestion| {
+                let needs_info = discussion::needs_info(
+                    &db,
+                    suggestion.suggestion_type,
+                    suggestion.suggestion_id,
+                );
+                (suggestion, needs_info)
+            })
+            .collect();
+
+        (pending, SuggestionOutcome::fetch_all_for_user(&db, user_id))
+    })
+    .await
+    .unwrap();
+
+    Ok(MySuggestionsPage {
+        auth: user.into(),
+        i18n_info,
+        pending,
+        reviewed,
+    })
+}
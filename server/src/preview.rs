@@ -0,0 +1,247 @@
+//! Previews of how a suggested word would look once it is accepted, so that moderators can check
+//! the entry as a whole rather than field by field. Nothing is written to the database.
+
+use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::outcomes::SuggestionType;
+use crate::database::scopes::may_moderate;
+use crate::database::suggestion::{SenseRef, SenseRefId, SuggestedWord};
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
+use isixhosa_common::database::{DbBase, ModeratorAccessDb};
+use isixhosa_common::templates::WordDetails;
+use isixhosa_common::types::{ExistingExample, ExistingLinkedWord, ExistingSense, ExistingWord};
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{Filter, Rejection, Reply};
+
+pub fn preview(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    warp::path!["moderation" / "preview" / u64]
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(preview_page)
+        .debug_boxed()
+}
+
+#[instrument(name = "Display suggestion preview page", skip(user, db))]
+async fn preview_page(
+    suggestion_id: u64,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let (i18n_clone, moderator) = (i18n_info.clone(), user.id);
+    let word = spawn_blocking_child(move || {
+        if !may_moderate(&db, moderator, SuggestionType::Word, suggestion_id) {
+            return None;
+        }
+
+        preview_word(&db, &i18n_clone, suggestion_id)
+    })
+    .await
+    .unwrap();
+
+    Ok(match word {
+        Some(word) => WordDetails {
+            auth: user.into(),
+            i18n_info,
+            word,
+            previous_success: None,
+            grammar_warnings: Vec::new(),
+            preview: true,
+        }
+        .into_response(),
+        None => NotFound {
+            auth: user.into(),
+            i18n_info,
+        }
+        .into_response(),
+    })
+}
+
+/// Items which do not exist yet are given the id of their suggestion counted down from the largest
+/// id, so that they cannot clash with the ids of existing items
+fn preview_id(suggestion_id: u64) -> u64 {
+    u64::MAX - suggestion_id
+}
+
+fn preview_sense_id(sense: &SenseRef) -> u64 {
+    match sense.id {
+        SenseRefId::Existing(id) => id,
+        SenseRefId::Suggested(id) => preview_id(id),
+    }
+}
+
+/// Builds the word as it would be if the suggested word were accepted along with its senses,
+/// examples, linked words, datasets and labels. Returns `None` if the suggestion does not exist,
+/// or if it is an edit of a word which does not exist anymore.
+#[instrument(name = "Build preview of suggested word", skip(db, i18n_info))]
+fn preview_word(
+    db: &impl ModeratorAccessDb,
+    i18n_info: &I18nInfo,
+    suggestion_id: u64,
+) -> Option<ExistingWord> {
+    let s = SuggestedWord::fetch_full(db, i18n_info, suggestion_id)?;
+    let this_id = s.this_id();
+
+    let mut word = match s.word_id {
+        Some(word_id) => ExistingWord::fetch_full(db, word_id)?,
+        None => ExistingWord {
+            word_id: 0,
+            english: String::new(),
+            xhosa: String::new(),
+            part_of_speech: None,
+            xhosa_tone_markings: String::new(),
+            infinitive: String::new(),
+            is_plural: false,
+            is_inchoative: false,
+            transitivity: None,
+            followed_by: None,
+            noun_class: None,
+            note: String::new(),
+            is_informal: false,
+            etymology_language: None,
+            etymology_form: String::new(),
+            etymology_note: String::new(),
+            literal_translation: String::new(),
+            senses: Vec::new(),
+            examples: Vec::new(),
+            linked_words: Vec::new(),
+            contributors: Vec::new(),
+            datasets: Vec::new(),
+            labels: Vec::new(),
+            sense_labels: Vec::new(),
+            citations: Vec::new(),
+            references: Vec::new(),
+            conjugation_overrides: Vec::new(),
+        },
+    };
+
+    s.apply_fields(&mut word);
+
+    for sense in &s.senses {
+        let sense_id = sense
+            .existing_sense_id
+            .unwrap_or_else(|| preview_id(sense.suggestion_id));
+
+        if sense.is_delete {
+            word.senses.retain(|existing| existing.sense_id != sense_id);
+            continue;
+        }
+
+        let edited = ExistingSense {
+            sense_id,
+            word_id: word.word_id,
+            sense_order: *sense.sense_order.current(),
+            english: sense.english.current().clone(),
+            is_informal: *sense.is_informal.current(),
+            examples: Vec::new(),
+            linked_words: Vec::new(),
+            labels: Vec::new(),
+        };
+
+        match word.senses.iter_mut().find(|e| e.sense_id == sense_id) {
+            // Keep the examples, linked words and labels already assigned to the sense
+            Some(existing) => {
+                existing.sense_order = edited.sense_order;
+                existing.english = edited.english;
+                existing.is_informal = edited.is_informal;
+            }
+            None => word.senses.push(edited),
+        }
+    }
+    word.senses.sort_by_key(|sense| sense.sense_order);
+
+    for example in &s.examples {
+        let example_id = example
+            .existing_example_id
+            .unwrap_or_else(|| preview_id(example.suggestion_id));
+
+        word.examples.retain(|e| e.example_id != example_id);
+        word.examples.push(ExistingExample {
+            example_id,
+            word_id: word.word_id,
+            english: example.english.current().clone(),
+            xhosa: example.xhosa.current().clone(),
+        });
+
+        for sense in &mut word.senses {
+            sense.examples.retain(|id| *id != example_id);
+        }
+        if let Some(sense) = example.sense.current() {
+            let sense_id = preview_sense_id(sense);
+            if let Some(sense) = word.senses.iter_mut().find(|e| e.sense_id == sense_id) {
+                sense.examples.push(example_id);
+            }
+        }
+    }
+
+    for link in &s.linked_words {
+        let link_id = link
+            .existing_linked_word_id
+            .unwrap_or_else(|| preview_id(link.suggestion_id));
+        let other = link.other(this_id).current().clone();
+
+        word.linked_words.retain(|l| l.link_id != link_id);
+        for sense in &mut word.senses {
+            sense.linked_words.retain(|id| *id != link_id);
+        }
+
+        // Words which are only suggested would not be linked until they are accepted too
+        if other.is_suggestion {
+            continue;
+        }
+
+        word.linked_words.push(ExistingLinkedWord {
+            link_id,
+            first_word_id: word.word_id,
+            second_word_id: other.id,
+            link_type: *link.link_type_for(this_id).current(),
+            other,
+        });
+
+        if let Some(sense) = link.sense.current() {
+            let sense_id = preview_sense_id(sense);
+            if let Some(sense) = word.senses.iter_mut().find(|e| e.sense_id == sense_id) {
+                sense.linked_words.push(link_id);
+            }
+        }
+    }
+
+    for attribution in &s.datasets {
+        word.datasets.retain(|d| d.id != attribution.dataset.id);
+        if !attribution.is_delete {
+            word.datasets.push(attribution.dataset.clone());
+        }
+    }
+
+    for suggestion in &s.labels {
+        let label_id = suggestion.label.id;
+
+        let Some(sense) = &suggestion.sense else {
+            word.labels.retain(|l| l.id != label_id);
+            if !suggestion.is_delete {
+                word.labels.push(suggestion.label.clone());
+            }
+            continue;
+        };
+
+        let sense_id = preview_sense_id(sense);
+        if let Some(sense) = word.senses.iter_mut().find(|e| e.sense_id == sense_id) {
+            sense.labels.retain(|id| *id != label_id);
+            if !suggestion.is_delete {
+                sense.labels.push(label_id);
+            }
+        }
+
+        if !word.sense_labels.iter().any(|l| l.id == label_id) {
+            word.sense_labels.push(suggestion.label.clone());
+        }
+    }
+
+    Some(word)
+}
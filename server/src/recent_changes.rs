@@ -0,0 +1,75 @@
+use crate::auth::with_any_auth;
+use crate::database::changes::{ChangeFilter, WordChange, CHANGES_PER_PAGE};
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, PublicAccessDb};
+use isixhosa_common::format::DisplayHtml;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "recent_changes.askama.html")]
+struct RecentChanges {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    filter: ChangeFilter,
+    changes: Vec<WordChange>,
+}
+
+impl RecentChanges {
+    /// The link to the next page of older changes, if there is one
+    fn older_changes_link(&self) -> Option<String> {
+        if self.changes.len() < CHANGES_PER_PAGE as usize {
+            return None;
+        }
+
+        let filter = ChangeFilter {
+            before: self.changes.last().map(|change| change.change_id),
+            ..self.filter.clone()
+        };
+
+        Some(format!(
+            "/recent-changes?{}",
+            serde_qs::to_string(&filter).unwrap()
+        ))
+    }
+}
+
+pub fn recent_changes(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    warp::path("recent-changes")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query())
+        .and(with_any_auth(db, site_ctx))
+        .and_then(recent_changes_page)
+        .debug_boxed()
+}
+
+#[instrument(name = "Display recent changes page", skip(auth, db))]
+async fn recent_changes_page(
+    filter: ChangeFilter,
+    auth: Auth,
+    i18n_info: I18nInfo,
+    db: impl PublicAccessDb,
+) -> Result<impl Reply, Rejection> {
+    spawn_blocking_child(move || {
+        let changes = WordChange::fetch_filtered(&db, &i18n_info, &filter);
+
+        Ok(RecentChanges {
+            auth,
+            i18n_info,
+            filter,
+            changes,
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,261 @@
+use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::references::{add_citation, remove_citation, NewCitation};
+use crate::database::scopes;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, ModeratorAccessDb};
+use isixhosa_common::language::ReferenceKind;
+use isixhosa_common::types::{ExistingWord, Reference};
+use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr, NoneAsEmptyString};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "references.askama.html")]
+struct ReferencesPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    word: ExistingWord,
+    /// Every reference in the bibliography, which any of them can be cited from
+    references: Vec<Reference>,
+    previous_success: Option<bool>,
+}
+
+/// What part of the word a citation is for, as chosen in the form: `word`, `sense-{id}` or
+/// `example-{id}`
+#[derive(Copy, Clone, Debug)]
+enum CitationTarget {
+    Word,
+    Sense(u64),
+    Example(u64),
+}
+
+#[derive(Debug)]
+struct InvalidCitationTarget(String);
+
+impl Display for InvalidCitationTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid citation target {}", self.0)
+    }
+}
+
+impl FromStr for CitationTarget {
+    type Err = InvalidCitationTarget;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || InvalidCitationTarget(s.to_owned());
+
+        match s.split_once('-') {
+            None if s == "word" => Ok(CitationTarget::Word),
+            Some(("sense", id)) => Ok(CitationTarget::Sense(id.parse().map_err(|_| err())?)),
+            Some(("example", id)) => Ok(CitationTarget::Example(id.parse().map_err(|_| err())?)),
+            _ => Err(err()),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Deserialize, Debug)]
+struct CitationForm {
+    reference_id: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    target: CitationTarget,
+    #[serde(default)]
+    pages: String,
+}
+
+#[serde_as]
+#[derive(Deserialize, Debug)]
+struct ReferenceForm {
+    kind: ReferenceKind,
+    author: String,
+    title: String,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    year: Option<u32>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    publisher: Option<String>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    url: Option<String>,
+}
+
+pub fn references(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let references_page = warp::get()
+        .and(warp::path![u64 / "references"])
+        .and(warp::path::end())
+        .and(warp::any().map(|| None)) // previous_success is None
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(word_references_page);
+
+    let submit_citation = warp::post()
+        .and(warp::path![u64 / "references" / "cite"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::form())
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(submit_citation_form);
+
+    let delete_citation = warp::post()
+        .and(warp::path![
+            u64 / "references" / "citation" / u64 / "delete"
+        ])
+        .and(warp::path::end())
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(submit_delete_citation);
+
+    let submit_reference = warp::post()
+        .and(warp::path![u64 / "references" / "add"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::form())
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(submit_reference_form);
+
+    warp::path("word")
+        .and(
+            references_page
+                .or(submit_citation)
+                .or(delete_citation)
+                .or(submit_reference),
+        )
+        .debug_boxed()
+}
+
+#[instrument(name = "Display word references page", skip(user, db))]
+async fn word_references_page(
+    word_id: u64,
+    previous_success: Option<bool>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let (word, references) = spawn_blocking_child(move || {
+        (
+            ExistingWord::fetch_full(&db, word_id),
+            Reference::fetch_all(&db),
+        )
+    })
+    .await
+    .unwrap();
+
+    Ok(match word {
+        Some(word) => ReferencesPage {
+            auth: user.into(),
+            i18n_info,
+            word,
+            references,
+            previous_success,
+        }
+        .into_response(),
+        None => NotFound {
+            auth: user.into(),
+            i18n_info,
+        }
+        .into_response(),
+    })
+}
+
+#[instrument(name = "Cite reference", skip(user, db))]
+async fn submit_citation_form(
+    word_id: u64,
+    form: CitationForm,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let (sense_id, example_id) = match form.target {
+        CitationTarget::Word => (None, None),
+        CitationTarget::Sense(id) => (Some(id), None),
+        CitationTarget::Example(id) => (None, Some(id)),
+    };
+    let citation = NewCitation {
+        reference_id: form.reference_id,
+        word_id,
+        sense_id,
+        example_id,
+        pages: form.pages,
+    };
+
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let citation_id = spawn_blocking_child(move || {
+        if !scopes::may_moderate_word(&db_clone, reviewer, word_id) {
+            return None;
+        }
+
+        add_citation(&db_clone, citation, reviewer)
+    })
+    .await
+    .unwrap();
+
+    word_references_page(word_id, Some(citation_id.is_some()), user, i18n_info, db).await
+}
+
+#[instrument(name = "Delete citation", skip(user, db))]
+async fn submit_delete_citation(
+    word_id: u64,
+    citation_id: u64,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let success = spawn_blocking_child(move || {
+        scopes::may_moderate_word(&db_clone, reviewer, word_id)
+            && remove_citation(&db_clone, word_id, citation_id, reviewer)
+    })
+    .await
+    .unwrap();
+
+    word_references_page(word_id, Some(success), user, i18n_info, db).await
+}
+
+#[instrument(name = "Add reference", skip(user, db))]
+async fn submit_reference_form(
+    word_id: u64,
+    form: ReferenceForm,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let reference = Reference {
+        id: 0,
+        kind: form.kind,
+        author: form.author.trim().to_owned(),
+        title: form.title.trim().to_owned(),
+        year: form.year,
+        publisher: form.publisher,
+        url: form.url,
+    };
+
+    let success = if reference.author.is_empty() || reference.title.is_empty() {
+        false
+    } else {
+        let db = db.clone();
+        let res = spawn_blocking_child(move || Reference::upsert(&db, None, &reference))
+            .await
+            .unwrap();
+
+        if let Err(error) = &res {
+            tracing::error!(?error, "Failed to add reference");
+        }
+
+        res.is_ok()
+    };
+
+    word_references_page(word_id, Some(success), user, i18n_info, db).await
+}
@@ -3,7 +3,8 @@ use crate::spawn_blocking_child;
 use anyhow::{Context, Result};
 use askama::Template;
 use isixhosa::noun::NounClass;
-use isixhosa_common::database::{GetWithSentinelExt, WordOrSuggestionId};
+use isixhosa_common::database::db_impl::DbImpl;
+use isixhosa_common::database::{GetWithSentinelExt, PublicAccessDb, WordOrSuggestionId};
 use isixhosa_common::format::DisplayHtml;
 use isixhosa_common::language::{NounClassExt, PartOfSpeech, Transitivity};
 use isixhosa_common::templates::AllWordsList;
@@ -15,7 +16,7 @@ use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::params;
 use serde::Serialize;
 use std::cmp::{max, Ordering};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
@@ -39,14 +40,18 @@ use xtra::prelude::*;
 
 const TANTIVY_WRITER_HEAP: usize = 128 * 1024 * 1024;
 const RESULTS: usize = 10;
+/// The maximum number of expressions containing a result which are appended to the results
+const EXPRESSION_RESULTS: usize = 5;
 
 pub struct TantivyClient {
     schema_info: SchemaInfo,
     tokenizer: TextAnalyzer,
     writer: Address<WriterActor>,
     searchers: Address<SearcherActor>,
+    /// The changes held back by the [`IndexBatch`] this client belongs to, if any
+    batch: Option<Arc<Mutex<Vec<IndexChange>>>>,
     /// Just the list portion of the 'all words' template (common/templates/all.askama.html)
-    all_words_html_cache: RwLock<Option<String>>,
+    all_words_html_cache: Arc<RwLock<Option<String>>>,
 }
 
 impl Debug for TantivyClient {
@@ -61,9 +66,20 @@ impl TantivyClient {
         db: Pool<SqliteConnectionManager>,
     ) -> Result<Arc<TantivyClient>> {
         let schema_info = Self::build_schema();
-        let dir = MmapDirectory::open(path)
+        let mut dir = MmapDirectory::open(path)
             .with_context(|| format!("Failed to open tantivy directory {:?}", path))?;
-        let reindex = !Index::exists(&dir)?;
+        let mut reindex = !Index::exists(&dir)?;
+
+        // An index built with an older schema cannot be opened, so it is rebuilt from scratch
+        if !reindex && Index::open(dir.clone())?.schema() != schema_info.schema {
+            info!("Search index schema has changed, rebuilding the index");
+            std::fs::remove_dir_all(path)?;
+            std::fs::create_dir_all(path)?;
+            dir = MmapDirectory::open(path)
+                .with_context(|| format!("Failed to open tantivy directory {:?}", path))?;
+            reindex = true;
+        }
+
         let index = Index::open_or_create(dir, schema_info.schema.clone())?;
 
         let lowercaser = TextAnalyzer::builder(SimpleTokenizer::default())
@@ -86,19 +102,20 @@ impl TantivyClient {
             tokenizer,
             writer,
             searchers: searchers.clone(),
-            all_words_html_cache: RwLock::new(None),
+            batch: None,
+            all_words_html_cache: Arc::new(RwLock::new(None)),
         };
         let client = Arc::new(client);
 
         for _ in 0..num_searchers {
-            let actor = SearcherActor::new(reader.clone(), client.clone());
+            let actor = SearcherActor::new(reader.clone(), client.clone(), DbImpl(db.clone()));
             xtra::spawn_tokio(actor, (searchers.clone(), mailbox.clone()));
         }
 
         if reindex {
             info!("Reindexing database");
             let now = Instant::now();
-            client.reindex_database(db).await;
+            client.reindex_database(DbImpl(db)).await;
             info!(
                 "Database reindexed in {:.2}ms",
                 now.elapsed().as_secs_f64() * 1_000.0
@@ -117,7 +134,8 @@ impl TantivyClient {
 
         let english = builder.add_text_field("english", text_options.clone());
         let xhosa = builder.add_text_field("xhosa", text_options.clone());
-        let xhosa_stemmed = builder.add_text_field("xhosa_stemmed", text_options);
+        let xhosa_stemmed = builder.add_text_field("xhosa_stemmed", text_options.clone());
+        let senses = builder.add_text_field("senses", text_options);
         let part_of_speech = builder.add_u64_field("part_of_speech", STORED);
         let is_plural = builder.add_u64_field("is_plural", STORED);
         let is_informal = builder.add_u64_field("is_informal", STORED);
@@ -127,12 +145,15 @@ impl TantivyClient {
         let suggesting_user = builder.add_u64_field("is_suggestion", STORED | INDEXED);
         let existing_id = builder.add_u64_field("existing_id", STORED | INDEXED);
         let suggestion_id = builder.add_u64_field("suggestion_id", STORED | INDEXED);
+        let labels = builder.add_u64_field("labels", STORED | INDEXED);
+        let rank_weight = builder.add_f64_field("rank_weight", STORED);
 
         SchemaInfo {
             schema: builder.build(),
             english,
             xhosa,
             xhosa_stemmed,
+            senses,
             part_of_speech,
             is_plural,
             is_inchoative,
@@ -142,6 +163,8 @@ impl TantivyClient {
             suggesting_user,
             existing_id,
             suggestion_id,
+            labels,
+            rank_weight,
         }
     }
 
@@ -151,6 +174,7 @@ impl TantivyClient {
             query = %query,
             include = ?include,
             exact = duplicate,
+            labels = ?labels,
         )
         skip_all,
     )]
@@ -159,6 +183,7 @@ impl TantivyClient {
         query: String,
         include: IncludeResults,
         duplicate: bool,
+        labels: Vec<u64>,
         i18n: I18nInfo,
     ) -> Result<Vec<Res>>
     where
@@ -169,6 +194,7 @@ impl TantivyClient {
                 query,
                 include,
                 duplicate,
+                labels,
                 i18n,
                 _phantom: PhantomData,
             })
@@ -197,7 +223,7 @@ impl TantivyClient {
     }
 
     #[instrument(name = "Reindex the database", skip_all)]
-    pub async fn reindex_database(&self, db: Pool<SqliteConnectionManager>) {
+    pub async fn reindex_database(&self, db: impl PublicAccessDb) {
         const SELECT: &str = "
             SELECT
                 word_id, english, xhosa, part_of_speech, is_plural, is_inchoative, is_informal, transitivity,
@@ -205,16 +231,73 @@ impl TantivyClient {
             FROM words
             ORDER BY word_id;
         ";
+        const SELECT_SENSES: &str =
+            "SELECT word_id, english FROM senses ORDER BY word_id, sense_order, sense_id;";
+        const SELECT_WORD_LABELS: &str = "
+            SELECT word_labels.word_id, word_labels.label_id, labels.rank_weight
+            FROM word_labels
+            INNER JOIN labels ON labels.label_id = word_labels.label_id;
+        ";
+        const SELECT_SENSE_LABELS: &str = "
+            SELECT DISTINCT senses.word_id, sense_labels.label_id
+            FROM sense_labels
+            INNER JOIN senses ON senses.sense_id = sense_labels.sense_id;
+        ";
 
         let span = info_span!("Fetch all existing words").or_current();
         let docs = tokio::task::spawn_blocking(move || {
             let _g = span.enter();
             let conn = db.get().unwrap();
+
+            let mut senses: HashMap<u64, Vec<String>> = HashMap::new();
+            conn.prepare(SELECT_SENSES)
+                .unwrap()
+                .query_map(params![], |row| {
+                    Ok((row.get("word_id")?, row.get("english")?))
+                })
+                .unwrap()
+                .for_each(|res| {
+                    let (word_id, english) = res.unwrap();
+                    senses.entry(word_id).or_default().push(english);
+                });
+
+            let mut labels: HashMap<u64, (Vec<u64>, f64)> = HashMap::new();
+            conn.prepare(SELECT_WORD_LABELS)
+                .unwrap()
+                .query_map(params![], |row| {
+                    Ok((
+                        row.get("word_id")?,
+                        row.get("label_id")?,
+                        row.get("rank_weight")?,
+                    ))
+                })
+                .unwrap()
+                .for_each(|res| {
+                    let (word_id, label_id, rank_weight): (u64, u64, f64) = res.unwrap();
+                    let (ids, weight) = labels.entry(word_id).or_insert((Vec::new(), 1.0));
+                    ids.push(label_id);
+                    *weight *= rank_weight;
+                });
+            conn.prepare(SELECT_SENSE_LABELS)
+                .unwrap()
+                .query_map(params![], |row| {
+                    Ok((row.get("word_id")?, row.get("label_id")?))
+                })
+                .unwrap()
+                .for_each(|res| {
+                    let (word_id, label_id) = res.unwrap();
+                    let (ids, _) = labels.entry(word_id).or_insert((Vec::new(), 1.0));
+                    ids.push(label_id);
+                });
+
             let mut stmt = conn.prepare(SELECT).unwrap();
 
             stmt.query_map(params![], |row| {
+                let word_id = row.get::<&str, i64>("word_id")? as u64;
+                let (labels, rank_weight) = labels.remove(&word_id).unwrap_or((Vec::new(), 1.0));
+
                 Ok(WordDocument {
-                    id: WordOrSuggestionId::existing(row.get::<&str, i64>("word_id")? as u64),
+                    id: WordOrSuggestionId::existing(word_id),
                     english: row.get("english")?,
                     xhosa: row.get("xhosa")?,
                     part_of_speech: row.get("part_of_speech")?,
@@ -224,6 +307,9 @@ impl TantivyClient {
                     suggesting_user: None,
                     noun_class: row.get_with_sentinel("noun_class")?,
                     is_informal: row.get("is_informal")?,
+                    senses: senses.remove(&word_id).unwrap_or_default(),
+                    labels,
+                    rank_weight,
                 })
             })
             .unwrap()
@@ -238,19 +324,126 @@ impl TantivyClient {
     }
 
     pub async fn add_new_word(&self, word: WordDocument) {
+        if let Some(batch) = &self.batch {
+            batch.lock().unwrap().push(IndexChange::Add(word));
+            return;
+        }
+
         self.invalidate_all_words_cache().await;
         self.writer.send(IndexWord(word)).await.unwrap()
     }
 
     pub async fn edit_word(&self, word: WordDocument) {
+        if let Some(batch) = &self.batch {
+            batch.lock().unwrap().push(IndexChange::Edit(word));
+            return;
+        }
+
         self.invalidate_all_words_cache().await;
         self.writer.send(EditWord(word)).await.unwrap()
     }
 
     pub async fn delete_word(&self, id: WordOrSuggestionId) {
+        if let Some(batch) = &self.batch {
+            batch.lock().unwrap().push(IndexChange::Delete(id));
+            return;
+        }
+
         self.invalidate_all_words_cache().await;
         self.writer.send(DeleteWord(id)).await.unwrap()
     }
+
+    /// Begins a batch of changes which are committed together. If this client belongs to a batch
+    /// already, the new batch's changes are added to it once it ends.
+    pub fn batch(&self) -> IndexBatch {
+        let client = TantivyClient {
+            schema_info: self.schema_info.clone(),
+            tokenizer: self.tokenizer.clone(),
+            writer: self.writer.clone(),
+            searchers: self.searchers.clone(),
+            batch: Some(Arc::default()),
+            all_words_html_cache: self.all_words_html_cache.clone(),
+        };
+
+        IndexBatch {
+            client: Arc::new(client),
+            parent: self.batch.clone(),
+        }
+    }
+}
+
+/// A change to the index held back by an [`IndexBatch`]
+#[derive(Debug)]
+pub enum IndexChange {
+    Add(WordDocument),
+    Edit(WordDocument),
+    Delete(WordOrSuggestionId),
+}
+
+/// Changes to the index which are only made once the batch ends, such as those of a bulk moderation
+/// action. Changes made with the batch's [client](IndexBatch::client) are held back until the batch
+/// is dropped, and are then all applied with a single commit. Nothing else is held back meanwhile.
+///
+/// A batch begun from the client of another batch passes its changes on to that batch instead,
+/// unless it is [discarded](IndexBatch::discard) or dropped while panicking.
+pub struct IndexBatch {
+    client: Arc<TantivyClient>,
+    parent: Option<Arc<Mutex<Vec<IndexChange>>>>,
+}
+
+impl IndexBatch {
+    pub fn client(&self) -> Arc<TantivyClient> {
+        self.client.clone()
+    }
+
+    /// Drops the changes made so far in this batch
+    pub fn discard(self) {
+        self.changes().clear();
+    }
+
+    /// Ends the batch, waiting until its changes have been committed
+    pub async fn commit(self) {
+        let changes = std::mem::take(&mut *self.changes());
+
+        match &self.parent {
+            Some(parent) => parent.lock().unwrap().extend(changes),
+            None if changes.is_empty() => {}
+            None => {
+                self.client
+                    .writer
+                    .send(ApplyChanges(changes))
+                    .await
+                    .unwrap();
+                self.client.invalidate_all_words_cache().await;
+            }
+        }
+    }
+
+    fn changes(&self) -> std::sync::MutexGuard<'_, Vec<IndexChange>> {
+        self.client.batch.as_ref().unwrap().lock().unwrap()
+    }
+}
+
+impl Drop for IndexBatch {
+    fn drop(&mut self) {
+        let changes = std::mem::take(&mut *self.changes());
+
+        if changes.is_empty() {
+            return;
+        }
+
+        match &self.parent {
+            Some(_) if std::thread::panicking() => {}
+            Some(parent) => parent.lock().unwrap().extend(changes),
+            None => {
+                let client = self.client.clone();
+                tokio::spawn(async move {
+                    client.writer.send(ApplyChanges(changes)).await.unwrap();
+                    client.invalidate_all_words_cache().await;
+                });
+            }
+        }
+    }
 }
 
 /// A search result intended to be passed to the JS frontend
@@ -290,6 +483,18 @@ impl WriterActor {
         }
     }
 
+    fn delete_word(writer: &mut IndexWriter, schema_info: &SchemaInfo, id: WordOrSuggestionId) {
+        let term = match id {
+            WordOrSuggestionId::ExistingWord { existing_id } => {
+                Term::from_field_u64(schema_info.existing_id, existing_id)
+            }
+            WordOrSuggestionId::Suggested { suggestion_id } => {
+                Term::from_field_u64(schema_info.suggestion_id, suggestion_id)
+            }
+        };
+        writer.delete_term(term);
+    }
+
     fn add_word(
         writer: &mut IndexWriter,
         schema_info: &SchemaInfo,
@@ -317,8 +522,17 @@ impl WriterActor {
             schema_info.is_informal => doc.is_informal as u64,
             schema_info.transitivity => doc.transitivity.map(|x| x as u64).unwrap_or(255),
             schema_info.noun_class => doc.noun_class.map(|x| x as u64).unwrap_or(255),
+            schema_info.rank_weight => doc.rank_weight,
         );
 
+        for sense in doc.senses {
+            tantivy_doc.add_text(schema_info.senses, sense);
+        }
+
+        for label in doc.labels {
+            tantivy_doc.add_u64(schema_info.labels, label);
+        }
+
         let (id_field, suggestion) = match doc.id {
             WordOrSuggestionId::Suggested { suggestion_id } => {
                 (schema_info.suggestion_id, suggestion_id)
@@ -353,6 +567,9 @@ pub struct ReindexWords(Vec<WordDocument>);
 #[derive(Debug)]
 pub struct IndexWord(WordDocument);
 
+#[derive(Debug)]
+pub struct ApplyChanges(Vec<IndexChange>);
+
 impl Handler<ReindexWords> for WriterActor {
     type Return = ();
 
@@ -418,15 +635,7 @@ impl Handler<EditWord> for WriterActor {
 
         spawn_blocking_child(move || {
             let mut writer = writer.lock().unwrap();
-            let term = match edit.0.id {
-                WordOrSuggestionId::ExistingWord { existing_id } => {
-                    Term::from_field_u64(schema_info.existing_id, existing_id)
-                }
-                WordOrSuggestionId::Suggested { suggestion_id } => {
-                    Term::from_field_u64(schema_info.suggestion_id, suggestion_id)
-                }
-            };
-            writer.delete_term(term);
+            Self::delete_word(&mut writer, &schema_info, edit.0.id);
             Self::add_word(&mut writer, &schema_info, edit.0).unwrap();
             writer.commit().unwrap();
         })
@@ -445,15 +654,42 @@ impl Handler<DeleteWord> for WriterActor {
 
         spawn_blocking_child(move || {
             let mut writer = writer.lock().unwrap();
-            let term = match delete.0 {
-                WordOrSuggestionId::ExistingWord { existing_id } => {
-                    Term::from_field_u64(schema_info.existing_id, existing_id)
-                }
-                WordOrSuggestionId::Suggested { suggestion_id } => {
-                    Term::from_field_u64(schema_info.suggestion_id, suggestion_id)
+            Self::delete_word(&mut writer, &schema_info, delete.0);
+            writer.commit().unwrap();
+        })
+        .await
+        .unwrap()
+    }
+}
+
+impl Handler<ApplyChanges> for WriterActor {
+    type Return = ();
+
+    #[instrument(
+        name = "Apply a batch of changes to tantivy",
+        fields(changes = changes.0.len()),
+        skip_all,
+    )]
+    async fn handle(&mut self, changes: ApplyChanges, _ctx: &mut xtra::Context<Self>) {
+        let writer = self.writer.clone();
+        let schema_info = self.schema_info.clone();
+
+        spawn_blocking_child(move || {
+            let mut writer = writer.lock().unwrap();
+
+            for change in changes.0 {
+                match change {
+                    IndexChange::Add(doc) => {
+                        Self::add_word(&mut writer, &schema_info, doc).unwrap();
+                    }
+                    IndexChange::Edit(doc) => {
+                        Self::delete_word(&mut writer, &schema_info, doc.id);
+                        Self::add_word(&mut writer, &schema_info, doc).unwrap();
+                    }
+                    IndexChange::Delete(id) => Self::delete_word(&mut writer, &schema_info, id),
                 }
-            };
-            writer.delete_term(term);
+            }
+
             writer.commit().unwrap();
         })
         .await
@@ -464,11 +700,13 @@ impl Handler<DeleteWord> for WriterActor {
 pub struct SearcherActor {
     reader: IndexReader,
     client: Arc<TantivyClient>,
+    /// Used to look up the expressions which contain a result
+    db: DbImpl,
 }
 
 impl SearcherActor {
-    fn new(reader: IndexReader, client: Arc<TantivyClient>) -> Self {
-        SearcherActor { reader, client }
+    fn new(reader: IndexReader, client: Arc<TantivyClient>, db: DbImpl) -> Self {
+        SearcherActor { reader, client, db }
     }
 }
 
@@ -482,6 +720,8 @@ pub struct SearchRequest<Res> {
     query: String,
     include: IncludeResults,
     duplicate: bool,
+    /// Only words with all of these labels, or senses with them, are included
+    labels: Vec<u64>,
     // It isn't great that we have to pass this in. The reason for it is that with this,
     // we can avoid getting results just to map them and collect again. But this introduces
     // a coupling between I18n and searching that is not great. Really the best solution
@@ -498,6 +738,7 @@ impl<Res> SearchRequest<Res> {
             query: self.query,
             include: self.include,
             duplicate: self.duplicate,
+            labels: self.labels,
             i18n: self.i18n,
             _phantom: PhantomData,
         }
@@ -532,7 +773,7 @@ impl SearcherActor {
         out: &mut HashSet<WordHit>,
     ) {
         let mut tokenized = tokenizer.token_stream(&req.query);
-        let mut queries: Vec<Box<dyn Query + 'static>> = Vec::with_capacity(3);
+        let mut queries: Vec<Box<dyn Query + 'static>> = Vec::with_capacity(4);
         tokenized.process(&mut |token| {
             let distance = match token.text.len() {
                 0..=2 => 0,
@@ -546,20 +787,28 @@ impl SearcherActor {
             let xhosa = Term::from_field_text(client.schema_info.xhosa, &token.text);
             let xhosa_stemmed =
                 Term::from_field_text(client.schema_info.xhosa_stemmed, &token.text);
+            let senses = Term::from_field_text(client.schema_info.senses, &token.text);
 
             let query_english = FuzzyTermQuery::new_prefix(english, distance, true);
             let query_xhosa = FuzzyTermQuery::new_prefix(xhosa, distance, true);
             let query_xhosa_stemmed = FuzzyTermQuery::new_prefix(xhosa_stemmed, distance, true);
+            let query_senses = FuzzyTermQuery::new_prefix(senses, distance, true);
 
             let this_term: Vec<Box<dyn Query + 'static>> = vec![
                 Box::new(query_english),
                 Box::new(query_xhosa),
                 Box::new(query_xhosa_stemmed),
+                Box::new(query_senses),
             ];
 
             queries.push(Box::new(BooleanQuery::union(this_term)));
         });
 
+        for label in &req.labels {
+            let label = Term::from_field_u64(client.schema_info.labels, *label);
+            queries.push(Box::new(TermQuery::new(label, IndexRecordOption::Basic)));
+        }
+
         let terms = BooleanQuery::intersection(queries);
 
         let not_suggestion = || {
@@ -602,6 +851,38 @@ impl SearcherActor {
 
         Span::current().record("results", count);
     }
+
+    /// Appends the expressions which contain any of the existing words in the results, so that
+    /// searching for a word also surfaces the idioms it is used in.
+    #[instrument(name = "Append expressions to results", fields(added), skip_all)]
+    fn append_expressions(db: &impl PublicAccessDb, hits: &mut Vec<WordHit>) {
+        let components: Vec<u64> = hits
+            .iter()
+            .filter(|hit| {
+                !hit.is_suggestion && hit.part_of_speech != Some(PartOfSpeech::Expression)
+            })
+            .map(|hit| hit.id)
+            .collect();
+        let mut added = 0;
+
+        'outer: for word_id in components {
+            for expression in WordHit::fetch_expressions_containing(db, word_id) {
+                if added >= EXPRESSION_RESULTS {
+                    break 'outer;
+                }
+
+                let present = hits
+                    .iter()
+                    .any(|hit| !hit.is_suggestion && hit.id == expression.id);
+                if !present {
+                    hits.push(expression);
+                    added += 1;
+                }
+            }
+        }
+
+        Span::current().record("added", added);
+    }
 }
 
 impl<Res> Handler<SearchRequest<Res>> for SearcherActor
@@ -632,12 +913,18 @@ where
                 let en_sim = sim(&hit.english);
                 // Temporary fix for "become ___" ranking very low
                 let en_inchoative_sim = sim(hit.english.trim_start_matches("become "));
-                let sim_score = max(xh_sim, max(en_sim, en_inchoative_sim));
-                // 1% penalty to any informal words to make them rank lower (they are usually less relevant)
-                let informal_penalty = if hit.is_informal { 0.99 } else { 1.0 };
-
+                let sense_sim = hit
+                    .senses
+                    .iter()
+                    .map(|sense| sim(sense.as_str()))
+                    .max()
+                    .unwrap_or_default();
+                let sim_score = max(max(xh_sim, sense_sim), max(en_sim, en_inchoative_sim));
+
+                // Labels are the only thing which changes the ranking. `is_informal` is only shown,
+                // and informal words are ranked lower by the 'informal' label (migrations/2.sql).
                 WordHitWithScore {
-                    score: sim_score * informal_penalty,
+                    score: sim_score * hit.rank_weight,
                     hit,
                 }
             }
@@ -667,6 +954,7 @@ where
         let mut searcher = self.reader.searcher();
         let client = self.client.clone();
         let mut tokenizer = self.client.tokenizer.clone();
+        let db = self.db.clone();
         let mut results = HashSet::with_capacity(10);
 
         spawn_blocking_child(move || {
@@ -713,10 +1001,13 @@ where
 
                 debug_span!("Sorting list based on score").in_scope(|| results.sort());
 
-                Ok(results
+                let mut hits: Vec<WordHit> =
+                    results.into_iter().take(RESULTS).map(|s| s.hit).collect();
+                SearcherActor::append_expressions(&db, &mut hits);
+
+                Ok(hits
                     .into_iter()
-                    .take(RESULTS)
-                    .map(|s| Res::from_with_i18n(s.hit, &req.i18n))
+                    .map(|hit| Res::from_with_i18n(hit, &req.i18n))
                     .collect())
             }
         })
@@ -762,6 +1053,7 @@ struct SchemaInfo {
     english: Field,
     xhosa: Field,
     xhosa_stemmed: Field,
+    senses: Field,
     part_of_speech: Field,
     is_plural: Field,
     is_inchoative: Field,
@@ -771,6 +1063,8 @@ struct SchemaInfo {
     suggesting_user: Field,
     existing_id: Field,
     suggestion_id: Field,
+    labels: Field,
+    rank_weight: Field,
 }
 
 #[derive(Clone, Debug)]
@@ -786,6 +1080,79 @@ pub struct WordDocument {
     pub suggesting_user: Option<NonZeroU64>,
     pub noun_class: Option<NounClass>,
     pub is_informal: bool,
+    /// The glosses of each sense of the word, in order
+    pub senses: Vec<String>,
+    /// The labels of the word and of each of its senses
+    pub labels: Vec<u64>,
+    /// The product of the rank weights of the labels of the word as a whole
+    pub rank_weight: f64,
+}
+
+impl WordDocument {
+    /// Replaces the sense glosses and labels of the document with those currently in the database.
+    pub fn with_senses_and_labels_from_db(mut self, db: &impl PublicAccessDb) -> Self {
+        self.senses = WordHit::fetch_sense_glosses(db, self.id);
+        (self.labels, self.rank_weight) = fetch_labels(db, self.id);
+        self
+    }
+}
+
+/// The ids of the labels of the word and its senses, and the product of the rank weights of the
+/// labels of the word as a whole. For a suggested word, these are the labels suggested along with
+/// it. The weights of labels of a single sense are ignored, as the word's other senses are no less
+/// relevant for them.
+#[instrument(level = "trace", name = "Fetch labels for indexing", skip(db))]
+fn fetch_labels(db: &impl PublicAccessDb, id: WordOrSuggestionId) -> (Vec<u64>, f64) {
+    const SELECT_EXISTING: &str = "
+        SELECT word_labels.label_id, labels.rank_weight, TRUE AS is_word_label
+            FROM word_labels
+            INNER JOIN labels ON labels.label_id = word_labels.label_id
+            WHERE word_labels.word_id = ?1
+        UNION ALL
+        SELECT DISTINCT sense_labels.label_id, 1.0, FALSE
+            FROM sense_labels
+            INNER JOIN senses ON senses.sense_id = sense_labels.sense_id
+            WHERE senses.word_id = ?1;
+    ";
+    const SELECT_SUGGESTED: &str = "
+        SELECT
+            label_suggestions.label_id, labels.rank_weight,
+            (existing_sense_id IS NULL AND sense_suggestion_id IS NULL) AS is_word_label
+        FROM label_suggestions
+        INNER JOIN labels ON labels.label_id = label_suggestions.label_id
+        WHERE suggested_word_id = ?1 AND NOT is_delete;
+    ";
+
+    let stmt = match id {
+        WordOrSuggestionId::ExistingWord { .. } => SELECT_EXISTING,
+        WordOrSuggestionId::Suggested { .. } => SELECT_SUGGESTED,
+    };
+
+    let conn = db.get().unwrap();
+    let mut labels = Vec::new();
+    let mut rank_weight = 1.0;
+
+    conn.prepare(stmt)
+        .unwrap()
+        .query_map(params![id.inner()], |row| {
+            Ok((
+                row.get("label_id")?,
+                row.get("rank_weight")?,
+                row.get("is_word_label")?,
+            ))
+        })
+        .unwrap()
+        .for_each(|res| {
+            let (label_id, weight, is_word_label): (u64, f64, bool) = res.unwrap();
+            if is_word_label {
+                rank_weight *= weight;
+            }
+            if !labels.contains(&label_id) {
+                labels.push(label_id);
+            }
+        });
+
+    (labels, rank_weight)
 }
 
 trait WordHitExt {
@@ -860,9 +1227,18 @@ impl WordHitExt for WordHit {
             is_inchoative: get_bool(&doc, schema_info.is_inchoative, "is_inchoative")?,
             is_informal: get_bool(&doc, schema_info.is_informal, "is_informal")?,
             transitivity: get_with_sentinel(&doc, schema_info.transitivity),
+            senses: doc
+                .get_all(schema_info.senses)
+                .filter_map(|v| v.as_str())
+                .map(ToOwned::to_owned)
+                .collect(),
             is_suggestion,
             noun_class: get_with_sentinel(&doc, schema_info.noun_class)
                 .map(|c: NounClass| c.to_prefixes()),
+            rank_weight: doc
+                .get_first(schema_info.rank_weight)
+                .and_then(|v| v.as_f64())
+                .unwrap_or(1.0),
         })
     }
 }
@@ -878,8 +1254,10 @@ impl From<WordDocument> for WordHit {
             is_inchoative: d.is_inchoative,
             is_informal: d.is_informal,
             transitivity: d.transitivity,
+            senses: d.senses,
             is_suggestion: d.suggesting_user.is_some(),
             noun_class: d.noun_class.map(|c| c.to_prefixes()),
+            rank_weight: d.rank_weight,
         }
     }
 }
@@ -84,6 +84,9 @@ impl Handler<Result<ws::Message, warp::Error>> for LiveSearchSession {
             struct Query {
                 search: String,
                 state: String,
+                /// Only include words with all of these labels
+                #[serde(default)]
+                labels: Vec<u64>,
             }
 
             if msg.to_str().unwrap().is_empty() {
@@ -105,7 +108,13 @@ impl Handler<Result<ws::Message, warp::Error>> for LiveSearchSession {
                     let reply = Reply {
                         results: self
                             .tantivy
-                            .search(query.search, self.include, false, self.i18n_info.clone())
+                            .search(
+                                query.search,
+                                self.include,
+                                false,
+                                query.labels,
+                                self.i18n_info.clone(),
+                            )
                             .await
                             .unwrap(),
                         state: query.state,
@@ -126,6 +135,7 @@ impl Handler<Result<ws::Message, warp::Error>> for LiveSearchSession {
                             query.to_owned(),
                             IncludeResults::AcceptedOnly,
                             false,
+                            Vec::new(),
                             self.i18n_info.clone(),
                         )
                         .await
@@ -0,0 +1,154 @@
+use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::scopes;
+use crate::database::split::{split_word, SplitWord};
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::search::TantivyClient;
+use crate::serialization::qs_form;
+use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, ModeratorAccessDb};
+use isixhosa_common::format::DisplayHtml;
+use isixhosa_common::language::WordLinkType;
+use isixhosa_common::types::ExistingWord;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::http::Uri;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "split.askama.html")]
+struct SplitWordPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    word: ExistingWord,
+    previous_success: Option<bool>,
+}
+
+#[derive(Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum SplitSide {
+    Original,
+    New,
+}
+
+#[derive(Deserialize, Debug)]
+struct SplitForm {
+    original_english: String,
+    new_english: String,
+    link_type: WordLinkType,
+    #[serde(default)]
+    examples: HashMap<u64, SplitSide>,
+    #[serde(default)]
+    linked_words: HashMap<u64, SplitSide>,
+}
+
+impl From<SplitForm> for SplitWord {
+    fn from(form: SplitForm) -> Self {
+        let moved = |items: HashMap<u64, SplitSide>| {
+            items
+                .into_iter()
+                .filter(|(_, side)| *side == SplitSide::New)
+                .map(|(id, _)| id)
+                .collect()
+        };
+
+        SplitWord {
+            original_english: form.original_english,
+            new_english: form.new_english,
+            link_type: form.link_type,
+            new_examples: moved(form.examples),
+            new_linked_words: moved(form.linked_words),
+        }
+    }
+}
+
+pub fn split(
+    db: DbBase,
+    tantivy: Arc<TantivyClient>,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let split_page = warp::get()
+        .and(warp::path![u64 / "split"])
+        .and(warp::path::end())
+        .and(warp::any().map(|| None)) // previous_success is None
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(split_word_page);
+
+    let submit_split = warp::post()
+        .and(warp::path![u64 / "split"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(qs_form())
+        .and(warp::any().map(move || tantivy.clone()))
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(submit_split_form);
+
+    warp::path("word")
+        .and(split_page.or(submit_split))
+        .debug_boxed()
+}
+
+#[instrument(name = "Display split word page", skip(user, db))]
+async fn split_word_page(
+    word_id: u64,
+    previous_success: Option<bool>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let word = spawn_blocking_child(move || ExistingWord::fetch_full(&db, word_id))
+        .await
+        .unwrap();
+
+    Ok(match word {
+        Some(word) => SplitWordPage {
+            auth: user.into(),
+            i18n_info,
+            word,
+            previous_success,
+        }
+        .into_response(),
+        None => NotFound {
+            auth: user.into(),
+            i18n_info,
+        }
+        .into_response(),
+    })
+}
+
+#[instrument(name = "Split word", skip(tantivy, user, db))]
+async fn submit_split_form(
+    word_id: u64,
+    form: SplitForm,
+    tantivy: Arc<TantivyClient>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let new_word_id = spawn_blocking_child(move || {
+        if !scopes::may_moderate_word(&db_clone, reviewer, word_id) {
+            return None;
+        }
+
+        split_word(&db_clone, tantivy, word_id, form.into(), reviewer)
+    })
+    .await
+    .unwrap();
+
+    match new_word_id {
+        Some(new_word_id) => {
+            let uri: Uri = format!("/word/{new_word_id}").parse().unwrap();
+            Ok(warp::redirect::see_other(uri).into_response())
+        }
+        None => split_word_page(word_id, Some(false), user, i18n_info, db)
+            .await
+            .map(Reply::into_response),
+    }
+}
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS bibliography (
+    reference_id  INTEGER PRIMARY KEY,
+    kind          INTEGER NOT NULL, -- dictionary, grammar, book, article, corpus or website
+    author        TEXT NOT NULL,
+    title         TEXT NOT NULL,
+    year          INTEGER,
+    publisher     TEXT,
+    url           TEXT
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS citations (
+    citation_id   INTEGER PRIMARY KEY AUTOINCREMENT,
+    reference_id  INTEGER NOT NULL REFERENCES bibliography(reference_id) ON DELETE CASCADE,
+    word_id       INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    sense_id      INTEGER REFERENCES senses(sense_id) ON DELETE CASCADE, -- null if not citing a specific sense
+    example_id    INTEGER REFERENCES examples(example_id) ON DELETE CASCADE, -- null if not citing an example
+    pages         TEXT NOT NULL -- e.g. "p. 112" or "pp. 40-42", may be empty
+);
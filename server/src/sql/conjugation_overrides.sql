@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS conjugation_overrides (
+    word_id   INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    tense     INTEGER NOT NULL,
+    polarity  INTEGER NOT NULL,
+    subject   INTEGER NOT NULL,
+    form      TEXT NOT NULL,
+
+    PRIMARY KEY (word_id, tense, polarity, subject)
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS example_sense_suggestions (
+    -- If both senses are null, the example is suggested to be removed from its sense
+    example_suggestion_id  INTEGER PRIMARY KEY REFERENCES example_suggestions(suggestion_id) ON DELETE CASCADE,
+    existing_sense_id      INTEGER REFERENCES senses(sense_id) ON DELETE CASCADE,
+    sense_suggestion_id    INTEGER REFERENCES sense_suggestions(suggestion_id) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS example_senses (
+    -- An example illustrates at most one sense of its word
+    example_id  INTEGER PRIMARY KEY REFERENCES examples(example_id) ON DELETE CASCADE,
+    sense_id    INTEGER NOT NULL REFERENCES senses(sense_id) ON DELETE CASCADE
+);
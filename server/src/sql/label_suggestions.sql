@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS label_suggestions (
+    suggestion_id        INTEGER PRIMARY KEY AUTOINCREMENT, -- id must be stable with deletion
+    label_id             INTEGER NOT NULL REFERENCES labels(label_id) ON DELETE CASCADE,
+    suggesting_user      INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    changes_summary      TEXT NOT NULL,
+    is_delete            BOOLEAN NOT NULL, -- whether or not this suggestion is to remove a given existing label
+
+    -- In the case of labelling an existing word
+    existing_word_id     INTEGER REFERENCES words(word_id) ON DELETE CASCADE,
+    -- In the case of labelling a suggested word
+    suggested_word_id    INTEGER REFERENCES word_suggestions(suggestion_id) ON DELETE CASCADE,
+
+    -- If both of these are null, the label is for the word as a whole. Otherwise, it is for the
+    -- given existing or suggested sense of the word.
+    existing_sense_id    INTEGER REFERENCES senses(sense_id) ON DELETE CASCADE,
+    sense_suggestion_id  INTEGER REFERENCES sense_suggestions(suggestion_id) ON DELETE CASCADE
+);
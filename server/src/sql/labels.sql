@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS labels (
+    label_id     INTEGER PRIMARY KEY,
+    kind         INTEGER NOT NULL, -- register, dialect, domain or usage
+    name         TEXT NOT NULL,
+    description  TEXT NOT NULL,
+    rank_weight  REAL NOT NULL -- multiplies the search ranking of words with this label
+);
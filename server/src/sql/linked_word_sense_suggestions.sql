@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS linked_word_sense_suggestions (
+    -- The sense is one of the senses of the word from whose page the link was suggested. If both
+    -- senses are null, the link is suggested to be removed from the senses of that word.
+    link_suggestion_id   INTEGER PRIMARY KEY REFERENCES linked_word_suggestions(suggestion_id) ON DELETE CASCADE,
+    -- The word whose sense the link is assigned to, needed when neither sense is given
+    existing_word_id     INTEGER REFERENCES words(word_id) ON DELETE CASCADE,
+    existing_sense_id    INTEGER REFERENCES senses(sense_id) ON DELETE CASCADE,
+    sense_suggestion_id  INTEGER REFERENCES sense_suggestions(suggestion_id) ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS linked_word_senses (
+    -- A link can belong to one sense of each of the two words which it links
+    link_id   INTEGER NOT NULL REFERENCES linked_words(link_id) ON DELETE CASCADE,
+    sense_id  INTEGER NOT NULL REFERENCES senses(sense_id) ON DELETE CASCADE,
+    PRIMARY KEY (link_id, sense_id)
+);
@@ -0,0 +1,11 @@
+-- Etymology of words
+ALTER TABLE words ADD COLUMN etymology_language INTEGER;
+ALTER TABLE words ADD COLUMN etymology_form TEXT NOT NULL DEFAULT('');
+ALTER TABLE words ADD COLUMN etymology_note TEXT NOT NULL DEFAULT('');
+ALTER TABLE word_suggestions ADD COLUMN etymology_language INTEGER;
+ALTER TABLE word_suggestions ADD COLUMN etymology_form TEXT;
+ALTER TABLE word_suggestions ADD COLUMN etymology_note TEXT;
+
+-- Literal translations of idioms and expressions
+ALTER TABLE words ADD COLUMN literal_translation TEXT NOT NULL DEFAULT('');
+ALTER TABLE word_suggestions ADD COLUMN literal_translation TEXT;
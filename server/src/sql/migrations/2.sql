@@ -0,0 +1,9 @@
+-- Informal words are ranked lower by an 'informal' label rather than a fixed penalty
+INSERT INTO labels (kind, name, description, rank_weight)
+    SELECT 1, 'informal', 'Informal or colloquial language', 0.99
+    WHERE NOT EXISTS (SELECT 1 FROM labels WHERE name = 'informal');
+
+INSERT INTO word_labels (word_id, label_id)
+    SELECT words.word_id, (SELECT MIN(label_id) FROM labels WHERE name = 'informal')
+    FROM words
+    WHERE words.is_informal;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS moderator_scopes (
+    scope_id    INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id     INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    -- Exactly one of these is set. They are not foreign keys, so that deleting a dataset or label
+    -- can never widen what a moderator may moderate.
+    dataset_id  INTEGER,
+    label_id    INTEGER
+);
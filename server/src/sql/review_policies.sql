@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS review_policies (
+    policy_id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    suggestion_type     INTEGER, -- NULL applies the policy to all types of suggestion
+    dataset_id          INTEGER REFERENCES datasets(dataset_id) ON DELETE CASCADE, -- NULL applies it to all words
+    required_approvals  INTEGER NOT NULL DEFAULT(1),
+    allow_voting        BOOLEAN NOT NULL DEFAULT(FALSE)
+);
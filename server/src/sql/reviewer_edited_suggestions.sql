@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS reviewer_edited_suggestions (
+    suggestion_type  INTEGER NOT NULL,
+    suggestion_id    INTEGER NOT NULL, -- edited by a moderator, so it is accepted with edits
+    PRIMARY KEY (suggestion_type, suggestion_id) ON CONFLICT IGNORE
+);
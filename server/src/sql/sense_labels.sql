@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS sense_labels (
+    sense_id  INTEGER NOT NULL REFERENCES senses(sense_id) ON DELETE CASCADE,
+    label_id  INTEGER NOT NULL REFERENCES labels(label_id) ON DELETE CASCADE,
+    UNIQUE(sense_id, label_id) ON CONFLICT IGNORE
+);
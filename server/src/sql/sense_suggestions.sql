@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS sense_suggestions (
+    suggestion_id      INTEGER PRIMARY KEY AUTOINCREMENT, -- id must be stable with deletion
+    suggesting_user    INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    -- In the case of adding a new sense to an existing word
+    existing_word_id   INTEGER REFERENCES words(word_id) ON DELETE CASCADE,
+    -- In the case of adding a new sense to a suggested word
+    suggested_word_id  INTEGER REFERENCES word_suggestions(suggestion_id) ON DELETE CASCADE,
+    -- In the case of updating or deleting an existing sense
+    existing_sense_id  INTEGER REFERENCES senses(sense_id) ON DELETE CASCADE,
+    changes_summary    TEXT NOT NULL,
+    is_delete          BOOLEAN NOT NULL, -- whether or not this suggestion is to delete the existing sense
+    sense_order        INTEGER,
+    english            TEXT,
+    is_informal        BOOLEAN
+);
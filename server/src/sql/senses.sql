@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS senses (
+    sense_id     INTEGER PRIMARY KEY AUTOINCREMENT,
+    word_id      INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    sense_order  INTEGER NOT NULL, -- position of the sense within the word, starting from 1
+    english      TEXT NOT NULL,
+    is_informal  BOOLEAN NOT NULL
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS suggestion_approvals (
+    suggestion_type  INTEGER NOT NULL,
+    suggestion_id    INTEGER NOT NULL,
+    moderator        INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    approved_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (suggestion_type, suggestion_id, moderator) ON CONFLICT IGNORE
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS suggestion_comments (
+    comment_id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    suggestion_type    INTEGER NOT NULL,
+    suggestion_id      INTEGER NOT NULL, -- not a foreign key, as it may point to any suggestion table
+    parent_comment_id  INTEGER REFERENCES suggestion_comments(comment_id) ON DELETE CASCADE,
+    author             INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    body               TEXT NOT NULL,
+    posted_at          TIMESTAMP WITH TIME ZONE NOT NULL
+);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS suggestion_outcomes (
+    outcome_id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    suggestion_type   INTEGER NOT NULL,
+    suggestion_id     INTEGER NOT NULL, -- not a foreign key, as the suggestion is deleted once reviewed
+    word_id           INTEGER, -- the existing word the suggestion was for, if any; not a foreign key
+    suggesting_user   INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    reviewing_user    INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    summary           TEXT NOT NULL, -- what was suggested, as it was when it was reviewed
+    changes_summary   TEXT NOT NULL,
+    outcome           INTEGER NOT NULL, -- accepted, accepted with edits, or rejected
+    rejection_reason  INTEGER,
+    reviewer_comment  TEXT NOT NULL DEFAULT(''),
+    reviewed_at       TIMESTAMP WITH TIME ZONE NOT NULL
+);
@@ -0,0 +1,49 @@
+CREATE TABLE IF NOT EXISTS suggestion_submissions (
+    suggestion_type  INTEGER NOT NULL,
+    suggestion_id    INTEGER NOT NULL, -- not a foreign key, as it may be in any of the suggestion tables
+    submitted_at     TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (suggestion_type, suggestion_id) ON CONFLICT REPLACE -- in case an id is reused
+);
+
+-- Suggestions are inserted in many places, so triggers record when each was submitted. Edits to a
+-- suggestion are updates, so they do not change this.
+CREATE TRIGGER IF NOT EXISTS word_suggestion_submitted AFTER INSERT ON word_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (1, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
+
+CREATE TRIGGER IF NOT EXISTS word_deletion_suggestion_submitted
+    AFTER INSERT ON word_deletion_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (2, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
+
+CREATE TRIGGER IF NOT EXISTS sense_suggestion_submitted AFTER INSERT ON sense_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (3, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
+
+CREATE TRIGGER IF NOT EXISTS example_suggestion_submitted AFTER INSERT ON example_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (4, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
+
+CREATE TRIGGER IF NOT EXISTS example_deletion_suggestion_submitted
+    AFTER INSERT ON example_deletion_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (5, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
+
+CREATE TRIGGER IF NOT EXISTS linked_word_suggestion_submitted
+    AFTER INSERT ON linked_word_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (6, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
+
+CREATE TRIGGER IF NOT EXISTS linked_word_deletion_suggestion_submitted
+    AFTER INSERT ON linked_word_deletion_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (7, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
+
+CREATE TRIGGER IF NOT EXISTS dataset_attribution_suggestion_submitted
+    AFTER INSERT ON dataset_attribution_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (8, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
+
+CREATE TRIGGER IF NOT EXISTS label_suggestion_submitted AFTER INSERT ON label_suggestions BEGIN
+    INSERT INTO suggestion_submissions VALUES (9, NEW.suggestion_id, CURRENT_TIMESTAMP);
+END;
@@ -0,0 +1,121 @@
+CREATE TABLE IF NOT EXISTS suggestion_votes (
+    suggestion_type  INTEGER NOT NULL,
+    suggestion_id    INTEGER NOT NULL,
+    voter            INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    vote             INTEGER NOT NULL, -- 1 for an upvote, -1 for a downvote
+    PRIMARY KEY (suggestion_type, suggestion_id, voter) ON CONFLICT REPLACE
+);
+
+-- Approvals and votes are given to a suggestion as it was at the time, so triggers delete them when
+-- the suggestion is deleted, including when it is deleted along with a word, or when what it
+-- suggests is changed. Suggestions are deleted and edited in many places, as with their submission
+-- times.
+
+CREATE TRIGGER IF NOT EXISTS word_suggestion_reviews_deleted
+    AFTER DELETE ON word_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 1 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 1 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS word_deletion_suggestion_reviews_deleted
+    AFTER DELETE ON word_deletion_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 2 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 2 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS sense_suggestion_reviews_deleted
+    AFTER DELETE ON sense_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 3 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 3 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS example_suggestion_reviews_deleted
+    AFTER DELETE ON example_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 4 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 4 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS example_deletion_suggestion_reviews_deleted
+    AFTER DELETE ON example_deletion_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 5 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 5 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS linked_word_suggestion_reviews_deleted
+    AFTER DELETE ON linked_word_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 6 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 6 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS linked_word_deletion_suggestion_reviews_deleted
+    AFTER DELETE ON linked_word_deletion_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 7 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 7 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS dataset_attribution_suggestion_reviews_deleted
+    AFTER DELETE ON dataset_attribution_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 8 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 8 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS label_suggestion_reviews_deleted
+    AFTER DELETE ON label_suggestions BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 9 AND suggestion_id = OLD.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 9 AND suggestion_id = OLD.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS word_suggestion_reviews_edited
+    AFTER UPDATE OF english, xhosa, part_of_speech, xhosa_tone_markings, infinitive, is_plural,
+        is_inchoative, is_informal, transitivity, followed_by, noun_class, note, etymology_language,
+        etymology_form, etymology_note, literal_translation ON word_suggestions
+    WHEN OLD.english IS NOT NEW.english OR OLD.xhosa IS NOT NEW.xhosa OR
+        OLD.part_of_speech IS NOT NEW.part_of_speech OR
+        OLD.xhosa_tone_markings IS NOT NEW.xhosa_tone_markings OR
+        OLD.infinitive IS NOT NEW.infinitive OR OLD.is_plural IS NOT NEW.is_plural OR
+        OLD.is_inchoative IS NOT NEW.is_inchoative OR OLD.is_informal IS NOT NEW.is_informal OR
+        OLD.transitivity IS NOT NEW.transitivity OR OLD.followed_by IS NOT NEW.followed_by OR
+        OLD.noun_class IS NOT NEW.noun_class OR OLD.note IS NOT NEW.note OR
+        OLD.etymology_language IS NOT NEW.etymology_language OR
+        OLD.etymology_form IS NOT NEW.etymology_form OR
+        OLD.etymology_note IS NOT NEW.etymology_note OR
+        OLD.literal_translation IS NOT NEW.literal_translation BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 1 AND suggestion_id = NEW.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 1 AND suggestion_id = NEW.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS sense_suggestion_reviews_edited
+    AFTER UPDATE OF is_delete, sense_order, english, is_informal ON sense_suggestions
+    WHEN OLD.is_delete IS NOT NEW.is_delete OR OLD.sense_order IS NOT NEW.sense_order OR
+        OLD.english IS NOT NEW.english OR OLD.is_informal IS NOT NEW.is_informal BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 3 AND suggestion_id = NEW.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 3 AND suggestion_id = NEW.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS example_suggestion_reviews_edited
+    AFTER UPDATE OF english, xhosa ON example_suggestions
+    WHEN OLD.english IS NOT NEW.english OR OLD.xhosa IS NOT NEW.xhosa BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 4 AND suggestion_id = NEW.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 4 AND suggestion_id = NEW.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS linked_word_suggestion_reviews_edited
+    AFTER UPDATE OF link_type ON linked_word_suggestions
+    WHEN OLD.link_type IS NOT NEW.link_type BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 6 AND suggestion_id = NEW.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 6 AND suggestion_id = NEW.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS dataset_attribution_suggestion_reviews_edited
+    AFTER UPDATE OF dataset_id, is_delete ON dataset_attribution_suggestions
+    WHEN OLD.dataset_id IS NOT NEW.dataset_id OR OLD.is_delete IS NOT NEW.is_delete BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 8 AND suggestion_id = NEW.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 8 AND suggestion_id = NEW.suggestion_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS label_suggestion_reviews_edited
+    AFTER UPDATE OF label_id, is_delete ON label_suggestions
+    WHEN OLD.label_id IS NOT NEW.label_id OR OLD.is_delete IS NOT NEW.is_delete BEGIN
+    DELETE FROM suggestion_approvals WHERE suggestion_type = 9 AND suggestion_id = NEW.suggestion_id;
+    DELETE FROM suggestion_votes WHERE suggestion_type = 9 AND suggestion_id = NEW.suggestion_id;
+END;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS suggestions_needing_info (
+    suggestion_type  INTEGER NOT NULL,
+    suggestion_id    INTEGER NOT NULL, -- parked until the suggesting user replies
+    marked_by        INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    marked_at        TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (suggestion_type, suggestion_id) ON CONFLICT REPLACE
+);
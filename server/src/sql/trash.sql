@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS trash (
+    trash_id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    item_type       INTEGER NOT NULL,
+    item_id         INTEGER NOT NULL,
+    word_id         INTEGER NOT NULL, -- not a foreign key, as the word may be in the trash itself
+    suggesting_user INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    reviewing_user  INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    deleted_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    snapshot        TEXT NOT NULL -- JSON snapshot of the item and everything deleted along with it
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS trusted_contributors (
+    user_id     INTEGER PRIMARY KEY ON CONFLICT IGNORE REFERENCES users(user_id) ON DELETE CASCADE,
+    trusted_at  TIMESTAMP WITH TIME ZONE NOT NULL,
+    automatic   BOOLEAN NOT NULL -- promoted for their acceptance rate rather than by hand
+);
@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS trusted_edits (
+    edit_id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    suggestion_type  INTEGER NOT NULL,
+    word_id          INTEGER NOT NULL, -- not a foreign key, as the edit may have deleted the word
+    suggesting_user  INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    summary          TEXT NOT NULL,
+    changes_summary  TEXT NOT NULL,
+    -- The revision of the word from before the edit was applied, or NULL if the edit added the word
+    revision_before  INTEGER,
+    -- The latest revision of the word once the edit was applied, or NULL if it has none
+    revision_after   INTEGER,
+    applied_at       TIMESTAMP WITH TIME ZONE NOT NULL,
+    reviewing_user   INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    reviewed_at      TIMESTAMP WITH TIME ZONE, -- NULL until a moderator has reviewed the edit
+    reverted         BOOLEAN NOT NULL DEFAULT(FALSE)
+);
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS word_changes (
+    change_id       INTEGER PRIMARY KEY AUTOINCREMENT,
+    word_id         INTEGER NOT NULL, -- not a foreign key, as changes to deleted words are kept
+    second_word_id  INTEGER, -- the other word in the case of a linked word change
+    change_target   INTEGER NOT NULL,
+    change_kind     INTEGER NOT NULL,
+    -- Users are kept as NULL rather than deleted so that the rest of the change is kept
+    suggesting_user INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    reviewing_user  INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    changed_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    old_value       TEXT, -- JSON snapshot of the item before the change, NULL if it was added
+    new_value       TEXT -- JSON snapshot of the item after the change, NULL if it was deleted
+);
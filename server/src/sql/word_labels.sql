@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS word_labels (
+    word_id   INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    label_id  INTEGER NOT NULL REFERENCES labels(label_id) ON DELETE CASCADE,
+    UNIQUE(word_id, label_id) ON CONFLICT IGNORE
+);
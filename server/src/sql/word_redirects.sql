@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS word_redirects (
+    -- Neither are foreign keys, as the merged word is moved to the trash and the word it was merged
+    -- into may be too, and both could be restored from there later
+    from_word_id  INTEGER PRIMARY KEY,
+    to_word_id    INTEGER NOT NULL
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS word_revisions (
+    revision_id     INTEGER PRIMARY KEY AUTOINCREMENT,
+    word_id         INTEGER NOT NULL, -- not a foreign key, as revisions of deleted words are kept
+    -- Both users are NULL for the baseline revision recorded before a word's first tracked change
+    suggesting_user INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    reviewing_user  INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    reverted_from   INTEGER REFERENCES word_revisions(revision_id) ON DELETE SET NULL,
+    snapshot        TEXT NOT NULL -- JSON snapshot of the word and all its associated items
+);
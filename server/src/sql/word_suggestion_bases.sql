@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS word_suggestion_bases (
+    suggestion_id  INTEGER PRIMARY KEY REFERENCES word_suggestions(suggestion_id) ON DELETE CASCADE,
+    -- JSON of the fields of the existing word when the suggestion was made or last rebased
+    base           TEXT NOT NULL
+);
@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS word_suggestion_duplicates (
+    suggestion_id   INTEGER PRIMARY KEY ON CONFLICT REPLACE
+                        REFERENCES word_suggestions(suggestion_id) ON DELETE CASCADE,
+    -- Whether the suggested new word had possible duplicates when it was last checked
+    has_duplicates  BOOLEAN NOT NULL
+);
+
+-- The suggestion is checked again when next needed, as it may duplicate different words now
+CREATE TRIGGER IF NOT EXISTS word_suggestion_duplicates_edited
+    AFTER UPDATE OF english, xhosa ON word_suggestions
+    WHEN OLD.english IS NOT NEW.english OR OLD.xhosa IS NOT NEW.xhosa BEGIN
+    DELETE FROM word_suggestion_duplicates WHERE suggestion_id = NEW.suggestion_id;
+END;
+
+-- The removed suggestion or word may have been the only duplicate of others, so those with
+-- duplicates are checked again
+CREATE TRIGGER IF NOT EXISTS word_suggestion_duplicates_suggestion_removed
+    AFTER DELETE ON word_suggestions BEGIN
+    DELETE FROM word_suggestion_duplicates WHERE has_duplicates;
+END;
+
+CREATE TRIGGER IF NOT EXISTS word_suggestion_duplicates_word_removed AFTER DELETE ON words BEGIN
+    DELETE FROM word_suggestion_duplicates WHERE has_duplicates;
+END;
@@ -18,5 +18,12 @@ CREATE TABLE IF NOT EXISTS word_suggestions (
     followed_by          TEXT,
     -- 255 is sentinel for "no noun class" as opposed to null which is noun class not changed
     noun_class           INTEGER,
-    note                 TEXT
+    note                 TEXT,
+
+    -- 255 is sentinel for "no source language", as for noun class
+    etymology_language   INTEGER,
+    etymology_form       TEXT,
+    etymology_note       TEXT,
+
+    literal_translation  TEXT
 );
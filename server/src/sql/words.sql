@@ -12,5 +12,11 @@ CREATE TABLE IF NOT EXISTS words (
     transitivity         INTEGER,
     followed_by          TEXT NOT NULL,
     noun_class           INTEGER,
-    note                 TEXT NOT NULL
+    note                 TEXT NOT NULL,
+
+    etymology_language   INTEGER,
+    etymology_form       TEXT NOT NULL DEFAULT(''),
+    etymology_note       TEXT NOT NULL DEFAULT(''),
+
+    literal_translation  TEXT NOT NULL DEFAULT('')
 );
@@ -6,14 +6,15 @@ use crate::i18n::I18nInfo;
 use crate::i18n::SiteContext;
 use crate::search::TantivyClient;
 use crate::serialization::qs_form;
+use crate::trusted;
 use crate::{spawn_blocking_child, DebugBoxedExt};
 use askama::Template;
 use isixhosa_click_macros::I18nTemplate;
 use isixhosa_common::auth::Auth;
 use isixhosa_common::database::{DbBase, UserAccessDb};
 use isixhosa_common::format::DisplayHtml;
-use isixhosa_common::language::{NounClassExt, Transitivity};
-use isixhosa_common::types::Dataset;
+use isixhosa_common::language::{GrammarWarning, NounClassExt, Transitivity};
+use isixhosa_common::types::{Dataset, Label};
 use serde::Deserialize;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::sync::Arc;
@@ -30,6 +31,9 @@ struct SubmitTemplate {
     action: SubmitFormAction,
     word: WordFormTemplate,
     datasets: Vec<Dataset>,
+    labels: Vec<Label>,
+    /// Likely mistakes in the word which was just submitted, if any
+    grammar_warnings: Vec<GrammarWarning>,
 }
 
 impl SubmitTemplate {
@@ -42,12 +46,14 @@ impl SubmitTemplate {
     }
 }
 
-#[derive(Deserialize, Debug, Default, Copy, Clone)]
+#[derive(Deserialize, Debug, Default, Clone)]
 enum SubmitFormAction {
     EditSuggestion {
         suggestion_id: u64,
         existing_id: Option<u64>,
         suggestion_anchor_ord: u32,
+        /// The query string of the page of the moderation queue to return to
+        queue: String,
     },
     #[default]
     SubmitNewWord,
@@ -71,7 +77,9 @@ pub fn submit(
         .and(with_user_auth(db.clone(), site_ctx.clone()))
         .and_then(submit_word_page);
 
+    let db_clone = db.clone();
     let submit_form = body::content_length_limit(64 * 1024)
+        .and(warp::any().map(move || db_clone.clone()))
         .and(warp::any().map(move || tantivy.clone()))
         .and(qs_form())
         .and(with_user_auth(db.clone(), site_ctx.clone()))
@@ -98,6 +106,7 @@ pub async fn edit_suggestion_page(
     user: FullUser,
     suggestion_id: u64,
     suggestion_anchor_ord: u32,
+    queue: String,
 ) -> Result<impl Reply, Rejection> {
     let db_clone = db.clone();
     let existing_id = spawn_blocking_child(move || {
@@ -112,6 +121,7 @@ pub async fn edit_suggestion_page(
             suggestion_id,
             existing_id,
             suggestion_anchor_ord,
+            queue,
         },
         user,
         i18n_info,
@@ -138,19 +148,31 @@ pub async fn edit_word_page(
     .await
 }
 
+async fn submit_word_page(
+    previous_success: Option<bool>,
+    action: SubmitFormAction,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    submit_word_page_with_warnings(previous_success, action, Vec::new(), user, i18n_info, db).await
+}
+
 // TODO(form validation): server side form validation
 #[instrument(name = "Display submit word page", skip_all)]
-async fn submit_word_page(
+async fn submit_word_page_with_warnings(
     previous_success: Option<bool>,
     action: SubmitFormAction,
+    grammar_warnings: Vec<GrammarWarning>,
     user: FullUser,
     i18n_info: I18nInfo,
     db: impl UserAccessDb,
 ) -> Result<impl Reply, Rejection> {
     let i18n_clone = i18n_info.clone();
     let db = db.clone();
-    let (word, datasets) = spawn_blocking_child(move || {
-        let template = match action {
+    let action_clone = action.clone();
+    let (word, datasets, labels) = spawn_blocking_child(move || {
+        let template = match action_clone {
             SubmitFormAction::EditSuggestion {
                 suggestion_id,
                 existing_id,
@@ -164,7 +186,8 @@ async fn submit_word_page(
         };
 
         let datasets = Dataset::fetch_all(&db);
-        (template, datasets)
+        let labels = Label::fetch_all(&db);
+        (template, datasets, labels)
     })
     .await
     .unwrap();
@@ -176,21 +199,28 @@ async fn submit_word_page(
         action,
         word,
         datasets,
+        labels,
+        grammar_warnings,
     })
 }
 
 #[instrument(name = "Submit word form", skip_all)]
 async fn submit_new_word_form(
+    db_base: DbBase,
     tantivy: Arc<TantivyClient>,
     word: WordSubmission,
     user: FullUser,
     i18n_info: I18nInfo,
     db: impl UserAccessDb,
 ) -> Result<impl Reply, Rejection> {
-    submit::submit_suggestion(word, tantivy, &user, &db, i18n_info.clone()).await;
-    submit_word_page(
+    let grammar_warnings = word.grammar_warnings();
+    let submitted =
+        submit::submit_suggestion(word, tantivy.clone(), &user, &db, i18n_info.clone()).await;
+    trusted::apply_if_trusted(db_base, tantivy, &user, i18n_info.clone(), submitted).await;
+    submit_word_page_with_warnings(
         Some(true),
         SubmitFormAction::SubmitNewWord,
+        grammar_warnings,
         user,
         i18n_info,
         db,
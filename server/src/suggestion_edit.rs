@@ -0,0 +1,287 @@
+use crate::auth::{with_user_auth, FullUser};
+use crate::database::submit::{
+    edit_example_suggestion, edit_linked_word_suggestion, ExampleSuggestionEdit,
+    LinkedWordSuggestionEdit,
+};
+use crate::database::suggestion::{SuggestedExample, SuggestedLinkedWord};
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt, NotFound};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::{Auth, Permissions};
+use isixhosa_common::database::{DbBase, UserAccessDb};
+use isixhosa_common::format::DisplayHtml;
+use isixhosa_common::types::PublicUserInfo;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "edit_example_suggestion.askama.html")]
+struct EditExampleSuggestionPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    example: SuggestedExample,
+    previous_success: Option<bool>,
+    /// Set when editing from the moderation page, which the form then returns to
+    suggestion_anchor_ord: Option<u32>,
+    /// The query string of the page of the moderation queue to return to
+    queue: String,
+}
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "edit_linked_word_suggestion.askama.html")]
+struct EditLinkedWordSuggestionPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    link: SuggestedLinkedWord,
+    previous_success: Option<bool>,
+    /// Set when editing from the moderation page, which the form then returns to
+    suggestion_anchor_ord: Option<u32>,
+    /// The query string of the page of the moderation queue to return to
+    queue: String,
+}
+
+impl EditExampleSuggestionPage {
+    fn form_action(&self) -> String {
+        match self.suggestion_anchor_ord {
+            Some(_) => format!(
+                "/moderation/edit/example/{}{}",
+                self.example.suggestion_id, self.queue
+            ),
+            None => format!("/suggestions/example/{}/edit", self.example.suggestion_id),
+        }
+    }
+}
+
+impl EditLinkedWordSuggestionPage {
+    fn form_action(&self) -> String {
+        match self.suggestion_anchor_ord {
+            Some(_) => format!(
+                "/moderation/edit/linked_word/{}{}",
+                self.link.suggestion_id, self.queue
+            ),
+            None => format!("/suggestions/linked_word/{}/edit", self.link.suggestion_id),
+        }
+    }
+}
+
+pub fn suggestion_edit(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let example_page = warp::get()
+        .and(warp::path!["example" / u64 / "edit"])
+        .and(warp::path::end())
+        .and(with_user_auth(db.clone(), site_ctx.clone()))
+        .and_then(own_example_suggestion_page);
+
+    let submit_example = warp::post()
+        .and(warp::path!["example" / u64 / "edit"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::form())
+        .and(with_user_auth(db.clone(), site_ctx.clone()))
+        .and_then(submit_example_edit);
+
+    let linked_word_page = warp::get()
+        .and(warp::path!["linked_word" / u64 / "edit"])
+        .and(warp::path::end())
+        .and(with_user_auth(db.clone(), site_ctx.clone()))
+        .and_then(own_linked_word_suggestion_page);
+
+    let submit_linked_word = warp::post()
+        .and(warp::path!["linked_word" / u64 / "edit"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(64 * 1024))
+        .and(body::form())
+        .and(with_user_auth(db, site_ctx))
+        .and_then(submit_linked_word_edit);
+
+    warp::path("suggestions")
+        .and(
+            example_page
+                .or(submit_example)
+                .or(linked_word_page)
+                .or(submit_linked_word),
+        )
+        .debug_boxed()
+}
+
+/// Suggestions may be edited by the user who made them until they are reviewed. Moderators edit
+/// other users' suggestions from the moderation page, which checks that they are in their scopes.
+fn may_edit(user: &FullUser, suggesting_user: &PublicUserInfo, from_moderation: bool) -> bool {
+    user.id == suggesting_user.id
+        || (from_moderation && user.permissions.contains(Permissions::Moderator))
+}
+
+#[instrument(name = "Display edit example suggestion page", skip(user, db))]
+pub async fn edit_example_suggestion_page(
+    db: impl UserAccessDb,
+    i18n_info: I18nInfo,
+    user: FullUser,
+    suggestion_id: u64,
+    suggestion_anchor_ord: Option<u32>,
+    queue: String,
+    previous_success: Option<bool>,
+) -> Result<impl Reply, Rejection> {
+    let example = spawn_blocking_child(move || SuggestedExample::fetch(&db, suggestion_id))
+        .await
+        .unwrap();
+
+    let from_moderation = suggestion_anchor_ord.is_some();
+    Ok(match example {
+        Some(example) if may_edit(&user, &example.suggesting_user, from_moderation) => {
+            EditExampleSuggestionPage {
+                auth: user.into(),
+                i18n_info,
+                example,
+                previous_success,
+                suggestion_anchor_ord,
+                queue,
+            }
+            .into_response()
+        }
+        _ => NotFound {
+            auth: user.into(),
+            i18n_info,
+        }
+        .into_response(),
+    })
+}
+
+#[instrument(name = "Display edit linked word suggestion page", skip(user, db))]
+pub async fn edit_linked_word_suggestion_page(
+    db: impl UserAccessDb,
+    i18n_info: I18nInfo,
+    user: FullUser,
+    suggestion_id: u64,
+    suggestion_anchor_ord: Option<u32>,
+    queue: String,
+    previous_success: Option<bool>,
+) -> Result<impl Reply, Rejection> {
+    let i18n_info_clone = i18n_info.clone();
+    let link = spawn_blocking_child(move || {
+        SuggestedLinkedWord::fetch(&db, i18n_info_clone, suggestion_id)
+    })
+    .await
+    .unwrap();
+
+    let from_moderation = suggestion_anchor_ord.is_some();
+    Ok(match link {
+        Some(link) if may_edit(&user, &link.suggesting_user, from_moderation) => {
+            EditLinkedWordSuggestionPage {
+                auth: user.into(),
+                i18n_info,
+                link,
+                previous_success,
+                suggestion_anchor_ord,
+                queue,
+            }
+            .into_response()
+        }
+        _ => NotFound {
+            auth: user.into(),
+            i18n_info,
+        }
+        .into_response(),
+    })
+}
+
+async fn own_example_suggestion_page(
+    suggestion_id: u64,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    edit_example_suggestion_page(
+        db,
+        i18n_info,
+        user,
+        suggestion_id,
+        None,
+        String::new(),
+        None,
+    )
+    .await
+}
+
+async fn own_linked_word_suggestion_page(
+    suggestion_id: u64,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    edit_linked_word_suggestion_page(
+        db,
+        i18n_info,
+        user,
+        suggestion_id,
+        None,
+        String::new(),
+        None,
+    )
+    .await
+}
+
+#[instrument(name = "Submit example suggestion edit", skip(user, db))]
+async fn submit_example_edit(
+    suggestion_id: u64,
+    edit: ExampleSuggestionEdit,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let user_clone = user.clone();
+    let success = spawn_blocking_child(move || {
+        SuggestedExample::fetch(&db_clone, suggestion_id)
+            .filter(|example| may_edit(&user_clone, &example.suggesting_user, false))
+            .map(|example| edit_example_suggestion(&db_clone, &example, edit))
+    })
+    .await
+    .unwrap();
+
+    edit_example_suggestion_page(
+        db,
+        i18n_info,
+        user,
+        suggestion_id,
+        None,
+        String::new(),
+        success,
+    )
+    .await
+}
+
+#[instrument(name = "Submit linked word suggestion edit", skip(user, db))]
+async fn submit_linked_word_edit(
+    suggestion_id: u64,
+    edit: LinkedWordSuggestionEdit,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let user_clone = user.clone();
+    let i18n_info_clone = i18n_info.clone();
+    let success = spawn_blocking_child(move || {
+        SuggestedLinkedWord::fetch(&db_clone, i18n_info_clone, suggestion_id)
+            .filter(|link| may_edit(&user_clone, &link.suggesting_user, false))
+            .map(|link| edit_linked_word_suggestion(&db_clone, &link, edit))
+    })
+    .await
+    .unwrap();
+
+    edit_linked_word_suggestion_page(
+        db,
+        i18n_info,
+        user,
+        suggestion_id,
+        None,
+        String::new(),
+        success,
+    )
+    .await
+}
@@ -0,0 +1,93 @@
+use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::scopes;
+use crate::database::trash::{self, TrashEntry};
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::search::TantivyClient;
+use crate::{spawn_blocking_child, DebugBoxedExt};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, ModeratorAccessDb};
+use isixhosa_common::format::DisplayHtml;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "trash.askama.html")]
+struct Trash {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    entries: Vec<TrashEntry>,
+    previous_success: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RestoreForm {
+    trash_id: u64,
+}
+
+pub fn trash(
+    db: DbBase,
+    tantivy: Arc<TantivyClient>,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let show_all = warp::get()
+        .and(warp::any().map(|| None)) // previous_success is None
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(trash_page);
+
+    let restore = warp::post()
+        .and(body::content_length_limit(4 * 1024))
+        .and(body::form::<RestoreForm>())
+        .and(warp::any().map(move || tantivy.clone()))
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(restore_from_trash);
+
+    warp::path!["moderation" / "trash"]
+        .and(warp::path::end())
+        .and(show_all.or(restore))
+        .debug_boxed()
+}
+
+#[instrument(name = "Display trash page", skip_all)]
+async fn trash_page(
+    previous_success: Option<bool>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    spawn_blocking_child(move || {
+        Ok(Trash {
+            auth: user.into(),
+            entries: trash::fetch_all(&db, &i18n_info),
+            i18n_info,
+            previous_success,
+        })
+    })
+    .await
+    .unwrap()
+}
+
+#[instrument(name = "Restore item from trash", skip(tantivy, user, db))]
+async fn restore_from_trash(
+    form: RestoreForm,
+    tantivy: Arc<TantivyClient>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let success = spawn_blocking_child(move || {
+        // Trashed words are not in the dictionary, so they are in no moderator's scope
+        scopes::is_unscoped(&db_clone, reviewer)
+            && trash::restore(&db_clone, tantivy, form.trash_id, reviewer)
+    })
+    .await
+    .unwrap();
+
+    trash_page(Some(success), user, i18n_info, db).await
+}
@@ -0,0 +1,265 @@
+use crate::auth::{with_moderator_auth, FullUser};
+use crate::database::conflicts;
+use crate::database::discussion;
+use crate::database::outcomes::{SuggestionOutcome, SuggestionSummary, SuggestionType};
+use crate::database::review::AppliedPolicy;
+use crate::database::revisions::WordRevision;
+use crate::database::scopes::ModeratorScopes;
+use crate::database::suggestion::SuggestedWord;
+use crate::database::trusted::TrustedEdit;
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::moderation::accept_suggestion;
+use crate::search::TantivyClient;
+use crate::{spawn_blocking_child, DebugBoxedExt};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::{Auth, Permissions};
+use isixhosa_common::database::db_impl::DbImpl;
+use isixhosa_common::database::{DbBase, ModeratorAccessDb, WordOrSuggestionId};
+use isixhosa_common::format::DisplayHtml;
+use serde::Deserialize;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use tracing::{instrument, Span};
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "trusted_edits.askama.html")]
+struct TrustedEdits {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    edits: Vec<TrustedEdit>,
+    previous_success: Option<bool>,
+}
+
+#[derive(Deserialize, Copy, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+enum TrustedEditAction {
+    Approve,
+    Revert,
+}
+
+#[derive(Deserialize, Debug)]
+struct TrustedEditForm {
+    edit_id: u64,
+    action: TrustedEditAction,
+}
+
+pub fn trusted_edits(
+    db: DbBase,
+    tantivy: Arc<TantivyClient>,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let show_all = warp::get()
+        .and(warp::any().map(|| None)) // previous_success is None
+        .and(with_moderator_auth(db.clone(), site_ctx.clone()))
+        .and_then(trusted_edits_page);
+
+    let review = warp::post()
+        .and(body::content_length_limit(4 * 1024))
+        .and(body::form::<TrustedEditForm>())
+        .and(warp::any().map(move || tantivy.clone()))
+        .and(with_moderator_auth(db, site_ctx))
+        .and_then(review_trusted_edit);
+
+    warp::path!["moderation" / "trusted_edits"]
+        .and(warp::path::end())
+        .and(show_all.or(review))
+        .debug_boxed()
+}
+
+#[instrument(name = "Display trusted edits page", skip_all)]
+async fn trusted_edits_page(
+    previous_success: Option<bool>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    spawn_blocking_child(move || {
+        let mut edits = TrustedEdit::fetch_all_unreviewed(&db);
+
+        if let Some(scopes) = ModeratorScopes::fetch_for_moderator(&db, user.id) {
+            edits
+                .retain(|edit| scopes.allows_word(&db, WordOrSuggestionId::existing(edit.word_id)));
+        }
+
+        Ok(TrustedEdits {
+            auth: user.into(),
+            i18n_info,
+            edits,
+            previous_success,
+        })
+    })
+    .await
+    .unwrap()
+}
+
+#[instrument(name = "Review trusted edit", skip(tantivy, user, db))]
+async fn review_trusted_edit(
+    form: TrustedEditForm,
+    tantivy: Arc<TantivyClient>,
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl ModeratorAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let db_clone = db.clone();
+    let reviewer = user.id;
+    let success = spawn_blocking_child(move || {
+        if let Some(scopes) = ModeratorScopes::fetch_for_moderator(&db_clone, reviewer) {
+            let in_scope =
+                TrustedEdit::fetch_unreviewed(&db_clone, form.edit_id).is_some_and(|edit| {
+                    scopes.allows_word(&db_clone, WordOrSuggestionId::existing(edit.word_id))
+                });
+
+            if !in_scope {
+                return false;
+            }
+        }
+
+        match form.action {
+            TrustedEditAction::Approve => TrustedEdit::approve(&db_clone, form.edit_id, reviewer),
+            TrustedEditAction::Revert => {
+                TrustedEdit::revert(&db_clone, tantivy, form.edit_id, reviewer)
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    trusted_edits_page(Some(success), user, i18n_info, db).await
+}
+
+/// Applies the suggestions just submitted straight away if the user is a trusted contributor, and
+/// logs them so that moderators can review them afterwards. Suggestions which a moderator has asked
+/// for more information on, which need the approval of several moderators, or which edit a word
+/// that has changed since they were made, are left pending.
+/// Moderators' own suggestions go through the moderation queue as usual.
+#[instrument(
+    name = "Apply trusted contributor suggestions",
+    fields(applied),
+    skip(db, tantivy, user, i18n_info, submitted)
+)]
+pub async fn apply_if_trusted(
+    db: DbBase,
+    tantivy: Arc<TantivyClient>,
+    user: &FullUser,
+    i18n_info: I18nInfo,
+    submitted: Vec<(SuggestionType, u64)>,
+) {
+    if user.permissions != Permissions::TrustedContributor {
+        return;
+    }
+
+    // Trusted contributors may apply their own suggestions as a moderator would
+    let db = DbImpl(db.0);
+    let user_id = user.id;
+
+    // In the order they were submitted, so that the word revisions are recorded in the order the
+    // changes were made
+    let db_clone = db.clone();
+    let pending: Vec<SuggestionSummary> = spawn_blocking_child(move || {
+        submitted
+            .into_iter()
+            .filter_map(|(suggestion_type, suggestion_id)| {
+                SuggestionSummary::fetch(&db_clone, suggestion_type, suggestion_id)
+            })
+            .filter(|summary| summary.suggesting_user == user_id)
+            .collect()
+    })
+    .await
+    .unwrap();
+
+    let mut applied = 0;
+    for summary in pending {
+        if apply_one(&db, tantivy.clone(), &i18n_info, summary, user_id).await {
+            applied += 1;
+        }
+    }
+
+    Span::current().record("applied", applied);
+}
+
+async fn apply_one(
+    db: &impl ModeratorAccessDb,
+    tantivy: Arc<TantivyClient>,
+    i18n_info: &I18nInfo,
+    summary: SuggestionSummary,
+    user_id: NonZeroU64,
+) -> bool {
+    let (suggestion_type, suggestion_id) = (summary.suggestion_type, summary.suggestion_id);
+
+    // Suggestions for suggested words are applied along with their word
+    if suggestion_type != SuggestionType::Word && summary.word_id.is_none() {
+        return false;
+    }
+
+    let (db_clone, i18n_clone) = (db.clone(), i18n_info.clone());
+    let revision_before = spawn_blocking_child(move || {
+        // The suggestion may have been applied along with its word already
+        let summary = SuggestionSummary::fetch(&db_clone, suggestion_type, suggestion_id)?;
+
+        let needs_info = discussion::needs_info(&db_clone, suggestion_type, suggestion_id);
+        let policy = AppliedPolicy::for_suggestion(&db_clone, &summary);
+        // As on the moderation page, edits to words which have changed since must be rebased first
+        let stale = suggestion_type == SuggestionType::Word
+            && summary.word_id.is_some()
+            && conflicts::needs_rebase(&db_clone, &i18n_clone, suggestion_id);
+        if needs_info || policy.required_approvals > 1 || stale {
+            return None;
+        }
+
+        Some(summary.word_id.and_then(|word_id| {
+            WordRevision::record_baseline(&db_clone, word_id);
+            WordRevision::latest_id(&db_clone, word_id)
+        }))
+    })
+    .await
+    .unwrap();
+
+    let Some(revision_before) = revision_before else {
+        return false;
+    };
+
+    let word_id = if suggestion_type == SuggestionType::Word {
+        let (db, i18n_info) = (db.clone(), i18n_info.clone());
+
+        // Words are accepted here rather than through the moderation page, as the id of a new
+        // word is needed for the log
+        let word_id = spawn_blocking_child(move || {
+            SuggestedWord::fetch_full(&db, &i18n_info, suggestion_id)
+                .unwrap()
+                .accept_whole_word_suggestion(&db, tantivy, user_id)
+        })
+        .await
+        .unwrap();
+        Some(word_id)
+    } else {
+        let accepted = accept_suggestion(
+            db,
+            i18n_info.clone(),
+            tantivy,
+            suggestion_type,
+            suggestion_id,
+            user_id,
+        )
+        .await;
+        summary.word_id.filter(|_| accepted)
+    };
+
+    let Some(word_id) = word_id else {
+        return false;
+    };
+
+    let db = db.clone();
+    spawn_blocking_child(move || {
+        SuggestionOutcome::record(&db, &summary, user_id, true, None, "");
+
+        let revision_after = WordRevision::latest_id(&db, word_id);
+        TrustedEdit::insert(&db, &summary, word_id, revision_before, revision_after);
+    })
+    .await
+    .unwrap();
+
+    true
+}
@@ -1,4 +1,5 @@
 use crate::auth::{FullUser, StaySignedInToken};
+use crate::database::scopes::ModeratorScope;
 use crate::{set_up_db, Config, UserCommand};
 use isixhosa_common::database::db_impl::DbImpl;
 use r2d2::Pool;
@@ -46,6 +47,45 @@ pub fn run_command(cfg: Config, command: UserCommand) -> anyhow::Result<()> {
             StaySignedInToken::delete_all(&db);
             println!("Logged out all users")
         }
+        UserCommand::PromoteTrusted {
+            min_accepted,
+            min_acceptance_rate,
+        } => {
+            let promoted =
+                FullUser::promote_trusted_contributors(&db, min_accepted, min_acceptance_rate);
+
+            if promoted.is_empty() {
+                println!("No users promoted");
+            } else {
+                println!("Promoted to trusted contributor:");
+                println!("{}", Table::new(promoted));
+            }
+        }
+        UserCommand::AddScope {
+            user,
+            dataset,
+            label,
+        } => {
+            let modified = ModeratorScope::add_by_email(&db, user, dataset, label);
+
+            if modified {
+                println!("Scope added");
+            } else {
+                println!("No changes made");
+            }
+        }
+        UserCommand::ClearScopes { user } => {
+            let modified = ModeratorScope::clear_by_email(&db, user);
+
+            if modified {
+                println!("Scopes cleared");
+            } else {
+                println!("No changes made");
+            }
+        }
+        UserCommand::ListScopes => {
+            println!("{}", Table::new(ModeratorScope::fetch_all(&db)));
+        }
     }
 
     Ok(())
@@ -0,0 +1,151 @@
+use crate::auth::{with_user_auth, FullUser};
+use crate::database::outcomes::{SuggestionSummary, SuggestionType};
+use crate::database::review::{self, AppliedPolicy, ReviewTally, Vote};
+use crate::i18n::I18nInfo;
+use crate::i18n::SiteContext;
+use crate::{spawn_blocking_child, DebugBoxedExt};
+use askama::Template;
+use isixhosa_click_macros::I18nTemplate;
+use isixhosa_common::auth::Auth;
+use isixhosa_common::database::{DbBase, UserAccessDb};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::instrument;
+use warp::http::Uri;
+use warp::{body, Filter, Rejection, Reply};
+
+#[derive(Template, I18nTemplate, Debug)]
+#[template(path = "voting.askama.html")]
+struct VotingPage {
+    auth: Auth,
+    i18n_info: I18nInfo,
+    suggestions: Vec<VotableSuggestion>,
+}
+
+#[derive(Debug)]
+struct VotableSuggestion {
+    suggestion: SuggestionSummary,
+    tally: ReviewTally,
+    own_vote: Option<Vote>,
+}
+
+impl VotableSuggestion {
+    fn anchor(&self) -> String {
+        anchor(
+            self.suggestion.suggestion_type,
+            self.suggestion.suggestion_id,
+        )
+    }
+
+    fn voted_up(&self) -> bool {
+        self.own_vote == Some(Vote::Up)
+    }
+
+    fn voted_down(&self) -> bool {
+        self.own_vote == Some(Vote::Down)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct VoteForm {
+    vote: Vote,
+}
+
+fn anchor(suggestion_type: SuggestionType, suggestion_id: u64) -> String {
+    format!("suggestion_{}_{}", suggestion_type.name(), suggestion_id)
+}
+
+pub fn voting(
+    db: DbBase,
+    site_ctx: Arc<SiteContext>,
+) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
+    let page = warp::get()
+        .and(warp::path!["vote"])
+        .and(warp::path::end())
+        .and(with_user_auth(db.clone(), site_ctx.clone()))
+        .and_then(voting_page);
+
+    let submit_vote = warp::post()
+        .and(warp::path![SuggestionType / u64 / "vote"])
+        .and(warp::path::end())
+        .and(body::content_length_limit(4 * 1024))
+        .and(body::form())
+        .and(with_user_auth(db, site_ctx))
+        .and_then(submit_vote);
+
+    warp::path("suggestions")
+        .and(page.or(submit_vote))
+        .debug_boxed()
+}
+
+#[instrument(name = "Display voting page", skip_all)]
+async fn voting_page(
+    user: FullUser,
+    i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let user_id = user.id;
+    let suggestions = spawn_blocking_child(move || {
+        let tallies = review::fetch_all_tallies(&db);
+        let own_votes = review::fetch_votes_by_user(&db, user_id);
+
+        let mut suggestions: Vec<VotableSuggestion> = SuggestionSummary::fetch_all_pending(&db)
+            .into_iter()
+            .filter(|suggestion| suggestion.suggesting_user != user_id)
+            .filter_map(|suggestion| {
+                let key = (suggestion.suggestion_type, suggestion.suggestion_id);
+                let tally = tallies.get(&key).copied()?;
+
+                tally.policy.allow_voting.then(|| VotableSuggestion {
+                    suggestion,
+                    tally,
+                    own_vote: own_votes.get(&key).copied(),
+                })
+            })
+            .collect();
+
+        // Oldest first, so that every suggestion gets its turn near the top
+        suggestions.sort_by_key(|votable| votable.suggestion.suggestion_id);
+        suggestions
+    })
+    .await
+    .unwrap();
+
+    Ok(VotingPage {
+        auth: user.into(),
+        i18n_info,
+        suggestions,
+    })
+}
+
+#[instrument(name = "Vote on suggestion", skip(user, _i18n_info, db))]
+async fn submit_vote(
+    suggestion_type: SuggestionType,
+    suggestion_id: u64,
+    form: VoteForm,
+    user: FullUser,
+    _i18n_info: I18nInfo,
+    db: impl UserAccessDb,
+) -> Result<impl Reply, Rejection> {
+    let voter = user.id;
+    spawn_blocking_child(move || {
+        let suggestion = match SuggestionSummary::fetch(&db, suggestion_type, suggestion_id) {
+            Some(suggestion) if suggestion.suggesting_user != voter => suggestion,
+            _ => return,
+        };
+
+        if AppliedPolicy::for_suggestion(&db, &suggestion).allow_voting {
+            review::vote(&db, suggestion_type, suggestion_id, voter, form.vote);
+        }
+    })
+    .await
+    .unwrap();
+
+    let uri: Uri = format!(
+        "/suggestions/vote#{}",
+        anchor(suggestion_type, suggestion_id)
+    )
+    .parse()
+    .unwrap();
+    Ok(warp::redirect::see_other(uri))
+}
@@ -24,6 +24,8 @@ export class LiveSearch {
         this.create_item_container = create_item_container;
         this.filter_fn = filter_fn;
         this.translations = translations
+        /* An optional select element with a label to filter the results by */
+        this.label_filter = null;
 
         this.id = next_id;
         next_id++;
@@ -73,9 +75,16 @@ export class LiveSearch {
     }
 
     refresh() {
-        if (this.input === document.activeElement && this.last_value !== this.input.value) {
-            ws.send(JSON.stringify({ search: this.input.value, state: this.id.toString() }));
-            this.last_value = this.input.value;
+        let labels = [];
+        if (this.label_filter != null && this.label_filter.value !== "") {
+            labels.push(parseInt(this.label_filter.value));
+        }
+
+        let value = JSON.stringify([this.input.value, labels]);
+
+        if (this.input === document.activeElement && this.last_value !== value) {
+            ws.send(JSON.stringify({ search: this.input.value, state: this.id.toString(), labels: labels }));
+            this.last_value = value;
         }
 
         if (this.input.value === "") {
@@ -1,4 +1,5 @@
 import { addFormData } from "/submit/util.js";
+import { senseSelect } from "/submit/sense.js";
 
 let current_example_id = 0;
 
@@ -41,7 +42,7 @@ function textField(name, label_txt, val, english) {
     return div;
 }
 
-export function addExample(translations, english, xhosa, suggestion_id, existing_id) {
+export function addExample(translations, english, xhosa, suggestion_id, existing_id, sense) {
     current_example_id += 1;
     let list = document.getElementById("examples");
     let item = document.createElement("li");
@@ -81,13 +82,14 @@ export function addExample(translations, english, xhosa, suggestion_id, existing
 
     sentence.appendChild(textField(`examples[${current_example_id}][english]`, `${translations["examples.source"]}:`, english, true));
     sentence.appendChild(textField(`examples[${current_example_id}][xhosa]`, `${translations["examples.target"]}:`, xhosa, false));
+    sentence.appendChild(senseSelect(translations, `examples[${current_example_id}][sense]`, sense));
 
     let delete_buttons = document.getElementsByClassName("delete_example");
 }
 
 export function addExamples(translations, examples) {
     for (let example of examples) {
-        addExample(translations, example.english, example.xhosa, example.suggestion_id, example.existing_id)
+        addExample(translations, example.english, example.xhosa, example.suggestion_id, example.existing_id, example.sense)
     }
 
     if (examples.length === 0) {
@@ -1,5 +1,6 @@
 import { LiveSearch } from "/live_search.js";
 import { addFormData } from "/submit/util.js";
+import { senseSelect } from "/submit/sense.js";
 
 let current_linked_word_id = 0;
 
@@ -121,7 +122,7 @@ function createLinkedWordSearch(translations, preset_word, preset_rendered, this
     return { input: input, popover: popover_container, search: search };
 }
 
-export function addLinkedWord(translations, this_word_id, link_type, other, other_rendered, suggestion_id, existing_id) {
+export function addLinkedWord(translations, this_word_id, link_type, other, other_rendered, suggestion_id, existing_id, sense) {
     current_linked_word_id += 1;
     let list = document.getElementById("linked_words");
     let item = document.createElement("li");
@@ -158,8 +159,23 @@ export function addLinkedWord(translations, this_word_id, link_type, other, othe
         { value: "plural_or_singular", text: translations["linked-words.plurality"] },
         { value: "alternate_use", text: translations["linked-words.alternate"] },
         { value: "antonym", text: translations["linked-words.antonym"] },
+        { value: "synonym", text: translations["linked-words.synonym"] },
         { value: "related", text: translations["linked-words.related"] },
         { value: "confusable", text: translations["linked-words.confusable"] },
+        { value: "derived_from", text: translations["linked-words.derived-from"] },
+        { value: "derivation_of", text: translations["linked-words.derivation-of"] },
+        { value: "causative_of", text: translations["linked-words.causative-of"] },
+        { value: "has_causative", text: translations["linked-words.has-causative"] },
+        { value: "applicative_of", text: translations["linked-words.applicative-of"] },
+        { value: "has_applicative", text: translations["linked-words.has-applicative"] },
+        { value: "passive_of", text: translations["linked-words.passive-of"] },
+        { value: "has_passive", text: translations["linked-words.has-passive"] },
+        { value: "diminutive_of", text: translations["linked-words.diminutive-of"] },
+        { value: "has_diminutive", text: translations["linked-words.has-diminutive"] },
+        { value: "hyponym_of", text: translations["linked-words.hyponym-of"] },
+        { value: "hypernym_of", text: translations["linked-words.hypernym-of"] },
+        { value: "component_of", text: translations["linked-words.component-of"] },
+        { value: "has_component", text: translations["linked-words.has-component"] },
     ];
 
     for (let type of types_list) {
@@ -191,6 +207,7 @@ export function addLinkedWord(translations, this_word_id, link_type, other, othe
     linked_word.appendChild(input);
     linked_word.appendChild(popover);
     select_input_container.appendChild(linked_word);
+    select_input_container.appendChild(senseSelect(translations, `linked_words[${current_linked_word_id}][sense]`, sense));
 
     let delete_buttons = document.getElementsByClassName("delete_linked_word");
 }
@@ -204,7 +221,8 @@ export function addLinkedWords(translations, linked_words, this_id) {
             linked_word.other,
             linked_word.other_rendered_plaintext,
             linked_word.suggestion_id,
-            linked_word.existing_id
+            linked_word.existing_id,
+            linked_word.sense
         )
     }
 
@@ -12,6 +12,7 @@ function setAllInvisible() {
     setAllEnabled("noun_option", false);
     setAllEnabled("verb_option", false);
     setAllEnabled("conjunction_option", false);
+    setAllEnabled("expression_option", false);
 }
 
 export function partOfSpeechChange() {
@@ -23,5 +24,7 @@ export function partOfSpeechChange() {
         setAllEnabled("noun_option", true);
     } else if (document.getElementById("conjunction_selected").selected) {
         setAllEnabled("conjunction_option", true);
+    } else if (document.getElementById("expression_selected").selected) {
+        setAllEnabled("expression_option", true);
     }
 }
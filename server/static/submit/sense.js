@@ -0,0 +1,201 @@
+import { addFormData, setupSelectMultiple } from "/submit/util.js";
+
+let current_sense_id = 0;
+let new_sense_count = 0;
+
+function sensesChanged() {
+    document.dispatchEvent(new Event("senses_changed"));
+}
+
+function removeSense(button_id) {
+    let button = document.getElementById(button_id);
+    let button_div = button.parentElement;
+    let list_div = button_div.parentElement;
+    let list_item = list_div.parentElement;
+    list_item.remove();
+    sensesChanged();
+}
+
+function currentSenses() {
+    return Array.from(document.querySelectorAll("#senses li[data-sense_key]")).map(item => ({
+        key: item.getAttribute("data-sense_key"),
+        english: item.querySelector(".sense_gloss").value,
+    }));
+}
+
+/* Fill the select with the senses currently in the form, keeping the selected sense if it still exists */
+function fillSenseSelect(translations, select, selected) {
+    select.innerHTML = "";
+
+    let none = document.createElement("option");
+    none.value = "";
+    none.innerText = translations["senses.none"];
+    select.add(none);
+
+    currentSenses().forEach((sense, i) => {
+        let option = document.createElement("option");
+        option.value = sense.key;
+        option.innerText = `${i + 1}. ${sense.english}`;
+
+        if (sense.key === selected) {
+            option.selected = true;
+        }
+
+        select.add(option);
+    });
+
+    // No point in choosing a sense if there are none
+    select.parentElement.hidden = select.options.length === 1;
+}
+
+export function senseSelect(translations, name, selected) {
+    let div = document.createElement("div");
+    div.className = "table_row_if_space";
+    let label = document.createElement("label");
+    let select = document.createElement("select");
+
+    label.innerText = `${translations["senses.sense"]}:`;
+    select.name = name;
+    select.className = "sense_select";
+
+    div.appendChild(label);
+    div.appendChild(select);
+
+    fillSenseSelect(translations, select, selected);
+    document.addEventListener("senses_changed", function() {
+        fillSenseSelect(translations, select, select.value);
+    });
+
+    return div;
+}
+
+export function addSense(translations, all_labels, english, is_informal, key, suggestion_id, existing_id, labels) {
+    current_sense_id += 1;
+
+    if (key == null) {
+        new_sense_count += 1;
+        key = `new-${new_sense_count}`;
+    }
+
+    let list = document.getElementById("senses");
+    let item = document.createElement("li");
+    item.setAttribute("data-sense_key", key);
+    list.insertBefore(item, document.getElementById("add_sense").parentElement);
+
+    let div = document.createElement("div");
+    item.appendChild(div);
+    div.classList.add("spaced_flex_list", "row_list");
+
+    let delete_button = document.createElement("button");
+    delete_button.type = "button";
+
+    let icon = document.getElementById("delete-button-template").content.cloneNode(true);
+    delete_button.appendChild(icon);
+    delete_button.setAttribute("aria-label", translations["delete"]);
+
+    delete_button.addEventListener("click", function() { removeSense(this.id) });
+    delete_button.id = `sense-${current_sense_id}`;
+    delete_button.classList.add("delete_sense", "delete_button");
+    let delete_div = document.createElement("div");
+    delete_div.className = "delete_button_container";
+    delete_div.appendChild(delete_button);
+    div.appendChild(delete_div);
+
+    div.appendChild(addFormData(`senses[${current_sense_id}][key]`, key));
+
+    let order = addFormData(`senses[${current_sense_id}][order]`, current_sense_id);
+    order.classList.add("sense_order");
+    div.appendChild(order);
+
+    if (suggestion_id != null) {
+        div.appendChild(addFormData(`senses[${current_sense_id}][suggestion_id]`, suggestion_id));
+    }
+
+    if (existing_id != null) {
+        div.appendChild(addFormData(`senses[${current_sense_id}][existing_id]`, existing_id));
+    }
+
+    let fields = document.createElement("div");
+    fields.classList.add("row_or_column", "table_if_space");
+    div.appendChild(fields);
+
+    let gloss_div = document.createElement("div");
+    gloss_div.className = "table_row_if_space";
+    let gloss_label = document.createElement("label");
+    let gloss = document.createElement("input");
+    gloss_label.innerText = `${translations["senses.gloss"]}:`;
+    gloss.type = "text";
+    gloss.name = `senses[${current_sense_id}][english]`;
+    gloss.className = "sense_gloss";
+    gloss.autocomplete = "off";
+    gloss.spellcheck = true;
+    gloss.setAttribute("data-lpignore", "true");
+    gloss.addEventListener("input", sensesChanged);
+
+    if (english != null) {
+        gloss.value = english;
+    }
+
+    gloss_div.appendChild(gloss_label);
+    gloss_div.appendChild(gloss);
+    fields.appendChild(gloss_div);
+
+    let informal_div = document.createElement("div");
+    informal_div.className = "table_row_if_space";
+    let informal_label = document.createElement("label");
+    let informal = document.createElement("input");
+    informal.type = "checkbox";
+    informal.id = `sense_informal_${current_sense_id}`;
+    informal.name = `senses[${current_sense_id}][is_informal]`;
+    informal.checked = is_informal === true;
+    informal_label.htmlFor = informal.id;
+    informal_label.innerText = translations["informal"];
+
+    informal_div.appendChild(informal_label);
+    informal_div.appendChild(informal);
+    fields.appendChild(informal_div);
+
+    if (all_labels.length !== 0) {
+        let labels_div = document.createElement("div");
+        labels_div.className = "table_row_if_space";
+        let labels_label = document.createElement("label");
+        let labels_select = document.createElement("select");
+        labels_select.id = `sense_labels_${current_sense_id}`;
+        labels_select.name = `senses[${current_sense_id}][labels][]`;
+        labels_select.multiple = true;
+        labels_select.size = Math.min(all_labels.length, 5);
+        labels_label.htmlFor = labels_select.id;
+        labels_label.innerText = `${translations["labels"]}:`;
+
+        for (let label of all_labels) {
+            let option = document.createElement("option");
+            option.value = label.id;
+            option.innerText = label.name;
+            option.selected = labels != null && labels.includes(label.id);
+            labels_select.add(option);
+        }
+
+        labels_div.appendChild(labels_label);
+        labels_div.appendChild(labels_select);
+        fields.appendChild(labels_div);
+        setupSelectMultiple(labels_div);
+    }
+
+    sensesChanged();
+}
+
+export function addSenses(translations, senses, all_labels) {
+    for (let sense of senses) {
+        addSense(
+            translations, all_labels, sense.english, sense.is_informal, sense.key, sense.suggestion_id,
+            sense.existing_id, sense.labels
+        );
+    }
+}
+
+/* Senses are numbered by their position in the form, so this must be called before submitting */
+export function updateSenseOrder() {
+    document.querySelectorAll("#senses .sense_order").forEach((input, i) => {
+        input.value = i + 1;
+    });
+}
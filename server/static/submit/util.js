@@ -8,9 +8,11 @@ export function addFormData(key, value) {
     return suggestion;
 }
 
-/* Do not require shift/ctrl click to select multiple */
-export function setupSelectMultiple () {
-    for (const select of document.querySelectorAll("select[multiple]")) {
+/* Do not require shift/ctrl click to select multiple. Selects which have already been set up are skipped. */
+export function setupSelectMultiple (root = document) {
+    for (const select of root.querySelectorAll("select[multiple]:not([data-easy_multiple])")) {
+        select.setAttribute("data-easy_multiple", "")
+
         for (const option of select.querySelectorAll('option')) {
             option.addEventListener('mousedown', evt => {
                 const scroll = option.parentElement.scrollTop
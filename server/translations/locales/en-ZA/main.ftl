@@ -23,6 +23,18 @@ site-settings = Site settings
         dataset attributions, you can attribute entries to their source properly and beautifully. The datasets that have
         been included in the dictionary can be managed below.
     .no-datasets = There are no datasets. Add one to get started.
+    .labels = Manage labels
+    .labels-text =
+        Labels mark words and senses by their register, dialect, domain, or usage, such as informal, archaic, or
+        medical. Users can filter their searches by label, and each label's rank weight is multiplied into the
+        search ranking of the words it is attached to, so that for example slang can be ranked below standard words.
+    .no-labels = There are no labels. Add one to get started.
+    .review-policies = Review policies
+    .review-policies-text =
+        By default, one moderator's approval is enough to accept a suggestion. Review policies can require more
+        approvals, or let logged-in users vote on suggestions, for the whole site, for one type of suggestion, or for the
+        words in one dataset. When several policies apply to a suggestion, the strictest of them is used.
+    .no-review-policies = There are no review policies, so every suggestion needs one moderator's approval.
 
 dataset = Dataset
     .datasets = Datasets
@@ -52,11 +64,51 @@ dataset-form = (Things in the dataset form - this string isn't used)
     .delete-fail = Failed to delete dataset.
     .current-icon = Current icon
 
+labels = Labels
+    .filter = Filter by label
+    .any = Any label
+    .register = Register
+    .dialect = Dialect
+    .domain = Domain
+    .usage = Usage
+
+label = Label
+    .add = Add label
+    .description = Description
+    .rank-weight = Rank weight
+    .rank-weight-value = Rank weight: { $weight }
+
+label-form = (Things in the label form - this string isn't used)
+    .name = Label name
+    .kind = Kind of label
+    .word-explanation = Labels which apply to the word as a whole. Labels for a single sense can be chosen with the sense.
+    .rank-weight-explanation =
+        Search results for words with this label have their ranking multiplied by this number. Use 1 to leave the
+        ranking unchanged, and a number less than 1 to rank the words lower. Labels of a single sense of a word do not
+        change its ranking.
+    .submit = Add label
+    .edit = Edit label
+    .confirm-delete = Are you sure you want to delete this label? It will be removed from all words and senses.
+    .add-success = Successfully added label.
+    .add-fail = Failed to add label. The rank weight must be a number greater than 0.
+    .delete-success = Successfully deleted label.
+    .delete-fail = Failed to delete label.
+
 unexpected-error = An unexpected error occurred.
 
 all-words = All words
     .description = Browse all the words in the dictionary
 
+expressions = Expressions
+    .description = Browse the idioms and other multi-word expressions in the dictionary
+    .explanation =
+        Idioms and other expressions made of several words. Each links to the words it is made of, and
+        searching for one of those words also shows the expressions it is used in.
+    .none = No expressions have been added yet.
+    .expression = Expression
+    .meaning = Meaning
+    .components = Made up of
+
 about = About
     .description = { site.short-name } is a free, open, online dictionary for { target-language } and { source-language }.
     .aim = Aim
@@ -223,6 +275,7 @@ submit = Submit a word
     .select-inchoative = Inchoative?
     .add-example = Add another
     .add-linked-word = Add another
+    .add-sense = Add another
     .submit-new = Suggest word
     .submit-edit-suggestion = Submit edit to suggestion
     .submit-edit = Suggest edit to word
@@ -267,6 +320,46 @@ moderation = Moderation
     .reject-deletion = Reject deletion
     .edited-examples-and-links = Edited examples and linked words
     .no-suggestions = There are no suggestions to review at this time.
+    .scoped = You may only moderate words in the datasets and labels assigned to you, so suggestions for other words are not shown.
+    .filter = Filter
+    .clear-filters = Clear filters
+    .filter-any = Any
+    .filter-dataset = Dataset
+    .filter-older-than = Older than (days)
+    .filter-newer-than = Newer than (days)
+    .filter-duplicates = Possible duplicates
+    .filter-with-duplicates = New words with possible duplicates
+    .filter-without-duplicates = Without possible duplicates
+    .filter-search = Word contains
+    .sort = Sort by
+    .sort-oldest = Oldest first
+    .sort-newest = Newest first
+    .queue-total =
+        { $total ->
+            [one] One item matches.
+           *[other] { $total } items match.
+        }
+    .pages = Pages
+    .page-of = Page { $page } of { $pages }
+    .previous-page = Previous page
+    .next-page = Next page
+    .bulk-select = Select
+    .bulk-select-all = Select all on this page
+    .bulk-action = Action for the selected suggestions
+    .bulk-accept = Accept selected
+    .bulk-reject = Reject selected
+    .bulk-apply = Apply to selected
+    .bulk-confirm = Are you sure you want to apply this to all of the selected suggestions?
+    .bulk-result =
+        { $method ->
+            [accept] Accepted
+            [reject] Rejected
+           *[other] Processed
+        } { $done } of { $total } selected suggestions.
+    .bulk-not-found = this suggestion no longer exists.
+    .bulk-not-allowed = this suggestion is outside of the datasets and labels assigned to you.
+    .bulk-failed = an error occurred with this suggestion.
+    .bulk-needs-rebase = the word was changed since this suggestion was made, so it must be rebased before it can be accepted.
     .action-success =
         {$method ->
             [accept] Successfully accepted suggestion.
@@ -292,6 +385,191 @@ moderation = Moderation
            *[other] suggestion
         }?
     .confirm-reject = Are you sure you want to reject this suggestion?
+    .discussion =
+        Discussion ({ $comments ->
+            [one] 1 comment
+           *[other] { $comments } comments
+        })
+    .needs-info = Needs info
+    .preview = Preview
+    .conflict-changed = The word was changed since this suggestion was made
+    .conflict-suggestion = Overlaps with suggestion #{ $id } by { $username }
+    .conflict-field = Field
+    .conflict-original = Original
+    .conflict-this = This suggestion
+    .conflict-other = Suggestion #{ $id }
+    .conflict-current = Word now
+    .rebase = Rebase
+    .rebase-explanation = Accept this suggestion over the changes made to the word since it was suggested
+    .confirm-rebase = Are you sure you want this suggestion to replace the changes made to the word since it was suggested?
+    .approval-recorded = Your approval was recorded. This suggestion has { $approvals } of the { $required } approvals it needs to be accepted.
+    .approvals = Moderator approvals
+    .approvals-count = { $approvals }/{ $required } approvals
+    .votes = Votes
+
+recent-changes = Recent changes
+    .description = All recently accepted changes to words in the dictionary.
+    .english = { source-language }
+    .xhosa = { target-language }
+    .item = Item changed
+    .change-type = Type of change
+    .all = All
+    .filter = Filter
+    .clear-filters = Clear filters
+    .no-changes = There are no changes to show.
+    .time = Time
+    .reviewed-by = Reviewed by
+    .word-history = Show all changes to this word
+    .deleted-word = Deleted word #{ $id }
+    .deleted-user = Deleted user
+    .older = Older changes
+    .target-word = Word
+    .target-example = Example
+    .target-linked-word = Linked word
+    .target-dataset-attribution = Dataset attribution
+    .target-sense = Sense
+    .target-label = Label
+    .target-citation = Citation
+    .kind-added = added
+    .kind-edited = edited
+    .kind-deleted = deleted
+
+word-history = History
+    .description = All past versions of a word in the dictionary.
+    .revision = Revision #{ $id }
+    .current = current
+    .untracked = Version from before changes were tracked
+    .reverted-from = Reverted to revision #{ $id }
+    .no-revisions = No changes have been made to this word yet.
+    .revert = Revert to this revision
+    .confirm-revert = Are you sure you want to revert this word to this revision?
+    .revert-success = Successfully reverted the word.
+    .revert-failure = Failed to revert the word.
+
+trash = Trash
+    .explanation = Deleted words, examples, and linked words are kept here for { $days } days before they are permanently deleted.
+    .empty = The trash is empty.
+    .deleted-at = Deleted at
+    .purged-at = Permanently deleted on
+    .restore = Restore
+    .confirm-restore = Are you sure you want to restore this item?
+    .restore-success = Successfully restored the item.
+    .restore-failure = Failed to restore the item. Its word may not exist anymore.
+
+trusted-edits = Trusted edits
+    .explanation = Suggestions by trusted contributors are applied as soon as they are made. Review them here, and revert any which should not have been made. Reverting an edit restores the word to how it was before the edit.
+    .empty = There are no trusted edits to review at this time.
+    .applied-at = Applied at
+    .history = View the word's history
+    .approve = Approve
+    .revert = Revert
+    .confirm-revert = Are you sure you want to revert this edit?
+    .superseded = The word has been changed since this edit, and reverting it would undo those changes too. Edit the word by hand or restore a revision from its history instead.
+    .review-success = Successfully reviewed the edit.
+    .review-failure = Failed to review the edit. It may have been reviewed already, its word may not exist anymore, or the word may have been changed since.
+
+merge = Merge word
+    .description = Merge a duplicate word into another word.
+    .explanation =
+        All examples, linked words, contributors, datasets, and pending suggestions for this word will
+        be moved to the word it is merged into. This word will then be moved to the trash, and its page
+        will redirect to the other word.
+    .into = ID of the word to merge into
+    .submit = Merge
+    .confirm = Are you sure you want to merge these words?
+    .failure = Failed to merge the words. Check that the word to merge into exists and is not this word.
+
+split = Split word
+    .description = Split a word with two different meanings into two separate words.
+    .explanation =
+        A new word will be created with the same grammatical information as this word. Contributors and
+        datasets are copied to both words, and the two words will be linked to each other. Choose which
+        word each example and linked word belongs to.
+    .original-english = Meaning of this word
+    .new-english = Meaning of the new word
+    .link-type = Link between the two words
+    .original-word = This word
+    .new-word = New word
+    .submit = Split
+    .confirm = Are you sure you want to split this word?
+    .failure = Failed to split the word. Both meanings must be filled in.
+
+suggestion-edit = Edit suggestion
+    .example = Edit example suggestion
+    .linked-word = Edit linked word suggestion
+    .existing-example = This suggestion edits an existing example:
+    .link-explanation = Choose how the first word is related to the second word.
+    .first-word = First word
+    .second-word = Second word
+    .success = Suggestion successfully edited!
+    .failure = There was an error editing the suggestion. Check that all fields are filled in and that the link type makes sense for the two words.
+
+my-suggestions = My suggestions
+    .pending = Waiting for review
+    .reviewed = Reviewed
+    .no-pending = You have no suggestions waiting for review.
+    .no-reviewed = None of your suggestions have been reviewed yet.
+    .type = Type
+    .suggestion = Suggestion
+    .outcome = Outcome
+    .moderator-comment = Moderator's comment
+    .reviewed-at = Reviewed at
+    .accepted = Accepted
+    .accepted-with-edits = Accepted with edits
+    .rejected = Rejected
+    .type-word = Word
+    .type-word-deletion = Word deletion
+    .type-sense = Meaning
+    .type-example = Example
+    .type-example-deletion = Example deletion
+    .type-linked-word = Linked word
+    .type-linked-word-deletion = Linked word deletion
+    .type-dataset-attribution = Dataset attribution
+    .type-label = Label
+
+discussion = Discussion
+    .comments = Comments
+    .no-comments = Nobody has commented on this suggestion yet.
+    .contributor = suggested this
+    .reply = Reply
+    .post = Post
+    .new-comment = New comment
+    .failure = There was an error posting your comment. Check that it is not empty.
+    .needs-info-explanation = A moderator needs more information before reviewing this suggestion. It will be reviewed once the contributor replies.
+    .mark-needs-info = Ask for more information
+    .clear-needs-info = Ready for review
+
+voting = Vote on suggestions
+    .explanation = Help the moderators by voting on suggestions from other contributors. Moderators see the votes when reviewing the suggestions.
+    .no-suggestions = There are no suggestions to vote on at this time.
+    .up = Vote for this suggestion
+    .down = Vote against this suggestion
+    .link = Vote on suggestions from other contributors
+
+review-policy = Review policy
+    .suggestion-type = Type of suggestion
+    .dataset = Dataset
+    .required-approvals = Moderator approvals required
+    .allow-voting = Allow voting by logged-in users
+    .any = Any
+    .yes = Yes
+    .no = No
+    .add = Add review policy
+    .add-success = Review policy successfully added!
+    .add-fail = Failed to add review policy. At least one approval must be required.
+    .delete-success = Review policy successfully deleted!
+    .delete-fail = Failed to delete review policy.
+    .confirm-delete = Are you sure you want to delete this review policy?
+
+rejection-reason = Reason for rejecting
+    .choose = Choose a reason
+    .comment = Comment for the contributor
+    .duplicate = Duplicate of an existing word or suggestion
+    .incorrect = Incorrect
+    .unclear = Unclear or incomplete
+    .style-guide = Doesn't follow the style guide
+    .out-of-scope = Not suitable for the dictionary
+    .other = Other
 
 tracing = Tracing
 
@@ -303,6 +581,12 @@ word = Word details page
     .suggest-edit = Suggest edit
     .suggest-delete = Suggest deletion
     .confirm-delete = Are you sure you want to suggest this word be deleted?
+    .history = View history
+    .merge = Merge into another word
+    .split = Split into two words
+    .references = Manage references
+    .conjugation = Edit conjugation
+    .preview = This is a preview of how the word will look once the suggestion is accepted.
     .success-message =
         Successfully {$action ->
             [edit] suggested edit
@@ -360,6 +644,7 @@ word-hit = Word hit, e.g as it appears in the search box
             [preposition] { preposition }
             [ideophone] { ideophone }
             [boundmorpheme] { boundmorpheme }
+            [expression] { expression }
            *[other]{""}
         }
     .class = class
@@ -381,6 +666,21 @@ linked-words = Linked words
     .related = Related meaning
     .confusable = Confusable
     .alternate = Alternate Use
+    .synonym = Synonym
+    .derived-from = Derived from
+    .derivation-of = Has derived form
+    .causative-of = Causative of
+    .has-causative = Has causative form
+    .applicative-of = Applicative of
+    .has-applicative = Has applicative form
+    .passive-of = Passive of
+    .has-passive = Has passive form
+    .diminutive-of = Diminutive of
+    .has-diminutive = Has diminutive form
+    .hyponym-of = A kind of (hyponym of)
+    .hypernym-of = Broader term for (hypernym of)
+    .component-of = Part of compound or idiom
+    .has-component = Has component word
     .choose = Choose how the words are related
     .search = Search for a linked word...
 
@@ -395,6 +695,137 @@ no = no
 
 note = Note
 
+etymology = Etymology
+    .explanation = Where the word comes from, for instance the language it was loaned from.
+    .language = Source language
+    .choose-language = Choose the language the word comes from
+    .form = Source form
+    .form-explanation = How the word is written in the language it comes from, e.g. "oranje" for "i-orenji".
+    .note = Etymology notes
+
+etymology-language = Source language
+    .english = English
+    .afrikaans = Afrikaans
+    .dutch = Dutch
+    .khoekhoe = Khoekhoe
+    .san = San
+    .zulu = isiZulu
+    .sotho = Sesotho
+    .portuguese = Portuguese
+    .other = Another language
+
+literal-translation = Literal translation
+    .explanation =
+        The word-for-word meaning of an expression, e.g. "to hit the nail on the head" for an idiom meaning
+        "to be exactly right". Put the idiomatic meaning in the { source-language } field.
+
+references = References
+    .description = Cite printed or online works for a word, its senses and its examples.
+    .explanation =
+        Cite the dictionaries, grammars, corpora and other works which this entry is based on. References
+        are shared between all words, so a work only needs to be added once.
+    .bibliography = Bibliography
+    .success = The references were saved successfully.
+    .failure = The references could not be saved. Please check the form and try again.
+    .citations = Citations
+    .no-citations = This word does not cite any references yet.
+    .no-references = No references have been added yet. Add one below to cite it.
+    .reference = Reference
+    .cites = Cites
+    .whole-word = The whole word
+    .sense = Sense
+    .example = Example
+    .pages = Pages
+    .pages-placeholder = e.g. p. 112
+    .cite = Cite a reference
+    .remove = Remove citation
+    .confirm-remove = Are you sure you want to remove this citation?
+    .add = Add a new reference
+    .add-explanation = Once added, the reference can be cited from any word.
+    .kind = Kind of work
+    .kind-dictionary = Dictionary
+    .kind-grammar = Grammar
+    .kind-book = Book
+    .kind-article = Article
+    .kind-corpus = Corpus
+    .kind-website = Website
+    .author = Author
+    .title = Title
+    .year = Year
+    .publisher = Publisher
+    .url = Link
+
+grammar-warnings = Possible grammar mistakes
+    .explanation =
+        Your suggestion was saved, but some of its grammatical information looks wrong. Please check it
+        and edit the suggestion if needed. Irregular words may be correct anyway.
+    .noun-prefix = The noun does not begin with a prefix of its noun class.
+    .plural = Whether the noun is plural does not match its noun class.
+    .invalid-infinitive = The infinitive should begin with uku-, ukw- or uk-.
+    .infinitive-mismatch = The infinitive does not match the verb.
+
+noun-forms = Other forms
+    .explanation =
+        Forms generated from the noun by the regular rules, so they may be wrong for irregular nouns.
+        The locative means "in", "at" or "on" the noun, and the diminutive means a small one.
+    .plural = Plural
+    .singular = Singular
+    .locative = Locative
+    .diminutive = Diminutive
+    .counterpart-english = { source-language } meaning of the new entry
+    .create-counterpart = Create entry
+
+concords = Concords
+    .explanation =
+        The prefixes and words which agree with the noun, e.g. the subject concord on a verb whose subject
+        is the noun, or the demonstrative meaning "this" or "that".
+    .kind = Concord
+    .concord = Form
+    .subject = Subject concord
+    .object = Object concord
+    .possessive = Possessive concord
+    .adjective = Adjective concord
+    .relative = Relative concord
+    .demonstrative-near = Demonstrative (this)
+    .demonstrative-middle = Demonstrative (that)
+    .demonstrative-far = Demonstrative (that over there)
+    .more = All nouns in this class
+
+noun-class-page = Noun class
+    .description = The concords of a noun class, and every noun in the dictionary which belongs to it.
+    .other-classes = Noun classes
+    .nouns = Nouns in this class
+    .none = There are no nouns in this class in the dictionary yet.
+
+conjugation = Conjugation
+    .description = View and correct the generated conjugation table of a verb.
+    .explanation =
+        The verb conjugated for each subject in the most common tenses. Forms are generated from the
+        infinitive, so irregular verbs may have been corrected by moderators.
+    .json = Download as JSON
+    .inchoative-note =
+        This verb is inchoative, so the perfect tense describes a present state, e.g. "ndilambile" means
+        "I am hungry".
+    .present = Present tense
+    .past = Past tense
+    .future = Future tense
+    .perfect = Perfect tense
+    .subjunctive = Subjunctive
+    .positive = Positive
+    .negative = Negative
+    .subject = Subject
+    .first-singular = 1st person singular (I)
+    .second-singular = 2nd person singular (you)
+    .first-plural = 1st person plural (we)
+    .second-plural = 2nd person plural (you all)
+    .success = The conjugation was saved successfully.
+    .failure = The conjugation could not be saved. Only verbs with a regular infinitive can be conjugated.
+    .override-explanation =
+        Forms are generated from the infinitive of the verb. Change a form to correct it if the verb is
+        irregular, or clear it to go back to the generated form.
+    .overridden = (corrected)
+    .save = Save conjugation
+
 no-grammatical-info =
     This word doesn't have any further information yet. You can help by { $edit-link }editing this entry</a>.
 
@@ -439,6 +870,19 @@ ideophone = ideophone
     .capitalised = Ideophone
 boundmorpheme = bound morpheme
     .capitalised = Bound morpheme
+expression = expression (idiom)
+    .capitalised = Expression (idiom)
+
+senses = Senses
+    .explanation =
+        If this word has several distinct meanings, add each one as a separate sense. Examples and linked
+        words can then be assigned to the sense they belong to.
+    .sense = Sense
+    .number = Sense number
+    .gloss = Meaning
+    .none = No particular sense
+    .other-examples = Other examples
+    .other-linked-words = Other linked words
 
 examples = Example sentences
     .source = { source-language } example